@@ -31,9 +31,10 @@ use jj_lib::op_store::OperationId;
 use jj_lib::ref_name::WorkspaceName;
 use jj_lib::ref_name::WorkspaceNameBuf;
 use jj_lib::repo::ReadonlyRepo;
-use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::settings::UserSettings;
 use jj_lib::signing::Signer;
+use jj_lib::sparse::SparsePatterns;
+use jj_lib::sparse::SparseProfile;
 use jj_lib::store::Store;
 use jj_lib::working_copy::CheckoutError;
 use jj_lib::working_copy::CheckoutStats;
@@ -171,10 +172,14 @@ impl WorkingCopy for ConflictsWorkingCopy {
         self.inner.tree()
     }
 
-    fn sparse_patterns(&self) -> Result<&[RepoPathBuf], WorkingCopyStateError> {
+    fn sparse_patterns(&self) -> Result<&SparsePatterns, WorkingCopyStateError> {
         self.inner.sparse_patterns()
     }
 
+    fn sparse_profile(&self) -> Result<Option<&str>, WorkingCopyStateError> {
+        self.inner.sparse_profile()
+    }
+
     fn start_mutation(&self) -> Result<Box<dyn LockedWorkingCopy>, WorkingCopyStateError> {
         let inner = self.inner.start_mutation()?;
         Ok(Box::new(LockedConflictsWorkingCopy {
@@ -274,17 +279,28 @@ impl LockedWorkingCopy for LockedConflictsWorkingCopy {
         self.inner.recover(commit).await
     }
 
-    fn sparse_patterns(&self) -> Result<&[RepoPathBuf], WorkingCopyStateError> {
+    fn sparse_patterns(&self) -> Result<&SparsePatterns, WorkingCopyStateError> {
         self.inner.sparse_patterns()
     }
 
+    fn sparse_profile(&self) -> Result<Option<&str>, WorkingCopyStateError> {
+        self.inner.sparse_profile()
+    }
+
     async fn set_sparse_patterns(
         &mut self,
-        new_sparse_patterns: Vec<RepoPathBuf>,
+        new_sparse_patterns: SparsePatterns,
     ) -> Result<CheckoutStats, CheckoutError> {
         self.inner.set_sparse_patterns(new_sparse_patterns).await
     }
 
+    async fn set_sparse_profile(
+        &mut self,
+        profile: SparseProfile,
+    ) -> Result<CheckoutStats, CheckoutError> {
+        self.inner.set_sparse_profile(profile).await
+    }
+
     async fn finish(
         self: Box<Self>,
         operation_id: OperationId,
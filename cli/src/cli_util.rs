@@ -34,7 +34,6 @@ use std::sync::LazyLock;
 use std::time::SystemTime;
 
 use bstr::ByteVec as _;
-use chrono::TimeZone as _;
 use clap::ArgAction;
 use clap::ArgMatches;
 use clap::Command;
@@ -80,6 +79,8 @@ use jj_lib::matchers::Matcher;
 use jj_lib::matchers::NothingMatcher;
 use jj_lib::merge::Diff;
 use jj_lib::merge::MergedTreeValue;
+use jj_lib::merge_driver::MergeDriver;
+use jj_lib::merge_driver::MergeDrivers;
 use jj_lib::merged_tree::MergedTree;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::op_heads_store;
@@ -129,6 +130,7 @@ use jj_lib::str_util::StringExpression;
 use jj_lib::str_util::StringMatcher;
 use jj_lib::str_util::StringPattern;
 use jj_lib::transaction::Transaction;
+use jj_lib::tree_merge::MergeStrategy;
 use jj_lib::working_copy;
 use jj_lib::working_copy::CheckoutStats;
 use jj_lib::working_copy::LockedWorkingCopy;
@@ -348,6 +350,30 @@ impl CommandHelper {
         &self.data.config_env
     }
 
+    /// Output format selected by the `--output` global option.
+    pub fn output_format(&self) -> OutputFormat {
+        self.data.global_args.output.unwrap_or_default()
+    }
+
+    /// Returns the template to render list items with.
+    ///
+    /// In JSON output mode, this is the builtin `json` template. Otherwise,
+    /// it's the `--template` argument or the config value of `setting_name`.
+    pub fn list_template_text(
+        &self,
+        template: Option<&str>,
+        setting_name: &'static str,
+    ) -> Result<String, CommandError> {
+        match (self.output_format(), template) {
+            (OutputFormat::Json, Some(_)) => Err(user_error(
+                "`--output=json` cannot be used with `--template`",
+            )),
+            (OutputFormat::Json, None) => Ok("json".to_owned()),
+            (OutputFormat::Text, Some(template)) => Ok(template.to_owned()),
+            (OutputFormat::Text, None) => Ok(self.settings().get_string(setting_name)?),
+        }
+    }
+
     /// Unprocessed (or unresolved) configuration data.
     ///
     /// Use this only if the unmodified config data is needed. For example, `jj
@@ -830,6 +856,7 @@ pub struct WorkspaceCommandEnvironment {
     immutable_heads_expression: Arc<UserRevsetExpression>,
     short_prefixes_expression: Option<Arc<UserRevsetExpression>>,
     conflict_marker_style: ConflictMarkerStyle,
+    merge_drivers: Vec<MergeDriver>,
 }
 
 impl WorkspaceCommandEnvironment {
@@ -857,6 +884,7 @@ impl WorkspaceCommandEnvironment {
             immutable_heads_expression: RevsetExpression::root(),
             short_prefixes_expression: None,
             conflict_marker_style: settings.get("ui.conflict-marker-style")?,
+            merge_drivers: MergeDriver::all_from_settings(settings)?,
         };
         env.reload_revset_expressions(ui)?;
         Ok(env)
@@ -897,18 +925,11 @@ impl WorkspaceCommandEnvironment {
             path_converter: &self.path_converter,
             workspace_name: &self.workspace_name,
         };
-        let now = if let Some(timestamp) = self.settings.commit_timestamp() {
-            chrono::Local
-                .timestamp_millis_opt(timestamp.timestamp.0)
-                .unwrap()
-        } else {
-            chrono::Local::now()
-        };
         RevsetParseContext {
             aliases_map: &self.revset_aliases_map,
             local_variables: HashMap::new(),
             user_email: self.settings.user_email(),
-            date_pattern_context: now.into(),
+            date_pattern_context: self.settings.date_pattern_context(),
             default_ignored_remote: self.default_ignored_remote,
             fileset_aliases_map: &self.fileset_aliases_map,
             use_glob_by_default: self.revsets_use_glob_by_default,
@@ -1286,7 +1307,11 @@ impl WorkspaceCommandHelper {
         let mut tx = tx.into_inner();
         let old_git_head = self.repo().view().git_head().clone();
         let new_git_head = tx.repo().view().git_head().clone();
-        if let Some(new_git_head_id) = new_git_head.as_normal() {
+        if new_git_head == old_git_head {
+            // Only the HEADs of the linked worktrees changed
+            self.finish_transaction(ui, tx, "import git head", git_import_export_lock)
+                .await?;
+        } else if let Some(new_git_head_id) = new_git_head.as_normal() {
             let workspace_name = self.workspace_name().to_owned();
             let new_git_head_commit = tx.repo().store().get_commit(new_git_head_id)?;
             let wc_commit = tx
@@ -1962,6 +1987,60 @@ to the current parents may contain changes from multiple commits.
         Err(error)
     }
 
+    /// Starts a background process watching the working copy unless one is
+    /// already running. The first snapshot still scans the whole working copy.
+    /// No daemon is started while the last one ran out of inotify watches, so
+    /// snapshots scan the whole working copy instead.
+    #[cfg(target_os = "linux")]
+    fn start_inotify_daemon(&self) {
+        use std::fs;
+
+        use jj_lib::fsmonitor::inotify;
+
+        let Some(wc) = self
+            .working_copy()
+            .downcast_ref::<jj_lib::local_working_copy::LocalWorkingCopy>()
+        else {
+            return;
+        };
+        let daemon_dir = inotify::daemon_dir(wc.state_path());
+        if inotify::is_daemon_running(&daemon_dir).unwrap_or(true)
+            || inotify::is_watch_limit_reached(&daemon_dir)
+        {
+            return;
+        }
+        // Errors of the daemon are appended to a log file, which is started
+        // over once it gets large.
+        let open_log = || {
+            let log_path = inotify::daemon_log_path(&daemon_dir);
+            fs::create_dir_all(&daemon_dir)?;
+            let truncate = fs::metadata(&log_path).is_ok_and(|metadata| metadata.len() > 64 * 1024);
+            fs::File::options()
+                .create(true)
+                .append(!truncate)
+                .write(true)
+                .truncate(truncate)
+                .open(&log_path)
+        };
+        let result = open_log().and_then(|log_file| {
+            use std::os::unix::process::CommandExt as _;
+
+            let jj_executable_path = std::env::current_exe()?;
+            std::process::Command::new(jj_executable_path)
+                .arg("--repository")
+                .arg(self.workspace_root())
+                .args(["--ignore-working-copy", "debug", "inotify", "daemon"])
+                .stdin(std::process::Stdio::null())
+                .stdout(std::process::Stdio::null())
+                .stderr(log_file)
+                .process_group(0)
+                .spawn()
+        });
+        if let Err(err) = result {
+            tracing::warn!(?err, "Failed to start the inotify daemon");
+        }
+    }
+
     #[instrument(skip_all)]
     async fn snapshot_working_copy(
         &mut self,
@@ -1975,6 +2054,13 @@ to the current parents may contain changes from multiple commits.
         let options = self
             .snapshot_options_with_start_tracking_matcher(&auto_tracking_matcher)
             .map_err(snapshot_command_error)?;
+        #[cfg(target_os = "linux")]
+        if matches!(
+            jj_lib::fsmonitor::FsmonitorSettings::from_settings(self.settings()),
+            Ok(jj_lib::fsmonitor::FsmonitorSettings::Inotify)
+        ) {
+            self.start_inotify_daemon();
+        }
 
         // Compare working-copy tree and operation with repo's, and reload as needed.
         let mut locked_ws = self
@@ -2138,11 +2224,16 @@ to the current parents may contain changes from multiple commits.
     }
 
     pub fn start_transaction(&mut self) -> WorkspaceCommandTransaction<'_> {
-        let tx = start_repo_transaction(
+        let mut tx = start_repo_transaction(
             self.repo(),
             self.workspace_name(),
             self.env.command.string_args(),
         );
+        // Merge drivers are external commands, so they're only run when
+        // rewriting commits, not when e.g. displaying merged trees.
+        let mut merge_options = tx.repo().merge_options().clone();
+        merge_options.merge_drivers = MergeDrivers::new(self.env.merge_drivers.clone());
+        tx.repo_mut().set_merge_options(merge_options);
         let id_prefix_context = mem::take(&mut self.user_repo.id_prefix_context);
         WorkspaceCommandTransaction {
             helper: self,
@@ -2162,6 +2253,19 @@ to the current parents may contain changes from multiple commits.
         if num_rebased > 0 {
             writeln!(ui.status(), "Rebased {num_rebased} descendant commits")?;
         }
+        // The same files may be merged more than once in a transaction.
+        let merge_driver_failures = tx.repo().merge_options().merge_drivers.take_failures();
+        for failure in merge_driver_failures
+            .iter()
+            .unique_by(|failure| (&failure.path, failure.error.to_string()))
+        {
+            writeln!(
+                ui.warning_default(),
+                "Failed to merge {path} with merge driver.",
+                path = self.format_file_path(&failure.path)
+            )?;
+            print_error_sources(ui, Some(&failure.error))?;
+        }
 
         for (name, wc_commit_id) in &tx.repo().view().wc_commit_ids().clone() {
             // This can fail if trunk() bookmark gets deleted or conflicted. If
@@ -2585,6 +2689,14 @@ impl WorkspaceCommandTransaction<'_> {
         self.tx.repo_mut()
     }
 
+    /// Resolves conflicting hunks of trees merged in this transaction by the
+    /// given `strategy`.
+    pub fn set_merge_strategy(&mut self, strategy: MergeStrategy) {
+        let mut options = self.tx.repo().merge_options().clone();
+        options.strategy = strategy;
+        self.tx.repo_mut().set_merge_options(options);
+    }
+
     pub fn check_out(&mut self, commit: &Commit) -> Result<Commit, CheckOutCommitError> {
         let name = self.helper.workspace_name().to_owned();
         self.id_prefix_context.take(); // invalidate
@@ -3086,6 +3198,16 @@ pub fn print_checkout_stats(
     stats: &CheckoutStats,
     new_commit: &Commit,
 ) -> Result<(), std::io::Error> {
+    if let Some(name) = &stats.updated_sparse_profile {
+        writeln!(ui.status(), "Updated sparse patterns from profile {name}")?;
+    }
+    if let Some(failure) = &stats.failed_sparse_profile {
+        writeln!(ui.warning_default(), "{}", failure.message)?;
+        writeln!(
+            ui.hint_default(),
+            "The working copy keeps the patterns from the previous version of the profile."
+        )?;
+    }
     if stats.added_files > 0 || stats.updated_files > 0 || stats.removed_files > 0 {
         writeln!(
             ui.status(),
@@ -3110,6 +3232,14 @@ Discard the conflicting changes with `jj restore --from {}`.",
             short_commit_hash(new_commit.id())
         )?;
     }
+    for failure in &stats.failed_submodules {
+        writeln!(
+            ui.warning_default(),
+            "Failed to check out submodule {}: {}",
+            failure.path.as_internal_file_string(),
+            failure.message
+        )?;
+    }
     Ok(())
 }
 
@@ -3569,6 +3699,16 @@ pub struct GlobalArgs {
     #[arg(add = ArgValueCandidates::new(complete::operations))]
     pub at_operation: Option<String>,
 
+    /// Output format of list-like commands
+    ///
+    /// `--output=json` prints one JSON object per line (or a single object
+    /// for `jj status`) instead of the human-readable output. It's supported
+    /// by `jj log`, `jj op log`, `jj bookmark list`, `jj tag list`, `jj
+    /// workspace list`, `jj file list`, and `jj status`. See the "JSON
+    /// output" section of the templates documentation for the object shapes.
+    #[arg(long, value_name = "FORMAT", global = true)]
+    pub output: Option<OutputFormat>,
+
     /// Enable debug logging
     #[arg(long, global = true)]
     pub debug: bool,
@@ -3577,6 +3717,41 @@ pub struct GlobalArgs {
     pub early_args: EarlyArgs,
 }
 
+/// Output format selected by `--output`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable output
+    #[default]
+    Text,
+    /// Machine-readable JSON objects
+    Json,
+}
+
+/// Strategy for resolving conflicting hunks, selected by `--strategy`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum MergeStrategyArg {
+    /// Leave conflicting hunks as conflicts
+    #[default]
+    Conflict,
+    /// Keep the lines of all sides of conflicting hunks
+    Union,
+    /// Take the first side of conflicting hunks
+    Ours,
+    /// Take the last side of conflicting hunks
+    Theirs,
+}
+
+impl From<MergeStrategyArg> for MergeStrategy {
+    fn from(arg: MergeStrategyArg) -> Self {
+        match arg {
+            MergeStrategyArg::Conflict => Self::Conflict,
+            MergeStrategyArg::Union => Self::Union,
+            MergeStrategyArg::Ours => Self::Ours,
+            MergeStrategyArg::Theirs => Self::Theirs,
+        }
+    }
+}
+
 #[derive(clap::Args, Clone, Debug)]
 pub struct EarlyArgs {
     /// When to colorize output
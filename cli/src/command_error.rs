@@ -41,6 +41,8 @@ use jj_lib::op_heads_store::OpHeadsStoreError;
 use jj_lib::op_store::OpStoreError;
 use jj_lib::op_walk::OpsetEvaluationError;
 use jj_lib::op_walk::OpsetResolutionError;
+use jj_lib::opset::OpsetParseError;
+use jj_lib::opset::OpsetParseErrorKind;
 use jj_lib::repo::CheckOutCommitError;
 use jj_lib::repo::EditCommitError;
 use jj_lib::repo::RepoLoaderError;
@@ -53,6 +55,8 @@ use jj_lib::revset::RevsetParseError;
 use jj_lib::revset::RevsetParseErrorKind;
 use jj_lib::revset::RevsetResolutionError;
 use jj_lib::secure_config::SecureConfigError;
+use jj_lib::sparse::SPARSE_PROFILE_DIR;
+use jj_lib::sparse::SparseProfileError;
 use jj_lib::str_util::StringPatternParseError;
 use jj_lib::trailer::TrailerParseError;
 use jj_lib::transaction::TransactionCommitError;
@@ -386,6 +390,7 @@ impl From<OpHeadResolutionError> for CommandError {
 impl From<OpsetEvaluationError> for CommandError {
     fn from(err: OpsetEvaluationError) -> Self {
         match err {
+            OpsetEvaluationError::OpsetParse(err) => err.into(),
             OpsetEvaluationError::OpsetResolution(err) => {
                 let hint = opset_resolution_error_hint(&err);
                 let mut cmd_err = user_error(err);
@@ -465,9 +470,14 @@ impl From<ConflictResolveError> for CommandError {
             ConflictResolveError::Io(err) => err.into(),
             _ => {
                 let hint = match &err {
-                    ConflictResolveError::ConflictTooComplicated { .. } => {
-                        Some("Edit the conflict markers manually to resolve this.".to_owned())
-                    }
+                    ConflictResolveError::ConflictTooComplicated { .. } => Some(
+                        "Edit the conflict markers manually or use `jj resolve --tool=:builtin` \
+                         to resolve this."
+                            .to_owned(),
+                    ),
+                    ConflictResolveError::NotNormalFiles { .. } => Some(
+                        "Use `jj resolve --tool=:builtin` to pick one of the sides.".to_owned(),
+                    ),
                     ConflictResolveError::ExecutableConflict { .. } => {
                         Some("Use `jj file chmod` to update the executable bit.".to_owned())
                     }
@@ -648,6 +658,18 @@ impl From<FilesetParseError> for CommandError {
     }
 }
 
+impl From<OpsetParseError> for CommandError {
+    fn from(err: OpsetParseError) -> Self {
+        let hint = opset_parse_error_hint(&err);
+        let mut cmd_err = user_error_with_message(
+            format!("Failed to parse operation set: {}", err.kind()),
+            err,
+        );
+        cmd_err.extend_hints(hint);
+        cmd_err
+    }
+}
+
 impl From<RecoverWorkspaceError> for CommandError {
     fn from(err: RecoverWorkspaceError) -> Self {
         match err {
@@ -750,6 +772,8 @@ impl From<FixError> for CommandError {
                 "An error occurred while attempting to fix file content",
                 err,
             ),
+            FixError::CachePath(err) => err.into(),
+            FixError::Cache(_) => internal_error(err),
         }
     }
 }
@@ -763,6 +787,19 @@ impl From<BisectionError> for CommandError {
     }
 }
 
+impl From<SparseProfileError> for CommandError {
+    fn from(err: SparseProfileError) -> Self {
+        match err {
+            SparseProfileError::Backend(err) => err.into(),
+            SparseProfileError::NotFound { .. } => user_error(err).hinted(format!(
+                "Sparse profiles are read from `{SPARSE_PROFILE_DIR}/<name>.toml` in the \
+                 working-copy commit."
+            )),
+            _ => user_error(err),
+        }
+    }
+}
+
 impl From<SecureConfigError> for CommandError {
     fn from(err: SecureConfigError) -> Self {
         internal_error_with_message("Failed to determine the secure config for a repo", err)
@@ -859,6 +896,21 @@ fn fileset_parse_error_hint(err: &FilesetParseError) -> Option<String> {
     }
 }
 
+fn opset_parse_error_hint(err: &OpsetParseError) -> Option<String> {
+    match err.kind() {
+        OpsetParseErrorKind::SyntaxError => Some(String::from(
+            "See https://docs.jj-vcs.dev/latest/operation-log/ for the operation set syntax.",
+        )),
+        OpsetParseErrorKind::NoSuchFunction {
+            name: _,
+            candidates,
+        } => format_similarity_hint(candidates),
+        OpsetParseErrorKind::InvalidArguments { .. } | OpsetParseErrorKind::Expression(_) => {
+            find_source_parse_error_hint(&err)
+        }
+    }
+}
+
 fn opset_resolution_error_hint(err: &OpsetResolutionError) -> Option<String> {
     match err {
         OpsetResolutionError::MultipleOperations {
@@ -872,6 +924,9 @@ fn opset_resolution_error_hint(err: &OpsetResolutionError) -> Option<String> {
         | OpsetResolutionError::InvalidIdPrefix(_)
         | OpsetResolutionError::NoSuchOperation(_)
         | OpsetResolutionError::AmbiguousIdPrefix(_) => None,
+        OpsetResolutionError::TooManyOperations { .. } => {
+            Some("Try specifying the operation by ID".to_owned())
+        }
     }
 }
 
@@ -0,0 +1,45 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCompleter;
+use jj_lib::bisect::Evaluation;
+use tracing::instrument;
+
+use super::mark_revisions;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Mark revisions as bad
+///
+/// Then checks out the next revision to evaluate, or prints the result if the
+/// bisection is complete.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct BisectBadArgs {
+    /// The revisions to mark [default: the revision being evaluated]
+    #[arg(value_name = "REVSETS")]
+    #[arg(add = ArgValueCompleter::new(complete::revset_expression_all))]
+    revisions: Vec<RevisionArg>,
+}
+
+#[instrument(skip_all)]
+pub(crate) async fn cmd_bisect_bad(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BisectBadArgs,
+) -> Result<(), CommandError> {
+    mark_revisions(ui, command, &args.revisions, Evaluation::Bad).await
+}
@@ -12,16 +12,61 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod bad;
+mod good;
+mod reset;
 mod run;
+mod skip;
+mod start;
+
+use std::collections::HashSet;
+use std::io;
+use std::path::PathBuf;
+use std::slice;
+
+use futures::TryStreamExt as _;
+use jj_lib::backend::CommitId;
+use jj_lib::bisect::BisectionResult;
+use jj_lib::bisect::Bisector;
+use jj_lib::bisect::Evaluation;
+use jj_lib::bisect::NextStep;
+use jj_lib::commit::Commit;
+use jj_lib::file_util::IoResultExt as _;
+use jj_lib::file_util::PathError;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_store::OperationId;
+use jj_lib::repo::Repo;
+use jj_lib::revset::ResolvedRevsetExpression;
+use serde::Deserialize;
+use serde::Serialize;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::cli_util::short_commit_hash;
+use crate::cli_util::short_operation_hash;
 use crate::command_error::CommandError;
+use crate::command_error::internal_error_with_message;
+use crate::command_error::user_error;
 use crate::ui::Ui;
 
-/// Find a bad revision by bisection.
+/// Find a bad revision by bisection
+///
+/// A bisection can either be driven by a command with `jj bisect run`, or be
+/// started with `jj bisect start` and continued by marking the revisions it
+/// checks out with `jj bisect good`, `jj bisect bad`, or `jj bisect skip`.
+///
+/// The state of the bisection is kept in the repo until the search completes,
+/// so an interrupted `jj bisect run` can be resumed, and the two ways can be
+/// mixed. Use `jj bisect reset` to abandon a bisection.
 #[derive(clap::Subcommand, Clone, Debug)]
 pub enum BisectCommand {
+    Bad(bad::BisectBadArgs),
+    Good(good::BisectGoodArgs),
+    Reset(reset::BisectResetArgs),
     Run(run::BisectRunArgs),
+    Skip(skip::BisectSkipArgs),
+    Start(start::BisectStartArgs),
 }
 
 pub async fn cmd_bisect(
@@ -30,6 +75,390 @@ pub async fn cmd_bisect(
     subcommand: &BisectCommand,
 ) -> Result<(), CommandError> {
     match subcommand {
+        BisectCommand::Bad(args) => bad::cmd_bisect_bad(ui, command, args).await,
+        BisectCommand::Good(args) => good::cmd_bisect_good(ui, command, args).await,
+        BisectCommand::Reset(args) => reset::cmd_bisect_reset(ui, command, args),
         BisectCommand::Run(args) => run::cmd_bisect_run(ui, command, args).await,
+        BisectCommand::Skip(args) => skip::cmd_bisect_skip(ui, command, args).await,
+        BisectCommand::Start(args) => start::cmd_bisect_start(ui, command, args).await,
+    }
+}
+
+/// State of the bisection in progress, stored in the repo so that it can be
+/// continued by later commands.
+///
+/// Evaluations are recorded as reported by the user, i.e. before being
+/// inverted for `--find-good`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct BisectState {
+    /// Operation the bisection was started at.
+    start_operation: String,
+    /// Commits in the range to bisect.
+    range: Vec<String>,
+    /// Whether to find the first good revision instead of the first bad one.
+    find_good: bool,
+    good: Vec<String>,
+    bad: Vec<String>,
+    skipped: Vec<String>,
+    /// Revision most recently checked out for evaluation.
+    current: Option<String>,
+}
+
+impl BisectState {
+    fn path(workspace_command: &WorkspaceCommandHelper) -> PathBuf {
+        workspace_command.repo_path().join("bisect_state")
+    }
+
+    /// Loads the state of the bisection in progress, if any.
+    fn load(workspace_command: &WorkspaceCommandHelper) -> Result<Option<Self>, CommandError> {
+        let path = Self::path(workspace_command);
+        let content = match std::fs::read(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(source) => return Err(PathError { path, source }.into()),
+        };
+        let state = serde_json::from_slice(&content).map_err(|err| {
+            internal_error_with_message("Failed to parse the bisection state", err)
+        })?;
+        Ok(Some(state))
+    }
+
+    /// Loads the state of the bisection in progress, failing if there's none.
+    fn load_in_progress(workspace_command: &WorkspaceCommandHelper) -> Result<Self, CommandError> {
+        Self::load(workspace_command)?.ok_or_else(|| {
+            user_error("No bisection in progress")
+                .hinted("Use `jj bisect start` or `jj bisect run --range` to start one.")
+        })
+    }
+
+    fn save(&self, workspace_command: &WorkspaceCommandHelper) -> Result<(), CommandError> {
+        let path = Self::path(workspace_command);
+        let content = serde_json::to_vec(self).map_err(|err| {
+            internal_error_with_message("Failed to serialize the bisection state", err)
+        })?;
+        std::fs::write(&path, content).context(&path)?;
+        Ok(())
+    }
+
+    fn remove(workspace_command: &WorkspaceCommandHelper) -> Result<(), CommandError> {
+        let path = Self::path(workspace_command);
+        match std::fs::remove_file(&path) {
+            Err(source) if source.kind() != io::ErrorKind::NotFound => {
+                Err(PathError { path, source }.into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Converts an evaluation reported by the user to the one to feed to the
+    /// bisector.
+    fn target_evaluation(&self, evaluation: Evaluation) -> Evaluation {
+        if self.find_good {
+            evaluation.invert()
+        } else {
+            evaluation
+        }
+    }
+
+    fn record(&mut self, id: &CommitId, evaluation: Evaluation) {
+        let ids = match evaluation {
+            Evaluation::Good => &mut self.good,
+            Evaluation::Bad => &mut self.bad,
+            Evaluation::Skip => &mut self.skipped,
+            // An aborted bisection can be resumed, so don't record anything.
+            Evaluation::Abort => return,
+        };
+        ids.push(id.hex());
+    }
+
+    fn range_ids(&self) -> Result<Vec<CommitId>, CommandError> {
+        parse_commit_ids(&self.range)
+    }
+
+    /// Creates a bisector with all the recorded evaluations applied.
+    async fn bisector<'repo>(
+        &self,
+        repo: &'repo dyn Repo,
+    ) -> Result<Bisector<'repo>, CommandError> {
+        let range = ResolvedRevsetExpression::commits(self.range_ids()?);
+        let mut bisector = Bisector::new(repo, range).await?;
+        for (ids, evaluation) in [
+            (&self.good, Evaluation::Good),
+            (&self.bad, Evaluation::Bad),
+            (&self.skipped, Evaluation::Skip),
+        ] {
+            for id in parse_commit_ids(ids)? {
+                bisector.mark(id, self.target_evaluation(evaluation));
+            }
+        }
+        Ok(bisector)
+    }
+}
+
+fn parse_commit_ids(hexes: &[String]) -> Result<Vec<CommitId>, CommandError> {
+    hexes
+        .iter()
+        .map(|hex| {
+            CommitId::try_from_hex(hex).ok_or_else(|| {
+                internal_error_with_message(
+                    "Failed to parse the bisection state",
+                    format!("Invalid commit ID: {hex}"),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Starts a new bisection of the `range` and saves its state. Revisions in
+/// `skip` are skipped without being evaluated.
+async fn start_bisection(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    range: &[RevisionArg],
+    skip: &[RevisionArg],
+    find_good: bool,
+) -> Result<BisectState, CommandError> {
+    if BisectState::load(workspace_command)?.is_some() {
+        return Err(user_error("A bisection is already in progress")
+            .hinted("Use `jj bisect reset` to abandon it."));
     }
+    let range_ids: Vec<CommitId> = workspace_command
+        .parse_union_revsets(ui, range)?
+        .evaluate_to_commit_ids()?
+        .try_collect()
+        .await?;
+    let mut state = BisectState {
+        start_operation: workspace_command.repo().op_id().hex(),
+        range: range_ids.iter().map(|id| id.hex()).collect(),
+        find_good,
+        ..BisectState::default()
+    };
+    if !skip.is_empty() {
+        let skip_ids: Vec<CommitId> = workspace_command
+            .parse_union_revsets(ui, skip)?
+            .evaluate_to_commit_ids()?
+            .try_collect()
+            .await?;
+        let range_ids: HashSet<_> = range_ids.into_iter().collect();
+        let bisector = state.bisector(workspace_command.repo().as_ref()).await?;
+        // The heads of the range are assumed to be bad, so they can't be skipped.
+        let skip_ids = skip_ids
+            .into_iter()
+            .filter(|id| range_ids.contains(id) && !bisector.bad_commits().contains(id))
+            .collect::<Vec<_>>();
+        for id in &skip_ids {
+            state.record(id, Evaluation::Skip);
+        }
+    }
+    state.save(workspace_command)?;
+    Ok(state)
+}
+
+/// Marks `revisions` (or the revision being evaluated) with the `evaluation`
+/// reported by the user, then continues the bisection.
+async fn mark_revisions(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    revisions: &[RevisionArg],
+    evaluation: Evaluation,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let mut state = BisectState::load_in_progress(&workspace_command)?;
+    let ids: Vec<CommitId> = if revisions.is_empty() {
+        let current = state.current.as_slice();
+        if current.is_empty() {
+            return Err(user_error("No revision is being evaluated")
+                .hinted("Specify the revisions to mark."));
+        }
+        parse_commit_ids(current)?
+    } else {
+        workspace_command
+            .parse_union_revsets(ui, revisions)?
+            .evaluate_to_commit_ids()?
+            .try_collect()
+            .await?
+    };
+
+    let repo = workspace_command.repo().clone();
+    let range_ids: HashSet<_> = state.range_ids()?.into_iter().collect();
+    let bisector = state.bisector(repo.as_ref()).await?;
+    let target_evaluation = state.target_evaluation(evaluation);
+    let mut new_ids = vec![];
+    for id in ids {
+        if !range_ids.contains(&id) {
+            return Err(user_error(format!(
+                "Revision {} is not in the bisection range",
+                short_commit_hash(&id)
+            )));
+        }
+        let marked = if bisector.good_commits().contains(&id) {
+            Some(Evaluation::Good)
+        } else if bisector.bad_commits().contains(&id) {
+            Some(Evaluation::Bad)
+        } else if bisector.skipped_commits().contains(&id) {
+            Some(Evaluation::Skip)
+        } else {
+            None
+        };
+        match marked {
+            None => new_ids.push(id),
+            Some(marked) if marked == target_evaluation => {}
+            Some(marked) => {
+                return Err(user_error(format!(
+                    "Revision {} is already marked {}",
+                    short_commit_hash(&id),
+                    evaluation_name(state.target_evaluation(marked))
+                )));
+            }
+        }
+    }
+    drop(bisector);
+
+    for id in &new_ids {
+        state.record(id, evaluation);
+    }
+    continue_bisection(ui, &mut workspace_command, state).await
+}
+
+fn evaluation_name(evaluation: Evaluation) -> &'static str {
+    match evaluation {
+        Evaluation::Good => "good",
+        Evaluation::Bad => "bad",
+        Evaluation::Skip => "skipped",
+        Evaluation::Abort => "aborted",
+    }
+}
+
+/// Checks out the next revision to evaluate, or prints the result of the
+/// bisection and removes its state if the search is complete.
+async fn continue_bisection(
+    ui: &mut Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+    mut state: BisectState,
+) -> Result<(), CommandError> {
+    let repo = workspace_command.repo().clone();
+    let mut bisector = state.bisector(repo.as_ref()).await?;
+    match bisector.next_step().await? {
+        NextStep::Evaluate(commit) => {
+            write_progress(ui, workspace_command, &bisector, slice::from_ref(&commit)).await?;
+            state.current = Some(commit.id().hex());
+            state.save(workspace_command)?;
+            check_out_for_evaluation(ui, workspace_command, &commit).await
+        }
+        NextStep::Done(result) => {
+            BisectState::remove(workspace_command)?;
+            write_result(ui, workspace_command, &state, result)
+        }
+    }
+}
+
+/// Prints how many revisions are left to test, and the revisions to be
+/// evaluated next.
+async fn write_progress(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    bisector: &Bisector<'_>,
+    commits: &[Commit],
+) -> Result<(), CommandError> {
+    let mut formatter = ui.stdout_formatter();
+    let (lower, upper) = bisector.remaining_revset().await?.count_estimate()?;
+    let lower_steps = ((lower + 1) as f64).log2().ceil() as usize;
+    if upper == Some(lower) {
+        writeln!(
+            formatter,
+            "Bisecting: {lower} revisions left to test after this (roughly {lower_steps} steps)"
+        )?;
+    } else {
+        writeln!(
+            formatter,
+            "Bisecting: at least {lower} revisions left to test after this (at least roughly \
+             {lower_steps} steps)"
+        )?;
+    }
+    // TODO: Show a graph of the current range instead?
+    let commit_template = workspace_command.commit_summary_template();
+    if let [commit] = commits {
+        write!(formatter, "Now evaluating: ")?;
+        commit_template.format(commit, formatter.as_mut())?;
+        writeln!(formatter)?;
+    } else {
+        writeln!(formatter, "Now evaluating {} revisions:", commits.len())?;
+        for commit in commits {
+            write!(formatter, "  ")?;
+            commit_template.format(commit, formatter.as_mut())?;
+            writeln!(formatter)?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks out `commit` in the workspace so that it can be evaluated.
+async fn check_out_for_evaluation(
+    ui: &mut Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+    commit: &Commit,
+) -> Result<(), CommandError> {
+    let mut tx = workspace_command.start_transaction();
+    tx.check_out(commit)?;
+    tx.finish(
+        ui,
+        format!("Updated to revision {} for bisection", commit.id().hex()),
+    )
+    .await
+}
+
+/// Prints the result of a completed bisection.
+fn write_result(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    state: &BisectState,
+    result: BisectionResult,
+) -> Result<(), CommandError> {
+    let mut formatter = ui.stdout_formatter();
+    writeln!(
+        formatter,
+        "Search complete. To discard any revisions created during search, run:"
+    )?;
+    write_restore_command(formatter.as_mut(), state)?;
+
+    let target = if state.find_good { "good" } else { "bad" };
+    match result {
+        BisectionResult::Abort => Err(user_error("Bisection aborted")),
+        BisectionResult::Indeterminate => Err(user_error(format!(
+            "Could not find the first {target} revision. Was the input range empty?"
+        ))),
+        BisectionResult::Found(first_target_commits) => {
+            let commit_template = workspace_command.commit_summary_template();
+            if let [first_target_commit] = first_target_commits.as_slice() {
+                write!(formatter, "The first {target} revision is: ")?;
+                commit_template.format(first_target_commit, formatter.as_mut())?;
+                writeln!(formatter)?;
+            } else {
+                writeln!(formatter, "The first {target} revisions are:")?;
+                for first_target_commit in first_target_commits {
+                    commit_template.format(&first_target_commit, formatter.as_mut())?;
+                    writeln!(formatter)?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+fn write_restore_command(
+    formatter: &mut dyn io::Write,
+    state: &BisectState,
+) -> Result<(), CommandError> {
+    let op_id = OperationId::try_from_hex(&state.start_operation).ok_or_else(|| {
+        internal_error_with_message(
+            "Failed to parse the bisection state",
+            format!("Invalid operation ID: {}", state.start_operation),
+        )
+    })?;
+    writeln!(
+        formatter,
+        "  jj op restore {}",
+        short_operation_hash(&op_id)
+    )?;
+    Ok(())
 }
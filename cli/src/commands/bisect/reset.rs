@@ -0,0 +1,45 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use tracing::instrument;
+
+use super::BisectState;
+use super::write_restore_command;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Abandon the bisection in progress
+///
+/// The revisions checked out during the bisection are left as they are. Restore
+/// the operation printed by this command to discard them.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct BisectResetArgs {}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_bisect_reset(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &BisectResetArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let state = BisectState::load_in_progress(&workspace_command)?;
+    BisectState::remove(&workspace_command)?;
+    let mut formatter = ui.stdout_formatter();
+    writeln!(
+        formatter,
+        "Bisection reset. To discard any revisions created during search, run:"
+    )?;
+    write_restore_command(formatter.as_mut(), &state)
+}
@@ -12,23 +12,45 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io;
+use std::io::Write as _;
+use std::iter;
+use std::path::PathBuf;
+use std::process::ExitStatus;
+
 use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
 use jj_lib::bisect::BisectionResult;
-use jj_lib::bisect::Bisector;
 use jj_lib::bisect::Evaluation;
+use jj_lib::bisect::NextStep;
 use jj_lib::commit::Commit;
+use jj_lib::file_util::IoResultExt as _;
+use jj_lib::local_working_copy::LocalWorkingCopyFactory;
+use jj_lib::local_working_copy::TreeStateSettings;
 use jj_lib::object_id::ObjectId as _;
+use jj_lib::ref_name::WorkspaceName;
+use jj_lib::ref_name::WorkspaceNameBuf;
+use jj_lib::workspace::Workspace;
+use jj_lib::workspace_store::SimpleWorkspaceStore;
+use jj_lib::workspace_store::WorkspaceStore as _;
 use tracing::instrument;
 
+use super::BisectState;
+use super::check_out_for_evaluation;
+use super::start_bisection;
+use super::write_progress;
+use super::write_result;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::cli_util::WorkspaceCommandHelper;
-use crate::cli_util::short_operation_hash;
 use crate::command_error::CommandError;
 use crate::command_error::cli_error;
+use crate::command_error::config_error_with_message;
 use crate::command_error::internal_error_with_message;
-use crate::command_error::user_error;
 use crate::command_error::user_error_with_message;
+use crate::commands::run::RunOutcome;
+use crate::commands::run::RunSlot;
+use crate::commands::run::Runner;
 use crate::complete;
 use crate::config::CommandNameAndArgs;
 use crate::ui::Ui;
@@ -50,6 +72,17 @@ use crate::ui::Ui;
 /// error code depending on the outcome (e.g. `exit 0` to mark the revision as
 /// good in Bash or Fish).
 ///
+/// If a bisection is already in progress, for example one started with `jj
+/// bisect start` or a previous `jj bisect run` that was interrupted, it is
+/// continued when no `--range` is given.
+///
+/// With `--jobs` greater than 1, several revisions are evaluated at once, each
+/// in a temporary workspace under `.jj/bisect/`. The command is run there
+/// instead of in the current workspace, so `jj` commands run by it operate on
+/// the temporary workspace. The output of the command is captured and printed
+/// once all the revisions have been evaluated. The temporary workspaces are
+/// forgotten when the bisection stops.
+///
 /// Example: To run `cargo test` with the changes from revision `xyz` applied:
 ///
 /// `jj bisect run --range v1.0..main -- bash -c "jj duplicate -r xyz -B @ &&
@@ -61,10 +94,23 @@ pub(crate) struct BisectRunArgs {
     /// This is typically a range like `v1.0..main`. The heads of the range are
     /// assumed to be bad. Ancestors of the range that are not also in the range
     /// are assumed to be good.
-    #[arg(long, short, value_name = "REVSETS", required = true)]
+    ///
+    /// Required unless a bisection is already in progress.
+    #[arg(long, short, value_name = "REVSETS")]
     #[arg(add = ArgValueCompleter::new(complete::revset_expression_all))]
     range: Vec<RevisionArg>,
 
+    /// Revisions to skip without evaluating them
+    ///
+    /// Useful for revisions that are known to be unbuildable, for example.
+    #[arg(long, value_name = "REVSETS", requires = "range")]
+    #[arg(add = ArgValueCompleter::new(complete::revset_expression_all))]
+    skip: Vec<RevisionArg>,
+
+    /// Number of revisions to evaluate in parallel
+    #[arg(long, short, value_name = "N", default_value_t = 1)]
+    jobs: usize,
+
     /// Deprecated. Use positional arguments instead.
     #[arg(
         long = "command",
@@ -97,7 +143,12 @@ pub(crate) struct BisectRunArgs {
     ///
     /// Inverts the interpretation of exit statuses (excluding special exit
     /// statuses).
-    #[arg(long, value_name = "TARGET", default_value = "false")]
+    #[arg(
+        long,
+        value_name = "TARGET",
+        default_value = "false",
+        requires = "range"
+    )]
     find_good: bool,
 }
 
@@ -119,115 +170,59 @@ pub(crate) async fn cmd_bisect_run(
         return Err(cli_error("Command argument is required"));
     }
 
-    let input_range = workspace_command
-        .parse_union_revsets(ui, &args.range)?
-        .resolve()?;
+    let mut state = if args.range.is_empty() {
+        BisectState::load_in_progress(&workspace_command)?
+    } else {
+        start_bisection(
+            ui,
+            &workspace_command,
+            &args.range,
+            &args.skip,
+            args.find_good,
+        )
+        .await?
+    };
 
     let initial_repo = workspace_command.repo().clone();
 
-    let mut bisector = Bisector::new(initial_repo.as_ref(), input_range).await?;
+    let mut bisector = state.bisector(initial_repo.as_ref()).await?;
     let bisection_result = loop {
-        match bisector.next_step().await? {
-            jj_lib::bisect::NextStep::Evaluate(commit) => {
-                {
-                    let mut formatter = ui.stdout_formatter();
-                    let (lower, upper) = bisector.remaining_revset().await?.count_estimate()?;
-                    let lower_steps = ((lower + 1) as f64).log2().ceil() as usize;
-                    if upper == Some(lower) {
-                        writeln!(
-                            formatter,
-                            "Bisecting: {lower} revisions left to test after this (roughly \
-                             {lower_steps} steps)"
-                        )?;
-                    } else {
-                        writeln!(
-                            formatter,
-                            "Bisecting: at least {lower} revisions left to test after this (at \
-                             least roughly {lower_steps} steps)"
-                        )?;
-                    }
-                    // TODO: Show a graph of the current range instead?
-                    let commit_template = workspace_command.commit_summary_template();
-                    write!(formatter, "Now evaluating: ")?;
-                    commit_template.format(&commit, formatter.as_mut())?;
-                    writeln!(formatter)?;
-                }
-
-                let cmd = get_command(args);
-                let evaluation = evaluate_commit(ui, &mut workspace_command, cmd, &commit).await?;
-
-                {
-                    let mut formatter = ui.stdout_formatter();
-                    let message = match evaluation {
-                        Evaluation::Good => "The revision is good.",
-                        Evaluation::Bad => "The revision is bad.",
-                        Evaluation::Skip => {
-                            "It could not be determined if the revision is good or bad."
-                        }
-                        Evaluation::Abort => {
-                            "Evaluation command returned 127 (command not found) - aborting \
-                             bisection."
-                        }
-                    };
-                    writeln!(formatter, "{message}")?;
-                    writeln!(formatter)?;
-                }
-
-                if args.find_good {
-                    // If we're looking for the first good revision,
-                    // invert the evaluation result.
-                    bisector.mark(commit.id().clone(), evaluation.invert());
-                } else {
-                    bisector.mark(commit.id().clone(), evaluation);
-                }
-
-                // Reload the workspace because the evaluation command may run `jj` commands.
-                workspace_command = command.workspace_helper(ui)?;
-            }
-            jj_lib::bisect::NextStep::Done(bisection_result) => {
+        let commits = match bisector.next_step().await? {
+            NextStep::Evaluate(commit) if args.jobs <= 1 => vec![commit],
+            NextStep::Evaluate(_) => bisector.next_candidates(args.jobs).await?,
+            NextStep::Done(bisection_result) => {
                 break bisection_result;
             }
-        }
-    };
+        };
+        write_progress(ui, &workspace_command, &bisector, &commits).await?;
 
-    let mut formatter = ui.stdout_formatter();
-    writeln!(
-        formatter,
-        "Search complete. To discard any revisions created during search, run:"
-    )?;
-    writeln!(
-        formatter,
-        "  jj op restore {}",
-        short_operation_hash(initial_repo.op_id())
-    )?;
+        let evaluations = if let [commit] = commits.as_slice()
+            && args.jobs <= 1
+        {
+            state.current = Some(commit.id().hex());
+            state.save(&workspace_command)?;
+            check_out_for_evaluation(ui, &mut workspace_command, commit).await?;
+            let evaluation = evaluate_commit(get_command(args), commit)?;
+            write_evaluation(ui, evaluation)?;
+            // Reload the workspace because the evaluation command may run `jj` commands.
+            workspace_command = command.workspace_helper(ui)?;
+            vec![evaluation]
+        } else {
+            evaluate_in_workspaces(ui, command, &mut workspace_command, args, &commits).await?
+        };
 
-    let target = if args.find_good { "good" } else { "bad" };
-    match bisection_result {
-        BisectionResult::Abort => {
-            return Err(user_error("Bisection aborted"));
+        for (commit, evaluation) in iter::zip(&commits, evaluations) {
+            bisector.mark(commit.id().clone(), state.target_evaluation(evaluation));
+            state.record(commit.id(), evaluation);
         }
-        BisectionResult::Indeterminate => {
-            return Err(user_error(format!(
-                "Could not find the first {target} revision. Was the input range empty?"
-            )));
-        }
-        BisectionResult::Found(first_target_commits) => {
-            let commit_template = workspace_command.commit_summary_template();
-            if let [first_target_commit] = first_target_commits.as_slice() {
-                write!(formatter, "The first {target} revision is: ")?;
-                commit_template.format(first_target_commit, formatter.as_mut())?;
-                writeln!(formatter)?;
-            } else {
-                writeln!(formatter, "The first {target} revisions are:")?;
-                for first_target_commit in first_target_commits {
-                    commit_template.format(&first_target_commit, formatter.as_mut())?;
-                    writeln!(formatter)?;
-                }
-            }
-        }
-    }
+        state.save(&workspace_command)?;
+    };
 
-    Ok(())
+    forget_workspaces(ui, &mut workspace_command).await?;
+    if !matches!(bisection_result, BisectionResult::Abort) {
+        BisectState::remove(&workspace_command)?;
+    }
+    write_result(ui, &workspace_command, &state, bisection_result)
 }
 
 fn get_command(args: &BisectRunArgs) -> std::process::Command {
@@ -240,31 +235,186 @@ fn get_command(args: &BisectRunArgs) -> std::process::Command {
     }
 }
 
-async fn evaluate_commit(
-    ui: &mut Ui,
-    workspace_command: &mut WorkspaceCommandHelper,
+fn jj_executable_path() -> Result<PathBuf, CommandError> {
+    std::env::current_exe()
+        .map_err(|err| internal_error_with_message("Could not get path for the jj executable", err))
+}
+
+/// Runs the evaluation command in the workspace, where `commit` is checked out.
+fn evaluate_commit(
     mut cmd: std::process::Command,
     commit: &Commit,
 ) -> Result<Evaluation, CommandError> {
+    tracing::info!(?cmd, "running bisection evaluation command");
+    let status = cmd
+        .env("JJ_EXECUTABLE_PATH", jj_executable_path()?)
+        .env("JJ_BISECT_TARGET", commit.id().hex())
+        .status()
+        .map_err(|err| user_error_with_message("Failed to run evaluation command", err))?;
+    Ok(evaluation_from_status(status))
+}
+
+/// Evaluates all `commits` at once, each in a workspace of its own.
+async fn evaluate_in_workspaces(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    workspace_command: &mut WorkspaceCommandHelper,
+    args: &BisectRunArgs,
+    commits: &[Commit],
+) -> Result<Vec<Evaluation>, CommandError> {
+    // The commits may have been loaded before the workspace was reloaded, and
+    // trees can only be compared within the same store.
+    let store = commits[0].store().clone();
+    let slots = check_out_in_workspaces(ui, command, workspace_command, commits).await?;
+    let tree_state_settings =
+        TreeStateSettings::try_from_user_settings(workspace_command.settings()).map_err(|err| {
+            config_error_with_message("Failed to read the tree state settings", err)
+        })?;
+    let auto_tracking_matcher = workspace_command.auto_tracking_matcher(ui)?;
+    let snapshot_options = workspace_command
+        .snapshot_options_with_start_tracking_matcher(auto_tracking_matcher.as_ref())?;
+    let jj_executable_path = jj_executable_path()?;
+    let build_command = |commit: &Commit| {
+        let mut cmd = get_command(args);
+        cmd.env("JJ_EXECUTABLE_PATH", &jj_executable_path)
+            .env("JJ_BISECT_TARGET", commit.id().hex());
+        cmd
+    };
+    let runner = Runner {
+        store: &store,
+        slots: &slots,
+        command_description: "evaluation command",
+        build_command: &build_command,
+        tree_state_settings: &tree_state_settings,
+        snapshot_options: &snapshot_options,
+    };
+    let outcomes = runner.run_each(commits)?;
+    // Reload the workspace because the evaluation command may run `jj` commands.
+    *workspace_command = command.workspace_helper(ui)?;
+
+    let commit_template = workspace_command.commit_summary_template();
+    let mut evaluations = vec![];
+    for (commit, RunOutcome { output, .. }) in iter::zip(commits, outcomes) {
+        {
+            let mut formatter = ui.stdout_formatter();
+            write!(formatter, "Evaluated: ")?;
+            commit_template.format(commit, formatter.as_mut())?;
+            writeln!(formatter)?;
+        }
+        ui.stdout().write_all(&output.stdout)?;
+        ui.stderr().write_all(&output.stderr)?;
+        let evaluation = evaluation_from_status(output.status);
+        write_evaluation(ui, evaluation)?;
+        evaluations.push(evaluation);
+    }
+    Ok(evaluations)
+}
+
+/// Checks out each of `commits` in a workspace under `.jj/bisect/`, creating
+/// the workspaces as needed. Returns the working copies of the workspaces.
+async fn check_out_in_workspaces(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    workspace_command: &mut WorkspaceCommandHelper,
+    commits: &[Commit],
+) -> Result<Vec<RunSlot>, CommandError> {
+    let bisect_dir = bisect_workspaces_dir(workspace_command);
+    let mut slots = vec![];
+    let mut names = vec![];
+    let mut repo = workspace_command.repo().clone();
+    for slot in 0..commits.len() {
+        let name = bisect_workspace_name(workspace_command.workspace_name(), slot);
+        let workspace_root = bisect_dir.join(slot.to_string());
+        let jj_dir = workspace_root.join(".jj");
+        if !jj_dir.exists() || repo.view().get_wc_commit_id(&name).is_none() {
+            // Leftovers from an interrupted run would show up as untracked files.
+            if workspace_root.exists() {
+                std::fs::remove_dir_all(&workspace_root).context(&workspace_root)?;
+            }
+            std::fs::create_dir_all(&workspace_root).context(&workspace_root)?;
+            (_, repo) = Workspace::init_workspace_with_existing_repo(
+                &workspace_root,
+                workspace_command.repo_path(),
+                &repo,
+                &LocalWorkingCopyFactory {},
+                name.clone(),
+            )
+            .await?;
+        }
+        slots.push(RunSlot {
+            working_copy_path: workspace_root,
+            state_path: jj_dir.join("working_copy"),
+        });
+        names.push(name);
+    }
+    if repo.op_id() != workspace_command.repo().op_id() {
+        *workspace_command = command.workspace_helper(ui)?;
+    }
+
     let mut tx = workspace_command.start_transaction();
-    let commit_id_hex = commit.id().hex();
-    tx.check_out(commit)?;
+    for (name, commit) in iter::zip(names, commits) {
+        tx.repo_mut().check_out(name, commit).await?;
+    }
     tx.finish(
         ui,
-        format!("Updated to revision {commit_id_hex} for bisection"),
+        format!("Updated {} workspaces for bisection", commits.len()),
     )
     .await?;
+    Ok(slots)
+}
 
-    let jj_executable_path = std::env::current_exe().map_err(|err| {
-        internal_error_with_message("Could not get path for the jj executable", err)
-    })?;
-    tracing::info!(?cmd, "running bisection evaluation command");
-    let status = cmd
-        .env("JJ_EXECUTABLE_PATH", jj_executable_path)
-        .env("JJ_BISECT_TARGET", &commit_id_hex)
-        .status()
-        .map_err(|err| user_error_with_message("Failed to run evaluation command", err))?;
-    let evaluation = if status.success() {
+/// Forgets the workspaces created by [`check_out_in_workspaces()`].
+async fn forget_workspaces(
+    ui: &mut Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+) -> Result<(), CommandError> {
+    let prefix = bisect_workspace_name_prefix(workspace_command.workspace_name());
+    let names = workspace_command
+        .repo()
+        .view()
+        .wc_commit_ids()
+        .keys()
+        .filter(|name| {
+            name.as_str()
+                .strip_prefix(&prefix)
+                .is_some_and(|slot| slot.parse::<usize>().is_ok())
+        })
+        .cloned()
+        .collect_vec();
+    if !names.is_empty() {
+        let workspace_store = SimpleWorkspaceStore::load(workspace_command.repo_path())?;
+        let mut tx = workspace_command.start_transaction();
+        for name in &names {
+            tx.repo_mut().remove_wc_commit(name).await?;
+        }
+        workspace_store.forget(&names.iter().map(|name| name.as_ref()).collect_vec())?;
+        tx.finish(ui, "Forgot workspaces used for bisection")
+            .await?;
+    }
+    let bisect_dir = bisect_workspaces_dir(workspace_command);
+    if bisect_dir.exists() {
+        std::fs::remove_dir_all(&bisect_dir).context(&bisect_dir)?;
+    }
+    Ok(())
+}
+
+fn bisect_workspaces_dir(workspace_command: &WorkspaceCommandHelper) -> PathBuf {
+    workspace_command
+        .workspace_root()
+        .join(".jj")
+        .join("bisect")
+}
+
+fn bisect_workspace_name_prefix(workspace_name: &WorkspaceName) -> String {
+    format!("{}-bisect-", workspace_name.as_str())
+}
+
+fn bisect_workspace_name(workspace_name: &WorkspaceName, slot: usize) -> WorkspaceNameBuf {
+    format!("{}{slot}", bisect_workspace_name_prefix(workspace_name)).into()
+}
+
+fn evaluation_from_status(status: ExitStatus) -> Evaluation {
+    if status.success() {
         Evaluation::Good
     } else {
         match status.code() {
@@ -272,7 +422,20 @@ async fn evaluate_commit(
             Some(127) => Evaluation::Abort,
             _ => Evaluation::Bad,
         }
-    };
+    }
+}
 
-    Ok(evaluation)
+fn write_evaluation(ui: &Ui, evaluation: Evaluation) -> io::Result<()> {
+    let mut formatter = ui.stdout_formatter();
+    let message = match evaluation {
+        Evaluation::Good => "The revision is good.",
+        Evaluation::Bad => "The revision is bad.",
+        Evaluation::Skip => "It could not be determined if the revision is good or bad.",
+        Evaluation::Abort => {
+            "Evaluation command returned 127 (command not found) - aborting bisection."
+        }
+    };
+    writeln!(formatter, "{message}")?;
+    writeln!(formatter)?;
+    Ok(())
 }
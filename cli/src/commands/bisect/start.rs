@@ -0,0 +1,73 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCompleter;
+use tracing::instrument;
+
+use super::continue_bisection;
+use super::start_bisection;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Start a bisection to find the first bad revision by hand
+///
+/// Checks out the first revision to evaluate. Mark it with `jj bisect good`,
+/// `jj bisect bad`, or `jj bisect skip` to check out the next one, until the
+/// first bad revision is found. The bisection can also be handed over to a
+/// command at any point with `jj bisect run`.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct BisectStartArgs {
+    /// Range of revisions to bisect
+    ///
+    /// This is typically a range like `v1.0..main`. The heads of the range are
+    /// assumed to be bad. Ancestors of the range that are not also in the range
+    /// are assumed to be good.
+    #[arg(long, short, value_name = "REVSETS", required = true)]
+    #[arg(add = ArgValueCompleter::new(complete::revset_expression_all))]
+    range: Vec<RevisionArg>,
+
+    /// Revisions to skip without evaluating them
+    ///
+    /// Useful for revisions that are known to be unbuildable, for example.
+    #[arg(long, value_name = "REVSETS")]
+    #[arg(add = ArgValueCompleter::new(complete::revset_expression_all))]
+    skip: Vec<RevisionArg>,
+
+    /// Whether to find the first good revision instead
+    ///
+    /// Inverts the meaning of `jj bisect good` and `jj bisect bad`.
+    #[arg(long)]
+    find_good: bool,
+}
+
+#[instrument(skip_all)]
+pub(crate) async fn cmd_bisect_start(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BisectStartArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let state = start_bisection(
+        ui,
+        &workspace_command,
+        &args.range,
+        &args.skip,
+        args.find_good,
+    )
+    .await?;
+    continue_bisection(ui, &mut workspace_command, state).await
+}
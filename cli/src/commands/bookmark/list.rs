@@ -149,12 +149,8 @@ pub async fn cmd_bookmark_list(
 
     let template: TemplateRenderer<Rc<CommitRef>> = {
         let language = workspace_command.commit_template_language();
-        let text = match &args.template {
-            Some(value) => value.to_owned(),
-            None => workspace_command
-                .settings()
-                .get("templates.bookmark_list")?,
-        };
+        let text =
+            command.list_template_text(args.template.as_deref(), "templates.bookmark_list")?;
         workspace_command
             .parse_template(ui, &language, &text)?
             .labeled(["bookmark_list"])
@@ -0,0 +1,127 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(target_os = "linux")]
+use std::io::Write as _;
+
+use clap::Subcommand;
+#[cfg(target_os = "linux")]
+use jj_lib::fsmonitor::FsmonitorSettings;
+#[cfg(target_os = "linux")]
+use jj_lib::fsmonitor::inotify;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+#[cfg(target_os = "linux")]
+use crate::commands::debug::check_local_disk_wc;
+use crate::ui::Ui;
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum DebugInotifyCommand {
+    /// Check whether the inotify monitor is enabled and whether its daemon is
+    /// running
+    Status,
+    QueryChangedFiles,
+    /// Watch the working copy in the foreground
+    ///
+    /// This is normally started in the background by any command that
+    /// snapshots the working copy, in which case errors are written to the
+    /// `daemon.log` file shown by `jj debug inotify status`.
+    Daemon,
+    /// Ask the daemon watching the working copy to exit
+    Stop,
+}
+
+#[cfg(target_os = "linux")]
+pub async fn cmd_debug_inotify(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &DebugInotifyCommand,
+) -> Result<(), CommandError> {
+    // Don't snapshot: that would start the daemon as a side effect.
+    let workspace = command.load_workspace()?;
+    let wc = check_local_disk_wc(workspace.working_copy())?;
+    let daemon_dir = inotify::daemon_dir(wc.state_path());
+    match subcommand {
+        DebugInotifyCommand::Status => {
+            match FsmonitorSettings::from_settings(command.settings())? {
+                FsmonitorSettings::Inotify => {
+                    writeln!(ui.stdout(), "Inotify is enabled via `fsmonitor.backend`.")?;
+                }
+                _ => {
+                    writeln!(
+                        ui.stdout(),
+                        r#"Inotify is disabled. Set `fsmonitor.backend="inotify"` to enable."#
+                    )?;
+                }
+            }
+            let running = inotify::is_daemon_running(&daemon_dir).map_err(user_error)?;
+            writeln!(
+                ui.stdout(),
+                "The inotify daemon is {}.",
+                if running { "running" } else { "not running" }
+            )?;
+            if inotify::is_watch_limit_reached(&daemon_dir) {
+                writeln!(
+                    ui.stdout(),
+                    "The inotify daemon ran out of watches and won't be started again until \
+                     `fs.inotify.max_user_watches` is raised."
+                )?;
+            }
+            let log_path = inotify::daemon_log_path(&daemon_dir);
+            if let Ok(log) = fs::read_to_string(&log_path)
+                && !log.is_empty()
+            {
+                writeln!(
+                    ui.stdout(),
+                    "Errors of daemons started in the background ({}):",
+                    log_path.display()
+                )?;
+                write!(ui.stdout(), "{log}")?;
+            }
+        }
+        DebugInotifyCommand::QueryChangedFiles => {
+            let (_clock, changed_files) =
+                inotify::query_changed_files(&daemon_dir, None).map_err(user_error)?;
+            writeln!(ui.stdout(), "Changed files: {changed_files:?}")?;
+        }
+        DebugInotifyCommand::Daemon => {
+            let base_ignores = command.workspace_helper_no_snapshot(ui)?.base_ignores()?;
+            inotify::run_daemon(workspace.workspace_root(), &daemon_dir, base_ignores)
+                .map_err(user_error)?;
+        }
+        DebugInotifyCommand::Stop => {
+            if inotify::stop_daemon(&daemon_dir).map_err(user_error)? {
+                writeln!(ui.status(), "Asked the inotify daemon to stop")?;
+            } else {
+                writeln!(ui.status(), "The inotify daemon is not running")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub async fn cmd_debug_inotify(
+    _ui: &mut Ui,
+    _command: &CommandHelper,
+    _subcommand: &DebugInotifyCommand,
+) -> Result<(), CommandError> {
+    Err(user_error(
+        "The inotify filesystem monitor is only supported on Linux",
+    ))
+}
@@ -16,10 +16,13 @@ mod copy_detection;
 mod fileset;
 mod index;
 mod index_changed_paths;
+mod init_remote;
 mod init_simple;
+mod inotify;
 mod local_working_copy;
 mod object;
 mod reindex;
+mod remote_store_server;
 mod revset;
 mod snapshot;
 mod stacked_table;
@@ -40,14 +43,20 @@ use self::index::DebugIndexArgs;
 use self::index::cmd_debug_index;
 use self::index_changed_paths::DebugIndexChangedPathsArgs;
 use self::index_changed_paths::cmd_debug_index_changed_paths;
+use self::init_remote::DebugInitRemoteArgs;
+use self::init_remote::cmd_debug_init_remote;
 use self::init_simple::DebugInitSimpleArgs;
 use self::init_simple::cmd_debug_init_simple;
+use self::inotify::DebugInotifyCommand;
+use self::inotify::cmd_debug_inotify;
 use self::local_working_copy::DebugLocalWorkingCopyArgs;
 use self::local_working_copy::cmd_debug_local_working_copy;
 use self::object::DebugObjectArgs;
 use self::object::cmd_debug_object;
 use self::reindex::DebugReindexArgs;
 use self::reindex::cmd_debug_reindex;
+use self::remote_store_server::DebugRemoteStoreServerArgs;
+use self::remote_store_server::cmd_debug_remote_store_server;
 use self::revset::DebugRevsetArgs;
 use self::revset::cmd_debug_revset;
 use self::snapshot::DebugSnapshotArgs;
@@ -75,11 +84,15 @@ pub enum DebugCommand {
     Fileset(DebugFilesetArgs),
     Index(DebugIndexArgs),
     IndexChangedPaths(DebugIndexChangedPathsArgs),
+    InitRemote(DebugInitRemoteArgs),
     InitSimple(DebugInitSimpleArgs),
+    #[command(subcommand)]
+    Inotify(DebugInotifyCommand),
     LocalWorkingCopy(DebugLocalWorkingCopyArgs),
     #[command(subcommand)]
     Object(DebugObjectArgs),
     Reindex(DebugReindexArgs),
+    RemoteStoreServer(DebugRemoteStoreServerArgs),
     Revset(DebugRevsetArgs),
     Snapshot(DebugSnapshotArgs),
     StackedTable(DebugStackedTableArgs),
@@ -102,12 +115,17 @@ pub async fn cmd_debug(
         DebugCommand::IndexChangedPaths(args) => {
             cmd_debug_index_changed_paths(ui, command, args).await
         }
+        DebugCommand::InitRemote(args) => cmd_debug_init_remote(ui, command, args).await,
         DebugCommand::InitSimple(args) => cmd_debug_init_simple(ui, command, args).await,
+        DebugCommand::Inotify(args) => cmd_debug_inotify(ui, command, args).await,
         DebugCommand::LocalWorkingCopy(args) => {
             cmd_debug_local_working_copy(ui, command, args).await
         }
         DebugCommand::Object(args) => cmd_debug_object(ui, command, args).await,
         DebugCommand::Reindex(args) => cmd_debug_reindex(ui, command, args).await,
+        DebugCommand::RemoteStoreServer(args) => {
+            cmd_debug_remote_store_server(ui, command, args).await
+        }
         DebugCommand::Revset(args) => cmd_debug_revset(ui, command, args).await,
         DebugCommand::Snapshot(args) => cmd_debug_snapshot(ui, command, args).await,
         DebugCommand::StackedTable(args) => cmd_debug_stacked_table(ui, command, args).await,
@@ -0,0 +1,50 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use jj_lib::remote_backend::RemoteStoreServer;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error_with_message;
+use crate::ui::Ui;
+
+/// Serve a remote content-addressed store from a local directory
+///
+/// This is a reference implementation of the store used by repos created with
+/// `jj debug init-remote`. It's meant for testing, and doesn't authenticate
+/// clients.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct DebugRemoteStoreServerArgs {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:0")]
+    listen: String,
+    /// Directory to store objects in
+    #[arg(value_hint = clap::ValueHint::DirPath)]
+    path: String,
+}
+
+pub(crate) async fn cmd_debug_remote_store_server(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DebugRemoteStoreServerArgs,
+) -> Result<(), CommandError> {
+    let store_path = command.cwd().join(&args.path);
+    let server = RemoteStoreServer::bind(&args.listen, &store_path)
+        .map_err(|err| user_error_with_message("Failed to start remote store server", err))?;
+    writeln!(ui.status(), "Listening on {}", server.url()?)?;
+    server.serve()?;
+    Ok(())
+}
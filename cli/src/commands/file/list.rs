@@ -69,10 +69,7 @@ pub(crate) async fn cmd_file_list(
     let matcher = fileset_expression.to_matcher();
     let template: TemplateRenderer<TreeEntry> = {
         let language = workspace_command.commit_template_language();
-        let text = match &args.template {
-            Some(value) => value.to_owned(),
-            None => workspace_command.settings().get("templates.file_list")?,
-        };
+        let text = command.list_template_text(args.template.as_deref(), "templates.file_list")?;
         workspace_command
             .parse_template(ui, &language, &text)?
             .labeled(["file_list"])
@@ -14,6 +14,7 @@
 
 use std::collections::HashMap;
 use std::io::Write as _;
+use std::iter;
 use std::path::Path;
 use std::process::Stdio;
 
@@ -27,6 +28,8 @@ use jj_lib::fileset::FilesetDiagnostics;
 use jj_lib::fileset::FilesetExpression;
 use jj_lib::fileset::FilesetParseContext;
 use jj_lib::fix::FileToFix;
+use jj_lib::fix::FixCache;
+use jj_lib::fix::FixCacheKey;
 use jj_lib::fix::FixError;
 use jj_lib::fix::ParallelFileFixer;
 use jj_lib::fix::fix_files;
@@ -75,6 +78,11 @@ use crate::ui::Ui;
 /// reused. This means that tools used with `jj fix` must produce deterministic
 /// output.
 ///
+/// The results are also cached in the repository, keyed by the tool's command
+/// line and the input file content, so the tools are not run again on content
+/// they have already processed in previous invocations. Use `--no-cache` to
+/// ignore the cached results, for example after upgrading a tool.
+///
 /// ### Configuration
 ///
 /// See `jj help -k config` chapter `Code formatting and other file content
@@ -171,6 +179,10 @@ pub(crate) struct FixArgs {
     /// specified, all files in the repo will be fixed.
     #[arg(long)]
     include_unchanged_files: bool,
+
+    /// Run the tools even if their results for the same input are cached
+    #[arg(long)]
+    no_cache: bool,
 }
 
 #[instrument(skip_all)]
@@ -218,6 +230,7 @@ pub(crate) async fn cmd_fix(
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
     let matcher = fileset_expression.to_matcher();
 
+    let cache = FixCache::load(workspace_command.repo_path().join("fix_cache"))?;
     let mut tx = workspace_command.start_transaction();
     let mut parallel_fixer = ParallelFileFixer::new(|store, file_to_fix| {
         fix_one_file(
@@ -225,6 +238,8 @@ pub(crate) async fn cmd_fix(
             &workspace_root,
             &path_converter,
             &tools_config,
+            &cache,
+            !args.no_cache,
             store,
             file_to_fix,
         )
@@ -241,6 +256,7 @@ pub(crate) async fn cmd_fix(
         &mut parallel_fixer,
     )
     .await?;
+    cache.save()?;
     writeln!(
         ui.status(),
         "Fixed {} commits of {} checked.",
@@ -261,58 +277,115 @@ pub(crate) async fn cmd_fix(
 /// is simply skipped and we proceed to invoke the next tool (this is
 /// indistinguishable from succeeding with no changes).
 ///
+/// Successful tool invocations are recorded in the `cache`. If `use_cache` is
+/// true, a tool isn't invoked at all if its output for the same input content
+/// is already cached.
+///
 /// TODO: Better error handling so we can tell the user what went wrong with
 /// each failed input.
+#[expect(clippy::too_many_arguments)]
 async fn fix_one_file(
     ui: &Ui,
     workspace_root: &Path,
     path_converter: &RepoPathUiConverter,
     tools_config: &ToolsConfig,
+    cache: &FixCache,
+    use_cache: bool,
     store: &Store,
     file_to_fix: &FileToFix,
 ) -> Result<Option<FileId>, FixError> {
-    let mut matching_tools = tools_config
+    let matching_tools = tools_config
         .tools
         .iter()
-        .filter(|tool_config| tool_config.matcher.matches(&file_to_fix.repo_path))
-        .peekable();
-    if matching_tools.peek().is_some() {
-        // The first matching tool gets its input from the committed file, and any
-        // subsequent matching tool gets its input from the previous matching tool's
-        // output.
-        let mut old_content = vec![];
-        let mut read = store
-            .read_file(&file_to_fix.repo_path, &file_to_fix.file_id)
-            .await?;
-        read.read_to_end(&mut old_content).await?;
-        let new_content = matching_tools.fold(old_content.clone(), |prev_content, tool_config| {
-            match run_tool(
-                ui,
-                workspace_root,
-                path_converter,
-                &tool_config.command,
-                file_to_fix,
-                &prev_content,
-            ) {
-                Ok(next_content) => next_content,
-                // TODO: Because the stderr is passed through, this isn't always failing
-                // silently, but it should do something better will the exit code, tool
-                // name, etc.
-                Err(()) => prev_content,
+        .filter(|tool_config| tool_config.matcher.matches(&file_to_fix.repo_path));
+    let vars = tool_variables(workspace_root, file_to_fix);
+    // The first matching tool gets its input from the committed file, and any
+    // subsequent matching tool gets its input from the previous matching tool's
+    // output. The content is only read if a tool actually needs to be run.
+    let mut file_id = file_to_fix.file_id.clone();
+    let mut content: Option<Vec<u8>> = None;
+    for tool_config in matching_tools {
+        let mut command = tool_config.command.to_command_with_variables(&vars);
+        let cache_key = FixCacheKey::new(&command_cache_args(&command), &file_id);
+        if use_cache && let Some(cached_file_id) = cache.get(&cache_key) {
+            tracing::debug!(?command, ?file_to_fix.repo_path, "using cached fix tool output");
+            if cached_file_id != file_id {
+                content = None;
+                file_id = cached_file_id;
             }
-        });
-        if new_content != old_content {
-            // TODO: send futures back over channel
-            let new_file_id = store
-                .write_file(&file_to_fix.repo_path, &mut new_content.as_slice())
-                .await?;
-            return Ok(Some(new_file_id));
+            continue;
+        }
+        let old_content = match content.take() {
+            Some(content) => content,
+            None => {
+                let mut content = vec![];
+                let mut read = store.read_file(&file_to_fix.repo_path, &file_id).await?;
+                read.read_to_end(&mut content).await?;
+                content
+            }
+        };
+        match run_tool(
+            ui,
+            workspace_root,
+            path_converter,
+            &tool_config.command,
+            &mut command,
+            file_to_fix,
+            &old_content,
+        ) {
+            Ok(new_content) => {
+                if new_content != old_content {
+                    // TODO: send futures back over channel
+                    file_id = store
+                        .write_file(&file_to_fix.repo_path, &mut new_content.as_slice())
+                        .await?;
+                }
+                cache.insert(cache_key, file_id.clone());
+                content = Some(new_content);
+            }
+            // TODO: Because the stderr is passed through, this isn't always failing
+            // silently, but it should do something better will the exit code, tool
+            // name, etc.
+            Err(()) => content = Some(old_content),
         }
     }
-    Ok(None)
+    Ok((file_id != file_to_fix.file_id).then_some(file_id))
 }
 
-/// Runs the `tool_command` to fix the given file content.
+/// Returns the variables that can be substituted in the tool commands.
+fn tool_variables<'a>(
+    workspace_root: &'a Path,
+    file_to_fix: &'a FileToFix,
+) -> HashMap<&'static str, &'a str> {
+    let mut vars: HashMap<&str, &str> = HashMap::new();
+    vars.insert("path", file_to_fix.repo_path.as_internal_file_string());
+    // TODO: workspace_root.to_str() returns None if the workspace path is not
+    // UTF-8, but we ignore that failure so `jj fix` still runs in that
+    // situation. Maybe we should do something like substituting bytes instead
+    // of strings so we can handle any Path here.
+    if let Some(root) = workspace_root.to_str() {
+        vars.insert("root", root);
+    }
+    vars
+}
+
+/// Returns the parts of the `command` that identify the tool invocation in the
+/// cache.
+fn command_cache_args(command: &std::process::Command) -> Vec<Vec<u8>> {
+    let envs = command.get_envs().flat_map(|(key, value)| {
+        [
+            key.as_encoded_bytes(),
+            value.unwrap_or_default().as_encoded_bytes(),
+        ]
+    });
+    iter::once(command.get_program().as_encoded_bytes())
+        .chain(command.get_args().map(|arg| arg.as_encoded_bytes()))
+        .chain(envs)
+        .map(|bytes| bytes.to_vec())
+        .collect()
+}
+
+/// Runs the `command` built from `tool_command` to fix the given file content.
 ///
 /// The `old_content` is assumed to be that of the `file_to_fix`'s `FileId`, but
 /// this is not verified.
@@ -325,19 +398,10 @@ fn run_tool(
     workspace_root: &Path,
     path_converter: &RepoPathUiConverter,
     tool_command: &CommandNameAndArgs,
+    command: &mut std::process::Command,
     file_to_fix: &FileToFix,
     old_content: &[u8],
 ) -> Result<Vec<u8>, ()> {
-    let mut vars: HashMap<&str, &str> = HashMap::new();
-    vars.insert("path", file_to_fix.repo_path.as_internal_file_string());
-    // TODO: workspace_root.to_str() returns None if the workspace path is not
-    // UTF-8, but we ignore that failure so `jj fix` still runs in that
-    // situation. Maybe we should do something like substituting bytes instead
-    // of strings so we can handle any Path here.
-    if let Some(root) = workspace_root.to_str() {
-        vars.insert("root", root);
-    }
-    let mut command = tool_command.to_command_with_variables(&vars);
     tracing::debug!(?command, ?file_to_fix.repo_path, "spawning fix tool");
     let Ok(mut child) = command
         .current_dir(workspace_root)
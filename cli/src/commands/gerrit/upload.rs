@@ -19,6 +19,7 @@ use std::sync::Arc;
 
 use bstr::BStr;
 use futures::TryStreamExt as _;
+use itertools::Itertools as _;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
 use jj_lib::git;
@@ -30,6 +31,7 @@ use jj_lib::object_id::ObjectId as _;
 use jj_lib::repo::Repo as _;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::settings::UserSettings;
+use jj_lib::signing::SignPolicy;
 use jj_lib::store::Store;
 use jj_lib::trailer::Trailer;
 use jj_lib::trailer::parse_description_trailers;
@@ -42,7 +44,11 @@ use crate::command_error::internal_error;
 use crate::command_error::user_error;
 use crate::command_error::user_error_with_message;
 use crate::git_util::GitSubprocessUi;
+use crate::git_util::check_sign_policy_before_push;
+use crate::git_util::needs_signing_on_push;
 use crate::git_util::print_push_stats;
+use crate::git_util::sign_on_push_settings;
+use crate::git_util::warn_unchecked_signatures_on_dry_run;
 use crate::ui::Ui;
 
 /// Upload changes to Gerrit for code review, or update existing changes.
@@ -496,6 +502,15 @@ pub async fn cmd_gerrit_upload(
         }
     }
 
+    let sign_on_push = tx.settings().get_bool("git.sign-on-push")?;
+    let sign_settings = sign_on_push_settings(tx.settings());
+    let sign_policy = SignPolicy::from_settings(tx.settings())?;
+    let commits_to_check = if sign_policy.is_required_on_push() {
+        to_upload.clone()
+    } else {
+        vec![]
+    };
+
     let mut old_to_new: HashMap<CommitId, Commit> = HashMap::new();
     for original_commit in to_upload.into_iter().rev() {
         let trailers = parse_description_trailers(original_commit.description());
@@ -569,8 +584,13 @@ pub async fn cmd_gerrit_upload(
             .map(|id| old_to_new.get(id).map_or(id, |p| p.id()).clone())
             .collect();
 
+        let needs_signing = sign_on_push
+            && !args.dry_run
+            && needs_signing_on_push(&sign_settings, &original_commit);
+
         if new_description == original_commit.description()
             && new_parents == original_commit.parent_ids()
+            && !needs_signing
         {
             // map the old commit to itself
             old_to_new.insert(original_commit.id().clone(), original_commit);
@@ -579,7 +599,7 @@ pub async fn cmd_gerrit_upload(
 
         // rewrite the set of parents to point to the commits that were
         // previously rewritten in toposort order
-        let new_commit = tx
+        let mut commit_builder = tx
             .repo_mut()
             .rewrite_commit(&original_commit)
             .set_description(new_description)
@@ -588,13 +608,30 @@ pub async fn cmd_gerrit_upload(
             // Otherwise, `jj gerrit upload @ && jj gerrit upload @` will upload
             // two patchsets with the only difference being the timestamp.
             .set_committer(original_commit.committer().clone())
-            .set_author(original_commit.author().clone())
-            .write()
-            .await?;
+            .set_author(original_commit.author().clone());
+        if needs_signing {
+            commit_builder = commit_builder.set_sign_behavior(sign_settings.behavior);
+        }
+        let new_commit = commit_builder.write().await?;
 
         old_to_new.insert(original_commit.id().clone(), new_commit);
     }
 
+    // In dry-run mode, these commits would be signed before pushing.
+    let (commits_to_sign, commits_to_check): (Vec<_>, Vec<_>) =
+        commits_to_check.into_iter().partition(|commit| {
+            sign_on_push && args.dry_run && needs_signing_on_push(&sign_settings, commit)
+        });
+    warn_unchecked_signatures_on_dry_run(ui, commits_to_sign.len())?;
+    let commits_to_check = commits_to_check
+        .into_iter()
+        .map(|commit| {
+            let new_commit = old_to_new[commit.id()].clone();
+            (commit, new_commit)
+        })
+        .collect_vec();
+    check_sign_policy_before_push(tx.base_workspace_helper(), &sign_policy, &commits_to_check)?;
+
     let remote_ref = format!("refs/for/{remote_branch}");
     writeln!(
         ui.status(),
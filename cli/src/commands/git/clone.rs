@@ -23,6 +23,7 @@ use jj_lib::file_util;
 use jj_lib::git;
 use jj_lib::git::FetchTagsOverride;
 use jj_lib::git::GitFetch;
+use jj_lib::git::GitFetchFilter;
 use jj_lib::git::GitFetchRefExpression;
 use jj_lib::git::GitSettings;
 use jj_lib::git::expand_fetch_refspecs;
@@ -105,6 +106,17 @@ pub struct GitCloneArgs {
     #[arg(long)]
     depth: Option<NonZeroU32>,
 
+    /// Create a partial clone that omits some objects from the initial fetch
+    ///
+    /// The omitted objects are fetched from the remote on demand when they
+    /// are needed, e.g. when a file is checked out or diffed. Such commands
+    /// will fail if the remote is unreachable and the objects haven't been
+    /// fetched before.
+    ///
+    /// The remote must support partial clones.
+    #[arg(long, value_enum, value_name = "FILTER")]
+    filter: Option<PartialCloneFilter>,
+
     /// Configure when to fetch tags
     ///
     /// Unless otherwise specified, the initial clone will fetch all tags,
@@ -134,6 +146,26 @@ pub struct GitCloneArgs {
     // TODO: add --tag option and save it in jj's repo config? (#7819)
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+enum PartialCloneFilter {
+    /// Omit all file contents
+    #[value(name = "blob:none")]
+    BlobNone,
+
+    /// Omit all file contents and directory listings
+    #[value(name = "tree:0")]
+    TreeZero,
+}
+
+impl PartialCloneFilter {
+    fn as_fetch_filter(self) -> GitFetchFilter {
+        match self {
+            Self::BlobNone => GitFetchFilter::BlobNone,
+            Self::TreeZero => GitFetchFilter::TreeZero,
+        }
+    }
+}
+
 fn clone_destination_for_source(source: &str) -> Option<&str> {
     let destination = source.strip_suffix(".git").unwrap_or(source);
     let destination = destination.strip_suffix('/').unwrap_or(destination);
@@ -215,6 +247,7 @@ pub async fn cmd_git_clone(
             // will be loaded here?
             &ref_expr,
             args.depth,
+            args.filter,
             args.fetch_tags,
         )
         .await?;
@@ -338,6 +371,7 @@ async fn fetch_new_remote(
     remote_name: &RemoteName,
     ref_expr: &GitFetchRefExpression,
     depth: Option<NonZeroU32>,
+    filter: Option<PartialCloneFilter>,
     fetch_tags: Option<FetchTagsMode>,
 ) -> Result<(Option<RefNameBuf>, bool), CommandError> {
     writeln!(
@@ -362,6 +396,7 @@ async fn fetch_new_remote(
             fetch_refspecs,
             &mut GitSubprocessUi::new(ui),
             depth,
+            filter.map(PartialCloneFilter::as_fetch_filter),
             match fetch_tags {
                 // If not explicitly specified on the CLI, override the remote
                 // configuration and fetch all tags by default since this is
@@ -226,7 +226,7 @@ pub async fn cmd_git_fetch(
 
     for (remote, expanded) in expansions {
         let mut callback = GitSubprocessUi::new(ui);
-        git_fetch.fetch(remote, expanded, &mut callback, None, fetch_tags)?;
+        git_fetch.fetch(remote, expanded, &mut callback, None, None, fetch_tags)?;
     }
 
     let import_stats = git_fetch.import_refs().await?;
@@ -29,6 +29,7 @@ use futures::future::try_join_all;
 use indexmap::IndexSet;
 use itertools::Itertools as _;
 use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
 use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::git;
 use jj_lib::git::GitPushOptions;
@@ -51,7 +52,7 @@ use jj_lib::revset::RemoteRefSymbolExpression;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::UserRevsetExpression;
 use jj_lib::rewrite::CommitRewriter;
-use jj_lib::signing::SignBehavior;
+use jj_lib::signing::SignPolicy;
 use jj_lib::str_util::StringExpression;
 use jj_lib::view::View;
 
@@ -71,7 +72,11 @@ use crate::commands::git::get_single_remote;
 use crate::complete;
 use crate::formatter::Formatter;
 use crate::git_util::GitSubprocessUi;
+use crate::git_util::check_sign_policy_before_push;
+use crate::git_util::needs_signing_on_push;
 use crate::git_util::print_push_stats;
+use crate::git_util::sign_on_push_settings;
+use crate::git_util::warn_unchecked_signatures_on_dry_run;
 use crate::progress::ProgressWriter;
 use crate::revset_util::parse_bookmark_name;
 use crate::revset_util::parse_union_name_patterns;
@@ -435,10 +440,36 @@ pub async fn cmd_git_push(
     let to_push_expr = ready_to_push_revset_expression(&tx, remote, &bookmark_updates);
     validate_commits_ready_to_push(ui, tx.base_workspace_helper(), to_push_expr.clone(), args)
         .await?;
-    if !args.dry_run && tx.settings().get_bool("git.sign-on-push")? {
-        bookmark_updates =
-            sign_commits_before_push(ui, &mut tx, to_push_expr, bookmark_updates).await?;
+    let sign_policy = SignPolicy::from_settings(tx.settings())?;
+    let mut commits_to_check: Vec<(Commit, Commit)> = if sign_policy.is_required_on_push() {
+        tx.base_workspace_helper()
+            .attach_revset_evaluator(to_push_expr.clone())
+            .evaluate_to_commits()?
+            .map_ok(|commit| (commit.clone(), commit))
+            .try_collect()
+            .await?
+    } else {
+        vec![]
+    };
+    if tx.settings().get_bool("git.sign-on-push")? {
+        if args.dry_run {
+            // These commits would be signed before pushing.
+            let sign_settings = sign_on_push_settings(tx.settings());
+            let num_checked = commits_to_check.len();
+            commits_to_check.retain(|(commit, _)| !needs_signing_on_push(&sign_settings, commit));
+            warn_unchecked_signatures_on_dry_run(ui, num_checked - commits_to_check.len())?;
+        } else {
+            let rewritten_commits;
+            (bookmark_updates, rewritten_commits) =
+                sign_commits_before_push(ui, &mut tx, to_push_expr, bookmark_updates).await?;
+            for (commit, new_commit) in &mut commits_to_check {
+                if let Some(new_id) = rewritten_commits.get(commit.id()) {
+                    *new_commit = tx.repo().store().get_commit_async(new_id).await?;
+                }
+            }
+        }
     }
+    check_sign_policy_before_push(tx.base_workspace_helper(), &sign_policy, &commits_to_check)?;
 
     if let Some(mut formatter) = ui.status_formatter() {
         writeln!(
@@ -567,28 +598,30 @@ async fn validate_commits_ready_to_push(
 /// Signs commits before pushing.
 ///
 /// Returns the updated list of bookmark names and corresponding
-/// [`BookmarkPushUpdate`]s.
+/// [`BookmarkPushUpdate`]s, and the mapping of rewritten commits.
 async fn sign_commits_before_push(
     ui: &Ui,
     tx: &mut WorkspaceCommandTransaction<'_>,
     commits_to_push: Arc<UserRevsetExpression>,
     bookmark_updates: Vec<(RefNameBuf, Diff<Option<CommitId>>)>,
-) -> Result<Vec<(RefNameBuf, Diff<Option<CommitId>>)>, CommandError> {
-    let mut sign_settings = tx.settings().sign_settings();
-    sign_settings.behavior = SignBehavior::Own;
+) -> Result<
+    (
+        Vec<(RefNameBuf, Diff<Option<CommitId>>)>,
+        HashMap<CommitId, CommitId>,
+    ),
+    CommandError,
+> {
+    let sign_settings = sign_on_push_settings(tx.settings());
     let commit_ids: IndexSet<CommitId> = tx
         .base_workspace_helper()
         .attach_revset_evaluator(commits_to_push)
         .evaluate_to_commits()?
-        // TODO: make filter condition configurable by revset?
-        .try_filter(|commit| {
-            future::ready(!commit.is_signed() && sign_settings.should_sign(commit.store_commit()))
-        })
+        .try_filter(|commit| future::ready(needs_signing_on_push(&sign_settings, commit)))
         .map_ok(|commit| commit.id().clone())
         .try_collect()
         .await?;
     if commit_ids.is_empty() {
-        return Ok(bookmark_updates);
+        return Ok((bookmark_updates, HashMap::new()));
     }
 
     let mut old_to_new_commits_map: HashMap<CommitId, CommitId> = HashMap::new();
@@ -654,7 +687,7 @@ async fn sign_commits_before_push(
         }
     }
 
-    Ok(bookmark_updates)
+    Ok((bookmark_updates, old_to_new_commits_map))
 }
 
 fn print_commits_ready_to_push(
@@ -0,0 +1,888 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Interactive mode of `jj log`.
+
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::io;
+use std::io::IsTerminal as _;
+use std::io::Write;
+use std::ops::Range;
+
+use crossterm::ExecutableCommand as _;
+use crossterm::event::Event;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use crossterm::event::{self};
+use crossterm::terminal::EnterAlternateScreen;
+use crossterm::terminal::LeaveAlternateScreen;
+use crossterm::terminal::disable_raw_mode;
+use crossterm::terminal::enable_raw_mode;
+use jj_lib::backend::ChangeId;
+use jj_lib::backend::CommitId;
+use jj_lib::graph::GraphEdge;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+use ratatui::Terminal;
+use ratatui::layout::Constraint;
+use ratatui::layout::Direction;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::prelude::CrosstermBackend;
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::text::Text;
+
+use super::LogArgs;
+use super::graph_nodes;
+use super::parse_log_revset;
+use super::to_graphlog_edges;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::LogContentFormat;
+use crate::cli_util::OutputFormat;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::cli_util::format_template;
+use crate::cli_util::short_change_hash;
+use crate::command_error::CommandError;
+use crate::command_error::internal_error;
+use crate::command_error::user_error;
+use crate::commands::abandon;
+use crate::commands::describe;
+use crate::commands::edit;
+use crate::commands::new;
+use crate::commands::rebase;
+use crate::commands::squash;
+use crate::formatter::FormatterExt as _;
+use crate::graphlog::GraphStyle;
+use crate::graphlog::get_graphlog;
+use crate::ui::Ui;
+
+/// Width of the selection gutter to the left of the graph.
+const GUTTER_WIDTH: usize = 2;
+
+pub(super) async fn cmd_log_interactive(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &LogArgs,
+) -> Result<(), CommandError> {
+    if command.output_format() == OutputFormat::Json {
+        return Err(user_error(
+            "`--output=json` cannot be used with `--interactive`",
+        ));
+    }
+    if !io::stdout().is_terminal() {
+        return Err(user_error("`jj log --interactive` requires a terminal"));
+    }
+    let mut workspace_command = command.workspace_helper(ui)?;
+    // Render once before entering the TUI so that parse errors and warnings
+    // are reported as usual.
+    let rendered = render_log(ui, ui, command, &workspace_command, args, &HashSet::new()).await?;
+    let state = State::new(rendered.entries, rendered.text.height());
+
+    // The terminal is restored when `tui` is dropped, even on error.
+    let mut tui = Tui::enter()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    terminal.clear()?;
+
+    run_tui(
+        ui,
+        command,
+        &mut workspace_command,
+        args,
+        &mut tui,
+        &mut terminal,
+        state,
+        rendered.text,
+    )
+    .await
+}
+
+/// Keeps the terminal in raw mode on the alternate screen until dropped.
+struct Tui {
+    active: bool,
+}
+
+impl Tui {
+    fn enter() -> io::Result<Self> {
+        let mut tui = Self { active: false };
+        tui.resume()?;
+        Ok(tui)
+    }
+
+    fn resume(&mut self) -> io::Result<()> {
+        // Set before switching so that a partial switch is undone on drop.
+        self.active = true;
+        io::stdout().execute(EnterAlternateScreen)?;
+        enable_raw_mode()
+    }
+
+    fn suspend(&mut self) -> io::Result<()> {
+        self.active = false;
+        disable_raw_mode()?;
+        io::stdout().execute(LeaveAlternateScreen)?;
+        Ok(())
+    }
+}
+
+impl Drop for Tui {
+    fn drop(&mut self) {
+        if self.active {
+            self.suspend().ok();
+        }
+    }
+}
+
+/// The log output as rendered for the TUI.
+struct RenderedLog {
+    text: Text<'static>,
+    entries: Vec<Entry>,
+}
+
+/// A commit node in the rendered log.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Entry {
+    commit_id: CommitId,
+    change_id: ChangeId,
+    /// Lines occupied by the node (including its diff if expanded).
+    lines: Range<usize>,
+}
+
+/// Writer which keeps track of the number of lines written so far.
+struct LineCountingWriter<'a> {
+    buffer: Vec<u8>,
+    lines: &'a Cell<usize>,
+}
+
+impl Write for LineCountingWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        let newlines = buf.iter().filter(|&&b| b == b'\n').count();
+        self.lines.set(self.lines.get() + newlines);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Renders the graph log the same way `jj log` would, except that the patch
+/// is only shown for the `expanded` commits.
+///
+/// Revsets and templates are parsed with `parse_ui` so that warnings can be
+/// suppressed while the TUI is active.
+async fn render_log(
+    ui: &Ui,
+    parse_ui: &Ui,
+    command: &CommandHelper,
+    workspace_command: &WorkspaceCommandHelper,
+    args: &LogArgs,
+    expanded: &HashSet<CommitId>,
+) -> Result<RenderedLog, CommandError> {
+    let settings = workspace_command.settings();
+    let fileset_expression = workspace_command.parse_file_patterns(parse_ui, &args.paths)?;
+    let revset =
+        parse_log_revset(parse_ui, workspace_command, args, &fileset_expression)?.evaluate()?;
+    let prio_revset = settings.get_string("revsets.log-graph-prioritize")?;
+    let prio_revset = workspace_command.parse_revset(parse_ui, &RevisionArg::from(prio_revset))?;
+    let matcher = fileset_expression.to_matcher();
+    let store = workspace_command.repo().store();
+    let diff_renderer = workspace_command.diff_renderer_for_log(&args.diff_format, true)?;
+    let graph_style = GraphStyle::from_settings(settings)?;
+    let use_elided_nodes = settings.get_bool("ui.log-synthetic-elided-nodes")?;
+    let with_content_format = LogContentFormat::new(ui, settings)?.sub_width(GUTTER_WIDTH);
+
+    let language = workspace_command.commit_template_language();
+    let template_string = command.list_template_text(args.template.as_deref(), "templates.log")?;
+    let template = workspace_command
+        .parse_template(parse_ui, &language, &template_string)?
+        .labeled(["log", "commit"]);
+    let node_template = workspace_command
+        .parse_template(
+            parse_ui,
+            &language,
+            &settings.get_string("templates.log_node")?,
+        )?
+        .labeled(["log", "commit", "node"]);
+
+    let line_count = Cell::new(0);
+    let mut writer = LineCountingWriter {
+        buffer: vec![],
+        lines: &line_count,
+    };
+    let mut entries = vec![];
+    {
+        let mut graph = get_graphlog(graph_style, &mut writer);
+        let iter = graph_nodes(
+            revset.as_ref(),
+            &prio_revset,
+            args.limit.unwrap_or(usize::MAX),
+            args.reversed,
+        )
+        .await?;
+        for node in iter {
+            let (commit_id, edges) = node?;
+            let (graphlog_edges, elided_targets) = to_graphlog_edges(edges, use_elided_nodes);
+            let mut buffer = vec![];
+            let key = (commit_id, false);
+            let commit = store.get_commit(&key.0)?;
+            let within_graph = with_content_format.sub_width(graph.width(&key, &graphlog_edges));
+            within_graph.write(ui.new_formatter(&mut buffer).as_mut(), |formatter| {
+                template.format(&commit, formatter)
+            })?;
+            if let Some(renderer) = &diff_renderer
+                && expanded.contains(commit.id())
+            {
+                let mut formatter = ui.new_formatter(&mut buffer);
+                renderer
+                    .show_patch(
+                        ui,
+                        formatter.as_mut(),
+                        &commit,
+                        matcher.as_ref(),
+                        within_graph.width(),
+                    )
+                    .await?;
+            }
+            let start = line_count.get();
+            let change_id = commit.change_id().clone();
+            let commit = Some(commit);
+            let node_symbol = format_template(ui, &commit, &node_template);
+            graph.add_node(
+                &key,
+                &graphlog_edges,
+                &node_symbol,
+                &String::from_utf8_lossy(&buffer),
+            )?;
+            entries.push(Entry {
+                commit_id: key.0,
+                change_id,
+                lines: start..line_count.get(),
+            });
+
+            for elided_target in elided_targets {
+                let elided_key = (elided_target, true);
+                let real_key = (elided_key.0.clone(), false);
+                let edges = [GraphEdge::direct(real_key)];
+                let mut buffer = vec![];
+                let within_graph = with_content_format.sub_width(graph.width(&elided_key, &edges));
+                within_graph.write(ui.new_formatter(&mut buffer).as_mut(), |formatter| {
+                    writeln!(formatter.labeled("elided"), "(elided revisions)")
+                })?;
+                let node_symbol = format_template(ui, &None, &node_template);
+                graph.add_node(
+                    &elided_key,
+                    &edges,
+                    &node_symbol,
+                    &String::from_utf8_lossy(&buffer),
+                )?;
+            }
+        }
+    }
+    let text = ansi_to_tui::IntoText::into_text(&writer.buffer)
+        .map_err(|err| internal_error(format!("Failed to render log output: {err}")))?;
+    Ok(RenderedLog { text, entries })
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Mode {
+    Normal,
+    /// Choosing the destination to rebase `source` (and its descendants) onto.
+    Rebase {
+        source: CommitId,
+    },
+}
+
+/// An operation to run on the repository.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Action {
+    Edit(CommitId),
+    New(CommitId),
+    Squash(CommitId),
+    Abandon(CommitId),
+    Describe(CommitId),
+    Rebase {
+        source: CommitId,
+        destination: CommitId,
+    },
+}
+
+/// What to do after a key has been handled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Outcome {
+    Continue,
+    Quit,
+    /// The log needs to be rendered again, e.g. to show or hide a diff.
+    Refresh,
+    Run(Action),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct State {
+    entries: Vec<Entry>,
+    /// Total number of lines in the rendered log.
+    num_lines: usize,
+    /// The current selection as an index into `entries`
+    selection: usize,
+    /// The first line of the log shown on screen.
+    scroll: usize,
+    expanded: HashSet<CommitId>,
+    mode: Mode,
+    /// Message shown above the help line, e.g. the result of the last action.
+    message: Option<String>,
+}
+
+impl State {
+    fn new(entries: Vec<Entry>, num_lines: usize) -> Self {
+        Self {
+            entries,
+            num_lines,
+            selection: 0,
+            scroll: 0,
+            expanded: HashSet::new(),
+            mode: Mode::Normal,
+            message: None,
+        }
+    }
+
+    fn selected(&self) -> Option<&Entry> {
+        self.entries.get(self.selection)
+    }
+
+    /// Replaces the entries after the log has been rendered again, keeping
+    /// the selection on the same change if it's still visible.
+    fn update_entries(&mut self, entries: Vec<Entry>, num_lines: usize) {
+        let selected_change_id = self.selected().map(|entry| entry.change_id.clone());
+        self.entries = entries;
+        self.num_lines = num_lines;
+        let selection = selected_change_id.and_then(|change_id| {
+            self.entries
+                .iter()
+                .position(|entry| entry.change_id == change_id)
+        });
+        self.selection = selection.unwrap_or(self.selection);
+        self.selection = self.selection.min(self.entries.len().saturating_sub(1));
+        self.expanded
+            .retain(|id| self.entries.iter().any(|entry| entry.commit_id == *id));
+        if let Mode::Rebase { source } = &self.mode
+            && !self.entries.iter().any(|entry| entry.commit_id == *source)
+        {
+            self.mode = Mode::Normal;
+        }
+    }
+
+    /// Adjusts the scroll position so that the selected entry is visible in
+    /// a viewport of `height` lines.
+    fn scroll_to_selection(&mut self, height: usize) {
+        let Some(entry) = self.selected() else {
+            self.scroll = 0;
+            return;
+        };
+        let lines = entry.lines.clone();
+        if lines.start < self.scroll {
+            self.scroll = lines.start;
+        } else if lines.end > self.scroll + height {
+            // Show as much of the entry as possible, but always its first line.
+            self.scroll = lines.end.saturating_sub(height).min(lines.start);
+        }
+        self.scroll = self.scroll.min(self.num_lines.saturating_sub(height));
+    }
+
+    fn handle_key_event(&mut self, event: KeyEvent) -> Outcome {
+        match (event.code, event.modifiers) {
+            (KeyCode::Char('c'), KeyModifiers::CONTROL) => return Outcome::Quit,
+            (KeyCode::Down | KeyCode::Char('j'), KeyModifiers::NONE) => {
+                if self.selection + 1 < self.entries.len() {
+                    self.selection += 1;
+                }
+                return Outcome::Continue;
+            }
+            (KeyCode::Up | KeyCode::Char('k'), KeyModifiers::NONE) => {
+                if self.selection > 0 {
+                    self.selection -= 1;
+                }
+                return Outcome::Continue;
+            }
+            _ => {}
+        }
+        self.message = None;
+        let Some(selected) = self.selected().map(|entry| entry.commit_id.clone()) else {
+            return match event.code {
+                KeyCode::Char('q') | KeyCode::Esc => Outcome::Quit,
+                _ => Outcome::Continue,
+            };
+        };
+        match &self.mode {
+            Mode::Normal => match (event.code, event.modifiers) {
+                (KeyCode::Char('q'), KeyModifiers::NONE) => Outcome::Quit,
+                (KeyCode::Enter | KeyCode::Tab, KeyModifiers::NONE) => {
+                    if !self.expanded.remove(&selected) {
+                        self.expanded.insert(selected);
+                    }
+                    Outcome::Refresh
+                }
+                (KeyCode::Char('e'), KeyModifiers::NONE) => Outcome::Run(Action::Edit(selected)),
+                (KeyCode::Char('n'), KeyModifiers::NONE) => Outcome::Run(Action::New(selected)),
+                (KeyCode::Char('s'), KeyModifiers::NONE) => Outcome::Run(Action::Squash(selected)),
+                (KeyCode::Char('a'), KeyModifiers::NONE) => Outcome::Run(Action::Abandon(selected)),
+                (KeyCode::Char('d'), KeyModifiers::NONE) => {
+                    Outcome::Run(Action::Describe(selected))
+                }
+                (KeyCode::Char('r'), KeyModifiers::NONE) => {
+                    self.mode = Mode::Rebase { source: selected };
+                    Outcome::Continue
+                }
+                _ => Outcome::Continue,
+            },
+            Mode::Rebase { source } => match (event.code, event.modifiers) {
+                (KeyCode::Enter, KeyModifiers::NONE) => {
+                    let action = Action::Rebase {
+                        source: source.clone(),
+                        destination: selected,
+                    };
+                    self.mode = Mode::Normal;
+                    Outcome::Run(action)
+                }
+                (KeyCode::Esc | KeyCode::Char('q'), KeyModifiers::NONE) => {
+                    self.mode = Mode::Normal;
+                    Outcome::Continue
+                }
+                _ => Outcome::Continue,
+            },
+        }
+    }
+}
+
+fn help_line(mode: &Mode) -> Line<'static> {
+    let help_items: &[(&str, &str)] = match mode {
+        Mode::Normal => &[
+            ("↓/j", "down"),
+            ("↑/k", "up"),
+            ("enter", "toggle diff"),
+            ("e", "edit"),
+            ("n", "new"),
+            ("s", "squash"),
+            ("a", "abandon"),
+            ("d", "describe"),
+            ("r", "rebase"),
+            ("q", "quit"),
+        ],
+        Mode::Rebase { .. } => &[
+            ("↓/j", "down"),
+            ("↑/k", "up"),
+            ("enter", "rebase onto selected revision"),
+            ("esc", "cancel"),
+        ],
+    };
+    let mut help_spans = Vec::new();
+    for (i, (key, desc)) in help_items.iter().enumerate() {
+        if i > 0 {
+            help_spans.push(Span::raw(" • "));
+        }
+        help_spans.push(Span::styled(*key, Style::default().fg(Color::Magenta)));
+        help_spans.push(Span::raw(format!(" {desc}")));
+    }
+    Line::from(help_spans)
+}
+
+#[expect(clippy::too_many_arguments)]
+async fn run_tui<B: ratatui::backend::Backend>(
+    ui: &Ui,
+    command: &CommandHelper,
+    workspace_command: &mut WorkspaceCommandHelper,
+    args: &LogArgs,
+    tui: &mut Tui,
+    terminal: &mut Terminal<B>,
+    mut state: State,
+    mut text: Text<'static>,
+) -> Result<(), CommandError> {
+    // Output of commands would mess up the screen.
+    let mut quiet_ui = Ui::null();
+    loop {
+        terminal
+            .draw(|frame| {
+                let layout = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Fill(1),
+                        Constraint::Length(1),
+                        Constraint::Length(1),
+                    ])
+                    .split(frame.area());
+                state.scroll_to_selection(layout[0].height.into());
+                render(&state, &text, frame, layout[0]);
+                if let Some(message) = &state.message {
+                    frame.render_widget(Text::from(message.as_str()), layout[1]);
+                }
+                frame.render_widget(help_line(&state.mode), layout[2]);
+            })
+            .map_err(|e| internal_error(format!("Failed to draw TUI: {e}")))?;
+
+        let Event::Key(event) =
+            event::read().map_err(|e| internal_error(format!("Failed to read TUI events: {e}")))?
+        else {
+            continue;
+        };
+        // On Windows, we get Press and Release (and maybe Repeat) events, but on Linux
+        // we only get Press.
+        if event.is_release() {
+            continue;
+        }
+        match state.handle_key_event(event) {
+            Outcome::Continue => continue,
+            Outcome::Quit => return Ok(()),
+            Outcome::Refresh => {}
+            Outcome::Run(action) => {
+                let old_op_id = workspace_command.repo().op_id().clone();
+                let result = run_action(
+                    &mut quiet_ui,
+                    command,
+                    workspace_command,
+                    tui,
+                    terminal,
+                    &action,
+                )
+                .await;
+                // The command loaded the workspace on its own.
+                *workspace_command = command.workspace_helper_no_snapshot(&quiet_ui)?;
+                state.message = Some(match result {
+                    Ok(_) if *workspace_command.repo().op_id() == old_op_id => {
+                        "Nothing changed".to_owned()
+                    }
+                    Ok(message) => message,
+                    Err(err) => format!("Error: {}", err.error),
+                });
+            }
+        }
+        let rendered = render_log(
+            ui,
+            &quiet_ui,
+            command,
+            workspace_command,
+            args,
+            &state.expanded,
+        )
+        .await?;
+        state.update_entries(rendered.entries, rendered.text.height());
+        text = rendered.text;
+    }
+}
+
+fn render(state: &State, text: &Text<'_>, frame: &mut ratatui::Frame, main_area: Rect) {
+    let [gutter_area, log_area] =
+        Layout::horizontal([Constraint::Length(GUTTER_WIDTH as u16), Constraint::Fill(1)])
+            .areas(main_area);
+    let visible_lines = state.scroll..state.scroll + usize::from(main_area.height);
+
+    let rebase_source = match &state.mode {
+        Mode::Normal => None,
+        Mode::Rebase { source } => Some(source),
+    };
+    let mut gutter_lines = vec![Line::default(); main_area.height.into()];
+    for (i, entry) in state.entries.iter().enumerate() {
+        let marker = if i == state.selection {
+            "▶"
+        } else if Some(&entry.commit_id) == rebase_source {
+            "⇢"
+        } else {
+            continue;
+        };
+        if visible_lines.contains(&entry.lines.start) {
+            gutter_lines[entry.lines.start - state.scroll] = Line::from(marker);
+        }
+    }
+    frame.render_widget(Text::from(gutter_lines), gutter_area);
+
+    let selected_lines = state
+        .selected()
+        .map(|entry| entry.lines.clone())
+        .unwrap_or_default();
+    let log_lines = text
+        .lines
+        .iter()
+        .enumerate()
+        .skip(state.scroll)
+        .take(main_area.height.into())
+        .map(|(i, line)| {
+            if selected_lines.contains(&i) {
+                line.clone()
+                    .patch_style(Style::default().add_modifier(Modifier::BOLD))
+            } else {
+                line.clone()
+            }
+        })
+        .collect::<Vec<_>>();
+    frame.render_widget(Text::from(log_lines), log_area);
+}
+
+/// Runs `action` by invoking the corresponding command, and returns a message
+/// describing the result.
+async fn run_action<B: ratatui::backend::Backend>(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    workspace_command: &WorkspaceCommandHelper,
+    tui: &mut Tui,
+    terminal: &mut Terminal<B>,
+    action: &Action,
+) -> Result<String, CommandError> {
+    let store = workspace_command.repo().store();
+    let message = match action {
+        Action::Edit(id) => {
+            let commit = store.get_commit(id)?;
+            let args = parse_command_args(&["edit", &id.hex()])?;
+            edit::cmd_edit(ui, command, &args).await?;
+            format!("Editing {}", short_change_hash(commit.change_id()))
+        }
+        Action::New(id) => {
+            let commit = store.get_commit(id)?;
+            let args = parse_command_args(&["new", &id.hex()])?;
+            new::cmd_new(ui, command, &args).await?;
+            format!(
+                "Created new commit on top of {}",
+                short_change_hash(commit.change_id())
+            )
+        }
+        Action::Abandon(id) => {
+            let commit = store.get_commit(id)?;
+            let args = parse_command_args(&["abandon", &id.hex()])?;
+            abandon::cmd_abandon(ui, command, &args).await?;
+            format!("Abandoned {}", short_change_hash(commit.change_id()))
+        }
+        Action::Describe(id) => {
+            let commit = store.get_commit(id)?;
+            let args = parse_command_args(&["describe", &id.hex()])?;
+            with_suspended_tui(tui, terminal, describe::cmd_describe(ui, command, &args)).await?;
+            format!("Described {}", short_change_hash(commit.change_id()))
+        }
+        Action::Squash(id) => {
+            let commit = store.get_commit(id)?;
+            let args = parse_command_args(&["squash", "--revision", &id.hex()])?;
+            with_suspended_tui(tui, terminal, squash::cmd_squash(ui, command, &args)).await?;
+            format!("Squashed {}", short_change_hash(commit.change_id()))
+        }
+        Action::Rebase {
+            source,
+            destination,
+        } => {
+            let commit = store.get_commit(source)?;
+            let destination = store.get_commit(destination)?;
+            let args = parse_command_args(&[
+                "rebase",
+                "--source",
+                &source.hex(),
+                "--onto",
+                &destination.id().hex(),
+            ])?;
+            rebase::cmd_rebase(ui, command, &args).await?;
+            format!(
+                "Rebased {} onto {}",
+                short_change_hash(commit.change_id()),
+                short_change_hash(destination.change_id())
+            )
+        }
+    };
+    Ok(message)
+}
+
+/// Parses the arguments of a command as if it were run from the command line.
+fn parse_command_args<T: clap::Args>(args: &[&str]) -> Result<T, CommandError> {
+    let name = args[0].to_owned();
+    let matches = T::augment_args(clap::Command::new(name)).try_get_matches_from(args)?;
+    Ok(T::from_arg_matches(&matches)?)
+}
+
+/// Restores the terminal while `future` runs, e.g. to let the user edit a
+/// description in their editor.
+async fn with_suspended_tui<B: ratatui::backend::Backend, T>(
+    tui: &mut Tui,
+    terminal: &mut Terminal<B>,
+    future: impl Future<Output = Result<T, CommandError>>,
+) -> Result<T, CommandError> {
+    tui.suspend()?;
+    let result = future.await;
+    tui.resume()?;
+    terminal
+        .clear()
+        .map_err(|e| internal_error(format!("Failed to draw TUI: {e}")))?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: u8, lines: Range<usize>) -> Entry {
+        Entry {
+            commit_id: CommitId::new(vec![id]),
+            change_id: ChangeId::new(vec![id]),
+            lines,
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn test_state() -> State {
+        State::new(vec![entry(1, 0..2), entry(2, 2..4), entry(3, 4..6)], 7)
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut state = test_state();
+        assert_eq!(state.handle_key_event(key(KeyCode::Up)), Outcome::Continue);
+        assert_eq!(state.selection, 0);
+        assert_eq!(
+            state.handle_key_event(key(KeyCode::Char('j'))),
+            Outcome::Continue
+        );
+        assert_eq!(
+            state.handle_key_event(key(KeyCode::Down)),
+            Outcome::Continue
+        );
+        assert_eq!(
+            state.handle_key_event(key(KeyCode::Down)),
+            Outcome::Continue
+        );
+        assert_eq!(state.selection, 2);
+        state.handle_key_event(key(KeyCode::Char('k')));
+        assert_eq!(state.selection, 1);
+        assert_eq!(
+            state.handle_key_event(key(KeyCode::Char('q'))),
+            Outcome::Quit
+        );
+    }
+
+    #[test]
+    fn test_toggle_expanded() {
+        let mut state = test_state();
+        state.selection = 1;
+        assert_eq!(
+            state.handle_key_event(key(KeyCode::Enter)),
+            Outcome::Refresh
+        );
+        assert!(state.expanded.contains(&CommitId::new(vec![2])));
+        assert_eq!(state.handle_key_event(key(KeyCode::Tab)), Outcome::Refresh);
+        assert!(state.expanded.is_empty());
+    }
+
+    #[test]
+    fn test_actions() {
+        let mut state = test_state();
+        let id = CommitId::new(vec![1]);
+        assert_eq!(
+            state.handle_key_event(key(KeyCode::Char('e'))),
+            Outcome::Run(Action::Edit(id.clone()))
+        );
+        assert_eq!(
+            state.handle_key_event(key(KeyCode::Char('a'))),
+            Outcome::Run(Action::Abandon(id.clone()))
+        );
+        assert_eq!(
+            state.handle_key_event(key(KeyCode::Char('s'))),
+            Outcome::Run(Action::Squash(id.clone()))
+        );
+        assert_eq!(
+            state.handle_key_event(key(KeyCode::Char('d'))),
+            Outcome::Run(Action::Describe(id.clone()))
+        );
+        assert_eq!(
+            state.handle_key_event(key(KeyCode::Char('n'))),
+            Outcome::Run(Action::New(id))
+        );
+    }
+
+    #[test]
+    fn test_rebase_mode() {
+        let mut state = test_state();
+        state.handle_key_event(key(KeyCode::Char('r')));
+        assert_eq!(
+            state.mode,
+            Mode::Rebase {
+                source: CommitId::new(vec![1])
+            }
+        );
+        // Actions are disabled while choosing the destination
+        assert_eq!(
+            state.handle_key_event(key(KeyCode::Char('a'))),
+            Outcome::Continue
+        );
+        state.handle_key_event(key(KeyCode::Down));
+        state.handle_key_event(key(KeyCode::Down));
+        assert_eq!(
+            state.handle_key_event(key(KeyCode::Enter)),
+            Outcome::Run(Action::Rebase {
+                source: CommitId::new(vec![1]),
+                destination: CommitId::new(vec![3]),
+            })
+        );
+        assert_eq!(state.mode, Mode::Normal);
+
+        state.handle_key_event(key(KeyCode::Char('r')));
+        assert_eq!(state.handle_key_event(key(KeyCode::Esc)), Outcome::Continue);
+        assert_eq!(state.mode, Mode::Normal);
+    }
+
+    #[test]
+    fn test_parse_command_args() {
+        let id = "0123456789abcdef";
+        parse_command_args::<edit::EditArgs>(&["edit", id]).unwrap();
+        parse_command_args::<new::NewArgs>(&["new", id]).unwrap();
+        parse_command_args::<abandon::AbandonArgs>(&["abandon", id]).unwrap();
+        parse_command_args::<describe::DescribeArgs>(&["describe", id]).unwrap();
+        parse_command_args::<squash::SquashArgs>(&["squash", "--revision", id]).unwrap();
+        parse_command_args::<rebase::RebaseArgs>(&["rebase", "--source", id, "--onto", id])
+            .unwrap();
+    }
+
+    #[test]
+    fn test_update_entries_keeps_selection() {
+        let mut state = test_state();
+        state.selection = 1;
+        state.expanded.insert(CommitId::new(vec![2]));
+        state.expanded.insert(CommitId::new(vec![3]));
+        // Change 2 was rewritten and moved to the top, change 3 disappeared
+        let rewritten = Entry {
+            commit_id: CommitId::new(vec![20]),
+            ..entry(2, 0..3)
+        };
+        state.update_entries(vec![rewritten, entry(1, 3..5)], 5);
+        assert_eq!(state.selection, 0);
+        assert!(state.expanded.is_empty());
+
+        state.selection = 1;
+        state.update_entries(vec![entry(4, 0..2)], 2);
+        assert_eq!(state.selection, 0);
+    }
+
+    #[test]
+    fn test_scroll_to_selection() {
+        let mut state = test_state();
+        state.selection = 2;
+        state.scroll_to_selection(3);
+        assert_eq!(state.scroll, 3);
+        state.selection = 1;
+        state.scroll_to_selection(3);
+        assert_eq!(state.scroll, 2);
+        state.selection = 0;
+        state.scroll_to_selection(10);
+        assert_eq!(state.scroll, 0);
+    }
+}
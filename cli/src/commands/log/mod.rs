@@ -12,36 +12,51 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod interactive;
+
 use std::cmp::min;
+use std::collections::VecDeque;
+use std::iter;
+use std::sync::Arc;
 
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
 use futures::TryStreamExt as _;
+use itertools::Either;
 use itertools::Itertools as _;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
+use jj_lib::fileset::FilesetExpression;
 use jj_lib::graph::GraphEdge;
 use jj_lib::graph::GraphEdgeType;
+use jj_lib::graph::GraphNode;
 use jj_lib::graph::TopoGroupedGraphIterator;
 use jj_lib::graph::reverse_graph;
 use jj_lib::repo::Repo as _;
+use jj_lib::revset::Revset;
 use jj_lib::revset::RevsetEvaluationError;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetFilterPredicate;
 use jj_lib::revset::RevsetIteratorExt as _;
+use jj_lib::store::Store;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
+use crate::cli_util::OutputFormat;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::format_template;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::commit_templater::SignatureBatch;
 use crate::complete;
 use crate::diff_util::DiffFormatArgs;
 use crate::formatter::FormatterExt as _;
 use crate::graphlog::GraphStyle;
 use crate::graphlog::get_graphlog;
+use crate::revset_util::RevsetExpressionEvaluator;
 use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
 
@@ -124,6 +139,15 @@ pub(crate) struct LogArgs {
     #[arg(long, conflicts_with_all = ["DiffFormatArgs", "no_graph", "patch", "reversed", "template"])]
     count: bool,
 
+    /// Browse the log interactively
+    ///
+    /// Revisions can be selected with the arrow keys, and their diff can be
+    /// shown inline. The selected revision can be edited, described, squashed
+    /// into its parent, abandoned, or rebased, and a new revision can be
+    /// created on top of it. Each action is recorded as a separate operation.
+    #[arg(long, short, conflicts_with_all = ["count", "no_graph", "patch"])]
+    interactive: bool,
+
     #[command(flatten)]
     diff_format: DiffFormatArgs,
 }
@@ -134,30 +158,15 @@ pub(crate) async fn cmd_log(
     command: &CommandHelper,
     args: &LogArgs,
 ) -> Result<(), CommandError> {
+    if args.interactive {
+        return interactive::cmd_log_interactive(ui, command, args).await;
+    }
     let workspace_command = command.workspace_helper(ui)?;
     let settings = workspace_command.settings();
 
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
     let mut explicit_paths = fileset_expression.explicit_paths().collect_vec();
-    let revset_expression = {
-        // only use default revset if neither revset nor path are specified
-        let mut expression = if args.revisions.is_empty() && args.paths.is_empty() {
-            let revset_string = settings.get_string("revsets.log")?;
-            workspace_command.parse_revset(ui, &RevisionArg::from(revset_string))?
-        } else if !args.revisions.is_empty() {
-            workspace_command.parse_union_revsets(ui, &args.revisions)?
-        } else {
-            // a path was specified so we use all() and add path filter later
-            workspace_command.attach_revset_evaluator(RevsetExpression::all())
-        };
-        if !args.paths.is_empty() {
-            // Beware that args.paths = ["root:."] is not identical to []. The
-            // former will filter out empty commits.
-            let predicate = RevsetFilterPredicate::File(fileset_expression.clone());
-            expression.intersect_with(&RevsetExpression::filter(predicate));
-        }
-        expression
-    };
+    let revset_expression = parse_log_revset(ui, &workspace_command, args, &fileset_expression)?;
 
     let revset = revset_expression.evaluate()?;
 
@@ -187,6 +196,12 @@ pub(crate) async fn cmd_log(
 
     let store = repo.store();
     let diff_renderer = workspace_command.diff_renderer_for_log(&args.diff_format, args.patch)?;
+    let json_output = command.output_format() == OutputFormat::Json;
+    if json_output && diff_renderer.is_some() {
+        return Err(user_error(
+            "`--output=json` cannot be used with diff options",
+        ));
+    }
     let graph_style = GraphStyle::from_settings(settings)?;
 
     let use_elided_nodes = settings.get_bool("ui.log-synthetic-elided-nodes")?;
@@ -194,18 +209,18 @@ pub(crate) async fn cmd_log(
 
     let template: TemplateRenderer<Commit>;
     let node_template: TemplateRenderer<Option<Commit>>;
+    let signature_batch: SignatureBatch;
     {
         let language = workspace_command.commit_template_language();
-        let template_string = match &args.template {
-            Some(value) => value.clone(),
-            None => settings.get_string("templates.log")?,
-        };
+        let template_string =
+            command.list_template_text(args.template.as_deref(), "templates.log")?;
         template = workspace_command
             .parse_template(ui, &language, &template_string)?
             .labeled(["log", "commit"]);
         node_template = workspace_command
             .parse_template(ui, &language, &settings.get_string("templates.log_node")?)?
             .labeled(["log", "commit", "node"]);
+        signature_batch = language.keyword_cache().signature_batch().clone();
     }
 
     {
@@ -213,61 +228,21 @@ pub(crate) async fn cmd_log(
         let mut formatter = ui.stdout_formatter();
         let formatter = formatter.as_mut();
 
-        if !args.no_graph {
+        if !args.no_graph && !json_output {
             let mut raw_output = formatter.raw()?;
             let mut graph = get_graphlog(graph_style, raw_output.as_mut());
-            let iter: Box<dyn Iterator<Item = _>> = {
-                let mut forward_iter = TopoGroupedGraphIterator::new(revset.iter_graph(), |id| id);
-
-                let has_commit = revset.containing_fn();
-
-                let mut prio_stream = prio_revset.evaluate_to_commit_ids()?;
-                while let Some(prio) = prio_stream.try_next().await? {
-                    if has_commit(&prio)? {
-                        forward_iter.prioritize_branch(prio);
-                    }
-                }
-
-                // The input to TopoGroupedGraphIterator shouldn't be truncated
-                // because the prioritized commit must exist in the input set.
-                let forward_iter = forward_iter.take(args.limit.unwrap_or(usize::MAX));
-                if args.reversed {
-                    Box::new(reverse_graph(forward_iter, |id| id)?.into_iter().map(Ok))
-                } else {
-                    Box::new(forward_iter)
-                }
-            };
+            let iter = graph_nodes(
+                revset.as_ref(),
+                &prio_revset,
+                args.limit.unwrap_or(usize::MAX),
+                args.reversed,
+            )
+            .await?;
+            let iter = queue_signature_batches(iter, store, signature_batch, |node| &node.0);
             for node in iter {
                 let (commit_id, edges) = node?;
 
-                // The graph is keyed by (CommitId, is_synthetic)
-                let mut graphlog_edges = vec![];
-                // TODO: Should we update revset.iter_graph() to yield a `has_missing` flag
-                // instead of all the missing edges since we don't care about
-                // where they point here anyway?
-                let mut missing_edge_id = None;
-                let mut elided_targets = vec![];
-                for edge in edges {
-                    match edge.edge_type {
-                        GraphEdgeType::Missing => {
-                            missing_edge_id = Some(edge.target);
-                        }
-                        GraphEdgeType::Direct => {
-                            graphlog_edges.push(GraphEdge::direct((edge.target, false)));
-                        }
-                        GraphEdgeType::Indirect => {
-                            if use_elided_nodes {
-                                elided_targets.push(edge.target.clone());
-                                graphlog_edges.push(GraphEdge::direct((edge.target, true)));
-                            } else {
-                                graphlog_edges.push(GraphEdge::indirect((edge.target, false)));
-                            }
-                        }
-                    }
-                }
-                if let Some(missing_edge_id) = missing_edge_id {
-                    graphlog_edges.push(GraphEdge::missing((missing_edge_id, false)));
-                }
+                let (graphlog_edges, elided_targets) = to_graphlog_edges(edges, use_elided_nodes);
                 let mut buffer = vec![];
                 let key = (commit_id, false);
                 let commit = store.get_commit(&key.0)?;
@@ -332,10 +307,16 @@ pub(crate) async fn cmd_log(
                     Box::new(forward_iter)
                 }
             };
+            let iter = queue_signature_batches(iter, store, signature_batch, |id| id);
             for commit_or_error in iter.commits(store) {
                 let commit = commit_or_error?;
-                with_content_format
-                    .write(formatter, |formatter| template.format(&commit, formatter))?;
+                if json_output {
+                    // Don't wrap JSON lines
+                    template.format(&commit, formatter)?;
+                } else {
+                    with_content_format
+                        .write(formatter, |formatter| template.format(&commit, formatter))?;
+                }
                 if let Some(renderer) = &diff_renderer {
                     let width = ui.term_width();
                     renderer
@@ -388,3 +369,127 @@ pub(crate) async fn cmd_log(
 
     Ok(())
 }
+
+fn parse_log_revset<'a>(
+    ui: &Ui,
+    workspace_command: &'a WorkspaceCommandHelper,
+    args: &LogArgs,
+    fileset_expression: &FilesetExpression,
+) -> Result<RevsetExpressionEvaluator<'a>, CommandError> {
+    // only use default revset if neither revset nor path are specified
+    let mut expression = if args.revisions.is_empty() && args.paths.is_empty() {
+        let revset_string = workspace_command.settings().get_string("revsets.log")?;
+        workspace_command.parse_revset(ui, &RevisionArg::from(revset_string))?
+    } else if !args.revisions.is_empty() {
+        workspace_command.parse_union_revsets(ui, &args.revisions)?
+    } else {
+        // a path was specified so we use all() and add path filter later
+        workspace_command.attach_revset_evaluator(RevsetExpression::all())
+    };
+    if !args.paths.is_empty() {
+        // Beware that args.paths = ["root:."] is not identical to []. The
+        // former will filter out empty commits.
+        let predicate = RevsetFilterPredicate::File(fileset_expression.clone());
+        expression.intersect_with(&RevsetExpression::filter(predicate));
+    }
+    Ok(expression)
+}
+
+/// Number of commits read ahead and queued for batch signature verification.
+const SIGNATURE_BATCH_SIZE: usize = 50;
+
+/// Reads `iter` in chunks, and queues the commits of each chunk for batch
+/// signature verification before the chunk is rendered. Does nothing unless
+/// the templates show signatures.
+fn queue_signature_batches<'a, T: 'a, E: 'a>(
+    iter: impl Iterator<Item = Result<T, E>> + 'a,
+    store: &'a Arc<Store>,
+    batch: SignatureBatch,
+    commit_id: fn(&T) -> &CommitId,
+) -> impl Iterator<Item = Result<T, E>> + 'a {
+    if !batch.is_used() {
+        return Either::Left(iter);
+    }
+    let mut iter = iter.fuse();
+    let mut chunk = VecDeque::new();
+    Either::Right(iter::from_fn(move || {
+        if chunk.is_empty() {
+            chunk.extend(iter.by_ref().take(SIGNATURE_BATCH_SIZE));
+            let commits = chunk.iter().flatten().map(commit_id);
+            batch.extend(commits.filter_map(|id| store.get_commit(id).ok()));
+        }
+        chunk.pop_front()
+    }))
+}
+
+type GraphNodeIter<'a> =
+    Box<dyn Iterator<Item = Result<GraphNode<CommitId>, RevsetEvaluationError>> + 'a>;
+
+/// Iterates over the graph nodes of `revset` in the order they should be
+/// rendered, with the branches in `prio_revset` emitted first.
+async fn graph_nodes<'a>(
+    revset: &'a dyn Revset,
+    prio_revset: &RevsetExpressionEvaluator<'_>,
+    limit: usize,
+    reversed: bool,
+) -> Result<GraphNodeIter<'a>, CommandError> {
+    let mut forward_iter = TopoGroupedGraphIterator::new(revset.iter_graph(), |id| id);
+
+    let has_commit = revset.containing_fn();
+
+    let mut prio_stream = prio_revset.evaluate_to_commit_ids()?;
+    while let Some(prio) = prio_stream.try_next().await? {
+        if has_commit(&prio)? {
+            forward_iter.prioritize_branch(prio);
+        }
+    }
+
+    // The input to TopoGroupedGraphIterator shouldn't be truncated
+    // because the prioritized commit must exist in the input set.
+    let forward_iter = forward_iter.take(limit);
+    if reversed {
+        Ok(Box::new(
+            reverse_graph(forward_iter, |id| id)?.into_iter().map(Ok),
+        ))
+    } else {
+        Ok(Box::new(forward_iter))
+    }
+}
+
+/// Converts revset graph edges to edges keyed by `(CommitId, is_synthetic)`.
+///
+/// Returns the edges and the targets of the synthetic "elided revisions"
+/// nodes to be added after the current node.
+fn to_graphlog_edges(
+    edges: Vec<GraphEdge<CommitId>>,
+    use_elided_nodes: bool,
+) -> (Vec<GraphEdge<(CommitId, bool)>>, Vec<CommitId>) {
+    let mut graphlog_edges = vec![];
+    // TODO: Should we update revset.iter_graph() to yield a `has_missing` flag
+    // instead of all the missing edges since we don't care about
+    // where they point here anyway?
+    let mut missing_edge_id = None;
+    let mut elided_targets = vec![];
+    for edge in edges {
+        match edge.edge_type {
+            GraphEdgeType::Missing => {
+                missing_edge_id = Some(edge.target);
+            }
+            GraphEdgeType::Direct => {
+                graphlog_edges.push(GraphEdge::direct((edge.target, false)));
+            }
+            GraphEdgeType::Indirect => {
+                if use_elided_nodes {
+                    elided_targets.push(edge.target.clone());
+                    graphlog_edges.push(GraphEdge::direct((edge.target, true)));
+                } else {
+                    graphlog_edges.push(GraphEdge::indirect((edge.target, false)));
+                }
+            }
+        }
+    }
+    if let Some(missing_edge_id) = missing_edge_id {
+        graphlog_edges.push(GraphEdge::missing((missing_edge_id, false)));
+    }
+    (graphlog_edges, elided_targets)
+}
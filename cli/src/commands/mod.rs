@@ -42,6 +42,7 @@ mod new;
 mod next;
 mod operation;
 mod parallelize;
+mod patch;
 mod prev;
 mod rebase;
 mod redo;
@@ -76,7 +77,9 @@ use tracing::instrument;
 
 use crate::cli_util::Args;
 use crate::cli_util::CommandHelper;
+use crate::cli_util::OutputFormat;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
 use crate::complete;
 use crate::ui::Ui;
 
@@ -133,6 +136,8 @@ enum Command {
     #[command(visible_alias = "op")]
     Operation(operation::OperationCommand),
     Parallelize(parallelize::ParallelizeArgs),
+    #[command(subcommand)]
+    Patch(patch::PatchCommand),
     Prev(prev::PrevArgs),
     Rebase(rebase::RebaseArgs),
     Redo(redo::RedoArgs),
@@ -140,8 +145,6 @@ enum Command {
     Restore(restore::RestoreArgs),
     Revert(revert::RevertArgs),
     Root(root::RootArgs),
-    #[command(hide = true)]
-    // TODO: Flesh out.
     Run(run::RunArgs),
     Show(show::ShowArgs),
     Sign(sign::SignArgs),
@@ -169,6 +172,11 @@ pub fn default_app() -> clap::Command {
 #[instrument(skip_all)]
 pub async fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), CommandError> {
     let subcommand = Command::from_arg_matches(command_helper.matches()).unwrap();
+    if command_helper.output_format() == OutputFormat::Json && !supports_json_output(&subcommand) {
+        return Err(user_error(
+            "`--output=json` is not supported by this command",
+        ));
+    }
     match &subcommand {
         Command::Abandon(args) => abandon::cmd_abandon(ui, command_helper, args).await,
         Command::Absorb(args) => absorb::cmd_absorb(ui, command_helper, args).await,
@@ -200,6 +208,7 @@ pub async fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<
         Command::Next(args) => next::cmd_next(ui, command_helper, args).await,
         Command::Operation(args) => operation::cmd_operation(ui, command_helper, args).await,
         Command::Parallelize(args) => parallelize::cmd_parallelize(ui, command_helper, args).await,
+        Command::Patch(args) => patch::cmd_patch(ui, command_helper, args).await,
         Command::Prev(args) => prev::cmd_prev(ui, command_helper, args).await,
         Command::Rebase(args) => rebase::cmd_rebase(ui, command_helper, args).await,
         Command::Redo(args) => redo::cmd_redo(ui, command_helper, args).await,
@@ -226,6 +235,20 @@ pub async fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<
     }
 }
 
+/// Returns true if the command can print its output as JSON objects.
+fn supports_json_output(subcommand: &Command) -> bool {
+    matches!(
+        subcommand,
+        Command::Bookmark(bookmark::BookmarkCommand::List(_))
+            | Command::File(file::FileCommand::List(_))
+            | Command::Log(_)
+            | Command::Operation(operation::OperationCommand::Log(_))
+            | Command::Status(_)
+            | Command::Tag(tag::TagCommand::List(_))
+            | Command::Workspace(workspace::WorkspaceCommand::List(_))
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
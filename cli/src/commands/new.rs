@@ -26,6 +26,7 @@ use jj_lib::rewrite::rebase_commit;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::MergeStrategyArg;
 use crate::cli_util::RevisionArg;
 use crate::cli_util::compute_commit_location;
 use crate::cli_util::merge_args_with;
@@ -156,6 +157,14 @@ pub(crate) struct NewArgs {
     )]
     #[arg(add = ArgValueCompleter::new(complete::revset_expression_mutable))]
     insert_before: Option<Vec<RevisionArg>>,
+
+    /// How to resolve conflicting hunks when merging multiple parents
+    ///
+    /// `ours` takes the hunk from the first parent, and `theirs` takes the
+    /// hunk from the last parent. `union` keeps the lines from all parents.
+    /// Descendants rebased by `--insert-before` are merged the same way.
+    #[arg(long, value_enum, default_value_t)]
+    strategy: MergeStrategyArg,
 }
 
 #[instrument(skip_all)]
@@ -204,6 +213,7 @@ pub(crate) async fn cmd_new(
     let parent_commit_ids_set: HashSet<CommitId> = parent_commit_ids.iter().cloned().collect();
 
     let mut tx = workspace_command.start_transaction();
+    tx.set_merge_strategy(args.strategy.into());
     let merged_tree = merge_commit_trees(tx.repo(), &parent_commits).await?;
     let mut commit_builder = tx
         .repo_mut()
@@ -20,6 +20,7 @@ use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
 use jj_lib::op_walk;
 
+use super::parse_union_opsets;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::short_operation_hash;
 use crate::command_error::CommandError;
@@ -37,13 +38,19 @@ use crate::ui::Ui;
 /// To discard recent operations, use `jj op restore <operation ID>` followed
 /// by `jj op abandon <operation ID>..@-`.
 ///
+/// Any set of operations can be abandoned. For example,
+/// `jj op abandon 'snapshot() & before("30 days ago")'` discards old
+/// working-copy snapshots. The descendants of the abandoned operations will be
+/// reparented onto their nearest remaining ancestors.
+///
 /// Previous versions of a change (or predecessors) are also discarded if they
 /// become unreachable from the operation history. The abandoned operations,
 /// commits, and other unreachable objects can later be garbage collected by
 /// using `jj util gc` command.
 #[derive(clap::Args, Clone, Debug)]
 pub struct OperationAbandonArgs {
-    /// The operation or operation range to abandon
+    /// The operations to abandon
+    #[arg(value_name = "OPSET")]
     #[arg(add = ArgValueCandidates::new(complete::operations))]
     operation: String,
 }
@@ -65,35 +72,16 @@ pub async fn cmd_op_abandon(
         return Err(cli_error("--at-op is not respected"));
     }
     let current_head_ops = op_walk::get_current_head_ops(op_store, op_heads_store.as_ref()).await?;
-    let resolve_op = |op_str| op_walk::resolve_op_at(op_store, &current_head_ops, op_str);
-    let (abandon_root_op, abandon_head_ops) =
-        if let Some((root_op_str, head_op_str)) = args.operation.split_once("..") {
-            let root_op = if root_op_str.is_empty() {
-                repo_loader.root_operation().await
-            } else {
-                resolve_op(root_op_str).await?
-            };
-            let head_ops = if head_op_str.is_empty() {
-                current_head_ops.clone()
-            } else {
-                vec![resolve_op(head_op_str).await?]
-            };
-            (root_op, head_ops)
-        } else {
-            let op = resolve_op(&args.operation).await?;
-            let parent_ops = op.parents().await?;
-            let parent_op = match parent_ops.len() {
-                0 => return Err(user_error("Cannot abandon the root operation")),
-                1 => parent_ops.into_iter().next().unwrap(),
-                _ => return Err(user_error("Cannot abandon a merge operation")),
-            };
-            (parent_op, vec![op])
-        };
+    let expression = parse_union_opsets(repo_loader.settings(), slice::from_ref(&args.operation))?;
+    let abandon_ops = op_walk::resolve_ops_at(op_store, &current_head_ops, &expression).await?;
 
-    if let Some(op) = abandon_head_ops
+    if abandon_ops
         .iter()
-        .find(|op| current_head_ops.contains(op))
+        .any(|op| op.id() == op_store.root_operation_id())
     {
+        return Err(user_error("Cannot abandon the root operation"));
+    }
+    if let Some(op) = abandon_ops.iter().find(|op| current_head_ops.contains(op)) {
         let mut err = user_error(format!(
             "Cannot abandon the current operation {}",
             short_operation_hash(op.id())
@@ -105,18 +93,9 @@ pub async fn cmd_op_abandon(
     }
 
     // Reparent descendants, count the number of abandoned operations.
-    let stats = op_walk::reparent_range(
-        op_store.as_ref(),
-        &abandon_head_ops,
-        &current_head_ops,
-        &abandon_root_op,
-    )
-    .await?;
-    assert_eq!(
-        current_head_ops.len(),
-        stats.new_head_ids.len(),
-        "all current_head_ops should be reparented as they aren't included in abandon_head_ops"
-    );
+    let abandon_ids = abandon_ops.iter().map(|op| op.id().clone()).collect();
+    let stats =
+        op_walk::abandon_operations(op_store.as_ref(), &current_head_ops, &abandon_ids).await?;
     let reparented_head_ops = || iter::zip(&current_head_ops, &stats.new_head_ids);
     if reparented_head_ops().all(|(old, new_id)| old.id() == new_id) {
         writeln!(ui.status(), "Nothing changed.")?;
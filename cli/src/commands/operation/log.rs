@@ -27,11 +27,14 @@ use jj_lib::repo::RepoLoader;
 
 use super::diff::parse_op_diff_changes_in;
 use super::diff::show_op_diff;
+use super::parse_union_opsets;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
+use crate::cli_util::OutputFormat;
 use crate::cli_util::WorkspaceCommandEnvironment;
 use crate::cli_util::format_template;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
 use crate::complete;
 use crate::diff_util::DiffFormatArgs;
 use crate::diff_util::DiffRenderer;
@@ -50,6 +53,18 @@ use crate::ui::Ui;
 /// to inspect the current state without mutation.
 #[derive(clap::Args, Clone, Debug)]
 pub struct OperationLogArgs {
+    /// Which operations to show
+    ///
+    /// If no operations are specified, all operations reachable from the
+    /// current operation are shown. See the [operation log documentation] for
+    /// the operation set syntax.
+    ///
+    /// [operation log documentation]:
+    ///     https://docs.jj-vcs.dev/latest/operation-log/#operation-sets
+    #[arg(long, short = 'r', value_name = "OPSETS")]
+    #[arg(add = ArgValueCandidates::new(complete::operations))]
+    operations: Vec<String>,
+
     /// Limit number of operations to show
     ///
     /// Applied after operations are reordered topologically, but before being
@@ -112,7 +127,15 @@ pub async fn cmd_op_log(
         let workspace_command = command.workspace_helper(ui)?;
         let current_op = workspace_command.repo().operation();
         let repo_loader = workspace_command.workspace().repo_loader();
-        do_op_log(ui, workspace_command.env(), repo_loader, current_op, args).await
+        do_op_log(
+            ui,
+            command,
+            workspace_command.env(),
+            repo_loader,
+            current_op,
+            args,
+        )
+        .await
     } else {
         // Don't load the repo so that the operation history can be inspected
         // even with a corrupted repo state. For example, you can find the first
@@ -122,12 +145,13 @@ pub async fn cmd_op_log(
         let repo_loader = workspace.repo_loader();
         let current_op =
             command.resolve_operation(ui, workspace.repo_loader(), workspace.workspace_name())?;
-        do_op_log(ui, &workspace_env, repo_loader, &current_op, args).await
+        do_op_log(ui, command, &workspace_env, repo_loader, &current_op, args).await
     }
 }
 
 async fn do_op_log(
     ui: &mut Ui,
+    command: &CommandHelper,
     workspace_env: &WorkspaceCommandEnvironment,
     repo_loader: &RepoLoader,
     current_op: &Operation,
@@ -136,6 +160,7 @@ async fn do_op_log(
     let settings = repo_loader.settings();
     let graph_style = GraphStyle::from_settings(settings)?;
     let with_content_format = LogContentFormat::new(ui, settings)?;
+    let json_output = command.output_format() == OutputFormat::Json;
 
     let template: TemplateRenderer<Operation>;
     let op_node_template: TemplateRenderer<Operation>;
@@ -145,10 +170,7 @@ async fn do_op_log(
             Some(current_op.id()),
             workspace_env.operation_template_extensions(),
         );
-        let text = match &args.template {
-            Some(value) => value.to_owned(),
-            None => settings.get_string("templates.op_log")?,
-        };
+        let text = command.list_template_text(args.template.as_deref(), "templates.op_log")?;
         template = workspace_env
             .parse_template(ui, &language, &text)?
             .labeled(["op_log", "operation"]);
@@ -162,6 +184,11 @@ async fn do_op_log(
     }
 
     let diff_formats = diff_formats_for_log(settings, &args.diff_format, args.patch)?;
+    if json_output && (args.op_diff || !diff_formats.is_empty()) {
+        return Err(user_error(
+            "`--output=json` cannot be used with diff options",
+        ));
+    }
     let maybe_show_op_diff = if args.op_diff || !diff_formats.is_empty() {
         let template_text = settings.get_string("templates.commit_summary")?;
         let op_diff_changes_expr =
@@ -224,17 +251,32 @@ async fn do_op_log(
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
     let formatter = formatter.as_mut();
-    let stream =
-        op_walk::walk_ancestors(slice::from_ref(current_op)).take(args.limit.unwrap_or(usize::MAX));
+    let limit = args.limit.unwrap_or(usize::MAX);
+    // Operations selected by the opset expression, or None if unfiltered.
+    let selected_ops = if args.operations.is_empty() {
+        None
+    } else {
+        let expression = parse_union_opsets(settings, &args.operations)?;
+        let op_store = repo_loader.op_store();
+        Some(op_walk::resolve_ops_at(op_store, slice::from_ref(current_op), &expression).await?)
+    };
 
-    if !args.no_graph {
+    if !args.no_graph && !json_output {
         let mut raw_output = formatter.raw()?;
         let mut graph = get_graphlog(graph_style, raw_output.as_mut());
-        let stream = stream.map_ok(|op| {
-            let ids = op.parent_ids();
-            let edges = ids.iter().cloned().map(GraphEdge::direct).collect();
-            (op, edges)
-        });
+        let stream: BoxStream<'_, _> = if let Some(ops) = selected_ops {
+            let nodes = op_walk::build_graph_nodes(ops).await?;
+            stream::iter(nodes).map(Ok).take(limit).boxed()
+        } else {
+            op_walk::walk_ancestors(slice::from_ref(current_op))
+                .take(limit)
+                .map_ok(|op| {
+                    let ids = op.parent_ids();
+                    let edges = ids.iter().cloned().map(GraphEdge::direct).collect();
+                    (op, edges)
+                })
+                .boxed()
+        };
         let mut stream_nodes: BoxStream<'_, _> = if args.reversed {
             stream::iter(
                 reverse_graph(stream.collect::<Vec<_>>().await.into_iter(), Operation::id)?
@@ -265,13 +307,26 @@ async fn do_op_log(
             )?;
         }
     } else {
+        let stream: BoxStream<'_, _> = if let Some(ops) = selected_ops {
+            stream::iter(ops).map(Ok).take(limit).boxed()
+        } else {
+            op_walk::walk_ancestors(slice::from_ref(current_op))
+                .take(limit)
+                .boxed()
+        };
         let mut stream: BoxStream<'_, _> = if args.reversed {
             stream::iter(stream.collect::<Vec<_>>().await.into_iter().rev()).boxed()
         } else {
             stream.boxed()
         };
         while let Some(op) = stream.try_next().await? {
-            with_content_format.write(formatter, |formatter| template.format(&op, formatter))?;
+            if json_output {
+                // Don't wrap JSON lines
+                template.format(&op, formatter)?;
+            } else {
+                with_content_format
+                    .write(formatter, |formatter| template.format(&op, formatter))?;
+            }
             if let Some(show) = &maybe_show_op_diff {
                 show(ui, formatter, &op, &with_content_format).await?;
             }
@@ -20,6 +20,8 @@ mod restore;
 mod revert;
 mod show;
 
+use std::sync::Arc;
+
 use abandon::OperationAbandonArgs;
 use abandon::cmd_op_abandon;
 use clap::Subcommand;
@@ -27,6 +29,11 @@ use diff::OperationDiffArgs;
 use diff::cmd_op_diff;
 use integrate::OperationIntegrateArgs;
 use integrate::cmd_op_integrate;
+use itertools::Itertools as _;
+use jj_lib::opset;
+use jj_lib::opset::OpsetExpression;
+use jj_lib::opset::OpsetParseContext;
+use jj_lib::settings::UserSettings;
 use log::OperationLogArgs;
 use log::cmd_op_log;
 use restore::OperationRestoreArgs;
@@ -112,6 +119,25 @@ pub(crate) fn view_with_desired_portions_restored(
         remote_views: remote_source.remote_views.clone(),
         git_refs: current_view.git_refs.clone(),
         git_head: current_view.git_head.clone(),
+        git_worktree_heads: current_view.git_worktree_heads.clone(),
         wc_commit_ids: repo_source.wc_commit_ids.clone(),
     }
 }
+
+/// Parses operation set expressions, and takes the union of them.
+fn parse_union_opsets(
+    settings: &UserSettings,
+    texts: &[String],
+) -> Result<Arc<OpsetExpression>, CommandError> {
+    let context = OpsetParseContext {
+        date_pattern_context: settings.date_pattern_context(),
+    };
+    let expressions: Vec<_> = texts
+        .iter()
+        .map(|text| opset::parse(text, &context))
+        .try_collect()?;
+    Ok(expressions
+        .into_iter()
+        .reduce(|lhs, rhs| Arc::new(OpsetExpression::Union(lhs, rhs)))
+        .unwrap_or_else(|| Arc::new(OpsetExpression::None)))
+}
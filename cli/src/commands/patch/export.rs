@@ -0,0 +1,207 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::io::Write as _;
+use std::path::PathBuf;
+
+use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
+use futures::TryStreamExt as _;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::repo::Repo as _;
+use jj_lib::revset::RevsetStreamExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::short_commit_hash;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::complete;
+use crate::diff_util::DiffFormat;
+use crate::diff_util::DiffRenderer;
+use crate::diff_util::UnifiedDiffOptions;
+use crate::formatter::PlainTextFormatter;
+use crate::templater::TemplateRenderer;
+use crate::ui::Ui;
+
+/// Export revisions as email patches
+///
+/// Each revision is written to a `NNNN-<subject>.patch` file, parents first.
+/// The email headers are rendered with the `templates.patch_export` template,
+/// and are followed by the changes in Git diff format.
+///
+/// Merge commits cannot be exported.
+#[derive(clap::Args, Clone, Debug)]
+pub struct PatchExportArgs {
+    /// The revisions to export
+    #[arg(long, short, value_name = "REVSETS", default_value = "@")]
+    #[arg(add = ArgValueCompleter::new(complete::revset_expression_all))]
+    revisions: Vec<RevisionArg>,
+
+    /// Directory to write the patch files to
+    ///
+    /// Defaults to the current directory.
+    #[arg(long, short, value_name = "DIR", value_hint = clap::ValueHint::DirPath)]
+    output_directory: Option<PathBuf>,
+
+    /// Write all patches to stdout in mbox format instead of files
+    #[arg(long, conflicts_with = "output_directory")]
+    stdout: bool,
+
+    /// Render the email headers using the given template
+    ///
+    /// If not specified, this defaults to the `templates.patch_export`
+    /// setting.
+    #[arg(long, short = 'T')]
+    #[arg(add = ArgValueCandidates::new(complete::template_aliases))]
+    template: Option<String>,
+}
+
+#[instrument(skip_all)]
+pub async fn cmd_patch_export(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &PatchExportArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo();
+    let expression = workspace_command.parse_union_revsets(ui, &args.revisions)?;
+    let mut commits: Vec<Commit> = expression
+        .evaluate()?
+        .stream()
+        .commits(repo.store())
+        .try_collect()
+        .await?;
+    // Parents first, like `git format-patch`
+    commits.reverse();
+    for commit in &commits {
+        if commit.id() == repo.store().root_commit_id() {
+            return Err(user_error("Cannot export the root commit as a patch"));
+        }
+        if commit.parent_ids().len() > 1 {
+            return Err(user_error(format!(
+                "Cannot export merge commit {} as a patch",
+                short_commit_hash(commit.id())
+            )));
+        }
+    }
+    if commits.is_empty() {
+        writeln!(ui.status(), "No revisions to export.")?;
+        return Ok(());
+    }
+
+    let template_string = match &args.template {
+        Some(value) => value.clone(),
+        None => workspace_command
+            .settings()
+            .get_string("templates.patch_export")?,
+    };
+    let template = workspace_command.parse_commit_template(ui, &template_string)?;
+    let diff_options = UnifiedDiffOptions::from_settings(workspace_command.settings())?;
+    let diff_renderer =
+        workspace_command.diff_renderer(vec![DiffFormat::Git(Box::new(diff_options))]);
+    let signature = format!("-- \n{}\n", command.app().render_version());
+
+    if args.stdout {
+        let mut stdout = ui.stdout();
+        for commit in &commits {
+            let patch = render_patch(ui, &template, &diff_renderer, commit, &signature).await?;
+            stdout.write_all(&patch)?;
+        }
+        return Ok(());
+    }
+
+    let output_directory = args.output_directory.clone().unwrap_or_default();
+    if !output_directory.as_os_str().is_empty() {
+        fs::create_dir_all(&output_directory).map_err(|err| {
+            user_error_with_message(
+                format!("Failed to create directory {}", output_directory.display()),
+                err,
+            )
+        })?;
+    }
+    for (i, commit) in commits.iter().enumerate() {
+        let file_name = format!(
+            "{:04}-{}.patch",
+            i + 1,
+            patch_file_slug(commit.description().lines().next().unwrap_or_default())
+        );
+        let path = output_directory.join(file_name);
+        let patch = render_patch(ui, &template, &diff_renderer, commit, &signature).await?;
+        fs::write(&path, patch).map_err(|err| {
+            user_error_with_message(format!("Failed to write {}", path.display()), err)
+        })?;
+        writeln!(ui.status(), "{}", path.display())?;
+    }
+    Ok(())
+}
+
+async fn render_patch(
+    ui: &Ui,
+    template: &TemplateRenderer<'_, Commit>,
+    diff_renderer: &DiffRenderer<'_>,
+    commit: &Commit,
+    signature: &str,
+) -> Result<Vec<u8>, CommandError> {
+    let mut patch = template.format_plain_text(commit);
+    let mut formatter = PlainTextFormatter::new(&mut patch);
+    diff_renderer
+        .show_patch(
+            ui,
+            &mut formatter,
+            commit,
+            &EverythingMatcher,
+            ui.term_width(),
+        )
+        .await?;
+    patch.extend_from_slice(signature.as_bytes());
+    Ok(patch)
+}
+
+/// Turns the subject into a file name component the way `git format-patch`
+/// does.
+fn patch_file_slug(subject: &str) -> String {
+    const MAX_LEN: usize = 52;
+    let slug = subject
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '.' || c == '_'))
+        .filter(|word| !word.is_empty())
+        .join("-");
+    let mut slug = slug.trim_matches('.').to_owned();
+    if slug.len() > MAX_LEN {
+        slug.truncate(MAX_LEN);
+        slug = slug.trim_end_matches(['-', '.']).to_owned();
+    }
+    slug
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patch_file_slug() {
+        assert_eq!(patch_file_slug("cli: add `jj patch`"), "cli-add-jj-patch");
+        assert_eq!(patch_file_slug("..Fix v1.2 bug.."), "Fix-v1.2-bug");
+        assert_eq!(patch_file_slug(""), "");
+        assert_eq!(
+            patch_file_slug(&"a ".repeat(40)),
+            "a-a-a-a-a-a-a-a-a-a-a-a-a-a-a-a-a-a-a-a-a-a-a-a-a-a"
+        );
+    }
+}
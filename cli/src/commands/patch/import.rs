@@ -0,0 +1,118 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::io::Write as _;
+use std::path::PathBuf;
+
+use clap_complete::ArgValueCompleter;
+use jj_lib::backend::Signature;
+use jj_lib::patch::PatchApplyError;
+use jj_lib::patch::apply_file_patches;
+use jj_lib::patch::parse_mbox;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Apply email patches as new revisions
+///
+/// Each file can contain a single patch or a series of patches in mbox
+/// format, as written by `git format-patch` or `jj patch export`. The patches
+/// are applied in order, each on top of the previous one, and the resulting
+/// revisions keep the author and the description of the patches.
+///
+/// The working-copy commit is not changed.
+#[derive(clap::Args, Clone, Debug)]
+pub struct PatchImportArgs {
+    /// Patch or mbox files to import
+    #[arg(required = true, value_hint = clap::ValueHint::FilePath)]
+    paths: Vec<PathBuf>,
+
+    /// The revision to apply the first patch onto
+    #[arg(long, short, default_value = "@", value_name = "REVSET")]
+    #[arg(add = ArgValueCompleter::new(complete::revset_expression_all))]
+    onto: RevisionArg,
+}
+
+#[instrument(skip_all)]
+pub async fn cmd_patch_import(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &PatchImportArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let mut messages = vec![];
+    for path in &args.paths {
+        let content = fs::read(path).map_err(|err| {
+            user_error_with_message(format!("Failed to read {}", path.display()), err)
+        })?;
+        let parsed = parse_mbox(&content).map_err(|err| {
+            user_error_with_message(format!("Failed to parse {}", path.display()), err)
+        })?;
+        messages.extend(parsed);
+    }
+    if messages.is_empty() {
+        return Err(user_error("No patches found"));
+    }
+
+    let mut parent = workspace_command.resolve_single_rev(ui, &args.onto).await?;
+    let mut tx = workspace_command.start_transaction();
+    let mut new_commits = vec![];
+    for (i, message) in messages.iter().enumerate() {
+        let subject = message.description.lines().next().unwrap_or_default();
+        let new_tree = apply_file_patches(&parent.tree(), &message.files)
+            .await
+            .map_err(|err| match err {
+                PatchApplyError::Backend(err) => err.into(),
+                err => user_error_with_message(
+                    format!("Failed to apply patch {}: {subject}", i + 1),
+                    err,
+                ),
+            })?;
+        let mut commit_builder = tx
+            .repo_mut()
+            .new_commit(vec![parent.id().clone()], new_tree)
+            .set_description(&message.description);
+        if let Some(author) = &message.author {
+            let timestamp = author
+                .timestamp
+                .unwrap_or(commit_builder.committer().timestamp);
+            commit_builder = commit_builder.set_author(Signature {
+                name: author.name.clone(),
+                email: author.email.clone(),
+                timestamp,
+            });
+        }
+        parent = commit_builder.write().await?;
+        new_commits.push(parent.clone());
+    }
+
+    if let Some(mut formatter) = ui.status_formatter() {
+        writeln!(formatter, "Imported {} patches:", new_commits.len())?;
+        for commit in &new_commits {
+            write!(formatter, "  ")?;
+            tx.write_commit_summary(formatter.as_mut(), commit)?;
+            writeln!(formatter)?;
+        }
+    }
+    tx.finish(ui, format!("import {} patches", new_commits.len()))
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,41 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod export;
+mod import;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Exchange revisions as email patches
+///
+/// The patches are in the format of `git format-patch`, so they can be sent
+/// with `git send-email` and applied with `git am`, and vice versa.
+#[derive(clap::Subcommand, Clone, Debug)]
+pub enum PatchCommand {
+    Export(export::PatchExportArgs),
+    Import(import::PatchImportArgs),
+}
+
+pub async fn cmd_patch(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &PatchCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        PatchCommand::Export(args) => export::cmd_patch_export(ui, command, args).await,
+        PatchCommand::Import(args) => import::cmd_patch_import(ui, command, args).await,
+    }
+}
@@ -35,6 +35,7 @@ use jj_lib::rewrite::find_duplicate_divergent_commits;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::MergeStrategyArg;
 use crate::cli_util::RevisionArg;
 use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::compute_commit_location;
@@ -323,6 +324,13 @@ pub(crate) struct RebaseArgs {
     /// removed.
     #[arg(long)]
     simplify_parents: bool,
+
+    /// How to resolve conflicting hunks while rebasing
+    ///
+    /// `ours` takes the hunk from the destination, and `theirs` takes the
+    /// hunk from the rebased revision. `union` keeps the lines from both.
+    #[arg(long, value_enum, default_value_t)]
+    strategy: MergeStrategyArg,
 }
 
 #[derive(clap::Args, Clone, Debug)]
@@ -401,6 +409,7 @@ pub(crate) async fn cmd_rebase(
     }
 
     let mut tx = workspace_command.start_transaction();
+    tx.set_merge_strategy(args.strategy.into());
     let mut computed_move = compute_move_commits(tx.repo(), &loc).await?;
     if !args.keep_divergent {
         let abandoned_divergent =
@@ -567,7 +576,7 @@ async fn plan_rebase_branch(
     })
 }
 
-fn check_rebase_destinations(
+pub(crate) fn check_rebase_destinations(
     repo: &Arc<ReadonlyRepo>,
     new_parents: &[CommitId],
     commit: &Commit,
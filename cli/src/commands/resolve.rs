@@ -12,18 +12,43 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fs;
+use std::io;
+use std::io::Read as _;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use bstr::BString;
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
+use jj_lib::backend::BackendResult;
+use jj_lib::backend::CopyId;
+use jj_lib::backend::TreeValue;
+use jj_lib::conflicts::try_materialize_file_conflict_value;
+use jj_lib::conflicts::update_from_hunks;
+use jj_lib::files;
+use jj_lib::files::MergeResult;
+use jj_lib::matchers::Matcher;
+use jj_lib::merge::Merge;
+use jj_lib::merge::MergedTreeValue;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::merged_tree_builder::MergedTreeBuilder;
 use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo_path::RepoPathBuf;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::print_conflicted_paths;
 use crate::cli_util::print_unmatched_explicit_paths;
 use crate::command_error::CommandError;
 use crate::command_error::cli_error;
+use crate::command_error::internal_error_with_message;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
 use crate::complete;
 use crate::formatter::FormatterExt as _;
 use crate::ui::Ui;
@@ -39,6 +64,13 @@ use crate::ui::Ui;
 /// Note that conflicts can also be resolved without using this command. You may
 /// edit the conflict markers in the conflicted file directly with a text
 /// editor.
+///
+/// Conflicts can also be exported as JSON by `--export-json`, resolved by
+/// another program, and imported back by `--import-json`. Each conflicted file
+/// is split into hunks. A resolved hunk has a `resolved` field with its
+/// content. A conflicted hunk has a `conflict` field with the contents of its
+/// `bases` and `sides`. To resolve a conflicted hunk, add a `resolved` field to
+/// it. Hunks left unresolved remain conflicted after importing.
 //  TODOs:
 //   - `jj resolve --editor` to resolve a conflict in the default text editor. Should work for
 //     conflicts with 3+ adds. Useful to resolve conflicts in a commit other than the current one.
@@ -66,6 +98,22 @@ pub(crate) struct ResolveArgs {
     #[arg(add = ArgValueCandidates::new(complete::merge_editors))]
     tool: Option<String>,
 
+    /// Write the conflicted hunks to a JSON file instead of resolving them
+    ///
+    /// Use `-` to write to stdout. Conflicts in non-UTF-8 files and
+    /// conflicts involving anything other than regular files are skipped.
+    #[arg(long, value_name = "PATH", value_hint = clap::ValueHint::FilePath)]
+    #[arg(conflicts_with_all = ["list", "tool"])]
+    export_json: Option<PathBuf>,
+
+    /// Resolve conflicts from a JSON file written by `--export-json`
+    ///
+    /// Use `-` to read from stdin. The conflicts must not have changed since
+    /// they were exported.
+    #[arg(long, value_name = "PATH", value_hint = clap::ValueHint::FilePath)]
+    #[arg(conflicts_with_all = ["list", "tool", "export_json"])]
+    import_json: Option<PathBuf>,
+
     /// Only resolve conflicts in these paths. You can use the `--list` argument
     /// to find paths to use here.
     #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
@@ -104,16 +152,24 @@ pub(crate) async fn cmd_resolve(
             &workspace_command,
         );
     }
+    if let Some(output_path) = &args.export_json {
+        return export_conflicts_json(ui, &workspace_command, &tree, conflicts, output_path).await;
+    }
 
     let repo_paths = conflicts
         .iter()
         .map(|(path, _)| path.as_ref())
         .collect_vec();
     workspace_command.check_rewritable([commit.id()]).await?;
-    let merge_editor = workspace_command.merge_editor(ui, args.tool.as_deref())?;
+    let (new_tree, partial_resolution_error) = if let Some(input_path) = &args.import_json {
+        let exported = read_conflicts_json(input_path)?;
+        let new_tree = import_conflicts_json(&workspace_command, &tree, &matcher, exported).await?;
+        (new_tree, None)
+    } else {
+        let merge_editor = workspace_command.merge_editor(ui, args.tool.as_deref())?;
+        merge_editor.edit_files(ui, &tree, &repo_paths).await?
+    };
     let mut tx = workspace_command.start_transaction();
-    let (new_tree, partial_resolution_error) =
-        merge_editor.edit_files(ui, &tree, &repo_paths).await?;
     let new_commit = tx
         .repo_mut()
         .rewrite_commit(&commit)
@@ -147,3 +203,208 @@ pub(crate) async fn cmd_resolve(
     }
     Ok(())
 }
+
+/// Conflicts written by `jj resolve --export-json`.
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+struct ConflictsJson {
+    files: Vec<ConflictFileJson>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+struct ConflictFileJson {
+    path: String,
+    /// Labels of the bases and sides of the conflict, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    labels: Option<ConflictTermsJson>,
+    hunks: Vec<ConflictHunkJson>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+struct ConflictHunkJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    conflict: Option<ConflictTermsJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    resolved: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+struct ConflictTermsJson {
+    bases: Vec<String>,
+    sides: Vec<String>,
+}
+
+impl ConflictTermsJson {
+    fn from_merge(merge: &Merge<String>) -> Self {
+        Self {
+            bases: merge.removes().cloned().collect(),
+            sides: merge.adds().cloned().collect(),
+        }
+    }
+}
+
+async fn export_conflicts_json(
+    ui: &mut Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    tree: &MergedTree,
+    conflicts: Vec<(RepoPathBuf, BackendResult<MergedTreeValue>)>,
+    output_path: &Path,
+) -> Result<(), CommandError> {
+    let store = tree.store();
+    let mut files = vec![];
+    for (path, conflict) in conflicts {
+        let conflict = conflict?;
+        let ui_path = workspace_command.format_file_path(&path);
+        let Some(file) =
+            try_materialize_file_conflict_value(store, &path, &conflict, tree.labels()).await?
+        else {
+            writeln!(
+                ui.warning_default(),
+                "Skipping {ui_path}: {}",
+                conflict.describe(tree.labels())
+            )?;
+            continue;
+        };
+        let MergeResult::Conflict(hunks) =
+            files::merge_hunks(&file.contents, store.merge_options())
+        else {
+            continue;
+        };
+        let Ok(hunks) = hunks
+            .into_iter()
+            .map(|hunk| hunk.try_map(|content| String::from_utf8(content.to_vec())))
+            .try_collect::<_, Vec<_>, _>()
+        else {
+            writeln!(
+                ui.warning_default(),
+                "Skipping {ui_path}: The file is not valid UTF-8"
+            )?;
+            continue;
+        };
+        let hunks = hunks
+            .into_iter()
+            .map(|hunk| match hunk.into_resolved() {
+                Ok(content) => ConflictHunkJson {
+                    conflict: None,
+                    resolved: Some(content),
+                },
+                Err(merge) => ConflictHunkJson {
+                    conflict: Some(ConflictTermsJson::from_merge(&merge)),
+                    resolved: None,
+                },
+            })
+            .collect();
+        let labels = file
+            .labels
+            .has_labels()
+            .then(|| ConflictTermsJson::from_merge(file.labels.as_merge()));
+        files.push(ConflictFileJson {
+            path: path.as_internal_file_string().to_owned(),
+            labels,
+            hunks,
+        });
+    }
+
+    let mut json = serde_json::to_vec_pretty(&ConflictsJson { files })
+        .map_err(|err| internal_error_with_message("Failed to serialize conflicts", err))?;
+    json.push(b'\n');
+    if output_path == Path::new("-") {
+        ui.stdout().write_all(&json)?;
+    } else {
+        fs::write(output_path, json).map_err(|err| {
+            user_error_with_message(format!("Failed to write {}", output_path.display()), err)
+        })?;
+    }
+    Ok(())
+}
+
+fn read_conflicts_json(input_path: &Path) -> Result<ConflictsJson, CommandError> {
+    let content = if input_path == Path::new("-") {
+        let mut content = vec![];
+        io::stdin()
+            .read_to_end(&mut content)
+            .map_err(|err| user_error_with_message("Failed to read stdin", err))?;
+        content
+    } else {
+        fs::read(input_path).map_err(|err| {
+            user_error_with_message(format!("Failed to read {}", input_path.display()), err)
+        })?
+    };
+    serde_json::from_slice(&content).map_err(|err| {
+        user_error_with_message(format!("Failed to parse {}", input_path.display()), err)
+    })
+}
+
+/// Applies the hunk resolutions in `exported` to `tree`.
+async fn import_conflicts_json(
+    workspace_command: &WorkspaceCommandHelper,
+    tree: &MergedTree,
+    matcher: &dyn Matcher,
+    exported: ConflictsJson,
+) -> Result<MergedTree, CommandError> {
+    let store = tree.store();
+    let mut tree_builder = MergedTreeBuilder::new(tree.clone());
+    for file_json in exported.files {
+        let path = RepoPathBuf::from_internal_string(&file_json.path).map_err(|err| {
+            user_error_with_message(format!("Invalid path {:?}", file_json.path), err)
+        })?;
+        if !matcher.matches(&path) {
+            continue;
+        }
+        let ui_path = workspace_command.format_file_path(&path);
+        let Err(conflict) = tree.path_value(&path).await?.into_resolved() else {
+            return Err(user_error(format!("No conflict found at {ui_path}")));
+        };
+        let changed_error = || {
+            user_error(format!(
+                "Conflict in {ui_path} has changed since it was exported"
+            ))
+        };
+        let file = try_materialize_file_conflict_value(store, &path, &conflict, tree.labels())
+            .await?
+            .ok_or_else(changed_error)?;
+        let MergeResult::Conflict(old_hunks) =
+            files::merge_hunks(&file.contents, store.merge_options())
+        else {
+            return Err(changed_error());
+        };
+        if old_hunks.len() != file_json.hunks.len() {
+            return Err(changed_error());
+        }
+
+        let mut new_hunks = vec![];
+        for (old_hunk, hunk_json) in old_hunks.iter().zip(file_json.hunks) {
+            let old_terms = old_hunk
+                .as_resolved()
+                .is_none()
+                .then(|| old_hunk.map(|content| content.to_string()))
+                .map(|merge| ConflictTermsJson::from_merge(&merge));
+            if hunk_json.conflict != old_terms {
+                return Err(changed_error());
+            }
+            let new_hunk = match hunk_json.resolved {
+                Some(content) => Merge::resolved(BString::from(content)),
+                None => old_hunk.clone(),
+            };
+            new_hunks.push(new_hunk);
+        }
+        let new_file_ids =
+            update_from_hunks(&file.unsimplified_ids, store, &path, &new_hunks).await?;
+        let new_tree_value = match new_file_ids.into_resolved() {
+            Ok(file_id) => {
+                let executable = file.executable.ok_or_else(|| {
+                    user_error(format!(
+                        "Cannot resolve {ui_path} because of a conflict in the executable bit"
+                    ))
+                })?;
+                Merge::resolved(file_id.map(|id| TreeValue::File {
+                    id,
+                    executable,
+                    copy_id: CopyId::placeholder(),
+                }))
+            }
+            Err(file_ids) => conflict.with_new_file_ids(&file_ids),
+        };
+        tree_builder.set_or_remove(path, new_tree_value);
+    }
+    Ok(tree_builder.write_tree().await?)
+}
@@ -14,19 +14,58 @@
 
 //! This file contains the internal implementation of `run`.
 
+use std::collections::HashMap;
+use std::io::Write as _;
+use std::iter;
+use std::path::PathBuf;
+use std::process::Output;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
 use futures::TryStreamExt as _;
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
+use jj_lib::file_util::IoResultExt as _;
+use jj_lib::local_working_copy::TreeState;
+use jj_lib::local_working_copy::TreeStateSettings;
+use jj_lib::lock::FileLock;
+use jj_lib::merge::Merge;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+use jj_lib::store::Store;
+use jj_lib::working_copy::SnapshotOptions;
+use pollster::FutureExt as _;
+use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::short_commit_hash;
 use crate::command_error::CommandError;
+use crate::command_error::config_error_with_message;
+use crate::command_error::internal_error_with_message;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
 use crate::ui::Ui;
 
-/// (**Stub**, does not work yet) Run a command across a set of revisions.
+/// Run a command across a set of revisions.
+///
+/// Each revision is checked out into a private working copy under `.jj/run/`,
+/// and the command is run there by the shell (`sh -c` on Unix, `cmd /C` on
+/// Windows). Up to `--jobs` revisions are processed in parallel. The commit ID
+/// of the revision is available to the command in the `$JJ_RUN_COMMIT_ID`
+/// environment variable.
 ///
+/// If the command modifies files, the revision is rewritten with the new
+/// content and its descendants are rebased, like `jj fix` does. Changes made
+/// by a command that exits with a non-zero status are discarded.
 ///
-/// All recorded state will be persisted in the `.jj` directory, so occasionally
-/// a `jj run --clean` is needed to clean up disk space.
+/// The private working copies are kept between invocations so that build
+/// artifacts can be reused, so occasionally a `jj run --clean` is needed to
+/// clean up disk space.
 ///
 /// # Example
 ///
@@ -38,7 +77,8 @@ use crate::ui::Ui;
 #[command(verbatim_doc_comment)]
 pub struct RunArgs {
     /// The command to run across all selected revisions.
-    shell_command: String,
+    #[arg(required_unless_present = "clean")]
+    shell_command: Option<String>,
 
     /// The revisions to change.
     #[arg(
@@ -57,28 +97,346 @@ pub struct RunArgs {
     /// How many processes should run in parallel, uses by default all cores.
     #[arg(long, short)]
     jobs: Option<usize>,
+
+    /// Remove the working copies left in `.jj/run/` by previous invocations.
+    #[arg(long, conflicts_with = "shell_command")]
+    clean: bool,
+}
+
+/// Result of running the command in the working copy of a single revision.
+pub(crate) struct RunOutcome {
+    pub output: Output,
+    /// The snapshotted tree if the command succeeded and modified files.
+    pub new_tree: Option<MergedTree>,
 }
 
+#[instrument(skip_all)]
 pub async fn cmd_run(
     ui: &mut Ui,
     command: &CommandHelper,
     args: &RunArgs,
 ) -> Result<(), CommandError> {
-    let workspace_command = command.workspace_helper(ui)?;
-    let _resolved_commits: Vec<_> = workspace_command
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let run_dir = workspace_command.workspace_root().join(".jj").join("run");
+    if args.clean {
+        if run_dir.exists() {
+            std::fs::remove_dir_all(&run_dir).context(&run_dir)?;
+        }
+        writeln!(
+            ui.status(),
+            "Removed working copies in {}",
+            run_dir.display()
+        )?;
+        return Ok(());
+    }
+    let shell_command_text = args.shell_command.as_deref().unwrap();
+
+    // Run the command on parents before their children, so the output is
+    // presented in a natural order.
+    let mut commits: Vec<Commit> = workspace_command
         .parse_union_revsets(ui, &args.revisions)?
         .evaluate_to_commits()?
         .try_collect()
         .await?;
+    commits.reverse();
     // Jobs are resolved in this order:
     // 1. Commandline argument iff > 0.
     // 2. the amount of cores available.
     // 3. a single job, if all of the above fails.
-    let _jobs = match args.jobs {
+    let jobs = match args.jobs {
         Some(0) | None => std::thread::available_parallelism().map(|t| t.into()).ok(),
         Some(jobs) => Some(jobs),
     }
     // Fallback to a single user-visible job.
-    .unwrap_or(1usize);
-    Err(user_error("This is a stub, do not use"))
+    .unwrap_or(1usize)
+    .min(commits.len().max(1));
+
+    let tree_state_settings =
+        TreeStateSettings::try_from_user_settings(workspace_command.settings()).map_err(|err| {
+            config_error_with_message("Failed to read the tree state settings", err)
+        })?;
+    let auto_tracking_matcher = workspace_command.auto_tracking_matcher(ui)?;
+    let snapshot_options = workspace_command
+        .snapshot_options_with_start_tracking_matcher(auto_tracking_matcher.as_ref())?;
+    let store = workspace_command.repo().store().clone();
+
+    let slots = (0..jobs)
+        .map(|slot| {
+            let slot_dir = run_dir.join(slot.to_string());
+            RunSlot {
+                working_copy_path: slot_dir.join("working_copy"),
+                state_path: slot_dir.join("state"),
+            }
+        })
+        .collect_vec();
+    let build_command = |commit: &Commit| {
+        let mut command = shell_command(shell_command_text);
+        command.env("JJ_RUN_COMMIT_ID", commit.id().hex());
+        command
+    };
+    let runner = Runner {
+        store: &store,
+        slots: &slots,
+        command_description: &format!("`{shell_command_text}`"),
+        build_command: &build_command,
+        tree_state_settings: &tree_state_settings,
+        snapshot_options: &snapshot_options,
+    };
+    let outcomes = runner.run_all(&commits)?;
+
+    let mut new_trees: HashMap<CommitId, MergedTree> = HashMap::new();
+    let mut num_failed = 0;
+    for (commit, outcome) in commits.iter().zip(outcomes) {
+        let RunOutcome { output, new_tree } = outcome;
+        if let Some(mut formatter) = ui.status_formatter() {
+            write!(formatter, "Ran on ")?;
+            workspace_command.write_commit_summary(formatter.as_mut(), commit)?;
+            writeln!(formatter)?;
+        }
+        ui.stdout().write_all(&output.stdout)?;
+        ui.stderr().write_all(&output.stderr)?;
+        if !output.status.success() {
+            num_failed += 1;
+            writeln!(
+                ui.warning_default(),
+                "Command `{shell_command_text}` failed on revision {} with {}",
+                short_commit_hash(commit.id()),
+                output.status
+            )?;
+        }
+        if let Some(new_tree) = new_tree {
+            new_trees.insert(commit.id().clone(), new_tree);
+        }
+    }
+
+    workspace_command.check_rewritable(new_trees.keys()).await?;
+    let mut tx = workspace_command.start_transaction();
+    let num_modified = new_trees.len();
+    let mut num_rebased = 0;
+    tx.repo_mut()
+        .transform_descendants(new_trees.keys().cloned().collect(), async |rewriter| {
+            let Some(run_tree) = new_trees.remove(rewriter.old_commit().id()) else {
+                if rewriter.parents_changed() {
+                    rewriter.rebase().await?.write().await?;
+                    num_rebased += 1;
+                }
+                return Ok(());
+            };
+            // The command saw the revision as it was before any of its ancestors
+            // were rewritten, so apply its changes on top of the rebased tree.
+            let old_tree = rewriter.old_commit().tree();
+            let commit_label = rewriter.old_commit().conflict_label();
+            let commit_builder = rewriter.rebase().await?;
+            let new_tree = MergedTree::merge(Merge::from_vec(vec![
+                (commit_builder.tree(), format!("{commit_label} (rebased)")),
+                (old_tree, format!("{commit_label} (before running command)")),
+                (run_tree, format!("{commit_label} (after running command)")),
+            ]))
+            .await?;
+            commit_builder.set_tree(new_tree).write().await?;
+            Ok(())
+        })
+        .await?;
+    writeln!(
+        ui.status(),
+        "Modified {num_modified} commits of {} checked, rebased {num_rebased} descendant commits.",
+        commits.len()
+    )?;
+    tx.finish(
+        ui,
+        format!("run `{shell_command_text}` on {} commits", commits.len()),
+    )
+    .await?;
+    if num_failed > 0 {
+        return Err(user_error(format!(
+            "Command `{shell_command_text}` failed on {num_failed} revisions"
+        )));
+    }
+    Ok(())
+}
+
+/// Private working copy in which the command is run by a single job.
+pub(crate) struct RunSlot {
+    pub working_copy_path: PathBuf,
+    /// Directory where the tree state of the working copy is stored.
+    pub state_path: PathBuf,
+}
+
+/// Runs a command in private working copies, one per slot.
+pub(crate) struct Runner<'a> {
+    pub store: &'a Arc<Store>,
+    pub slots: &'a [RunSlot],
+    /// How the command is referred to in error messages.
+    pub command_description: &'a str,
+    /// Builds the command to run for the given revision.
+    pub build_command: &'a (dyn Fn(&Commit) -> std::process::Command + Sync),
+    pub tree_state_settings: &'a TreeStateSettings,
+    pub snapshot_options: &'a SnapshotOptions<'a>,
+}
+
+impl Runner<'_> {
+    /// Runs the command on all `commits` using one thread per slot. Returns
+    /// the outcomes in the same order as `commits`.
+    pub fn run_all(&self, commits: &[Commit]) -> Result<Vec<RunOutcome>, CommandError> {
+        let next_index = AtomicUsize::new(0);
+        self.run_in_slots(commits, |_| {
+            iter::repeat_with(|| next_index.fetch_add(1, Ordering::Relaxed))
+        })
+    }
+
+    /// Runs the command on each of `commits` in the slot of the same index.
+    /// Returns the outcomes in the same order as `commits`.
+    pub fn run_each(&self, commits: &[Commit]) -> Result<Vec<RunOutcome>, CommandError> {
+        assert!(commits.len() <= self.slots.len());
+        self.run_in_slots(commits, iter::once)
+    }
+
+    /// Runs the command on the commits at the indices produced by
+    /// `slot_indices` for each slot.
+    fn run_in_slots<I: Iterator<Item = usize>>(
+        &self,
+        commits: &[Commit],
+        slot_indices: impl Fn(usize) -> I + Sync,
+    ) -> Result<Vec<RunOutcome>, CommandError> {
+        let results: Vec<Vec<(usize, RunOutcome)>> = std::thread::scope(|s| {
+            let handles = self
+                .slots
+                .iter()
+                .enumerate()
+                .map(|(slot_index, slot)| {
+                    let slot_indices = &slot_indices;
+                    s.spawn(move || self.run_slot(slot, commits, slot_indices(slot_index)))
+                })
+                .collect_vec();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("run worker should not panic"))
+                .try_collect()
+        })?;
+        Ok(results
+            .into_iter()
+            .flatten()
+            .sorted_by_key(|(index, _)| *index)
+            .map(|(_, outcome)| outcome)
+            .collect())
+    }
+
+    /// Processes commits at the given `indices` in the working copy of the
+    /// given slot until an index is out of range.
+    fn run_slot(
+        &self,
+        slot: &RunSlot,
+        commits: &[Commit],
+        indices: impl Iterator<Item = usize>,
+    ) -> Result<Vec<(usize, RunOutcome)>, CommandError> {
+        let RunSlot {
+            working_copy_path,
+            state_path,
+        } = slot;
+        std::fs::create_dir_all(working_copy_path).context(working_copy_path)?;
+        std::fs::create_dir_all(state_path).context(state_path)?;
+        // Prevent concurrent invocations from sharing the working copy.
+        let _lock = FileLock::lock(state_path.join("run.lock")).map_err(|err| {
+            user_error_with_message(
+                format!(
+                    "Failed to lock the working copy for {}",
+                    self.command_description
+                ),
+                err,
+            )
+        })?;
+        let mut tree_state = self.load_tree_state(working_copy_path.clone(), state_path.clone())?;
+
+        let mut outcomes = vec![];
+        for index in indices {
+            let Some(commit) = commits.get(index) else {
+                break;
+            };
+            let outcome = self.run_one(&mut tree_state, commit)?;
+            outcomes.push((index, outcome));
+        }
+        tree_state
+            .save()
+            .map_err(|err| internal_error_with_message("Failed to save tree state", err))?;
+        Ok(outcomes)
+    }
+
+    fn load_tree_state(
+        &self,
+        working_copy_path: PathBuf,
+        state_path: PathBuf,
+    ) -> Result<TreeState, CommandError> {
+        let tree_state = if state_path.join("tree_state").exists() {
+            TreeState::load(
+                self.store.clone(),
+                working_copy_path,
+                state_path,
+                self.tree_state_settings,
+            )
+        } else {
+            TreeState::init(
+                self.store.clone(),
+                working_copy_path,
+                state_path,
+                self.tree_state_settings,
+            )
+        };
+        tree_state.map_err(|err| {
+            internal_error_with_message(
+                format!(
+                    "Failed to set up working copy for {}",
+                    self.command_description
+                ),
+                err,
+            )
+        })
+    }
+
+    fn run_one(
+        &self,
+        tree_state: &mut TreeState,
+        commit: &Commit,
+    ) -> Result<RunOutcome, CommandError> {
+        tree_state.check_out(&commit.tree()).map_err(|err| {
+            internal_error_with_message(
+                format!("Failed to check out revision {}", commit.id().hex()),
+                err,
+            )
+        })?;
+        // The working copy may be of a workspace, where the command may run `jj`.
+        tree_state
+            .save()
+            .map_err(|err| internal_error_with_message("Failed to save tree state", err))?;
+        let mut command = (self.build_command)(commit);
+        tracing::info!(?command, commit_id = %commit.id().hex(), "running command");
+        let output = command
+            .current_dir(tree_state.working_copy_path())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .map_err(|err| {
+                user_error_with_message(format!("Failed to run {}", self.command_description), err)
+            })?;
+        // Snapshot even if the command failed, so that the next revision checked
+        // out in this working copy doesn't inherit its changes.
+        tree_state.snapshot(self.snapshot_options).block_on()?;
+        let new_tree = tree_state.current_tree();
+        let new_tree = (output.status.success()
+            && new_tree.tree_ids_and_labels() != commit.tree().tree_ids_and_labels())
+        .then(|| new_tree.clone());
+        Ok(RunOutcome { output, new_tree })
+    }
+}
+
+fn shell_command(shell_command: &str) -> std::process::Command {
+    if cfg!(windows) {
+        let mut command = std::process::Command::new("cmd");
+        command.arg("/C").arg(shell_command);
+        command
+    } else {
+        let mut command = std::process::Command::new("sh");
+        command.arg("-c").arg(shell_command);
+        command
+    }
 }
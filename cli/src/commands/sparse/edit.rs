@@ -17,6 +17,8 @@ use std::path::Path;
 
 use itertools::Itertools as _;
 use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::sparse::SparseFileset;
+use jj_lib::sparse::SparsePatterns;
 use tracing::instrument;
 
 use super::update_sparse_patterns_with;
@@ -28,6 +30,9 @@ use crate::description_util::TextEditor;
 use crate::ui::Ui;
 
 /// Start an editor to update the patterns that are present in the working copy
+///
+/// If the patterns were set as a fileset expression, the expression is edited
+/// instead.
 #[derive(clap::Args, Clone, Debug)]
 pub struct SparseEditArgs {}
 
@@ -39,12 +44,22 @@ pub async fn cmd_sparse_edit(
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
     let editor = workspace_command.text_editor()?;
-    update_sparse_patterns_with(ui, &mut workspace_command, |_ui, old_patterns| {
-        let mut new_patterns = edit_sparse(&editor, old_patterns)?;
-        new_patterns.sort_unstable();
-        new_patterns.dedup();
-        Ok(new_patterns)
-    })
+    update_sparse_patterns_with(
+        ui,
+        &mut workspace_command,
+        |_ui, old_patterns| match old_patterns {
+            SparsePatterns::Prefixes(old_prefixes) => {
+                let mut new_prefixes = edit_sparse(&editor, old_prefixes)?;
+                new_prefixes.sort_unstable();
+                new_prefixes.dedup();
+                Ok(SparsePatterns::Prefixes(new_prefixes))
+            }
+            SparsePatterns::Fileset(old_fileset) => {
+                let new_fileset = edit_sparse_fileset(&editor, old_fileset)?;
+                Ok(SparsePatterns::Fileset(new_fileset))
+            }
+        },
+    )
     .await
 }
 
@@ -81,3 +96,17 @@ fn edit_sparse(
         })
         .try_collect()
 }
+
+fn edit_sparse_fileset(
+    editor: &TextEditor,
+    fileset: &SparseFileset,
+) -> Result<SparseFileset, CommandError> {
+    let content = editor
+        .edit_str(format!("{}\n", fileset.as_str()), Some(".jjsparse"))
+        .map_err(|err| err.with_name("sparse patterns"))?;
+    let text = content
+        .lines()
+        .filter(|line| !line.starts_with("JJ:"))
+        .join("\n");
+    Ok(SparseFileset::parse(text.trim())?)
+}
@@ -15,6 +15,7 @@
 use std::io::Write as _;
 use std::path::Path;
 
+use jj_lib::sparse::SparsePatterns;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
@@ -26,6 +27,10 @@ use crate::ui::Ui;
 /// By default, a newly cloned or initialized repo will have have a pattern
 /// matching all files from the repo root. That pattern is rendered as `.` (a
 /// single period).
+///
+/// If the patterns were set as a fileset expression, the expression is printed
+/// instead. If a sparse profile is in use, the patterns it resolved to are
+/// printed.
 #[derive(clap::Args, Clone, Debug)]
 pub struct SparseListArgs {}
 
@@ -36,12 +41,21 @@ pub async fn cmd_sparse_list(
     _args: &SparseListArgs,
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
-    for path in workspace_command.working_copy().sparse_patterns()? {
-        writeln!(
-            ui.stdout(),
-            "{}",
-            path.to_fs_path_unchecked(Path::new("")).display()
-        )?;
+    let working_copy = workspace_command.working_copy();
+    if let Some(profile) = working_copy.sparse_profile()? {
+        writeln!(ui.status(), "Patterns from sparse profile {profile}:")?;
+    }
+    match working_copy.sparse_patterns()? {
+        SparsePatterns::Prefixes(prefixes) => {
+            for path in prefixes {
+                writeln!(
+                    ui.stdout(),
+                    "{}",
+                    path.to_fs_path_unchecked(Path::new("")).display()
+                )?;
+            }
+        }
+        SparsePatterns::Fileset(fileset) => writeln!(ui.stdout(), "{}", fileset.as_str())?,
     }
     Ok(())
 }
@@ -14,17 +14,20 @@
 
 mod edit;
 mod list;
+mod profile;
 mod reset;
 mod set;
 
 use clap::Subcommand;
-use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::sparse::SparsePatterns;
 use tracing::instrument;
 
 use self::edit::SparseEditArgs;
 use self::edit::cmd_sparse_edit;
 use self::list::SparseListArgs;
 use self::list::cmd_sparse_list;
+use self::profile::SparseProfileCommand;
+use self::profile::cmd_sparse_profile;
 use self::reset::SparseResetArgs;
 use self::reset::cmd_sparse_reset;
 use self::set::SparseSetArgs;
@@ -42,6 +45,8 @@ use crate::ui::Ui;
 pub(crate) enum SparseCommand {
     Edit(SparseEditArgs),
     List(SparseListArgs),
+    #[command(subcommand)]
+    Profile(SparseProfileCommand),
     Reset(SparseResetArgs),
     Set(SparseSetArgs),
 }
@@ -55,6 +60,7 @@ pub(crate) async fn cmd_sparse(
     match subcommand {
         SparseCommand::Edit(args) => cmd_sparse_edit(ui, command, args).await,
         SparseCommand::List(args) => cmd_sparse_list(ui, command, args).await,
+        SparseCommand::Profile(args) => cmd_sparse_profile(ui, command, args).await,
         SparseCommand::Reset(args) => cmd_sparse_reset(ui, command, args).await,
         SparseCommand::Set(args) => cmd_sparse_set(ui, command, args).await,
     }
@@ -63,7 +69,7 @@ pub(crate) async fn cmd_sparse(
 async fn update_sparse_patterns_with(
     ui: &mut Ui,
     workspace_command: &mut WorkspaceCommandHelper,
-    f: impl FnOnce(&mut Ui, &[RepoPathBuf]) -> Result<Vec<RepoPathBuf>, CommandError>,
+    f: impl FnOnce(&mut Ui, &SparsePatterns) -> Result<SparsePatterns, CommandError>,
 ) -> Result<(), CommandError> {
     let (mut locked_ws, wc_commit) = workspace_command.start_working_copy_mutation()?;
     let new_patterns = f(ui, locked_ws.locked_wc().sparse_patterns()?)?;
@@ -0,0 +1,56 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod r#use;
+
+use clap::Subcommand;
+
+use self::r#use::SparseProfileUseArgs;
+use self::r#use::cmd_sparse_profile_use;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Manage sparse profiles stored in the repository
+///
+/// A sparse profile is a TOML file in the `.jjsparse` directory of the
+/// working-copy commit, e.g. `.jjsparse/backend.toml`:
+///
+/// ```toml
+/// # Directories to check out
+/// paths = ["services/api", "lib"]
+/// # Additional files to check out, as a fileset expression
+/// fileset = 'glob:"tools/*.sh"'
+/// # Other profiles to include
+/// include = ["common"]
+/// ```
+///
+/// The `.jjsparse` directory is always checked out along with the patterns from
+/// the profile. While a profile is in use, it's re-applied whenever a checkout
+/// changes the profile files.
+#[derive(Subcommand, Clone, Debug)]
+#[command(verbatim_doc_comment)]
+pub enum SparseProfileCommand {
+    Use(SparseProfileUseArgs),
+}
+
+pub async fn cmd_sparse_profile(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &SparseProfileCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        SparseProfileCommand::Use(args) => cmd_sparse_profile_use(ui, command, args).await,
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jj_lib::repo::Repo as _;
+use jj_lib::sparse::resolve_sparse_profile;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::print_checkout_stats;
+use crate::command_error::CommandError;
+use crate::command_error::internal_error_with_message;
+use crate::command_error::user_error;
+use crate::ui::Ui;
+
+/// Update the patterns from a sparse profile
+///
+/// The profile is read from `.jjsparse/NAME.toml` in the working-copy commit.
+/// Use `jj sparse set` or `jj sparse reset` to stop using the profile.
+#[derive(clap::Args, Clone, Debug)]
+pub struct SparseProfileUseArgs {
+    /// Name of the profile
+    name: String,
+}
+
+#[instrument(skip_all)]
+pub async fn cmd_sparse_profile_use(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &SparseProfileUseArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let commit_id = workspace_command
+        .get_wc_commit_id()
+        .ok_or_else(|| user_error("This command requires a working copy"))?;
+    let commit = workspace_command.repo().store().get_commit(commit_id)?;
+    let profile = resolve_sparse_profile(&commit.tree(), &args.name).await?;
+    let (mut locked_ws, wc_commit) = workspace_command.start_working_copy_mutation()?;
+    let stats = locked_ws
+        .locked_wc()
+        .set_sparse_profile(profile)
+        .await
+        .map_err(|err| internal_error_with_message("Failed to update working copy paths", err))?;
+    let operation_id = locked_ws.locked_wc().old_operation_id().clone();
+    locked_ws.finish(operation_id).await?;
+    print_checkout_stats(ui, &stats, &wc_commit)?;
+    Ok(())
+}
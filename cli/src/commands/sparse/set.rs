@@ -16,6 +16,8 @@ use std::collections::HashSet;
 
 use itertools::Itertools as _;
 use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::sparse::SparseFileset;
+use jj_lib::sparse::SparsePatterns;
 use tracing::instrument;
 
 use super::update_sparse_patterns_with;
@@ -28,6 +30,12 @@ use crate::ui::Ui;
 /// For example, if all you need is the `README.md` and the `lib/`
 /// directory, use `jj sparse set --clear --add README.md --add lib`.
 /// If you no longer need the `lib` directory, use `jj sparse set --remove lib`.
+///
+/// For finer control, the patterns can be given as a fileset expression. For
+/// example, to check out `services/api/` without its test fixtures, use `jj
+/// sparse set --fileset 'services/api ~ glob:"services/api/**/fixtures/**"'`.
+/// Paths in the expression are relative to the workspace root. `--add` and
+/// `--remove` then add or remove directories from the expression.
 #[derive(clap::Args, Clone, Debug)]
 pub struct SparseSetArgs {
     /// Patterns to add to the working copy
@@ -50,6 +58,10 @@ pub struct SparseSetArgs {
     /// Include no files in the working copy (combine with --add)
     #[arg(long)]
     clear: bool,
+
+    /// Replace the patterns with a fileset expression
+    #[arg(long, value_name = "FILESET", conflicts_with_all = ["add", "remove", "clear"])]
+    fileset: Option<String>,
 }
 
 #[instrument(skip_all)]
@@ -60,17 +72,36 @@ pub async fn cmd_sparse_set(
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
     update_sparse_patterns_with(ui, &mut workspace_command, |_ui, old_patterns| {
-        let mut new_patterns = HashSet::new();
-        if !args.clear {
-            new_patterns.extend(old_patterns.iter().cloned());
-            for path in &args.remove {
-                new_patterns.remove(path);
+        if let Some(text) = &args.fileset {
+            return Ok(SparsePatterns::Fileset(SparseFileset::parse(text)?));
+        }
+        let old_prefixes = match old_patterns {
+            _ if args.clear => &[],
+            SparsePatterns::Prefixes(prefixes) => prefixes.as_slice(),
+            SparsePatterns::Fileset(fileset) => {
+                let mut text = fileset.as_str().to_owned();
+                if !args.add.is_empty() {
+                    let added = SparsePatterns::Prefixes(args.add.clone()).to_fileset_text();
+                    text = format!("({text}) | {added}");
+                }
+                if !args.remove.is_empty() {
+                    let removed = SparsePatterns::Prefixes(args.remove.clone()).to_fileset_text();
+                    text = format!("({text}) ~ ({removed})");
+                }
+                return Ok(SparsePatterns::Fileset(SparseFileset::parse(text)?));
             }
+        };
+        let mut new_patterns = HashSet::new();
+        new_patterns.extend(old_prefixes.iter().cloned());
+        for path in &args.remove {
+            new_patterns.remove(path);
         }
         for path in &args.add {
             new_patterns.insert(path.to_owned());
         }
-        Ok(new_patterns.into_iter().sorted_unstable().collect())
+        Ok(SparsePatterns::Prefixes(
+            new_patterns.into_iter().sorted_unstable().collect(),
+        ))
     })
     .await
 }
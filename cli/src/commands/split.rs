@@ -11,18 +11,24 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::io::Write as _;
+use std::slice;
 
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
+use futures::StreamExt as _;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::matchers::FilesMatcher;
 use jj_lib::matchers::Matcher;
 use jj_lib::merge::Diff;
 use jj_lib::merge::Merge;
 use jj_lib::merged_tree::MergedTree;
 use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::rewrite::CommitRewriter;
 use jj_lib::rewrite::CommitWithSelection;
 use jj_lib::rewrite::EmptyBehavior;
@@ -32,6 +38,7 @@ use jj_lib::rewrite::RebaseOptions;
 use jj_lib::rewrite::RebasedCommit;
 use jj_lib::rewrite::RewriteRefsOptions;
 use jj_lib::rewrite::move_commits;
+use jj_lib::rewrite::restore_tree;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
@@ -42,6 +49,7 @@ use crate::cli_util::WorkspaceCommandTransaction;
 use crate::cli_util::compute_commit_location;
 use crate::cli_util::print_unmatched_explicit_paths;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
 use crate::complete;
 use crate::description_util::add_trailers;
 use crate::description_util::description_template;
@@ -105,6 +113,27 @@ use crate::ui::Ui;
 ///
 /// Splitting an empty commit is not supported because the same effect can be
 /// achieved with `jj new`.
+///
+/// With `--part` or `--by-directory`, the revision is split into any number of
+/// commits without starting a diff editor. Each part becomes its own commit,
+/// stacked in order on top of the parents of the split revision, and the
+/// remaining changes go into the last commit:
+///
+/// ```text
+/// L                 L'
+/// |                 |
+/// K (split)   =>    K3 (remaining)
+/// |                 |
+/// J                 K2 (--part 'glob:src/**')
+///                   |
+///                   K1 (--part 'glob:docs/**')
+///                   |
+///                   J
+/// ```
+///
+/// Each part gets the description given by `--message`, or the description of
+/// the split revision. The remaining changes keep the original description.
+/// The editor is only opened with `--editor`.
 #[derive(clap::Args, Clone, Debug)]
 #[command(verbatim_doc_comment)]
 pub(crate) struct SplitArgs {
@@ -196,8 +225,44 @@ pub(crate) struct SplitArgs {
     #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
     #[arg(add = ArgValueCompleter::new(complete::modified_revision_files))]
     paths: Vec<String>,
+
+    /// Put the changes matching this fileset in a separate commit (can be
+    /// repeated)
+    ///
+    /// Each part becomes its own commit, in the order given. Changes matching
+    /// more than one part go into the first of them.
+    #[arg(
+        long = "part",
+        value_name = "FILESET",
+        conflicts_with_all = SPLIT_INTO_PARTS_CONFLICTS,
+    )]
+    #[arg(add = ArgValueCompleter::new(complete::modified_revision_files))]
+    parts: Vec<String>,
+
+    /// Put the changes in each directory in a separate commit
+    ///
+    /// Changes to files directly in a directory (but not in its
+    /// subdirectories) go into one commit per directory, ordered by path.
+    /// Changes to files at the root of the repository are left in the
+    /// remaining changes.
+    #[arg(
+        long,
+        conflicts_with = "parts",
+        conflicts_with_all = SPLIT_INTO_PARTS_CONFLICTS,
+    )]
+    by_directory: bool,
 }
 
+const SPLIT_INTO_PARTS_CONFLICTS: [&str; 7] = [
+    "interactive",
+    "tool",
+    "paths",
+    "parallel",
+    "onto",
+    "insert_after",
+    "insert_before",
+];
+
 impl SplitArgs {
     /// Resolves the raw SplitArgs into the components necessary to run the
     /// command. Returns an error if the command cannot proceed.
@@ -275,6 +340,9 @@ pub(crate) async fn cmd_split(
     command: &CommandHelper,
     args: &SplitArgs,
 ) -> Result<(), CommandError> {
+    if !args.parts.is_empty() || args.by_directory {
+        return split_into_parts(ui, command, args).await;
+    }
     let mut workspace_command = command.workspace_helper(ui)?;
     let ResolvedSplitArgs {
         target_commit,
@@ -404,7 +472,14 @@ pub(crate) async fn cmd_split(
         )
         .await?
     } else {
-        rewrite_descendants(&mut tx, &target, first_commit, second_commit, parallel).await?
+        rewrite_descendants(
+            &mut tx,
+            &target.commit,
+            first_commit,
+            second_commit,
+            parallel,
+        )
+        .await?
     };
     if let Some(mut formatter) = ui.status_formatter() {
         if num_rebased > 0 {
@@ -495,7 +570,7 @@ async fn move_first_commit(
 
 async fn rewrite_descendants(
     tx: &mut WorkspaceCommandTransaction<'_>,
-    target: &CommitWithSelection,
+    target_commit: &Commit,
     first_commit: Commit,
     second_commit: Commit,
     parallel: bool,
@@ -506,12 +581,12 @@ async fn rewrite_descendants(
         // moves any bookmarks pointing to the target commit to the second
         // commit.
         tx.repo_mut()
-            .set_rewritten_commit(target.commit.id().clone(), second_commit.id().clone());
+            .set_rewritten_commit(target_commit.id().clone(), second_commit.id().clone());
     }
     let mut num_rebased = 0;
     tx.repo_mut()
         .transform_descendants(
-            vec![target.commit.id().clone()],
+            vec![target_commit.id().clone()],
             async |mut rewriter: CommitRewriter<'_>| {
                 num_rebased += 1;
                 if parallel && legacy_bookmark_behavior {
@@ -534,7 +609,7 @@ async fn rewrite_descendants(
     // Move the working copy commit (@) to the second commit for any workspaces
     // where the target commit is the working copy commit.
     for (name, working_copy_commit) in tx.base_repo().clone().view().wc_commit_ids() {
-        if working_copy_commit == target.commit.id() {
+        if working_copy_commit == target_commit.id() {
             tx.repo_mut().edit(name.clone(), &second_commit).await?;
         }
     }
@@ -542,6 +617,181 @@ async fn rewrite_descendants(
     Ok((first_commit, second_commit, num_rebased))
 }
 
+/// Splits the target revision into one commit per `--part` or directory,
+/// followed by a commit with the remaining changes.
+async fn split_into_parts(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &SplitArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let target_commit = workspace_command
+        .resolve_single_rev(ui, &args.revision)
+        .await?;
+    workspace_command
+        .check_rewritable([target_commit.id()])
+        .await?;
+    let parent_tree = target_commit
+        .parent_tree(workspace_command.repo().as_ref())
+        .await?;
+    let target_tree = target_commit.tree();
+    let matchers: Vec<Box<dyn Matcher>> = if args.by_directory {
+        changed_directory_matchers(&parent_tree, &target_tree).await?
+    } else {
+        let mut matchers = Vec::new();
+        for part in &args.parts {
+            let expression = workspace_command.parse_union_filesets(ui, slice::from_ref(part))?;
+            print_unmatched_explicit_paths(
+                ui,
+                &workspace_command,
+                &expression,
+                [&parent_tree, &target_tree],
+            )?;
+            matchers.push(expression.to_matcher());
+        }
+        matchers
+    };
+    if matchers.is_empty() {
+        return Err(user_error(
+            "No changes in subdirectories to split into separate revisions",
+        ));
+    }
+    let text_editor = workspace_command.text_editor()?;
+    let mut tx = workspace_command.start_transaction();
+
+    let mut part_commits: Vec<Commit> = Vec::new();
+    for (index, matcher) in matchers.iter().enumerate() {
+        let (base_tree, base_label) = match part_commits.last() {
+            Some(commit) => (commit.tree(), commit.conflict_label()),
+            None => (
+                parent_tree.clone(),
+                target_commit.parents_conflict_label().await?,
+            ),
+        };
+        let tree = restore_tree(
+            &target_tree,
+            &base_tree,
+            target_commit.conflict_label(),
+            base_label,
+            matcher.as_ref(),
+        )
+        .await?;
+        if tree.tree_ids() == base_tree.tree_ids() {
+            writeln!(
+                ui.warning_default(),
+                "No changes matched part {}, so its revision will be empty",
+                index + 1
+            )?;
+        }
+        let mut commit_builder = tx.repo_mut().rewrite_commit(&target_commit).detach();
+        commit_builder.set_tree(tree);
+        if let Some(parent) = part_commits.last() {
+            commit_builder
+                .set_parents(vec![parent.id().clone()])
+                .clear_rewrite_source();
+            // Generate a new change id so that the commit being split doesn't
+            // become divergent.
+            commit_builder.generate_new_change_id();
+        }
+        let description = match &args.message_paragraphs {
+            Some(paragraphs) => join_message_paragraphs(paragraphs),
+            None => commit_builder.description().to_owned(),
+        };
+        // Don't add trailers to an empty description for the same reason as
+        // the first commit of a two-way split.
+        let description = if !description.is_empty() || args.editor {
+            commit_builder.set_description(description);
+            add_trailers(ui, &tx, &commit_builder).await?
+        } else {
+            description
+        };
+        let description = if args.editor {
+            commit_builder.set_description(description);
+            let temp_commit = commit_builder.write_hidden().await?;
+            let intro = format!("Enter a description for part {} of the split.", index + 1);
+            let template = description_template(ui, &tx, &intro, &temp_commit)?;
+            edit_description(&text_editor, &template)?
+        } else {
+            description
+        };
+        commit_builder.set_description(description);
+        part_commits.push(commit_builder.write(tx.repo_mut()).await?);
+    }
+
+    // Create the last commit, which includes the changes not matched by any
+    // part.
+    let remaining_commit = {
+        let last_part = part_commits.last().unwrap();
+        let mut commit_builder = tx.repo_mut().rewrite_commit(&target_commit).detach();
+        commit_builder
+            .set_parents(vec![last_part.id().clone()])
+            .set_tree(target_tree)
+            .clear_rewrite_source();
+        commit_builder.generate_new_change_id();
+        let description = if args.editor {
+            let new_description = add_trailers(ui, &tx, &commit_builder).await?;
+            commit_builder.set_description(new_description);
+            let temp_commit = commit_builder.write_hidden().await?;
+            let intro = "Enter a description for the remaining changes.";
+            let template = description_template(ui, &tx, intro, &temp_commit)?;
+            edit_description(&text_editor, &template)?
+        } else {
+            commit_builder.description().to_owned()
+        };
+        commit_builder.set_description(description);
+        commit_builder.write(tx.repo_mut()).await?
+    };
+
+    let (_, remaining_commit, num_rebased) = rewrite_descendants(
+        &mut tx,
+        &target_commit,
+        part_commits[0].clone(),
+        remaining_commit,
+        false,
+    )
+    .await?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        if num_rebased > 0 {
+            writeln!(formatter, "Rebased {num_rebased} descendant commits")?;
+        }
+        for (index, commit) in part_commits.iter().enumerate() {
+            write!(formatter, "{:<17}: ", format!("Part {}", index + 1))?;
+            tx.write_commit_summary(formatter.as_mut(), commit)?;
+            writeln!(formatter)?;
+        }
+        write!(formatter, "Remaining changes: ")?;
+        tx.write_commit_summary(formatter.as_mut(), &remaining_commit)?;
+        writeln!(formatter)?;
+    }
+    tx.finish(ui, format!("split commit {}", target_commit.id().hex()))
+        .await?;
+    Ok(())
+}
+
+/// Returns a matcher for the changed files in each directory other than the
+/// root directory, ordered by directory path.
+async fn changed_directory_matchers(
+    parent_tree: &MergedTree,
+    tree: &MergedTree,
+) -> Result<Vec<Box<dyn Matcher>>, CommandError> {
+    let mut paths_by_dir: BTreeMap<RepoPathBuf, Vec<RepoPathBuf>> = BTreeMap::new();
+    let mut diff_stream = parent_tree.diff_stream(tree, &EverythingMatcher);
+    while let Some(entry) = diff_stream.next().await {
+        entry.values?;
+        let Some(dir) = entry.path.parent().filter(|dir| !dir.is_root()) else {
+            continue;
+        };
+        paths_by_dir
+            .entry(dir.to_owned())
+            .or_default()
+            .push(entry.path);
+    }
+    Ok(paths_by_dir
+        .into_values()
+        .map(|paths| Box::new(FilesMatcher::new(paths)) as Box<dyn Matcher>)
+        .collect())
+}
+
 /// Prompts the user to select the content they want in the first commit and
 /// returns the target commit and the tree corresponding to the selection.
 async fn select_diff(
@@ -33,6 +33,7 @@ use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::DiffSelector;
+use crate::cli_util::MergeStrategyArg;
 use crate::cli_util::RevisionArg;
 use crate::cli_util::WorkspaceCommandTransaction;
 use crate::cli_util::compute_commit_location;
@@ -176,6 +177,14 @@ pub(crate) struct SquashArgs {
     /// The source revision will not be abandoned
     #[arg(long, short)]
     keep_emptied: bool,
+
+    /// How to resolve conflicting hunks while squashing
+    ///
+    /// `ours` takes the hunk from the destination, and `theirs` takes the
+    /// hunk from the (last) source revision. `union` keeps the lines from
+    /// all of them.
+    #[arg(long, value_enum, default_value_t)]
+    strategy: MergeStrategyArg,
 }
 
 #[instrument(skip_all)]
@@ -249,6 +258,7 @@ pub(crate) async fn cmd_squash(
     };
 
     let mut tx = workspace_command.start_transaction();
+    tx.set_merge_strategy(args.strategy.into());
     let mut num_rebased = 0;
     let destination = if let Some(commit) = pre_existing_destination {
         commit
@@ -12,24 +12,33 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use futures::StreamExt as _;
 use futures::TryStreamExt as _;
 use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::copies::CopiesTreeDiffEntry;
 use jj_lib::copies::CopyRecords;
+use jj_lib::matchers::Matcher;
 use jj_lib::merge::Diff;
 use jj_lib::merged_tree::MergedTree;
+use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo as _;
 use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetFilterPredicate;
+use jj_lib::working_copy::SnapshotStats;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::OutputFormat;
 use crate::cli_util::print_conflicted_paths;
 use crate::cli_util::print_snapshot_stats;
 use crate::cli_util::print_unmatched_explicit_paths;
 use crate::command_error::CommandError;
+use crate::command_error::internal_error_with_message;
 use crate::diff_util::DiffFormat;
+use crate::diff_util::diff_status;
 use crate::diff_util::get_copy_records;
 use crate::formatter::FormatterExt as _;
 use crate::ui::Ui;
@@ -76,6 +85,24 @@ pub(crate) async fn cmd_status(
         .transpose()?;
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
     let matcher = fileset_expression.to_matcher();
+    if command.output_format() == OutputFormat::Json {
+        if let Some(wc_commit) = &maybe_wc_commit {
+            let tree = wc_commit.tree();
+            print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, [&tree])?;
+        }
+        let status = StatusJson::collect(
+            repo.as_ref(),
+            maybe_wc_commit.as_ref(),
+            &snapshot_stats,
+            matcher.as_ref(),
+        )
+        .await?;
+        let mut formatter = ui.stdout_formatter();
+        serde_json::to_writer(formatter.as_mut(), &status)
+            .map_err(|err| internal_error_with_message("Failed to serialize status", err))?;
+        writeln!(formatter)?;
+        return Ok(());
+    }
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
     let formatter = formatter.as_mut();
@@ -235,6 +262,93 @@ pub(crate) async fn cmd_status(
     Ok(())
 }
 
+/// Object printed by `jj status --output=json`.
+#[derive(Debug, serde::Serialize)]
+struct StatusJson {
+    working_copy: Option<Commit>,
+    parents: Vec<Commit>,
+    changes: Vec<StatusChangeJson>,
+    untracked: Vec<String>,
+    conflicts: Vec<RepoPathBuf>,
+    conflicted_bookmarks: Vec<String>,
+    conflicted_remote_bookmarks: Vec<String>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct StatusChangeJson {
+    status: &'static str,
+    path: RepoPathBuf,
+    /// Source path if this is a copy or rename.
+    source: Option<RepoPathBuf>,
+}
+
+impl StatusJson {
+    async fn collect(
+        repo: &ReadonlyRepo,
+        maybe_wc_commit: Option<&Commit>,
+        snapshot_stats: &SnapshotStats,
+        matcher: &dyn Matcher,
+    ) -> Result<Self, CommandError> {
+        let mut status = Self {
+            working_copy: maybe_wc_commit.cloned(),
+            parents: vec![],
+            changes: vec![],
+            untracked: vec![],
+            conflicts: vec![],
+            conflicted_bookmarks: repo
+                .view()
+                .local_bookmarks()
+                .filter(|(_, target)| target.has_conflict())
+                .map(|(name, _)| name.as_str().to_owned())
+                .collect(),
+            conflicted_remote_bookmarks: repo
+                .view()
+                .all_remote_bookmarks()
+                .filter(|(_, remote_ref)| remote_ref.target.has_conflict())
+                .map(|(symbol, _)| symbol.to_string())
+                .collect(),
+        };
+        let Some(wc_commit) = maybe_wc_commit else {
+            return Ok(status);
+        };
+        let parent_tree = wc_commit.parent_tree(repo).await?;
+        let tree = wc_commit.tree();
+        let mut copy_records = CopyRecords::default();
+        for parent in wc_commit.parent_ids() {
+            let records = get_copy_records(repo.store(), parent, wc_commit.id(), matcher).await?;
+            copy_records.add_records(records);
+        }
+        let mut tree_diff = parent_tree.diff_stream_with_copies(&tree, matcher, &copy_records);
+        while let Some(CopiesTreeDiffEntry { path, values }) = tree_diff.next().await {
+            let values = values?;
+            status.changes.push(StatusChangeJson {
+                status: diff_status(&path, &values).label(),
+                path: path.target().to_owned(),
+                source: path.to_diff().map(|paths| paths.before.to_owned()),
+            });
+        }
+        visit_collapsed_untracked_files(
+            snapshot_stats.untracked_paths.keys(),
+            tree.clone(),
+            |path, is_dir| {
+                status.untracked.push(if is_dir {
+                    path.to_internal_dir_string()
+                } else {
+                    path.as_internal_file_string().to_owned()
+                });
+                Ok(())
+            },
+        )
+        .await?;
+        status.conflicts = tree
+            .conflicts_matching(matcher)
+            .map(|(path, _)| path)
+            .collect();
+        status.parents = wc_commit.parents().await?;
+        Ok(status)
+    }
+}
+
 async fn visit_collapsed_untracked_files(
     untracked_paths: impl IntoIterator<Item = impl AsRef<RepoPath>>,
     tree: MergedTree,
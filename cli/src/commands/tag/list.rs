@@ -154,10 +154,7 @@ pub async fn cmd_tag_list(
     };
     let template: TemplateRenderer<Rc<CommitRef>> = {
         let language = workspace_command.commit_template_language();
-        let text = match &args.template {
-            Some(value) => value.to_owned(),
-            None => settings.get("templates.tag_list")?,
-        };
+        let text = command.list_template_text(args.template.as_deref(), "templates.tag_list")?;
         workspace_command
             .parse_template(ui, &language, &text)?
             .labeled(["tag_list"])
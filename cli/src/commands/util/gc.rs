@@ -16,6 +16,7 @@ use std::slice;
 use std::time::Duration;
 use std::time::SystemTime;
 
+use jj_lib::fix::FixCache;
 use jj_lib::repo::Repo as _;
 
 use crate::cli_util::CommandHelper;
@@ -63,5 +64,9 @@ pub async fn cmd_util_gc(
         .gc(slice::from_ref(repo.op_id()), keep_newer)
         .await?;
     repo.store().gc(repo.index(), keep_newer)?;
+    let fix_cache_dir = workspace_command.repo_path().join("fix_cache");
+    if fix_cache_dir.exists() {
+        FixCache::load(fix_cache_dir)?.gc(keep_newer)?;
+    }
     Ok(())
 }
@@ -21,6 +21,7 @@ use jj_lib::file_util::IoResultExt as _;
 use jj_lib::ref_name::WorkspaceNameBuf;
 use jj_lib::repo::Repo as _;
 use jj_lib::rewrite::merge_commit_trees;
+use jj_lib::sparse::SparsePatterns;
 use jj_lib::workspace::Workspace;
 use tracing::instrument;
 
@@ -153,12 +154,12 @@ pub async fn cmd_workspace_add(
 
     let sparsity = match args.sparse_patterns {
         SparseInheritance::Full => None,
-        SparseInheritance::Empty => Some(vec![]),
+        SparseInheritance::Empty => Some(SparsePatterns::Prefixes(vec![])),
         SparseInheritance::Copy => {
             let sparse_patterns = old_workspace_command
                 .working_copy()
                 .sparse_patterns()?
-                .to_vec();
+                .clone();
             Some(sparse_patterns)
         }
     };
@@ -53,12 +53,8 @@ pub async fn cmd_workspace_list(
     let template: TemplateRenderer<WorkspaceRef> = {
         let language = workspace_command.commit_template_language();
 
-        let text = match &args.template {
-            Some(value) => value.to_owned(),
-            None => workspace_command
-                .settings()
-                .get("templates.workspace_list")?,
-        };
+        let text =
+            command.list_template_text(args.template.as_deref(), "templates.workspace_list")?;
 
         workspace_command
             .parse_template(ui, &language, &text)?
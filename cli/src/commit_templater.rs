@@ -15,12 +15,15 @@
 //! Template environment for `jj log`, `jj evolog` and similar.
 
 use std::any::Any;
+use std::cell::Cell;
+use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::cmp::max;
 use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Display;
 use std::io;
+use std::mem;
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -35,6 +38,7 @@ use jj_lib::backend::CommitId;
 use jj_lib::backend::Timestamp;
 use jj_lib::backend::TreeValue;
 use jj_lib::commit::Commit;
+use jj_lib::commit::verify_commit_signatures;
 use jj_lib::conflict_labels::ConflictLabels;
 use jj_lib::conflicts;
 use jj_lib::conflicts::ConflictMarkerStyle;
@@ -649,8 +653,8 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             Self::TreeDiff(_) => None,
             Self::TreeDiffEntry(_) => None,
             Self::TreeDiffEntryList(_) => None,
-            Self::TreeEntry(_) => None,
-            Self::TreeEntryList(_) => None,
+            Self::TreeEntry(property) => Some(property.into_serialize()),
+            Self::TreeEntryList(property) => Some(property.into_serialize()),
             Self::DiffStats(_) => None,
             Self::DiffStatEntry(_) => None,
             Self::DiffStatEntryList(_) => None,
@@ -991,6 +995,7 @@ pub struct CommitKeywordCache<'repo> {
     tags_index: OnceCell<Rc<CommitRefsIndex>>,
     git_refs_index: OnceCell<Rc<CommitRefsIndex>>,
     is_immutable_fn: OnceCell<Rc<RevsetContainingFn<'repo>>>,
+    signature_batch: SignatureBatch,
 }
 
 impl<'repo> CommitKeywordCache<'repo> {
@@ -1009,6 +1014,10 @@ impl<'repo> CommitKeywordCache<'repo> {
             .get_or_init(|| Rc::new(build_commit_refs_index(repo.view().git_refs())))
     }
 
+    pub fn signature_batch(&self) -> &SignatureBatch {
+        &self.signature_batch
+    }
+
     pub fn is_immutable_fn(
         &self,
         language: &CommitTemplateLanguage<'repo>,
@@ -1122,9 +1131,12 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
     );
     map.insert(
         "signature",
-        |_language, _diagnostics, _build_ctx, self_property, function| {
+        |language, _diagnostics, _build_ctx, self_property, function| {
             function.expect_no_arguments()?;
-            let out_property = self_property.map(CryptographicSignature::new);
+            let batch = language.keyword_cache.signature_batch().clone();
+            batch.is_used.set(true);
+            let out_property =
+                self_property.map(move |commit| CryptographicSignature::new(commit, batch.clone()));
             Ok(out_property.into_dyn_wrapped())
         },
     );
@@ -2669,6 +2681,24 @@ pub struct TreeEntry {
     pub value: MergedTreeValue,
 }
 
+impl serde::Serialize for TreeEntry {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct as _;
+        let mut state = serializer.serialize_struct("TreeEntry", 4)?;
+        state.serialize_field("path", &self.path)?;
+        state.serialize_field("conflict", &!self.value.is_resolved())?;
+        state.serialize_field("file_type", describe_file_type(&self.value))?;
+        state.serialize_field(
+            "executable",
+            &is_executable_file(&self.value).unwrap_or_default(),
+        )?;
+        state.end()
+    }
+}
+
 fn builtin_tree_entry_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, TreeEntry> {
     // Not using maplit::hashmap!{} or custom declarative macro here because
     // code completion inside macro is quite restricted.
@@ -2862,17 +2892,51 @@ fn builtin_diff_stat_entry_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'r
     map
 }
 
+/// Signed commits which are about to be rendered.
+///
+/// Their signatures are verified together when the first signature is needed,
+/// which is much faster than verifying them one by one. Nothing is verified if
+/// the template doesn't use the signatures.
+#[derive(Clone, Debug, Default)]
+pub struct SignatureBatch {
+    pending: Rc<RefCell<Vec<Commit>>>,
+    is_used: Rc<Cell<bool>>,
+}
+
+impl SignatureBatch {
+    /// Whether any parsed template refers to the commit signatures. Commits
+    /// don't need to be queued otherwise.
+    pub fn is_used(&self) -> bool {
+        self.is_used.get()
+    }
+
+    /// Queues the signed commits for batch verification.
+    pub fn extend(&self, commits: impl IntoIterator<Item = Commit>) {
+        let commits = commits.into_iter().filter(Commit::is_signed);
+        self.pending.borrow_mut().extend(commits);
+    }
+
+    fn verify_pending(&self) {
+        let commits = mem::take(&mut *self.pending.borrow_mut());
+        // Errors will be reported when the signatures are verified again
+        // individually.
+        verify_commit_signatures(&commits);
+    }
+}
+
 #[derive(Debug)]
 pub struct CryptographicSignature {
     commit: Commit,
+    batch: SignatureBatch,
 }
 
 impl CryptographicSignature {
-    fn new(commit: Commit) -> Option<Self> {
-        commit.is_signed().then_some(Self { commit })
+    fn new(commit: Commit, batch: SignatureBatch) -> Option<Self> {
+        commit.is_signed().then_some(Self { commit, batch })
     }
 
     fn verify(&self) -> SignResult<Verification> {
+        self.batch.verify_pending();
         self.commit
             .verification()
             .transpose()
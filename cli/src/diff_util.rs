@@ -14,15 +14,18 @@
 
 use std::borrow::Cow;
 use std::cmp::max;
+use std::collections::HashMap;
 use std::future;
 use std::io;
 use std::iter;
+use std::mem;
 use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
 
 use bstr::BStr;
 use bstr::BString;
+use bstr::ByteSlice as _;
 use clap_complete::ArgValueCandidates;
 use futures::StreamExt as _;
 use futures::TryStreamExt as _;
@@ -49,6 +52,8 @@ use jj_lib::copies::CopyRecords;
 use jj_lib::diff::ContentDiff;
 use jj_lib::diff::DiffHunk;
 use jj_lib::diff::DiffHunkKind;
+use jj_lib::diff::TokenSyntax;
+use jj_lib::diff::find_syntax_token_ranges;
 use jj_lib::diff_presentation::DiffTokenType;
 use jj_lib::diff_presentation::FileContent;
 use jj_lib::diff_presentation::LineCompareMode;
@@ -79,6 +84,7 @@ use jj_lib::settings::UserSettings;
 use jj_lib::store::Store;
 use thiserror::Error;
 use tracing::instrument;
+use unicode_width::UnicodeWidthChar as _;
 use unicode_width::UnicodeWidthStr as _;
 
 use crate::command_error::CommandError;
@@ -101,7 +107,7 @@ use crate::ui::Ui;
 #[derive(clap::Args, Clone, Debug)]
 #[command(next_help_heading = "Diff Formatting Options")]
 #[command(group(clap::ArgGroup::new("short-format").args(&["summary", "stat", "types", "name_only"])))]
-#[command(group(clap::ArgGroup::new("long-format").args(&["git", "color_words"])))]
+#[command(group(clap::ArgGroup::new("long-format").args(&["git", "color_words", "side_by_side"])))]
 pub struct DiffFormatArgs {
     /// For each path, show only whether it was modified, added, or deleted
     #[arg(long, short)]
@@ -136,6 +142,10 @@ pub struct DiffFormatArgs {
     #[arg(long)]
     pub color_words: bool,
 
+    /// Show a two-column diff with old and new contents side by side
+    #[arg(long)]
+    pub side_by_side: bool,
+
     /// Generate diff by external command
     ///
     /// A builtin format can also be specified as `:<name>`. For example,
@@ -167,6 +177,7 @@ pub enum DiffFormat {
     NameOnly,
     Git(Box<UnifiedDiffOptions>),
     ColorWords(Box<ColorWordsDiffOptions>),
+    SideBySide(Box<SideBySideDiffOptions>),
     Tool(Box<ExternalMergeTool>),
 }
 
@@ -178,6 +189,7 @@ enum BuiltinFormatKind {
     NameOnly,
     Git,
     ColorWords,
+    SideBySide,
 }
 
 impl BuiltinFormatKind {
@@ -191,6 +203,7 @@ impl BuiltinFormatKind {
         Self::NameOnly,
         Self::Git,
         Self::ColorWords,
+        Self::SideBySide,
     ];
 
     fn from_name(name: &str) -> Result<Self, String> {
@@ -201,6 +214,7 @@ impl BuiltinFormatKind {
             "name-only" => Ok(Self::NameOnly),
             "git" => Ok(Self::Git),
             "color-words" => Ok(Self::ColorWords),
+            "side-by-side" => Ok(Self::SideBySide),
             _ => Err(format!("Invalid builtin diff format: {name}")),
         }
     }
@@ -224,6 +238,8 @@ impl BuiltinFormatKind {
             Some(Self::Git)
         } else if args.color_words {
             Some(Self::ColorWords)
+        } else if args.side_by_side {
+            Some(Self::SideBySide)
         } else {
             None
         }
@@ -232,7 +248,7 @@ impl BuiltinFormatKind {
     fn is_short(self) -> bool {
         match self {
             Self::Summary | Self::Stat | Self::Types | Self::NameOnly => true,
-            Self::Git | Self::ColorWords => false,
+            Self::Git | Self::ColorWords | Self::SideBySide => false,
         }
     }
 
@@ -244,6 +260,7 @@ impl BuiltinFormatKind {
             Self::NameOnly => "name-only",
             Self::Git => "git",
             Self::ColorWords => "color-words",
+            Self::SideBySide => "side-by-side",
         }
     }
 
@@ -271,6 +288,11 @@ impl BuiltinFormatKind {
                 options.merge_args(args);
                 Ok(DiffFormat::ColorWords(Box::new(options)))
             }
+            Self::SideBySide => {
+                let mut options = SideBySideDiffOptions::from_settings(settings)?;
+                options.merge_args(args);
+                Ok(DiffFormat::SideBySide(Box::new(options)))
+            }
         }
     }
 }
@@ -524,6 +546,20 @@ impl<'a> DiffRenderer<'a> {
                     )
                     .await?;
                 }
+                DiffFormat::SideBySide(options) => {
+                    let tree_diff = diff_stream();
+                    show_side_by_side_diff(
+                        *formatter.labeled("side_by_side"),
+                        store,
+                        tree_diff,
+                        conflict_labels,
+                        path_converter,
+                        options,
+                        self.conflict_marker_style,
+                        width,
+                    )
+                    .await?;
+                }
                 DiffFormat::Tool(tool) => {
                     match tool.diff_invocation_mode {
                         DiffToolMode::FileByFile => {
@@ -566,6 +602,7 @@ impl<'a> DiffRenderer<'a> {
         &self,
         formatter: &mut dyn Formatter,
         descriptions: Diff<&Merge<&str>>,
+        width: usize,
     ) -> Result<(), DiffRenderError> {
         if !descriptions.is_changed() {
             return Ok(());
@@ -604,6 +641,16 @@ impl<'a> DiffRenderer<'a> {
                         &materialize_options,
                     )?;
                 }
+                DiffFormat::SideBySide(options) => {
+                    writeln!(formatter.labeled("header"), "Modified commit description:")?;
+                    show_side_by_side_diff_texts(
+                        formatter,
+                        descriptions,
+                        options,
+                        &materialize_options,
+                        width,
+                    )?;
+                }
                 DiffFormat::Tool(_) => {
                     // TODO: materialize commit description as file?
                 }
@@ -643,6 +690,7 @@ impl<'a> DiffRenderer<'a> {
         self.show_diff_commit_descriptions(
             *formatter,
             Diff::new(&from_description, &to_description),
+            width,
         )?;
         self.show_diff_trees(
             ui,
@@ -739,6 +787,13 @@ pub struct ColorWordsDiffOptions {
     pub line_diff: LineDiffOptions,
     /// Maximum number of removed/added word alternation to inline.
     pub max_inline_alternation: Option<usize>,
+    /// Languages used to tokenize words, keyed by file extension.
+    pub syntax_by_extension: HashMap<String, TokenSyntax>,
+    /// Language used to tokenize words of the current file. If unset, words
+    /// are split at non-word characters.
+    pub syntax: Option<TokenSyntax>,
+    /// Whether to hide changed lines consisting of the same syntax tokens.
+    pub ignore_reformatting: bool,
 }
 
 impl ColorWordsDiffOptions {
@@ -759,9 +814,30 @@ impl ColorWordsDiffOptions {
             context: settings.get("diff.color-words.context")?,
             line_diff: LineDiffOptions::default(),
             max_inline_alternation,
+            syntax_by_extension: settings.get("diff.color-words.syntax")?,
+            syntax: None,
+            ignore_reformatting: settings.get("diff.color-words.ignore-reformatting")?,
         })
     }
 
+    /// Returns options to diff the file at `path`, with the language selected
+    /// by the file extension.
+    fn for_path(&self, path: &RepoPath) -> Cow<'_, Self> {
+        let syntax = path
+            .split()
+            .and_then(|(_, name)| Path::new(name.as_internal_str()).extension())
+            .and_then(|ext| self.syntax_by_extension.get(ext.to_str()?))
+            .copied();
+        if syntax == self.syntax {
+            Cow::Borrowed(self)
+        } else {
+            Cow::Owned(Self {
+                syntax,
+                ..self.clone()
+            })
+        }
+    }
+
     fn merge_args(&mut self, args: &DiffFormatArgs) {
         if let Some(context) = args.context {
             self.context = context;
@@ -955,8 +1031,22 @@ fn show_color_words_resolved_hunks(
     options: &ColorWordsDiffOptions,
 ) -> io::Result<DiffLineNumber> {
     let line_diff = diff_by_line(contents.into_array(), &options.line_diff.compare_mode);
+    let is_reformatted = |contents: &Diff<&BStr>| {
+        let Some(syntax) = options.syntax.filter(|_| options.ignore_reformatting) else {
+            return false;
+        };
+        let tokens = |text: &'_ [u8]| {
+            find_syntax_token_ranges(text, syntax)
+                .into_iter()
+                .map(|range| text[range].to_owned())
+                .collect_vec()
+        };
+        tokens(contents.before) == tokens(contents.after)
+    };
     // Matching entries shouldn't appear consecutively in diff of two inputs.
-    let mut context: Option<Diff<&BStr>> = None;
+    // However, reformatted hunks are also collected as contexts, resulting
+    // [matching, reformatted, matching] sequence.
+    let mut contexts: Vec<Diff<&BStr>> = Vec::new();
     let mut emitted = false;
 
     for hunk in line_diff.hunks() {
@@ -966,21 +1056,24 @@ fn show_color_words_resolved_hunks(
         let hunk_contents = Diff::new(left, right);
         match hunk.kind {
             DiffHunkKind::Matching => {
-                context = Some(hunk_contents);
+                contexts.push(hunk_contents);
+            }
+            DiffHunkKind::Different if is_reformatted(&hunk_contents) => {
+                contexts.push(hunk_contents);
             }
             DiffHunkKind::Different => {
                 let num_after = if emitted { options.context } else { 0 };
                 let num_before = options.context;
                 line_number = show_color_words_context_lines(
                     formatter,
-                    context.as_slice(),
+                    &contexts,
                     line_number,
                     labels,
                     options,
                     num_after,
                     num_before,
                 )?;
-                context = None;
+                contexts.clear();
                 emitted = true;
                 line_number = show_color_words_diff_lines(
                     formatter,
@@ -997,7 +1090,7 @@ fn show_color_words_resolved_hunks(
     let num_before = 0;
     show_color_words_context_lines(
         formatter,
-        context.as_slice(),
+        &contexts,
         line_number,
         labels,
         options,
@@ -1017,22 +1110,49 @@ fn show_color_words_context_lines(
     num_before: usize,
 ) -> io::Result<DiffLineNumber> {
     const SKIPPED_CONTEXT_LINE: &str = "    ...\n";
+    // Lines of reformatted contexts can't be paired up, so only the lines
+    // before and after them can be shown.
+    let is_paired = |contents: &&Diff<&BStr>| {
+        let num_lines = |side: &BStr| side.split_inclusive(|b| *b == b'\n').count();
+        num_lines(contents.before) == num_lines(contents.after)
+    };
+    let num_head_contexts = contexts.iter().take_while(is_paired).count();
+    let num_tail_contexts = contexts[num_head_contexts..]
+        .iter()
+        .rev()
+        .take_while(is_paired)
+        .count();
+    let head_contexts = 0..num_head_contexts;
+    let middle_contexts = num_head_contexts..contexts.len() - num_tail_contexts;
+    let tail_contexts = contexts.len() - num_tail_contexts..contexts.len();
     let extract = |after: bool| -> (Vec<&[u8]>, Vec<&[u8]>, u32) {
-        let mut lines = contexts
-            .iter()
-            .map(|contents| {
-                if after {
-                    contents.after
-                } else {
-                    contents.before
-                }
-            })
-            .flat_map(|side| side.split_inclusive(|b| *b == b'\n'))
-            .fuse();
-        let after_lines = lines.by_ref().take(num_after).collect();
-        let before_lines = lines.by_ref().rev().take(num_before + 1).collect();
-        let num_skipped: u32 = lines.count().try_into().unwrap();
-        (after_lines, before_lines, num_skipped)
+        let side_lines = |range: Range<usize>| {
+            contexts[range]
+                .iter()
+                .map(|contents| {
+                    if after {
+                        contents.after
+                    } else {
+                        contents.before
+                    }
+                })
+                .flat_map(|side| side.split_inclusive(|b| *b == b'\n'))
+                .fuse()
+        };
+        let mut head_lines = side_lines(head_contexts.clone());
+        let after_lines = head_lines.by_ref().take(num_after).collect();
+        let (before_lines, num_skipped) = if middle_contexts.is_empty() {
+            let before_lines = head_lines.by_ref().rev().take(num_before + 1).collect();
+            (before_lines, head_lines.count())
+        } else {
+            let mut tail_lines = side_lines(tail_contexts.clone());
+            let before_lines = tail_lines.by_ref().rev().take(num_before + 1).collect();
+            let num_skipped = head_lines.count()
+                + side_lines(middle_contexts.clone()).count()
+                + tail_lines.count();
+            (before_lines, num_skipped)
+        };
+        (after_lines, before_lines, num_skipped.try_into().unwrap())
     };
     let show = |formatter: &mut dyn Formatter,
                 [left_lines, right_lines]: [&[&[u8]]; 2],
@@ -1098,9 +1218,11 @@ fn show_color_words_diff_lines(
     labels: Diff<&str>,
     options: &ColorWordsDiffOptions,
 ) -> io::Result<DiffLineNumber> {
-    let word_diff_hunks = ContentDiff::by_word(contents.into_array())
-        .hunks()
-        .collect_vec();
+    let word_diff = match options.syntax {
+        Some(syntax) => ContentDiff::by_syntax_token(contents.into_array(), syntax),
+        None => ContentDiff::by_word(contents.into_array()),
+    };
+    let word_diff_hunks = word_diff.hunks().collect_vec();
     let can_inline = match options.max_inline_alternation {
         None => true,     // unlimited
         Some(0) => false, // no need to count alternation
@@ -1366,6 +1488,51 @@ fn basic_diff_file_type(value: &MaterializedTreeValue) -> &'static str {
     }
 }
 
+fn modified_file_description(
+    left_value: &MaterializedTreeValue,
+    right_value: &MaterializedTreeValue,
+) -> String {
+    match (left_value, right_value) {
+        (MaterializedTreeValue::File(left), MaterializedTreeValue::File(right)) => {
+            if left.executable && right.executable {
+                "Modified executable file".to_string()
+            } else if left.executable {
+                "Executable file became non-executable at".to_string()
+            } else if right.executable {
+                "Non-executable file became executable at".to_string()
+            } else {
+                "Modified regular file".to_string()
+            }
+        }
+        (
+            MaterializedTreeValue::FileConflict(_) | MaterializedTreeValue::OtherConflict { .. },
+            MaterializedTreeValue::FileConflict(_) | MaterializedTreeValue::OtherConflict { .. },
+        ) => "Modified conflict in".to_string(),
+        (
+            MaterializedTreeValue::FileConflict(_) | MaterializedTreeValue::OtherConflict { .. },
+            _,
+        ) => "Resolved conflict in".to_string(),
+        (
+            _,
+            MaterializedTreeValue::FileConflict(_) | MaterializedTreeValue::OtherConflict { .. },
+        ) => "Created conflict in".to_string(),
+        (MaterializedTreeValue::Symlink { .. }, MaterializedTreeValue::Symlink { .. }) => {
+            "Symlink target changed at".to_string()
+        }
+        (_, _) => {
+            let left_type = basic_diff_file_type(left_value);
+            let right_type = basic_diff_file_type(right_value);
+            let (first, rest) = left_type.split_at(1);
+            format!(
+                "{}{} became {} at",
+                first.to_ascii_uppercase(),
+                rest,
+                right_type
+            )
+        }
+    }
+}
+
 pub async fn show_color_words_diff(
     formatter: &mut dyn Formatter,
     store: &Store,
@@ -1387,6 +1554,7 @@ pub async fn show_color_words_diff(
         let right_path = path.target();
         let left_ui_path = path_converter.format_file_path(left_path);
         let right_ui_path = path_converter.format_file_path(right_path);
+        let options = options.for_path(right_path);
         let Diff {
             before: left_value,
             after: right_value,
@@ -1430,54 +1598,12 @@ pub async fn show_color_words_diff(
                         &ConflictLabels::unlabeled(),
                         &right_content.contents.conflict_labels,
                     ),
-                    options,
+                    &options,
                     &materialize_options,
                 )?;
             }
         } else if right_value.is_present() {
-            let description = match (&left_value, &right_value) {
-                (MaterializedTreeValue::File(left), MaterializedTreeValue::File(right)) => {
-                    if left.executable && right.executable {
-                        "Modified executable file".to_string()
-                    } else if left.executable {
-                        "Executable file became non-executable at".to_string()
-                    } else if right.executable {
-                        "Non-executable file became executable at".to_string()
-                    } else {
-                        "Modified regular file".to_string()
-                    }
-                }
-                (
-                    MaterializedTreeValue::FileConflict(_)
-                    | MaterializedTreeValue::OtherConflict { .. },
-                    MaterializedTreeValue::FileConflict(_)
-                    | MaterializedTreeValue::OtherConflict { .. },
-                ) => "Modified conflict in".to_string(),
-                (
-                    MaterializedTreeValue::FileConflict(_)
-                    | MaterializedTreeValue::OtherConflict { .. },
-                    _,
-                ) => "Resolved conflict in".to_string(),
-                (
-                    _,
-                    MaterializedTreeValue::FileConflict(_)
-                    | MaterializedTreeValue::OtherConflict { .. },
-                ) => "Created conflict in".to_string(),
-                (MaterializedTreeValue::Symlink { .. }, MaterializedTreeValue::Symlink { .. }) => {
-                    "Symlink target changed at".to_string()
-                }
-                (_, _) => {
-                    let left_type = basic_diff_file_type(&left_value);
-                    let right_type = basic_diff_file_type(&right_value);
-                    let (first, rest) = left_type.split_at(1);
-                    format!(
-                        "{}{} became {} at",
-                        first.to_ascii_uppercase(),
-                        rest,
-                        right_type
-                    )
-                }
-            };
+            let description = modified_file_description(&left_value, &right_value);
             let left_content = diff_content_as_merge(left_path, left_value).await?;
             let right_content = diff_content_as_merge(right_path, right_value).await?;
             if left_path == right_path {
@@ -1504,7 +1630,7 @@ pub async fn show_color_words_diff(
                         &left_content.contents.conflict_labels,
                         &right_content.contents.conflict_labels,
                     ),
-                    options,
+                    &options,
                     &materialize_options,
                 )?;
             }
@@ -1527,7 +1653,7 @@ pub async fn show_color_words_diff(
                         &left_content.contents.conflict_labels,
                         &ConflictLabels::unlabeled(),
                     ),
-                    options,
+                    &options,
                     &materialize_options,
                 )?;
             }
@@ -1830,6 +1956,323 @@ fn show_git_diff_texts<T: AsRef<[u8]>>(
     show_unified_diff_hunks(formatter, contents.as_ref().map(Cow::as_ref), options)
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SideBySideDiffOptions {
+    /// Number of context lines to show.
+    pub context: usize,
+    /// How lines are tokenized and compared.
+    pub line_diff: LineDiffOptions,
+}
+
+impl SideBySideDiffOptions {
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        Ok(Self {
+            context: settings.get("diff.side-by-side.context")?,
+            line_diff: LineDiffOptions::default(),
+        })
+    }
+
+    fn merge_args(&mut self, args: &DiffFormatArgs) {
+        if let Some(context) = args.context {
+            self.context = context;
+        }
+        self.line_diff.merge_args(args);
+    }
+}
+
+#[expect(clippy::too_many_arguments)]
+pub async fn show_side_by_side_diff(
+    formatter: &mut dyn Formatter,
+    store: &Store,
+    tree_diff: BoxStream<'_, CopiesTreeDiffEntry>,
+    conflict_labels: Diff<&ConflictLabels>,
+    path_converter: &RepoPathUiConverter,
+    options: &SideBySideDiffOptions,
+    marker_style: ConflictMarkerStyle,
+    width: usize,
+) -> Result<(), DiffRenderError> {
+    let materialize_options = ConflictMaterializeOptions {
+        marker_style,
+        marker_len: None,
+        merge: store.merge_options().clone(),
+    };
+    let mut diff_stream = materialized_diff_stream(store, tree_diff, conflict_labels);
+    while let Some(MaterializedTreeDiffEntry { path, values }) = diff_stream.next().await {
+        let left_path = path.source();
+        let right_path = path.target();
+        let left_ui_path = path_converter.format_file_path(left_path);
+        let right_ui_path = path_converter.format_file_path(right_path);
+        let Diff {
+            before: left_value,
+            after: right_value,
+        } = values?;
+
+        match (&left_value, &right_value) {
+            (MaterializedTreeValue::AccessDenied(source), _) => {
+                write!(
+                    formatter.labeled("access-denied"),
+                    "Access denied to {left_ui_path}:"
+                )?;
+                writeln!(formatter, " {source}")?;
+                continue;
+            }
+            (_, MaterializedTreeValue::AccessDenied(source)) => {
+                write!(
+                    formatter.labeled("access-denied"),
+                    "Access denied to {right_ui_path}:"
+                )?;
+                writeln!(formatter, " {source}")?;
+                continue;
+            }
+            _ => {}
+        }
+        let is_added_or_removed = left_value.is_absent() || right_value.is_absent();
+        if left_value.is_absent() {
+            let description = basic_diff_file_type(&right_value);
+            writeln!(
+                formatter.labeled("header"),
+                "Added {description} {right_ui_path}:"
+            )?;
+        } else if right_value.is_present() {
+            let description = modified_file_description(&left_value, &right_value);
+            if left_path == right_path {
+                writeln!(
+                    formatter.labeled("header"),
+                    "{description} {right_ui_path}:"
+                )?;
+            } else {
+                writeln!(
+                    formatter.labeled("header"),
+                    "{description} {right_ui_path} ({left_ui_path} => {right_ui_path}):"
+                )?;
+            }
+        } else {
+            let description = basic_diff_file_type(&left_value);
+            writeln!(
+                formatter.labeled("header"),
+                "Removed {description} {right_ui_path}:"
+            )?;
+        }
+        let left_content = diff_content(left_path, left_value, &materialize_options).await?;
+        let right_content = diff_content(right_path, right_value, &materialize_options).await?;
+        if left_content.is_binary || right_content.is_binary {
+            writeln!(formatter.labeled("binary"), "    (binary)")?;
+        } else if is_added_or_removed
+            && left_content.contents.is_empty()
+            && right_content.contents.is_empty()
+        {
+            writeln!(formatter.labeled("empty"), "    (empty)")?;
+        } else if left_content.contents != right_content.contents {
+            show_side_by_side_diff_hunks(
+                formatter,
+                Diff::new(&left_content.contents, &right_content.contents).map(BStr::new),
+                options,
+                width,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Generates diff of non-binary contents in side-by-side format.
+fn show_side_by_side_diff_texts<T: AsRef<[u8]>>(
+    formatter: &mut dyn Formatter,
+    contents: Diff<&Merge<T>>,
+    options: &SideBySideDiffOptions,
+    materialize_options: &ConflictMaterializeOptions,
+    width: usize,
+) -> io::Result<()> {
+    let contents = contents.map(|content| match content.as_resolved() {
+        Some(text) => Cow::Borrowed(BStr::new(text)),
+        None => Cow::Owned(materialize_merge_result_to_bytes(
+            content,
+            &ConflictLabels::unlabeled(),
+            materialize_options,
+        )),
+    });
+    show_side_by_side_diff_hunks(
+        formatter,
+        contents.as_ref().map(Cow::as_ref),
+        options,
+        width,
+    )
+}
+
+fn show_side_by_side_diff_hunks(
+    formatter: &mut dyn Formatter,
+    contents: Diff<&BStr>,
+    options: &SideBySideDiffOptions,
+    width: usize,
+) -> io::Result<()> {
+    const SKIPPED_CONTEXT_LINE: &str = "    ...\n";
+    // Each column consists of a line number, a space, and the content. The
+    // columns are separated by " │ ".
+    let content_width = max(
+        width.saturating_sub(2 * "1234 ".len() + " │ ".width()) / 2,
+        8,
+    );
+    let hunks = unified_diff_hunks(contents, options.context, options.line_diff.compare_mode);
+    for (i, hunk) in hunks.iter().enumerate() {
+        if i > 0 || hunk.left_line_range.start > 0 || hunk.right_line_range.start > 0 {
+            write!(formatter, "{SKIPPED_CONTEXT_LINE}")?;
+        }
+        let mut line_number = DiffLineNumber {
+            left: (hunk.left_line_range.start + 1).try_into().unwrap(),
+            right: (hunk.right_line_range.start + 1).try_into().unwrap(),
+        };
+        let mut lines = hunk.lines.iter().peekable();
+        while lines.peek().is_some() {
+            let is_type = |line_type| move |(ty, _): &&(DiffLineType, _)| *ty == line_type;
+            for (_, tokens) in lines.peeking_take_while(is_type(DiffLineType::Context)) {
+                show_side_by_side_row(
+                    formatter,
+                    Diff::new(
+                        Some((line_number.left, tokens.as_slice())),
+                        Some((line_number.right, tokens.as_slice())),
+                    ),
+                    Diff::new("context", "context"),
+                    content_width,
+                )?;
+                line_number.left += 1;
+                line_number.right += 1;
+            }
+            // Removed and added lines are aligned side by side.
+            let removed_lines = lines
+                .peeking_take_while(is_type(DiffLineType::Removed))
+                .collect_vec();
+            let added_lines = lines
+                .peeking_take_while(is_type(DiffLineType::Added))
+                .collect_vec();
+            for pair in removed_lines.iter().zip_longest(&added_lines) {
+                let (left, right) = pair.map_any(Some, Some).or(None, None);
+                let sides = Diff::new(
+                    left.map(|(_, tokens)| (line_number.left, tokens.as_slice())),
+                    right.map(|(_, tokens)| (line_number.right, tokens.as_slice())),
+                );
+                show_side_by_side_row(
+                    formatter,
+                    sides,
+                    Diff::new("removed", "added"),
+                    content_width,
+                )?;
+                line_number.left += u32::from(left.is_some());
+                line_number.right += u32::from(right.is_some());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Line number and tokens of a line to be shown in a side-by-side column.
+type SideBySideLine<'a> = (u32, &'a [(DiffTokenType, &'a [u8])]);
+
+/// Prints left and right lines in two columns, wrapping long lines.
+fn show_side_by_side_row(
+    formatter: &mut dyn Formatter,
+    sides: Diff<Option<SideBySideLine<'_>>>,
+    labels: Diff<&str>,
+    content_width: usize,
+) -> io::Result<()> {
+    let wrapped = sides.map(|side| {
+        side.map(|(line_number, tokens)| {
+            (line_number, wrap_diff_line_tokens(tokens, content_width))
+        })
+    });
+    let num_rows = [&wrapped.before, &wrapped.after]
+        .iter()
+        .filter_map(|side| side.as_ref().map(|(_, rows)| rows.len()))
+        .max()
+        .unwrap_or(0);
+    for row_index in 0..num_rows {
+        let left_cell = wrapped
+            .before
+            .as_ref()
+            .and_then(|(line_number, rows)| Some((line_number, rows.get(row_index)?)));
+        if let Some((line_number, row)) = left_cell {
+            let line_number = (row_index == 0).then_some(*line_number);
+            show_side_by_side_cell(*formatter.labeled(labels.before), line_number, row)?;
+            let (_, row_width) = row;
+            write!(formatter, "{:1$}", "", content_width - row_width)?;
+        } else {
+            write!(formatter, "{:1$}", "", "1234 ".len() + content_width)?;
+        }
+        write!(formatter, " │")?;
+        let right_cell = wrapped
+            .after
+            .as_ref()
+            .and_then(|(line_number, rows)| Some((line_number, rows.get(row_index)?)));
+        if let Some((line_number, row)) = right_cell {
+            let line_number = (row_index == 0).then_some(*line_number);
+            write!(formatter, " ")?;
+            show_side_by_side_cell(*formatter.labeled(labels.after), line_number, row)?;
+        }
+        writeln!(formatter)?;
+    }
+    Ok(())
+}
+
+/// Prints a line number (or blank for wrapped rows) and the row tokens.
+fn show_side_by_side_cell(
+    formatter: &mut dyn Formatter,
+    line_number: Option<u32>,
+    (tokens, _): &(Vec<(DiffTokenType, String)>, usize),
+) -> io::Result<()> {
+    if let Some(line_number) = line_number {
+        write!(formatter.labeled("line_number"), "{line_number:>4}")?;
+        write!(formatter, " ")?;
+    } else {
+        write!(formatter, "     ")?;
+    }
+    for (token_type, text) in tokens {
+        match token_type {
+            DiffTokenType::Matching => write!(formatter, "{text}")?,
+            DiffTokenType::Different => write!(formatter.labeled("token"), "{text}")?,
+        }
+    }
+    Ok(())
+}
+
+/// Splits line tokens into rows of the given display width. Tabs are expanded
+/// to spaces, and the trailing newline is removed.
+fn wrap_diff_line_tokens(
+    tokens: &[(DiffTokenType, &[u8])],
+    width: usize,
+) -> Vec<(Vec<(DiffTokenType, String)>, usize)> {
+    const TAB_WIDTH: usize = 4;
+    let mut rows = vec![];
+    let mut row_tokens: Vec<(DiffTokenType, String)> = vec![];
+    let mut row_width = 0;
+    for (token_type, content) in tokens {
+        let content = content.strip_suffix(b"\n").unwrap_or(content);
+        for (_, _, ch) in content.char_indices() {
+            let char_width = |row_width: usize| match ch {
+                '\t' => TAB_WIDTH - row_width % TAB_WIDTH,
+                _ => ch.width().unwrap_or(0),
+            };
+            if row_width > 0 && row_width + char_width(row_width) > width {
+                rows.push((mem::take(&mut row_tokens), row_width));
+                row_width = 0;
+            }
+            let char_width = char_width(row_width);
+            if row_tokens
+                .last()
+                .is_none_or(|(last_type, _)| last_type != token_type)
+            {
+                row_tokens.push((*token_type, String::new()));
+            }
+            let (_, text) = row_tokens.last_mut().unwrap();
+            if ch == '\t' {
+                text.extend(iter::repeat_n(' ', char_width));
+            } else {
+                text.push(ch);
+            }
+            row_width += char_width;
+        }
+    }
+    rows.push((row_tokens, row_width));
+    rows
+}
+
 #[instrument(skip_all)]
 pub async fn show_diff_summary(
     formatter: &mut dyn Formatter,
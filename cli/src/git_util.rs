@@ -29,6 +29,7 @@ use crossterm::terminal::ClearType;
 use indoc::writedoc;
 use itertools::Itertools as _;
 use jj_lib::commit::Commit;
+use jj_lib::commit::verify_commit_signatures_uncached;
 use jj_lib::git;
 use jj_lib::git::FailedRefExportReason;
 use jj_lib::git::GitExportStats;
@@ -46,15 +47,23 @@ use jj_lib::ref_name::RemoteRefSymbol;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo;
 use jj_lib::settings::RemoteSettingsMap;
+use jj_lib::settings::SignSettings;
+use jj_lib::settings::UserSettings;
+use jj_lib::signing::SignBehavior;
+use jj_lib::signing::SignPolicy;
+use jj_lib::signing::SignPolicyViolation;
 use jj_lib::workspace::Workspace;
 use unicode_width::UnicodeWidthStr as _;
 
 use crate::cleanup_guard::CleanupGuard;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::WorkspaceCommandTransaction;
 use crate::cli_util::print_updated_commits;
+use crate::cli_util::short_commit_hash;
 use crate::command_error::CommandError;
 use crate::command_error::cli_error;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
 use crate::formatter::Formatter;
 use crate::formatter::FormatterExt as _;
 use crate::revset_util::parse_remote_auto_track_bookmarks_map;
@@ -513,6 +522,17 @@ pub fn print_git_export_stats(ui: &Ui, stats: &GitExportStats) -> Result<(), std
             "#,
         )?;
     }
+    if stats
+        .failed_bookmarks
+        .iter()
+        .any(|(_, reason)| matches!(reason, FailedRefExportReason::CheckedOutInWorktree(_)))
+    {
+        writeln!(
+            ui.hint_default(),
+            "Switch the Git worktree to another branch or detach its HEAD (e.g. `git switch \
+             --detach`) to export the bookmark."
+        )?;
+    }
     Ok(())
 }
 
@@ -576,6 +596,87 @@ pub fn print_push_stats(ui: &Ui, stats: &GitPushStats) -> io::Result<()> {
     Ok(())
 }
 
+/// Returns the settings used to sign commits by `git.sign-on-push`.
+pub fn sign_on_push_settings(settings: &UserSettings) -> SignSettings {
+    let mut sign_settings = settings.sign_settings();
+    sign_settings.behavior = SignBehavior::Own;
+    sign_settings
+}
+
+/// Whether the commit will be signed by `git.sign-on-push`.
+pub fn needs_signing_on_push(sign_settings: &SignSettings, commit: &Commit) -> bool {
+    // TODO: make filter condition configurable by revset?
+    !commit.is_signed() && sign_settings.should_sign(commit.store_commit())
+}
+
+/// Warns that the signatures of `count` commits weren't checked against the
+/// `signing.require` policy because they would be signed on push.
+pub fn warn_unchecked_signatures_on_dry_run(ui: &Ui, count: usize) -> io::Result<()> {
+    if count > 0 {
+        writeln!(
+            ui.warning_default(),
+            "Not checking the signatures of {count} commits which would be signed on push."
+        )?;
+    }
+    Ok(())
+}
+
+/// Checks that the commits to be pushed are signed as required by the
+/// `signing.require` policy.
+///
+/// Each entry pairs a local commit with the commit that will actually be
+/// pushed, which differs if it is rewritten before pushing (e.g. signed by
+/// `git.sign-on-push`.) The latter is verified, and the former is reported.
+pub fn check_sign_policy_before_push(
+    workspace_helper: &WorkspaceCommandHelper,
+    policy: &SignPolicy,
+    commits: &[(Commit, Commit)],
+) -> Result<(), CommandError> {
+    if !policy.is_required_on_push() {
+        return Ok(());
+    }
+    let commits_to_push = commits.iter().map(|(_, new)| new.clone()).collect_vec();
+    // Bypass the verification cache: the policy must be checked against the
+    // current keyrings, not results that may be a day old.
+    let verifications = verify_commit_signatures_uncached(&commits_to_push);
+    for ((commit, _), verification) in iter::zip(commits, verifications) {
+        let verification = verification.map_err(|err| {
+            user_error_with_message(
+                format!(
+                    "Failed to verify signature of commit {}",
+                    short_commit_hash(commit.id())
+                ),
+                err,
+            )
+        })?;
+        let Err(violation) = policy.check(verification.as_ref()) else {
+            continue;
+        };
+        let mut error = user_error(format!(
+            "Won't push commit {} since {violation}",
+            short_commit_hash(commit.id())
+        ));
+        error.add_formatted_hint_with(|formatter| {
+            write!(formatter, "Rejected commit: ")?;
+            workspace_helper.write_commit_summary(formatter, commit)?;
+            Ok(())
+        });
+        match violation {
+            SignPolicyViolation::Unsigned | SignPolicyViolation::InvalidSignature(_) => {
+                error.add_hint(
+                    "Sign the commit with `jj sign`, or set `git.sign-on-push = true` to sign \
+                     your commits when pushing.",
+                );
+            }
+            SignPolicyViolation::UnidentifiedKey | SignPolicyViolation::UntrustedKey(_) => {
+                error.add_hint("Trusted keys are configured by `signing.trusted-keys`.");
+            }
+        }
+        return Err(error);
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::MAIN_SEPARATOR;
@@ -3,11 +3,13 @@ use std::path::Path;
 use std::sync::Arc;
 
 use futures::StreamExt as _;
+use futures::future::try_join_all;
 use futures::stream::BoxStream;
 use itertools::Itertools as _;
 use jj_lib::backend::BackendResult;
 use jj_lib::backend::CopyId;
 use jj_lib::backend::TreeValue;
+use jj_lib::conflict_labels::ConflictLabels;
 use jj_lib::conflicts;
 use jj_lib::conflicts::ConflictMarkerStyle;
 use jj_lib::conflicts::ConflictMaterializeOptions;
@@ -32,11 +34,8 @@ use jj_lib::object_id::ObjectId as _;
 use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::store::Store;
-use jj_lib::tree_merge::MergeOptions;
 use thiserror::Error;
 
-use super::MergeToolFile;
-
 #[derive(Debug, Error)]
 pub enum BuiltinToolError {
     #[error("Failed to record changes")]
@@ -48,6 +47,8 @@ pub enum BuiltinToolError {
     },
     #[error("Rendering {item} {id} is unimplemented for the builtin difftool/mergetool")]
     Unimplemented { item: &'static str, id: String },
+    #[error("Selected more than one side of the conflict at {path:?}")]
+    MultipleSidesSelected { path: RepoPathBuf },
     #[error("Backend error")]
     BackendError(#[from] jj_lib::backend::BackendError),
 }
@@ -671,28 +672,120 @@ fn make_merge_sections(
     Ok(sections)
 }
 
-fn make_merge_file(
-    merge_tool_file: &MergeToolFile,
-    options: &MergeOptions,
+fn is_text_contents(contents: &[u8]) -> bool {
+    !contents.contains(&0) && str::from_utf8(contents).is_ok()
+}
+
+/// Describes one side of a conflict that is resolved by picking a side.
+async fn describe_conflict_side(
+    store: &Store,
+    path: &RepoPath,
+    value: Option<&TreeValue>,
+) -> Result<String, BuiltinToolError> {
+    let description = match value {
+        None => "absent".to_owned(),
+        Some(value @ TreeValue::File { executable, .. }) => {
+            let materialized = conflicts::materialize_tree_value(
+                store,
+                path,
+                Merge::normal(value.clone()),
+                &ConflictLabels::unlabeled(),
+            )
+            .await?;
+            let kind = if *executable {
+                "executable file"
+            } else {
+                "file"
+            };
+            match materialized {
+                MaterializedTreeValue::File(mut file) => {
+                    let buf = file.read_all(path).await?;
+                    let contents = buf_to_file_contents(Some(file.id.hex()), buf);
+                    let kind = match contents {
+                        FileContents::Binary { .. } => format!("binary {kind}"),
+                        _ => kind.to_owned(),
+                    };
+                    format!("{kind} {}", contents.describe().unwrap_or_default())
+                }
+                MaterializedTreeValue::AccessDenied(err) => {
+                    format!("{kind} (access denied: {err})")
+                }
+                _ => unreachable!("file was materialized as another type"),
+            }
+        }
+        Some(TreeValue::Symlink(id)) => {
+            let target = store.read_symlink(path, id).await?;
+            format!("symlink to {target}")
+        }
+        Some(TreeValue::Tree(_)) => "directory".to_owned(),
+        Some(TreeValue::GitSubmodule(id)) => format!("Git submodule at commit {}", id.hex()),
+    };
+    Ok(description)
+}
+
+/// Makes a file for the merge editor from a conflict.
+///
+/// Conflicts between text files are presented as hunks, and their executable
+/// bit can be toggled if it's conflicted too. Other conflicts, such as binary
+/// files, symlinks, or a file and a directory, are presented as one binary
+/// section per side, of which at most one should be selected.
+async fn make_merge_file(
+    store: &Store,
+    repo_path: &RepoPath,
+    conflict: &MergedTreeValue,
+    labels: &ConflictLabels,
 ) -> Result<scm_record::File<'static>, BuiltinToolError> {
-    let file = &merge_tool_file.file;
-    let file_mode = if file.executable.expect("should have been resolved") {
-        mode::EXECUTABLE
+    let file = conflicts::try_materialize_file_conflict_value(store, repo_path, conflict, labels)
+        .await?
+        .filter(|file| {
+            file.contents
+                .iter()
+                .all(|contents| is_text_contents(contents))
+        });
+    let (file_mode, sections) = if let Some(file) = file {
+        let (file_mode, mut sections) = match file.executable {
+            Some(true) => (mode::EXECUTABLE, vec![]),
+            Some(false) => (mode::NORMAL, vec![]),
+            None => (
+                mode::NORMAL,
+                vec![scm_record::Section::FileMode {
+                    is_checked: false,
+                    mode: mode::EXECUTABLE,
+                }],
+            ),
+        };
+        let merge_result = files::merge_hunks(&file.contents, store.merge_options());
+        sections.extend(make_merge_sections(merge_result)?);
+        (file_mode, sections)
     } else {
-        mode::NORMAL
+        let (labels, conflict) = labels.simplify_with(conflict);
+        let file_mode = match conflict.first() {
+            None => mode::ABSENT,
+            Some(TreeValue::File {
+                executable: true, ..
+            }) => mode::EXECUTABLE,
+            Some(TreeValue::Symlink(_)) => mode::SYMLINK,
+            Some(_) => mode::NORMAL,
+        };
+        let mut sections = vec![];
+        for (index, value) in conflict.adds().enumerate() {
+            let description = describe_conflict_side(store, repo_path, value.as_ref()).await?;
+            let side = match labels.get_add(index) {
+                Some(label) => format!("side #{} ({label})", index + 1),
+                None => format!("side #{}", index + 1),
+            };
+            sections.push(scm_record::Section::Binary {
+                is_checked: false,
+                old_description: None,
+                new_description: Some(Cow::Owned(format!("{side}: {description}"))),
+            });
+        }
+        (file_mode, sections)
     };
-    // TODO: Maybe we should test binary contents here, and generate per-file
-    // Binary section to select either "our" or "their" file.
-    let merge_result = files::merge_hunks(&file.contents, options);
-    let sections = make_merge_sections(merge_result)?;
     Ok(scm_record::File {
         old_path: None,
         // Path for displaying purposes, not for file access.
-        path: Cow::Owned(
-            merge_tool_file
-                .repo_path
-                .to_fs_path_unchecked(Path::new("")),
-        ),
+        path: Cow::Owned(repo_path.to_fs_path_unchecked(Path::new(""))),
         file_mode,
         sections,
     })
@@ -700,17 +793,20 @@ fn make_merge_file(
 
 pub async fn edit_merge_builtin(
     tree: &MergedTree,
-    merge_tool_files: &[MergeToolFile],
+    conflicts: &[(RepoPathBuf, MergedTreeValue)],
 ) -> Result<MergedTree, BuiltinToolError> {
     let store = tree.store();
+    let files = try_join_all(
+        conflicts
+            .iter()
+            .map(|(path, conflict)| make_merge_file(store, path, conflict, tree.labels())),
+    )
+    .await?;
     let mut input = scm_record::helpers::CrosstermInput;
     let recorder = scm_record::Recorder::new(
         scm_record::RecordState {
             is_read_only: false,
-            files: merge_tool_files
-                .iter()
-                .map(|f| make_merge_file(f, store.merge_options()))
-                .try_collect()?,
+            files,
             commits: Default::default(),
         },
         &mut input,
@@ -719,14 +815,10 @@ pub async fn edit_merge_builtin(
     apply_merge_builtin(
         store,
         tree,
-        merge_tool_files
-            .iter()
-            .map(|file| file.repo_path.clone())
-            .collect_vec(),
+        conflicts.iter().map(|(path, _)| path.clone()).collect_vec(),
         &state.files,
     )
     .await
-    .map_err(BuiltinToolError::BackendError)
 }
 
 async fn apply_merge_builtin(
@@ -734,39 +826,78 @@ async fn apply_merge_builtin(
     tree: &MergedTree,
     changed_files: Vec<RepoPathBuf>,
     files: &[scm_record::File<'_>],
-) -> BackendResult<MergedTree> {
+) -> Result<MergedTree, BuiltinToolError> {
+    assert_eq!(
+        changed_files.len(),
+        files.len(),
+        "result had a different number of files"
+    );
     let mut tree_builder = MergedTreeBuilder::new(tree.clone());
-    apply_changes(
-        &mut tree_builder,
-        changed_files,
-        files,
-        async |path| tree.path_value(path).await,
-        // FIXME: It doesn't make sense to select a new value from the source tree.
-        // Presently, `select_right` is never actually called, since it is used to select binary
-        // sections, but `make_merge_file` does not produce `Binary` sections for conflicted files.
-        // This needs to be revisited when the UI becomes capable of representing binary conflicts.
-        async |path| tree.path_value(path).await,
-        async |path, contents, executable| {
-            let id = store.write_file(path, &mut &contents[..]).await?;
-            let tree_value = tree.path_value(path).await?;
-            let copy_id = resolve_file_copy_id(&tree_value).unwrap_or_else(CopyId::placeholder);
-            Ok(Merge::normal(TreeValue::File {
+    // TODO: Write files concurrently
+    for (path, file) in changed_files.into_iter().zip(files) {
+        let selected_sides = file
+            .sections
+            .iter()
+            .filter_map(|section| match section {
+                scm_record::Section::Binary { is_checked, .. } => Some(*is_checked),
+                _ => None,
+            })
+            .collect_vec();
+        if !selected_sides.is_empty() {
+            // The conflict was presented as one section per side.
+            match selected_sides
+                .iter()
+                .positions(|&is_checked| is_checked)
+                .at_most_one()
+            {
+                Ok(None) => {
+                    // No side was picked, so the conflict is left as is.
+                }
+                Ok(Some(index)) => {
+                    let conflict = tree.path_value(&path).await?.simplify();
+                    let value = conflict.get_add(index).cloned().flatten();
+                    tree_builder.set_or_remove(path, Merge::resolved(value));
+                }
+                Err(_) => return Err(BuiltinToolError::MultipleSidesSelected { path }),
+            }
+            continue;
+        }
+
+        let (
+            scm_record::SelectedChanges {
+                contents,
+                file_mode,
+            },
+            _unselected,
+        ) = file.get_selected_contents();
+        let contents = match contents {
+            scm_record::SelectedContents::Text { contents } => contents,
+            scm_record::SelectedContents::Unchanged => String::new(),
+            scm_record::SelectedContents::Binary { .. } => {
+                unreachable!("binary sections are handled above")
+            }
+        };
+        let id = store.write_file(&path, &mut contents.as_bytes()).await?;
+        let tree_value = tree.path_value(&path).await?;
+        let copy_id = resolve_file_copy_id(&tree_value).unwrap_or_else(CopyId::placeholder);
+        tree_builder.set_or_remove(
+            path,
+            Merge::normal(TreeValue::File {
                 id,
-                executable,
+                executable: file_mode == mode::EXECUTABLE,
                 copy_id,
-            }))
-        },
-    )
-    .await?;
-    tree_builder.write_tree().await
+            }),
+        );
+    }
+    Ok(tree_builder.write_tree().await?)
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeSet;
 
+    use assert_matches::assert_matches;
     use jj_lib::backend::FileId;
-    use jj_lib::conflict_labels::ConflictLabels;
     use jj_lib::conflicts::extract_as_single_hunk;
     use jj_lib::matchers::EverythingMatcher;
     use jj_lib::matchers::FilesMatcher;
@@ -1201,8 +1332,8 @@ mod tests {
             .copy_id(new_copy_id.clone());
         let tree = tree_builder.write_merged_tree();
 
-        let merge_tool_file = MergeToolFile::from_tree_and_path(&tree, file_path).block_on()?;
-        let merge_file = make_merge_file(&merge_tool_file, store.merge_options())?;
+        let conflict = tree.path_value(file_path).block_on()?;
+        let merge_file = make_merge_file(store, file_path, &conflict, tree.labels()).block_on()?;
         let tree = apply_merge_builtin(store, &tree, vec![file_path.to_owned()], &[merge_file])
             .block_on()?;
 
@@ -2151,6 +2282,307 @@ mod tests {
         Ok(())
     }
 
+    fn make_merge_files(
+        tree: &MergedTree,
+        paths: &[&RepoPath],
+    ) -> Result<Vec<scm_record::File<'static>>, BuiltinToolError> {
+        paths
+            .iter()
+            .map(|&path| {
+                let conflict = tree.path_value(path).block_on()?;
+                make_merge_file(tree.store(), path, &conflict, tree.labels()).block_on()
+            })
+            .collect()
+    }
+
+    fn apply_merge(
+        tree: &MergedTree,
+        paths: &[&RepoPath],
+        files: &[scm_record::File<'_>],
+    ) -> Result<MergedTree, BuiltinToolError> {
+        let changed_files = paths.iter().map(|&path| path.to_owned()).collect();
+        apply_merge_builtin(tree.store(), tree, changed_files, files).block_on()
+    }
+
+    fn select_section(file: &mut scm_record::File<'_>, index: usize) {
+        match &mut file.sections[index] {
+            scm_record::Section::Changed { lines } => {
+                for line in lines {
+                    line.is_checked = true;
+                }
+            }
+            scm_record::Section::FileMode { is_checked, .. }
+            | scm_record::Section::Binary { is_checked, .. } => *is_checked = true,
+            scm_record::Section::Unchanged { .. } => panic!("section is not editable"),
+        }
+    }
+
+    #[test]
+    fn test_edit_merge_builtin_binary_conflict() -> TestResult {
+        let test_repo = TestRepo::init();
+        let store = test_repo.repo.store();
+
+        let file_path = repo_path("file");
+        let mut tree_builder = testutils::TestThreeWayMergeTreeBuilder::new(store.clone());
+        tree_builder.base().file(file_path, "base\0");
+        tree_builder.parent1().file(file_path, "left\0");
+        tree_builder.parent2().file(file_path, "right\0");
+        let tree = tree_builder.write_merged_tree();
+
+        let mut files = make_merge_files(&tree, &[file_path])?;
+        insta::assert_debug_snapshot!(files, @r#"
+        [
+            File {
+                old_path: None,
+                path: "file",
+                file_mode: Unix(
+                    33188,
+                ),
+                sections: [
+                    Binary {
+                        is_checked: false,
+                        old_description: None,
+                        new_description: Some(
+                            "side #1: binary file 511052bc107aedf5067d (5B)",
+                        ),
+                    },
+                    Binary {
+                        is_checked: false,
+                        old_description: None,
+                        new_description: Some(
+                            "side #2: binary file 3a7a0a7ec5d74cb579cf (6B)",
+                        ),
+                    },
+                ],
+            },
+        ]
+        "#);
+
+        // Nothing selected: the conflict is left as is
+        let new_tree = apply_merge(&tree, &[file_path], &files)?;
+        assert_tree_eq!(new_tree, tree);
+
+        select_section(&mut files[0], 1);
+        let new_tree = apply_merge(&tree, &[file_path], &files)?;
+        insta::assert_snapshot!(dump_tree(&new_tree), @r#"
+        merged tree (sides: 1)
+          tree a8ebb2d2032e7edca756
+            file "file" (3a7a0a7ec5d74cb579cf): "right\0"
+        "#);
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_merge_builtin_symlink_and_directory_conflicts() -> TestResult {
+        let test_repo = TestRepo::init();
+        let store = test_repo.repo.store();
+
+        let symlink_path = repo_path("symlink");
+        let dir_path = repo_path("dir");
+        let mut tree_builder = testutils::TestThreeWayMergeTreeBuilder::new(store.clone());
+        tree_builder.base().file(symlink_path, "base\n");
+        tree_builder.parent1().symlink(symlink_path, "target");
+        tree_builder.parent2().file(symlink_path, "right\n");
+        tree_builder
+            .parent1()
+            .file(dir_path, "file\n")
+            .executable(true);
+        tree_builder
+            .parent2()
+            .file(&dir_path.join(repo_path_component("file")), "nested\n");
+        let tree = tree_builder.write_merged_tree();
+
+        let mut files = make_merge_files(&tree, &[symlink_path, dir_path])?;
+        insta::assert_debug_snapshot!(files, @r#"
+        [
+            File {
+                old_path: None,
+                path: "symlink",
+                file_mode: Unix(
+                    40960,
+                ),
+                sections: [
+                    Binary {
+                        is_checked: false,
+                        old_description: None,
+                        new_description: Some(
+                            "side #1: symlink to target",
+                        ),
+                    },
+                    Binary {
+                        is_checked: false,
+                        old_description: None,
+                        new_description: Some(
+                            "side #2: file 4ac8eb05298edc881fd1 (6B)",
+                        ),
+                    },
+                ],
+            },
+            File {
+                old_path: None,
+                path: "dir",
+                file_mode: Unix(
+                    33261,
+                ),
+                sections: [
+                    Binary {
+                        is_checked: false,
+                        old_description: None,
+                        new_description: Some(
+                            "side #1: executable file f239f77da55093d24ac9 (5B)",
+                        ),
+                    },
+                    Binary {
+                        is_checked: false,
+                        old_description: None,
+                        new_description: Some(
+                            "side #2: directory",
+                        ),
+                    },
+                ],
+            },
+        ]
+        "#);
+
+        select_section(&mut files[0], 0);
+        select_section(&mut files[1], 1);
+        let new_tree = apply_merge(&tree, &[symlink_path, dir_path], &files)?;
+        insta::assert_snapshot!(dump_tree(&new_tree), @r#"
+        merged tree (sides: 1)
+          tree c257208e46c6735d38a2
+            file "dir/file" (6209060941cd770c8d46): "nested\n"
+            symlink "symlink" (b87997a6740f2838b594)
+        "#);
+
+        // Selecting more than one side is an error
+        select_section(&mut files[0], 1);
+        assert_matches!(
+            apply_merge(&tree, &[symlink_path, dir_path], &files),
+            Err(BuiltinToolError::MultipleSidesSelected { path }) if path.as_ref() == symlink_path
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_merge_builtin_executable_conflict() -> TestResult {
+        let test_repo = TestRepo::init();
+        let store = test_repo.repo.store();
+
+        // One side deletes the file and the other makes it executable, so the
+        // executable bit can't be resolved.
+        let file_path = repo_path("file");
+        let mut tree_builder = testutils::TestThreeWayMergeTreeBuilder::new(store.clone());
+        tree_builder.base().file(file_path, "base\n");
+        tree_builder
+            .parent2()
+            .file(file_path, "right\n")
+            .executable(true);
+        let tree = tree_builder.write_merged_tree();
+
+        let mut files = make_merge_files(&tree, &[file_path])?;
+        insta::assert_debug_snapshot!(files[0].sections, @r#"
+        [
+            FileMode {
+                is_checked: false,
+                mode: Unix(
+                    33261,
+                ),
+            },
+            Changed {
+                lines: [
+                    SectionChangedLine {
+                        is_checked: false,
+                        change_type: Removed,
+                        line: "base\n",
+                    },
+                    SectionChangedLine {
+                        is_checked: false,
+                        change_type: Added,
+                        line: "right\n",
+                    },
+                ],
+            },
+        ]
+        "#);
+
+        select_section(&mut files[0], 0);
+        select_section(&mut files[0], 1);
+        let new_tree = apply_merge(&tree, &[file_path], &files)?;
+        insta::assert_snapshot!(dump_tree(&new_tree), @r#"
+        merged tree (sides: 1)
+          tree 99f2944df87a19a8a0e1
+            file "file" (4ac8eb05298edc881fd1): "right\n"
+        "#);
+        assert_matches!(
+            new_tree.path_value(file_path).block_on()?.into_resolved(),
+            Ok(Some(TreeValue::File {
+                executable: true,
+                ..
+            }))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_merge_builtin_n_way_conflict() -> TestResult {
+        let test_repo = TestRepo::init();
+        let store = test_repo.repo.store();
+
+        let file_path = repo_path("file");
+        let trees = ["a\nbase\n", "base\n", "b\nbase\n", "base\n", "c\nbase\n"].map(|contents| {
+            testutils::create_single_tree(&test_repo.repo, &[(file_path, contents)])
+        });
+        let tree = MergedTree::new(
+            store.clone(),
+            Merge::from_vec(trees.iter().map(|tree| tree.id().clone()).collect_vec()),
+            ConflictLabels::unlabeled(),
+        );
+
+        let mut files = make_merge_files(&tree, &[file_path])?;
+        insta::assert_debug_snapshot!(files[0].sections, @r#"
+        [
+            Changed {
+                lines: [
+                    SectionChangedLine {
+                        is_checked: false,
+                        change_type: Added,
+                        line: "a\n",
+                    },
+                    SectionChangedLine {
+                        is_checked: false,
+                        change_type: Added,
+                        line: "b\n",
+                    },
+                    SectionChangedLine {
+                        is_checked: false,
+                        change_type: Added,
+                        line: "c\n",
+                    },
+                ],
+            },
+            Unchanged {
+                lines: [
+                    "base\n",
+                ],
+            },
+        ]
+        "#);
+
+        // Keep the lines added by the first and last sides
+        let scm_record::Section::Changed { lines } = &mut files[0].sections[0] else {
+            panic!("expected a changed section");
+        };
+        lines[0].is_checked = true;
+        lines[2].is_checked = true;
+        let new_tree = apply_merge(&tree, &[file_path], &files)?;
+        insta::assert_snapshot!(dump_tree(&new_tree), @r#"
+        merged tree (sides: 1)
+          tree 4b9110d8cadb6c85d3b0
+            file "file" (5bfae785fd9ae4b93429): "a\nc\nbase\n"
+        "#);
+        Ok(())
+    }
+
     prop_state_machine! {
         #[test]
         fn test_edit_diff_builtin_all_or_nothing_proptest(
@@ -21,6 +21,7 @@ use jj_lib::matchers::NothingMatcher;
 use jj_lib::merge::Diff;
 use jj_lib::merged_tree::MergedTree;
 use jj_lib::merged_tree::TreeDiffEntry;
+use jj_lib::sparse::SparsePatterns;
 use jj_lib::working_copy::CheckoutError;
 use jj_lib::working_copy::SnapshotOptions;
 use tempfile::TempDir;
@@ -158,7 +159,7 @@ pub(crate) async fn check_out_trees(
             fsmonitor_settings: FsmonitorSettings::None,
         };
         let mut state = TreeState::init(store.clone(), wc_path, state_dir, &tree_state_settings)?;
-        state.set_sparse_patterns(changed_files.clone())?;
+        state.set_sparse_patterns(SparsePatterns::Prefixes(changed_files.clone()))?;
         state.check_out(tree)?;
         Ok(state)
     };
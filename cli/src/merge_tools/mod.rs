@@ -334,11 +334,7 @@ impl MergeToolFile {
         tree: &MergedTree,
         repo_path: &RepoPath,
     ) -> Result<Self, ConflictResolveError> {
-        let conflict = match tree.path_value(repo_path).await?.into_resolved() {
-            Err(conflict) => conflict,
-            Ok(Some(_)) => return Err(ConflictResolveError::NotAConflict(repo_path.to_owned())),
-            Ok(None) => return Err(ConflictResolveError::PathNotFound(repo_path.to_owned())),
-        };
+        let conflict = load_conflict(tree, repo_path).await?;
         let file =
             try_materialize_file_conflict_value(tree.store(), repo_path, &conflict, tree.labels())
                 .await?
@@ -367,6 +363,17 @@ impl MergeToolFile {
     }
 }
 
+async fn load_conflict(
+    tree: &MergedTree,
+    repo_path: &RepoPath,
+) -> Result<MergedTreeValue, ConflictResolveError> {
+    match tree.path_value(repo_path).await?.into_resolved() {
+        Err(conflict) => Ok(conflict),
+        Ok(Some(_)) => Err(ConflictResolveError::NotAConflict(repo_path.to_owned())),
+        Ok(None) => Err(ConflictResolveError::PathNotFound(repo_path.to_owned())),
+    }
+}
+
 /// Configured 3-way merge editor.
 #[derive(Clone, Debug)]
 pub struct MergeEditor {
@@ -433,29 +440,41 @@ impl MergeEditor {
         tree: &MergedTree,
         repo_paths: &[&RepoPath],
     ) -> Result<(MergedTree, Option<MergeToolPartialResolutionError>), ConflictResolveError> {
-        let merge_tool_files: Vec<MergeToolFile> = try_join_all(
-            repo_paths
-                .iter()
-                .map(|&repo_path| MergeToolFile::from_tree_and_path(tree, repo_path)),
-        )
-        .await?;
+        // Other tools than the builtin one only support 3-way conflicts
+        // between files.
+        let load_merge_tool_files = async || {
+            try_join_all(
+                repo_paths
+                    .iter()
+                    .map(|&repo_path| MergeToolFile::from_tree_and_path(tree, repo_path)),
+            )
+            .await
+        };
 
         match &self.tool {
             MergeTool::Builtin => {
-                let tree = edit_merge_builtin(tree, &merge_tool_files)
+                let conflicts = try_join_all(repo_paths.iter().map(async |&repo_path| {
+                    let conflict = load_conflict(tree, repo_path).await?;
+                    Ok::<_, ConflictResolveError>((repo_path.to_owned(), conflict))
+                }))
+                .await?;
+                let tree = edit_merge_builtin(tree, &conflicts)
                     .await
                     .map_err(Box::new)?;
                 Ok((tree, None))
             }
             MergeTool::Ours => {
+                let merge_tool_files = load_merge_tool_files().await?;
                 let tree = pick_conflict_side(tree, &merge_tool_files, 0).await?;
                 Ok((tree, None))
             }
             MergeTool::Theirs => {
+                let merge_tool_files = load_merge_tool_files().await?;
                 let tree = pick_conflict_side(tree, &merge_tool_files, 1).await?;
                 Ok((tree, None))
             }
             MergeTool::External(editor) => {
+                let merge_tool_files = load_merge_tool_files().await?;
                 external::run_mergetool_external(
                     ui,
                     &self.path_converter,
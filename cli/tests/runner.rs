@@ -25,6 +25,7 @@ mod test_config_command;
 mod test_config_schema;
 mod test_copy_detection;
 mod test_debug_command;
+mod test_debug_init_remote_command;
 mod test_debug_init_simple_command;
 mod test_describe_command;
 mod test_diff_command;
@@ -59,6 +60,7 @@ mod test_identical_commits;
 mod test_immutable_commits;
 mod test_interdiff_command;
 mod test_log_command;
+mod test_merge_drivers;
 mod test_metaedit_command;
 mod test_new_command;
 mod test_next_prev_commands;
@@ -67,6 +69,7 @@ mod test_op_restore_command;
 mod test_op_revert_command;
 mod test_operations;
 mod test_parallelize_command;
+mod test_patch_command;
 mod test_rebase_command;
 mod test_repo_change_report;
 mod test_resolve_command;
@@ -74,6 +77,7 @@ mod test_restore_command;
 mod test_revert_command;
 mod test_revset_output;
 mod test_root;
+mod test_run_command;
 mod test_show_command;
 mod test_sign_unsign_commands;
 mod test_simplify_parents_command;
@@ -45,7 +45,7 @@ fn test_bisect_run_empty_revset() -> TestResult {
     std::fs::write(&bisection_script, ["fail"].join("\0"))?;
     insta::assert_snapshot!(work_dir.run_jj(["bisect", "run", "--range=none()", &bisector_path]), @"
     Search complete. To discard any revisions created during search, run:
-      jj op restore 90267f31f904
+      jj op restore e39dc288903d
     [EOF]
     ------- stderr -------
     Error: Could not find the first bad revision. Was the input range empty?
@@ -83,7 +83,7 @@ fn test_bisect_run() -> TestResult {
     The revision is bad.
 
     Search complete. To discard any revisions created during search, run:
-      jj op restore 6f4b9c7057b1
+      jj op restore 4cb74757a8f9
     The first bad revision is: rlvkpnrz 7d980be7 a | a
     [EOF]
     ------- stderr -------
@@ -163,7 +163,7 @@ fn test_bisect_run_find_first_good() {
     The revision is good.
 
     Search complete. To discard any revisions created during search, run:
-      jj op restore 6f4b9c7057b1
+      jj op restore 4cb74757a8f9
     The first good revision is: rlvkpnrz 7d980be7 a | a
     [EOF]
     ------- stderr -------
@@ -266,7 +266,7 @@ fn test_bisect_run_with_args() {
     The revision is bad.
 
     Search complete. To discard any revisions created during search, run:
-      jj op restore 6f4b9c7057b1
+      jj op restore 4cb74757a8f9
     The first good revision is: royxmykx dffaa0d4 c | c
     [EOF]
     ------- stderr -------
@@ -324,7 +324,7 @@ fn test_bisect_run_crash() -> TestResult {
     The revision is bad.
 
     Search complete. To discard any revisions created during search, run:
-      jj op restore 6f4b9c7057b1
+      jj op restore 4cb74757a8f9
     The first bad revision is: rlvkpnrz 7d980be7 a | a
     [EOF]
     ------- stderr -------
@@ -360,7 +360,7 @@ fn test_bisect_run_abort() -> TestResult {
     Evaluation command returned 127 (command not found) - aborting bisection.
 
     Search complete. To discard any revisions created during search, run:
-      jj op restore e229f77ca8de
+      jj op restore cd3e30f228e8
     [EOF]
     ------- stderr -------
     Working copy  (@) now at: vruxwmqv 538d9e7f (empty) (no description set)
@@ -393,7 +393,7 @@ fn test_bisect_run_skip() -> TestResult {
     It could not be determined if the revision is good or bad.
 
     Search complete. To discard any revisions created during search, run:
-      jj op restore 07d6c9360663
+      jj op restore dc8b32d2ced4
     The first bad revision is: zsuskuln 123b4d91 b | b
     [EOF]
     ------- stderr -------
@@ -431,7 +431,7 @@ fn test_bisect_run_multiple_results() {
     The revision is good.
 
     Search complete. To discard any revisions created during search, run:
-      jj op restore 854a7d496ee7
+      jj op restore 00db3d1d6078
     The first bad revisions are:
     vruxwmqv a2dbb1aa d | d
     zsuskuln 123b4d91 b | b
@@ -477,7 +477,7 @@ fn test_bisect_run_write_file() -> TestResult {
     The revision is bad.
 
     Search complete. To discard any revisions created during search, run:
-      jj op restore 621116e08872
+      jj op restore ee7673caf72a
     The first bad revision is: rlvkpnrz 7d980be7 a | a
     [EOF]
     ------- stderr -------
@@ -543,7 +543,7 @@ fn test_bisect_run_jj_command() -> TestResult {
     The revision is bad.
 
     Search complete. To discard any revisions created during search, run:
-      jj op restore 621116e08872
+      jj op restore ee7673caf72a
     The first bad revision is: rlvkpnrz 7d980be7 a | a
     [EOF]
     ------- stderr -------
@@ -588,6 +588,352 @@ fn test_bisect_run_jj_command() -> TestResult {
     Ok(())
 }
 
+#[test]
+fn test_bisect_run_skip_revisions() -> TestResult {
+    let mut test_env = TestEnvironment::default();
+    let bisector_path = fake_bisector_path();
+    let bisection_script = test_env.set_up_fake_bisector();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+
+    // b is skipped without being evaluated
+    std::fs::write(&bisection_script, [""].join("\0"))?;
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "run", "--range=..c", "--skip=b", &bisector_path]), @"
+    Bisecting: 1 revisions left to test after this (roughly 1 steps)
+    Now evaluating: rlvkpnrz 7d980be7 a | a
+    fake-bisector testing commit 7d980be7a1d499e4d316ab4c01242885032f7eaf
+    The revision is good.
+
+    Search complete. To discard any revisions created during search, run:
+      jj op restore cd3e30f228e8
+    The first bad revision is: royxmykx dffaa0d4 c | c
+    [EOF]
+    ------- stderr -------
+    Working copy  (@) now at: vruxwmqv 538d9e7f (empty) (no description set)
+    Parent commit (@-)      : rlvkpnrz 7d980be7 a | a
+    Added 0 files, modified 0 files, removed 2 files
+    [EOF]
+    ");
+    Ok(())
+}
+
+#[test]
+fn test_bisect_run_parallel() {
+    let mut test_env = TestEnvironment::default();
+    let bisector_path = fake_bisector_path();
+    test_env.set_up_fake_bisector();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+    create_commit(&work_dir, "d", &["c"]);
+    create_commit(&work_dir, "e", &["d"]);
+    create_commit(&work_dir, "f", &["e"]);
+
+    // The command runs in temporary working copies, so the workspace isn't
+    // touched
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "run", "--range=..f", "--find-good", "--jobs=2", "--", &bisector_path, "--require-file=c"]), @"
+    Bisecting: 5 revisions left to test after this (roughly 3 steps)
+    Now evaluating 2 revisions:
+      vruxwmqv 86be7a22 d | d
+      zsuskuln 123b4d91 b | b
+    Evaluated: vruxwmqv 86be7a22 d | d
+    fake-bisector testing commit 86be7a223919b5526822f5553b4c42a859ced507
+    The revision is good.
+
+    Evaluated: zsuskuln 123b4d91 b | b
+    fake-bisector testing commit 123b4d91f6e5e39bfed39bae3bacf9380dc79078
+    The revision is bad.
+
+    Bisecting: 1 revisions left to test after this (roughly 1 steps)
+    Now evaluating: royxmykx dffaa0d4 c | c
+    Evaluated: royxmykx dffaa0d4 c | c
+    fake-bisector testing commit dffaa0d4daccf6cee70bac3498fae3b3fd5d6b5b
+    The revision is good.
+
+    Search complete. To discard any revisions created during search, run:
+      jj op restore 4cb74757a8f9
+    The first good revision is: royxmykx dffaa0d4 c | c
+    [EOF]
+    ");
+    assert!(!work_dir.root().join(".jj").join("bisect").exists());
+    insta::assert_snapshot!(get_log_output(&work_dir), @"
+    @  kmkuslswpqwq 8b67af288466 'f' files: f
+    ○  znkkpsqqskkl 62d30ded0e8f 'e' files: e
+    ○  vruxwmqvtpmx 86be7a223919 'd' files: d
+    ○  royxmykxtrkr dffaa0d4dacc 'c' files: c
+    ○  zsuskulnrvyr 123b4d91f6e5 'b' files: b
+    ○  rlvkpnrzqnoo 7d980be7a1d4 'a' files: a
+    ◆  zzzzzzzzzzzz 000000000000 '' files:
+    [EOF]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_bisect_run_parallel_in_workspaces() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+    create_commit(&work_dir, "d", &["c"]);
+
+    // `jj` commands run by the evaluation command operate on the workspace the
+    // revision is checked out in
+    let script = r#""$JJ_EXECUTABLE_PATH" log --no-graph -r @- -T description"#;
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "run", "--range=..d", "--jobs=2", "--", "sh", "-c", script]), @"
+    Bisecting: 3 revisions left to test after this (roughly 2 steps)
+    Now evaluating 2 revisions:
+      zsuskuln 123b4d91 b | b
+      rlvkpnrz 7d980be7 a | a
+    Evaluated: zsuskuln 123b4d91 b | b
+    b
+    The revision is good.
+
+    Evaluated: rlvkpnrz 7d980be7 a | a
+    a
+    The revision is good.
+
+    Bisecting: 1 revisions left to test after this (roughly 1 steps)
+    Now evaluating: royxmykx dffaa0d4 c | c
+    Evaluated: royxmykx dffaa0d4 c | c
+    c
+    The revision is good.
+
+    Search complete. To discard any revisions created during search, run:
+      jj op restore 5f0412ec5d1a
+    The first bad revision is: vruxwmqv 86be7a22 d | d
+    [EOF]
+    ");
+    assert!(!work_dir.root().join(".jj").join("bisect").exists());
+    insta::assert_snapshot!(work_dir.run_jj(["workspace", "list"]), @"
+    default: vruxwmqv 86be7a22 d | d
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_bisect_interactive() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+    create_commit(&work_dir, "d", &["c"]);
+    create_commit(&work_dir, "e", &["d"]);
+
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "good"]), @"
+    ------- stderr -------
+    Error: No bisection in progress
+    Hint: Use `jj bisect start` or `jj bisect run --range` to start one.
+    [EOF]
+    [exit status: 1]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "start", "--range=..e"]), @"
+    Bisecting: 4 revisions left to test after this (roughly 3 steps)
+    Now evaluating: zsuskuln 123b4d91 b | b
+    [EOF]
+    ------- stderr -------
+    Working copy  (@) now at: wqnwkozp 5befeada (empty) (no description set)
+    Parent commit (@-)      : zsuskuln 123b4d91 b | b
+    Added 0 files, modified 0 files, removed 3 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "start", "--range=..e"]), @"
+    ------- stderr -------
+    Error: A bisection is already in progress
+    Hint: Use `jj bisect reset` to abandon it.
+    [EOF]
+    [exit status: 1]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "good"]), @"
+    Bisecting: 2 revisions left to test after this (roughly 2 steps)
+    Now evaluating: royxmykx dffaa0d4 c | c
+    [EOF]
+    ------- stderr -------
+    Working copy  (@) now at: kxryzmor 076636e4 (empty) (no description set)
+    Parent commit (@-)      : royxmykx dffaa0d4 c | c
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    // Revisions can be marked explicitly, and marking them again is a no-op
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "good", "a|b"]), @"
+    Bisecting: 2 revisions left to test after this (roughly 2 steps)
+    Now evaluating: royxmykx dffaa0d4 c | c
+    [EOF]
+    ------- stderr -------
+    Working copy  (@) now at: nkmrtpmo 1601f7b4 (empty) (no description set)
+    Parent commit (@-)      : royxmykx dffaa0d4 c | c
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "bad", "e"]), @"
+    Bisecting: 2 revisions left to test after this (roughly 2 steps)
+    Now evaluating: royxmykx dffaa0d4 c | c
+    [EOF]
+    ------- stderr -------
+    Working copy  (@) now at: uyznsvlq dd798f83 (empty) (no description set)
+    Parent commit (@-)      : royxmykx dffaa0d4 c | c
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "bad", "b"]), @"
+    ------- stderr -------
+    Error: Revision 123b4d91f6e5 is already marked good
+    [EOF]
+    [exit status: 1]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "skip", "root()"]), @"
+    ------- stderr -------
+    Error: Revision 000000000000 is not in the bisection range
+    [EOF]
+    [exit status: 1]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "bad"]), @"
+    Search complete. To discard any revisions created during search, run:
+      jj op restore ee7673caf72a
+    The first bad revision is: royxmykx dffaa0d4 c | c
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "bad"]), @"
+    ------- stderr -------
+    Error: No bisection in progress
+    Hint: Use `jj bisect start` or `jj bisect run --range` to start one.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_bisect_reset() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+
+    work_dir
+        .run_jj(["bisect", "start", "--range=..c"])
+        .success();
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "reset"]), @"
+    Bisection reset. To discard any revisions created during search, run:
+      jj op restore cd3e30f228e8
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "reset"]), @"
+    ------- stderr -------
+    Error: No bisection in progress
+    Hint: Use `jj bisect start` or `jj bisect run --range` to start one.
+    [EOF]
+    [exit status: 1]
+    ");
+    // A new bisection can be started after resetting
+    work_dir
+        .run_jj(["bisect", "start", "--range=..c"])
+        .success();
+}
+
+#[test]
+fn test_bisect_run_resume() -> TestResult {
+    let mut test_env = TestEnvironment::default();
+    let bisector_path = fake_bisector_path();
+    let bisection_script = test_env.set_up_fake_bisector();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+    create_commit(&work_dir, "d", &["c"]);
+    create_commit(&work_dir, "e", &["d"]);
+
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "run", &bisector_path]), @"
+    ------- stderr -------
+    Error: No bisection in progress
+    Hint: Use `jj bisect start` or `jj bisect run --range` to start one.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // An aborted run can be resumed
+    std::fs::write(&bisection_script, ["abort"].join("\0"))?;
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "run", "--range=..e", &bisector_path]), @"
+    Bisecting: 4 revisions left to test after this (roughly 3 steps)
+    Now evaluating: zsuskuln 123b4d91 b | b
+    fake-bisector testing commit 123b4d91f6e5e39bfed39bae3bacf9380dc79078
+    Evaluation command returned 127 (command not found) - aborting bisection.
+
+    Search complete. To discard any revisions created during search, run:
+      jj op restore ee7673caf72a
+    [EOF]
+    ------- stderr -------
+    Working copy  (@) now at: wqnwkozp 5befeada (empty) (no description set)
+    Parent commit (@-)      : zsuskuln 123b4d91 b | b
+    Added 0 files, modified 0 files, removed 3 files
+    Error: Bisection aborted
+    [EOF]
+    [exit status: 1]
+    ");
+    std::fs::write(&bisection_script, ["fail"].join("\0"))?;
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "run", &bisector_path]), @"
+    Bisecting: 4 revisions left to test after this (roughly 3 steps)
+    Now evaluating: zsuskuln 123b4d91 b | b
+    fake-bisector testing commit 123b4d91f6e5e39bfed39bae3bacf9380dc79078
+    The revision is bad.
+
+    Bisecting: 1 revisions left to test after this (roughly 1 steps)
+    Now evaluating: rlvkpnrz 7d980be7 a | a
+    fake-bisector testing commit 7d980be7a1d499e4d316ab4c01242885032f7eaf
+    The revision is bad.
+
+    Search complete. To discard any revisions created during search, run:
+      jj op restore ee7673caf72a
+    The first bad revision is: rlvkpnrz 7d980be7 a | a
+    [EOF]
+    ------- stderr -------
+    Working copy  (@) now at: lylxulpl 0cb688b1 (empty) (no description set)
+    Parent commit (@-)      : zsuskuln 123b4d91 b | b
+    Working copy  (@) now at: rsllmpnm 5f328bc5 (empty) (no description set)
+    Parent commit (@-)      : rlvkpnrz 7d980be7 a | a
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+
+    // So can a bisection started interactively
+    work_dir
+        .run_jj(["bisect", "start", "--range=..e"])
+        .success();
+    work_dir.run_jj(["bisect", "bad"]).success();
+    std::fs::write(&bisection_script, [""].join("\0"))?;
+    insta::assert_snapshot!(work_dir.run_jj(["bisect", "run", &bisector_path]), @"
+    Bisecting: 1 revisions left to test after this (roughly 1 steps)
+    Now evaluating: rlvkpnrz 7d980be7 a | a
+    fake-bisector testing commit 7d980be7a1d499e4d316ab4c01242885032f7eaf
+    The revision is good.
+
+    Search complete. To discard any revisions created during search, run:
+      jj op restore a8737450c29f
+    The first bad revision is: zsuskuln 123b4d91 b | b
+    [EOF]
+    ------- stderr -------
+    Working copy  (@) now at: uyznsvlq d7389801 (empty) (no description set)
+    Parent commit (@-)      : rlvkpnrz 7d980be7 a | a
+    [EOF]
+    ");
+    Ok(())
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     let template = r#"separate(" ",
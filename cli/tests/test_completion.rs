@@ -230,6 +230,7 @@ fn test_bookmark_names() {
     --ignore-working-copy	Don't snapshot the working copy, and don't update it
     --ignore-immutable	Allow rewriting immutable commits
     --at-operation	Operation to load the repo at
+    --output	Output format of list-like commands
     --debug	Enable debug logging
     --color	When to colorize output
     --quiet	Silence non-primary command output
@@ -721,6 +722,7 @@ fn test_command_completion_short_name() {
     --ignore-working-copy	Don't snapshot the working copy, and don't update it
     --ignore-immutable	Allow rewriting immutable commits
     --at-operation	Operation to load the repo at
+    --output	Output format of list-like commands
     --debug	Enable debug logging
     --color	When to colorize output
     --quiet	Silence non-primary command output
@@ -1190,71 +1192,71 @@ fn test_operations() {
 
     let output = work_dir.complete_fish(["op", "show", ""]).success();
     insta::assert_snapshot!(output.take_stdout_n_lines(num_ops + 2), @"
-    9b559ee756d7	(2001-02-03 08:05:16) describe commit e0e6c0a964c024a49605805925672044dfae4181
-    2424cbddf672	(2001-02-03 08:05:15) describe commit 37df8a6c1874ff45621dee0f2b7a77169b65d257
-    b95dea46e909	(2001-02-03 08:05:14) describe commit c3588cff852e44b68297f51705d6e61888806ddd
-    006433125524	(2001-02-03 08:05:13) describe commit aa0b3230e3787076f232a08c8b1c7f54948a2d7a
-    4e01f7335c34	(2001-02-03 08:05:12) describe commit 96157804fd41363cb2ff8ff957ff1df1a2a1109a
-    d9412c797d9b	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
-    6ead3248a7c8	(2001-02-03 08:05:10) describe commit dd7390802e3ca4467ffa43f2e0c0374463d056f3
-    3274622dfd8b	(2001-02-03 08:05:09) describe commit 3ae22e7f50a15d393e412cca72d09a61165d0c84
-    8501e29d2d94	(2001-02-03 08:05:08) describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
-    90267f31f904	(2001-02-03 08:05:07) add workspace 'default'
+    697ef7ac0181	(2001-02-03 08:05:16) describe commit e0e6c0a964c024a49605805925672044dfae4181
+    fa2a5de517b8	(2001-02-03 08:05:15) describe commit 37df8a6c1874ff45621dee0f2b7a77169b65d257
+    21ca53d902d0	(2001-02-03 08:05:14) describe commit c3588cff852e44b68297f51705d6e61888806ddd
+    bdb57a3db4df	(2001-02-03 08:05:13) describe commit aa0b3230e3787076f232a08c8b1c7f54948a2d7a
+    1d1435567e26	(2001-02-03 08:05:12) describe commit 96157804fd41363cb2ff8ff957ff1df1a2a1109a
+    ecea0e1d0e25	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    d765a7d3972d	(2001-02-03 08:05:10) describe commit dd7390802e3ca4467ffa43f2e0c0374463d056f3
+    ff004c59950d	(2001-02-03 08:05:09) describe commit 3ae22e7f50a15d393e412cca72d09a61165d0c84
+    69e9fab8ce76	(2001-02-03 08:05:08) describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    e39dc288903d	(2001-02-03 08:05:07) add workspace 'default'
     000000000000	(1970-01-01 11:00:00)
     [EOF]
     ");
 
-    let output = work_dir.complete_fish(["op", "show", "9"]);
+    let output = work_dir.complete_fish(["op", "show", "e"]);
     insta::assert_snapshot!(output, @"
-    9b559ee756d7	(2001-02-03 08:05:16) describe commit e0e6c0a964c024a49605805925672044dfae4181
-    90267f31f904	(2001-02-03 08:05:07) add workspace 'default'
+    ecea0e1d0e25	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    e39dc288903d	(2001-02-03 08:05:07) add workspace 'default'
     [EOF]
     ");
     // make sure global --at-op flag is respected (should not include later
     // operations)
-    let output = work_dir.complete_fish(["--at-op", "90267f31f904", "op", "show", "9"]);
+    let output = work_dir.complete_fish(["--at-op", "e39dc288903d", "op", "show", "e"]);
     insta::assert_snapshot!(output, @"
-    90267f31f904	(2001-02-03 08:05:07) add workspace 'default'
+    e39dc288903d	(2001-02-03 08:05:07) add workspace 'default'
     [EOF]
     ");
 
-    let output = work_dir.complete_fish(["--at-op", "9b"]);
+    let output = work_dir.complete_fish(["--at-op", "697"]);
     insta::assert_snapshot!(output, @"
-    9b559ee756d7	(2001-02-03 08:05:16) describe commit e0e6c0a964c024a49605805925672044dfae4181
+    697ef7ac0181	(2001-02-03 08:05:16) describe commit e0e6c0a964c024a49605805925672044dfae4181
     [EOF]
     ");
 
-    let output = work_dir.complete_fish(["op", "abandon", "9b"]);
+    let output = work_dir.complete_fish(["op", "abandon", "697"]);
     insta::assert_snapshot!(output, @"
-    9b559ee756d7	(2001-02-03 08:05:16) describe commit e0e6c0a964c024a49605805925672044dfae4181
+    697ef7ac0181	(2001-02-03 08:05:16) describe commit e0e6c0a964c024a49605805925672044dfae4181
     [EOF]
     ");
 
-    let output = work_dir.complete_fish(["op", "diff", "--op", "9b"]);
+    let output = work_dir.complete_fish(["op", "diff", "--op", "697"]);
     insta::assert_snapshot!(output, @"
-    9b559ee756d7	(2001-02-03 08:05:16) describe commit e0e6c0a964c024a49605805925672044dfae4181
+    697ef7ac0181	(2001-02-03 08:05:16) describe commit e0e6c0a964c024a49605805925672044dfae4181
     [EOF]
     ");
-    let output = work_dir.complete_fish(["op", "diff", "--from", "9b"]);
+    let output = work_dir.complete_fish(["op", "diff", "--from", "697"]);
     insta::assert_snapshot!(output, @"
-    9b559ee756d7	(2001-02-03 08:05:16) describe commit e0e6c0a964c024a49605805925672044dfae4181
+    697ef7ac0181	(2001-02-03 08:05:16) describe commit e0e6c0a964c024a49605805925672044dfae4181
     [EOF]
     ");
-    let output = work_dir.complete_fish(["op", "diff", "--to", "9b"]);
+    let output = work_dir.complete_fish(["op", "diff", "--to", "697"]);
     insta::assert_snapshot!(output, @"
-    9b559ee756d7	(2001-02-03 08:05:16) describe commit e0e6c0a964c024a49605805925672044dfae4181
+    697ef7ac0181	(2001-02-03 08:05:16) describe commit e0e6c0a964c024a49605805925672044dfae4181
     [EOF]
     ");
 
-    let output = work_dir.complete_fish(["op", "restore", "9b"]);
+    let output = work_dir.complete_fish(["op", "restore", "697"]);
     insta::assert_snapshot!(output, @"
-    9b559ee756d7	(2001-02-03 08:05:16) describe commit e0e6c0a964c024a49605805925672044dfae4181
+    697ef7ac0181	(2001-02-03 08:05:16) describe commit e0e6c0a964c024a49605805925672044dfae4181
     [EOF]
     ");
 
-    let output = work_dir.complete_fish(["op", "revert", "9b"]);
+    let output = work_dir.complete_fish(["op", "revert", "697"]);
     insta::assert_snapshot!(output, @"
-    9b559ee756d7	(2001-02-03 08:05:16) describe commit e0e6c0a964c024a49605805925672044dfae4181
+    697ef7ac0181	(2001-02-03 08:05:16) describe commit e0e6c0a964c024a49605805925672044dfae4181
     [EOF]
     ");
 }
@@ -1502,6 +1504,7 @@ fn test_template_alias() {
     email_placeholder
     empty_commit_marker
     git_format_patch_email_headers
+    json
     name_placeholder
     [EOF]
     ");
@@ -1525,6 +1528,7 @@ fn test_merge_tools() {
     :name-only
     :git
     :color-words
+    :side-by-side
     diffedit3
     diffedit3-ssh
     difft
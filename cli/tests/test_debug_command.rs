@@ -359,6 +359,65 @@ fn test_debug_tree() {
     );
 }
 
+#[cfg(target_os = "linux")]
+#[test]
+fn test_debug_inotify() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["debug", "inotify", "status"]);
+    insta::assert_snapshot!(output, @r#"
+    Inotify is disabled. Set `fsmonitor.backend="inotify"` to enable.
+    The inotify daemon is not running.
+    [EOF]
+    "#);
+
+    // Snapshotting starts the daemon in the background
+    test_env.add_config(r#"fsmonitor.backend = "inotify""#);
+    work_dir.write_file("file", "contents\n");
+    let output = work_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    file
+    [EOF]
+    ");
+    let wait_for_status = |expected: &str| {
+        for _ in 0..500 {
+            let output = work_dir.run_jj(["debug", "inotify", "status"]).success();
+            if output.stdout.raw().contains(expected) {
+                return output;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        panic!("inotify daemon status didn't become {expected:?}");
+    };
+    insta::assert_snapshot!(wait_for_status("is running"), @r"
+    Inotify is enabled via `fsmonitor.backend`.
+    The inotify daemon is running.
+    [EOF]
+    ");
+
+    work_dir.write_file("file2", "contents\n");
+    let output = work_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    file
+    file2
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["debug", "inotify", "stop"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Asked the inotify daemon to stop
+    [EOF]
+    ");
+    insta::assert_snapshot!(wait_for_status("not running"), @r"
+    Inotify is enabled via `fsmonitor.backend`.
+    The inotify daemon is not running.
+    [EOF]
+    ");
+}
+
 fn filter_index_stats(output: CommandOutput) -> CommandOutput {
     let regex = Regex::new(r"    Name: [0-9a-z]+").unwrap();
     output.normalize_stdout_with(|text| regex.replace_all(&text, "    Name: [hash]").into_owned())
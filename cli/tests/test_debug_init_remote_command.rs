@@ -0,0 +1,74 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::thread;
+
+use jj_lib::remote_backend::RemoteStoreServer;
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_init_remote() {
+    let test_env = TestEnvironment::default();
+    let server_path = test_env.env_root().join("server");
+    let server = RemoteStoreServer::bind("127.0.0.1:0", &server_path).unwrap();
+    let url = server.url().unwrap();
+    thread::spawn(move || server.serve());
+
+    let output = test_env.run_jj_in(".", ["debug", "init-remote", &url, "repo"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Initialized repo in "repo"
+    [EOF]
+    "#);
+    let store_path = test_env.env_root().join("repo/.jj/repo/store");
+    assert!(store_path.join("cache").join("trees").is_dir());
+
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "contents\n");
+    work_dir.run_jj(["commit", "-m=first"]).success();
+    let output = work_dir.run_jj(["log", "-Tdescription", "--summary"]);
+    insta::assert_snapshot!(output, @"
+    @
+    ○  first
+    │  A file
+    ◆
+    [EOF]
+    ");
+    assert!(
+        server_path
+            .join("commits")
+            .read_dir()
+            .unwrap()
+            .next()
+            .is_some()
+    );
+
+    // Objects are read from the remote store if the cache is gone
+    std::fs::remove_dir_all(store_path.join("cache")).unwrap();
+    let output = work_dir.run_jj(["file", "show", "-r@-", "file"]);
+    insta::assert_snapshot!(output, @"
+    contents
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(".", ["debug", "init-remote", "ftp://example.com", "repo2"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Error: Failed to access the repository
+    Caused by: Invalid remote store URL "ftp://example.com", expected http://<host>:<port>[/<path>]
+    [EOF]
+    [exit status: 1]
+    "#);
+}
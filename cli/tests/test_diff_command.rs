@@ -348,6 +348,7 @@ fn test_diff_basic() {
     error: the argument '--template <TEMPLATE>' cannot be used with:
       --git
       --color-words
+      --side-by-side
 
     Usage: jj diff --template <TEMPLATE> [FILESETS]...
 
@@ -1982,6 +1983,170 @@ fn test_diff_ignore_whitespace() {
     ");
 }
 
+#[test]
+fn test_diff_color_words_syntax() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(r#"diff.color-words.syntax = { rs = "rust" }"#);
+
+    let content = indoc! {r#"
+        fn main() {
+            println!("hello world");
+        }
+
+        fn foo(a: u32, b: u32) {}
+    "#};
+    work_dir.write_file("main.rs", content);
+    work_dir.write_file("main.txt", content);
+    work_dir.run_jj(["new"]).success();
+    let content = indoc! {r#"
+        fn main() {
+            println!("hello, world");
+        }
+
+        fn foo(
+            a: u32,
+            b: u32
+        ) {
+        }
+    "#};
+    work_dir.write_file("main.rs", content);
+    work_dir.write_file("main.txt", content);
+
+    // String literal is highlighted as a unit in .rs file
+    let output = work_dir.run_jj(["diff", "--color=always"]);
+    insta::assert_snapshot!(output, @r#"
+    [38;5;3mModified regular file main.rs:[39m
+    [2m[38;5;1m   1[0m [2m[38;5;2m   1[0m: fn main() {
+    [38;5;1m   2[39m [38;5;2m   2[39m:     println!([4m[38;5;1m"hello world"[38;5;2m"hello, world"[24m[39m);
+    [2m[38;5;1m   3[0m [2m[38;5;2m   3[0m: }
+    [2m[38;5;1m   4[0m [2m[38;5;2m   4[0m: 
+    [38;5;1m   5[39m [38;5;2m   5[39m: fn foo([4m[38;5;2m[24m[39m
+    [38;5;1m   5[39m [38;5;2m   6[39m: [4m[38;5;2m    [24m[39ma: u32,[4m[38;5;2m[24m[39m
+    [38;5;1m   5[39m [38;5;2m   7[39m: [4m[38;5;2m   [24m[39m b: u32[4m[38;5;2m[24m[39m
+    [38;5;1m   5[39m [38;5;2m   8[39m: ) {[4m[38;5;2m[24m[39m
+    [38;5;1m   5[39m [38;5;2m   9[39m: }
+    [38;5;3mModified regular file main.txt:[39m
+    [2m[38;5;1m   1[0m [2m[38;5;2m   1[0m: fn main() {
+    [38;5;1m   2[39m [38;5;2m   2[39m:     println!("hello[4m[38;5;2m,[24m[39m world");
+    [2m[38;5;1m   3[0m [2m[38;5;2m   3[0m: }
+    [2m[38;5;1m   4[0m [2m[38;5;2m   4[0m: 
+    [38;5;1m   5[39m [38;5;2m   5[39m: fn foo([4m[38;5;2m[24m[39m
+    [38;5;1m   5[39m [38;5;2m   6[39m: [4m[38;5;2m    [24m[39ma: u32,[4m[38;5;2m[24m[39m
+    [38;5;1m   5[39m [38;5;2m   7[39m: [4m[38;5;2m   [24m[39m b: u32[4m[38;5;2m[24m[39m
+    [38;5;1m   5[39m [38;5;2m   8[39m: ) {[4m[38;5;2m[24m[39m
+    [38;5;1m   5[39m [38;5;2m   9[39m: }
+    [EOF]
+    "#);
+
+    // Reformatted lines are hidden as if they were unchanged
+    let output = work_dir.run_jj([
+        "diff",
+        "--color=always",
+        "--config=diff.color-words.ignore-reformatting=true",
+    ]);
+    insta::assert_snapshot!(output, @r#"
+    [38;5;3mModified regular file main.rs:[39m
+    [2m[38;5;1m   1[0m [2m[38;5;2m   1[0m: fn main() {
+    [38;5;1m   2[39m [38;5;2m   2[39m:     println!([4m[38;5;1m"hello world"[38;5;2m"hello, world"[24m[39m);
+    [2m[38;5;1m   3[0m [2m[38;5;2m   3[0m: }
+    [2m[38;5;1m   4[0m [2m[38;5;2m   4[0m: 
+        ...
+    [38;5;3mModified regular file main.txt:[39m
+    [2m[38;5;1m   1[0m [2m[38;5;2m   1[0m: fn main() {
+    [38;5;1m   2[39m [38;5;2m   2[39m:     println!("hello[4m[38;5;2m,[24m[39m world");
+    [2m[38;5;1m   3[0m [2m[38;5;2m   3[0m: }
+    [2m[38;5;1m   4[0m [2m[38;5;2m   4[0m: 
+    [38;5;1m   5[39m [38;5;2m   5[39m: fn foo([4m[38;5;2m[24m[39m
+    [38;5;1m   5[39m [38;5;2m   6[39m: [4m[38;5;2m    [24m[39ma: u32,[4m[38;5;2m[24m[39m
+    [38;5;1m   5[39m [38;5;2m   7[39m: [4m[38;5;2m   [24m[39m b: u32[4m[38;5;2m[24m[39m
+    [38;5;1m   5[39m [38;5;2m   8[39m: ) {[4m[38;5;2m[24m[39m
+    [38;5;1m   5[39m [38;5;2m   9[39m: }
+    [EOF]
+    "#);
+}
+
+#[test]
+fn test_diff_side_by_side() {
+    let mut test_env = TestEnvironment::default();
+    test_env.add_env_var("COLUMNS", "50");
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\nbar\nbaz\n");
+    work_dir.write_file("file2", "1\n2\n3\n4\n5\n6\n7\n8\n9\n");
+    work_dir.write_file("file3", "");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file(
+        "file1",
+        "foo\nbar is now a long line which has to be wrapped\n\tbaz\nqux\n",
+    );
+    work_dir.write_file("file2", "1\n2\n3\n4\nfive\n6\n7\n8\n9\n");
+    work_dir.remove_file("file3");
+    work_dir.write_file("file4", "new\n");
+
+    let output = work_dir.run_jj(["diff", "--side-by-side"]);
+    insta::assert_snapshot!(output, @"
+    Modified regular file file1:
+       1 foo                │    1 foo
+       2 bar                │    2 bar is now a long 
+                            │      line which has to 
+                            │      be wrapped
+       3 baz                │    3     baz
+                            │    4 qux
+    Modified regular file file2:
+        ...
+       2 2                  │    2 2
+       3 3                  │    3 3
+       4 4                  │    4 4
+       5 5                  │    5 five
+       6 6                  │    6 6
+       7 7                  │    7 7
+       8 8                  │    8 8
+    Removed regular file file3:
+        (empty)
+    Added regular file file4:
+                            │    1 new
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["diff", "--side-by-side", "--context=0", "--color=always"]);
+    insta::assert_snapshot!(output, @"
+    [38;5;3mModified regular file file1:[39m
+        ...
+    [38;5;1m   2 bar[39m                │ [38;5;2m   2 bar[4m is now a long [24m[39m
+                            │ [38;5;2m     [4mline which has to [24m[39m
+                            │ [38;5;2m     [4mbe wrapped[24m[39m
+    [38;5;1m   3 baz[39m                │ [38;5;2m   3 [4m    [24mbaz[39m
+                            │ [38;5;2m   4 [4mqux[24m[39m
+    [38;5;3mModified regular file file2:[39m
+        ...
+    [38;5;1m   5 [4m5[24m[39m                  │ [38;5;2m   5 [4mfive[24m[39m
+    [38;5;3mRemoved regular file file3:[39m
+    [38;5;6m    (empty)[39m
+    [38;5;3mAdded regular file file4:[39m
+                            │ [38;5;2m   1 [4mnew[24m[39m
+    [EOF]
+    ");
+
+    // Default diff format can be configured
+    let output = work_dir.run_jj([
+        "diff",
+        "--config=ui.diff-formatter=:side-by-side",
+        "--config=diff.side-by-side.context=1",
+        "file2",
+    ]);
+    insta::assert_snapshot!(output, @"
+    Modified regular file file2:
+        ...
+       4 4                  │    4 4
+       5 5                  │    5 five
+       6 6                  │    6 6
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_diff_skipped_context() {
     let test_env = TestEnvironment::default();
@@ -2713,6 +2878,32 @@ fn test_diff_conflict_sides_differ() {
     [38;5;1m   7[39m     : [4m[38;5;1mline 5[24m[39m
     [EOF]
     ");
+    let output = work_dir.run_jj([
+        "diff",
+        "--side-by-side",
+        "--context=1",
+        "--from=left1+right1",
+        "--to=left2+right2",
+    ]);
+    insta::assert_snapshot!(output, @r#"
+    Modified conflict in file:
+       1 line 1                                      │    1 left 1.1
+       2 line 2                                      │    2 line 2
+       3 <<<<<<< conflict 1 of 1                     │    3 <<<<<<< conflict 1 of 1
+       4 +++++++ zsuskuln 713a980c "left1"           │    4 +++++++ royxmykx b50b218b "left2"
+       5 left 3.1                                    │    5 left 3.1
+        ...
+       7 left 3.3                                    │    7 left 3.3
+                                                     │    8 left 3.4
+       8 %%%%%%% diff from: rlvkpnrz aa7e33ed "base" │    9 %%%%%%% diff from: rlvkpnrz aa7e33ed "base"
+       9 \\\\\\\        to: vruxwmqv 3fe2e860 "right │   10 \\\\\\\        to: znkkpsqq e57450eb "right
+         1"                                          │      2"
+      10 -line 3                                     │   11 -line 3
+        ...
+      13 line 4                                      │   14 line 4
+      14 line 5                                      │
+    [EOF]
+    "#);
 }
 
 #[test]
@@ -3852,6 +4043,21 @@ fn test_diff_binary() {
     [EOF]
     ");
 
+    let output = work_dir.run_jj(["diff", "--side-by-side"]);
+    insta::assert_snapshot!(output, @"
+    Added regular file binary_added.png:
+        (binary)
+    Modified regular file binary_modified.png:
+        (binary)
+    Modified regular file binary_modified_to_text.png:
+        (binary)
+    Removed regular file binary_removed.png:
+        (binary)
+    Added regular file binary_valid_utf8.png:
+        (binary)
+    [EOF]
+    ");
+
     let output = work_dir.run_jj(["diff", "--stat"]);
     // Rightmost display column          ->|
     insta::assert_snapshot!(output, @"
@@ -68,6 +68,21 @@ fn test_file_list() {
     [EOF]
     ");
 
+    // Can list as JSON
+    let output = work_dir.run_jj(["file", "list", "--output=json"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r#"
+    {"path":"conflict-exec-file","conflict":true,"file_type":"conflict","executable":true}
+    {"path":"conflict-file","conflict":true,"file_type":"conflict","executable":false}
+    {"path":"dir/file","conflict":false,"file_type":"file","executable":false}
+    {"path":"exec-file","conflict":false,"file_type":"file","executable":true}
+    [EOF]
+    "#);
+    let output = work_dir.run_jj(["file", "list", "-T", "json", "dir"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r#"
+    {"path":"dir/file","conflict":false,"file_type":"file","executable":false}
+    [EOF]
+    "#);
+
     // Can list files in another revision
     let output = work_dir.run_jj(["file", "list", "-r=first_parent(@)"]);
     insta::assert_snapshot!(output.normalize_backslash(), @"
@@ -1105,6 +1105,82 @@ fn test_deduplication() {
     ");
 }
 
+#[test]
+fn test_cache() {
+    // Log the tool invocations outside of the workspace, so the log isn't
+    // snapshotted.
+    let mut test_env = TestEnvironment::default();
+    set_up_fake_formatter(&mut test_env, &["--uppercase", "--tee", "../fixlog"]);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let fixlog_path = test_env.env_root().join("fixlog");
+    work_dir.write_file("file", "foo\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file", "bar\n");
+
+    let output = work_dir.run_jj(["fix", "-s", "@"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Fixed 1 commits of 1 checked.
+    Working copy  (@) now at: rlvkpnrz e6c204b0 (no description set)
+    Parent commit (@-)      : qpvuntsm d0c049cd (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(sorted_lines(fixlog_path.clone()), @"BAR");
+
+    // After undoing, the same content is fixed again without running the tool
+    work_dir.run_jj(["undo"]).success();
+    let output = work_dir.run_jj(["fix", "-s", "@"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Fixed 1 commits of 1 checked.
+    Working copy  (@) now at: rlvkpnrz a5344838 (no description set)
+    Parent commit (@-)      : qpvuntsm d0c049cd (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "file", "-r", "@"]);
+    insta::assert_snapshot!(output, @"
+    BAR
+    [EOF]
+    ");
+    insta::assert_snapshot!(sorted_lines(fixlog_path.clone()), @"BAR");
+
+    // The tool is run again if the cache is bypassed
+    work_dir.run_jj(["undo"]).success();
+    let output = work_dir.run_jj(["fix", "-s", "@", "--no-cache"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Fixed 1 commits of 1 checked.
+    Working copy  (@) now at: rlvkpnrz a289e211 (no description set)
+    Parent commit (@-)      : qpvuntsm d0c049cd (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(sorted_lines(fixlog_path.clone()), @"
+    BAR
+    BAR
+    ");
+
+    // Content that wasn't passed to the tool before is still fixed: "foo" in
+    // the parent, and the already fixed "BAR" in the child.
+    let output = work_dir.run_jj(["fix", "-s", "@-", "--include-unchanged-files"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Fixed 1 commits of 2 checked.
+    Working copy  (@) now at: rlvkpnrz d89ee4af (no description set)
+    Parent commit (@-)      : qpvuntsm a6fce805 (no description set)
+    [EOF]
+    ");
+    insta::assert_snapshot!(sorted_lines(fixlog_path), @"
+    BAR
+    BAR
+    BAR
+    FOO
+    ");
+}
+
 fn sorted_lines(path: PathBuf) -> String {
     let mut log: Vec<_> = std::fs::read_to_string(path.as_os_str())
         .unwrap()
@@ -1141,9 +1217,10 @@ fn test_executed_but_nothing_changed() {
     insta::assert_snapshot!(copy_content, @"content");
 
     // fix tools are always run from the workspace root, regardless of working
-    // directory at time of invocation.
+    // directory at time of invocation. The cache is bypassed to make sure the
+    // tool runs again.
     let sub_dir = work_dir.create_dir("dir");
-    let output = sub_dir.run_jj(["fix"]);
+    let output = sub_dir.run_jj(["fix", "--no-cache"]);
     insta::assert_snapshot!(output, @"
     ------- stderr -------
     Fixed 0 commits of 1 checked.
@@ -358,6 +358,98 @@ fn test_gerrit_upload_local_implicit_change_ids() {
     ");
 }
 
+#[test]
+fn test_gerrit_upload_sign_policy() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "remote"])
+        .success();
+    let remote_dir = test_env.work_dir("remote");
+    create_commit(&remote_dir, "a", &[]);
+
+    test_env
+        .run_jj_in(".", ["git", "clone", "remote", "local"])
+        .success();
+    let local_dir = test_env.work_dir("local");
+    create_commit(&local_dir, "b", &["a@origin"]);
+    create_commit(&local_dir, "c", &["b"]);
+    test_env.add_config(
+        r#"
+    signing.backend = "test"
+    signing.key = "impeccable"
+    signing.require = "push"
+    signing.trusted-keys = ["impeccable"]
+    "#,
+    );
+
+    // Unsigned commits are rejected
+    let output = local_dir.run_jj(["gerrit", "upload", "-r", "c", "--remote-branch=main"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Won't push commit 9590bf26e1f1 since it is not signed
+    Hint: Rejected commit: yqosqzyt 9590bf26 c | c
+    Hint: Sign the commit with `jj sign`, or set `git.sign-on-push = true` to sign your commits when pushing.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Unless they would be signed on push, which is reported in dry-run mode
+    let output = local_dir.run_jj([
+        "gerrit",
+        "upload",
+        "-r",
+        "c",
+        "--remote-branch=main",
+        "--dry-run",
+        "--config=git.sign-on-push=true",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Warning: Not checking the signatures of 2 commits which would be signed on push.
+    Found 1 heads to push to Gerrit (remote 'origin'), target branch 'main'
+    Dry-run: Would push yqosqzyt 9590bf26 c | c
+    [EOF]
+    ");
+    let output = local_dir.run_jj([
+        "gerrit",
+        "upload",
+        "-r",
+        "c",
+        "--remote-branch=main",
+        "--config=git.sign-on-push=true",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Found 1 heads to push to Gerrit (remote 'origin'), target branch 'main'
+    Pushing yqosqzyt 9590bf26 c | c
+    [EOF]
+    ");
+    let output = remote_dir.run_jj([
+        "util",
+        "exec",
+        "--",
+        "git",
+        "cat-file",
+        "commit",
+        "refs/for/main",
+    ]);
+    insta::assert_snapshot!(output, @"
+    tree 04a59185a0c5f4047e4fd3fa87b0c84e671b00ee
+    parent b38c27ed5f90e75cb28d49dc219122da18e039d8
+    author Test User <test.user@example.com> 981147913 +0700
+    committer Test User <test.user@example.com> 981147914 +0700
+    change-id yqosqzytrlswkspswpqrmlplxylrzsnz
+    gpgsig --- JJ-TEST-SIGNATURE ---
+     KEY: impeccable
+     56374bfd7d9148ef7971f256acb265a0370bcb82f7491d988a63f1113f358e4c70396fed7520c174413fcfbc0f4d43e06fe6cc655eb15ba05ff778e8925aa5a1
+
+    c
+
+    Change-Id: I19b790168e73f7a73a98deae21e807c06a6a6964
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_gerrit_upload_local_implicit_change_id_link() {
     let test_env = TestEnvironment::default();
@@ -890,6 +890,94 @@ fn test_git_colocated_external_checkout() -> TestResult {
     Ok(())
 }
 
+#[test]
+fn test_git_colocated_worktree_heads() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["ci", "-m=A"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@-", "feature"])
+        .success();
+    work_dir.run_jj(["new", "-m=B", "root()"]).success();
+    work_dir.run_jj(["new"]).success();
+
+    // Check out the bookmark in a linked worktree
+    let worktree_path = test_env.env_root().join("hotfix");
+    let status = std::process::Command::new("git")
+        .args([
+            "worktree",
+            "add",
+            worktree_path.to_str().unwrap(),
+            "feature",
+        ])
+        .current_dir(work_dir.root())
+        .status()
+        .expect("git worktree add failed to spawn");
+    assert!(status.success(), "git worktree add failed: {status}");
+
+    // The worktree HEAD is imported, and the working copy isn't affected
+    let output = work_dir.run_jj(["log", "-r=git_head(hotfix)", "-T=description"]);
+    insta::assert_snapshot!(output, @"
+    ○  A
+    │
+    ~
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @"
+    @  6f8612f0e7f6d52efd8a72615796df06f8d64cdc
+    ○  319eaafc8fd04c763a0683a000bba5452082feb3 B
+    │ ○  8777db25171cace71ad014598663d5ffc4fae6b1 feature A
+    ├─╯
+    ◆  0000000000000000000000000000000000000000
+    [EOF]
+    ");
+
+    // The bookmark checked out in the worktree isn't moved in Git
+    let output = work_dir.run_jj([
+        "bookmark",
+        "set",
+        "--allow-backwards",
+        "-r=subject(B)",
+        "feature",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Moved 1 bookmarks to zsuskuln 319eaafc feature* | (empty) B
+    Warning: Failed to export some bookmarks:
+      feature@git: Branch is checked out in Git worktree hotfix
+    Hint: Switch the Git worktree to another branch or detach its HEAD (e.g. `git switch --detach`) to export the bookmark.
+    [EOF]
+    ");
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "HEAD", "refs/heads/feature"])
+        .current_dir(&worktree_path)
+        .output()
+        .unwrap();
+    insta::assert_snapshot!(String::from_utf8(output.stdout).unwrap(), @"
+    8777db25171cace71ad014598663d5ffc4fae6b1
+    8777db25171cace71ad014598663d5ffc4fae6b1
+    ");
+
+    // Removed worktree is forgotten, and the bookmark can be exported
+    let status = std::process::Command::new("git")
+        .args(["worktree", "remove", worktree_path.to_str().unwrap()])
+        .current_dir(work_dir.root())
+        .status()
+        .expect("git worktree remove failed to spawn");
+    assert!(status.success(), "git worktree remove failed: {status}");
+    let output = work_dir.run_jj(["log", "-r=git_head(hotfix)", "-T=description"]);
+    insta::assert_snapshot!(output, @"");
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "refs/heads/feature"])
+        .current_dir(work_dir.root())
+        .output()
+        .unwrap();
+    insta::assert_snapshot!(String::from_utf8(output.stdout).unwrap(), @"319eaafc8fd04c763a0683a000bba5452082feb3");
+}
+
 #[test]
 #[cfg_attr(windows, ignore = "uses POSIX sh")]
 fn test_git_colocated_concurrent_checkout() -> TestResult {
@@ -1058,8 +1146,8 @@ fn test_git_colocated_undo_head_move() -> TestResult {
     let output = work_dir.run_jj(["undo"]);
     insta::assert_snapshot!(output, @"
     ------- stderr -------
-    Undid operation: 370aaac5a54d (2001-02-03 08:05:15) new empty commit
-    Restored to operation: f4eb73ce02a5 (2001-02-03 08:05:14) new empty commit
+    Undid operation: 7a38df294cba (2001-02-03 08:05:15) new empty commit
+    Restored to operation: 50e446a42aeb (2001-02-03 08:05:14) new empty commit
     Working copy  (@) now at: vruxwmqv 23e6e06a (empty) (no description set)
     Parent commit (@-)      : qpvuntsm e8849ae1 (empty) (no description set)
     [EOF]
@@ -121,8 +121,8 @@ fn test_git_export_undo() -> TestResult {
     let output = work_dir.run_jj(["undo"]);
     insta::assert_snapshot!(output, @"
     ------- stderr -------
-    Undid operation: 563b0274c404 (2001-02-03 08:05:10) export git refs
-    Restored to operation: a8cc177d3fa6 (2001-02-03 08:05:08) create bookmark a pointing to commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    Undid operation: ab278cf23619 (2001-02-03 08:05:10) export git refs
+    Restored to operation: 799c4e3b1b99 (2001-02-03 08:05:08) create bookmark a pointing to commit e8849ae12c709f2321908879bc724fdb2ab8a781
     [EOF]
     ");
     insta::assert_debug_snapshot!(get_git_repo_refs(&git_repo), @r#"
@@ -196,7 +196,7 @@ fn test_git_import_undo() -> TestResult {
     let output = work_dir.run_jj(["op", "restore", &base_operation_id]);
     insta::assert_snapshot!(output, @"
     ------- stderr -------
-    Restored to operation: 90267f31f904 (2001-02-03 08:05:07) add workspace 'default'
+    Restored to operation: e39dc288903d (2001-02-03 08:05:07) add workspace 'default'
     [EOF]
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @"");
@@ -277,7 +277,7 @@ fn test_git_import_move_export_with_default_undo() -> TestResult {
     let output = work_dir.run_jj(["op", "restore", &base_operation_id]);
     insta::assert_snapshot!(output, @"
     ------- stderr -------
-    Restored to operation: 90267f31f904 (2001-02-03 08:05:07) add workspace 'default'
+    Restored to operation: e39dc288903d (2001-02-03 08:05:07) add workspace 'default'
     Working copy  (@) now at: qpvuntsm e8849ae1 (empty) (no description set)
     Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
     [EOF]
@@ -310,6 +310,76 @@ fn test_git_import_move_export_with_default_undo() -> TestResult {
     Ok(())
 }
 
+#[test]
+fn test_git_import_deleted_remote_bookmark_not_resurrected() -> TestResult {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("remotes.origin.auto-track-bookmarks = '*'");
+    test_env.add_config("remotes.upstream.auto-track-bookmarks = '*'");
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = git::open(work_dir.root().join(".jj/repo/store/git"));
+
+    let commit_id = work_dir
+        .run_jj(&["log", "-Tcommit_id", "--no-graph", "-r@"])
+        .success()
+        .stdout
+        .into_raw();
+    let commit_id = gix::ObjectId::from_hex(commit_id.as_bytes())?;
+    let set_git_ref = |name: &str| {
+        git_repo.reference(
+            name,
+            commit_id,
+            gix::refs::transaction::PreviousValue::Any,
+            "",
+        )
+    };
+
+    set_git_ref("refs/remotes/origin/feature")?;
+    set_git_ref("refs/remotes/upstream/main")?;
+    let output = work_dir.run_jj(["git", "import"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    bookmark: feature@origin [new] tracked
+    bookmark: main@upstream  [new] tracked
+    Warning: The working-copy commit in workspace 'default' became immutable, so a new commit has been created on top of it.
+    Working copy  (@) now at: kkmpptxz 1c1c95df (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm e8849ae1 feature main | (empty) (no description set)
+    [EOF]
+    ");
+
+    // Bookmark deleted from origin is also deleted locally
+    git_repo
+        .find_reference("refs/remotes/origin/feature")?
+        .delete()?;
+    let output = work_dir.run_jj(["git", "import"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    bookmark: feature@origin [deleted] untracked
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @"
+    main: qpvuntsm e8849ae1 (empty) (no description set)
+      @upstream: qpvuntsm e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+
+    // Upstream which hasn't seen the deletion shouldn't bring it back
+    set_git_ref("refs/remotes/upstream/feature")?;
+    let output = work_dir.run_jj(["git", "import"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    bookmark: feature@upstream [new] untracked
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @"
+    feature@upstream: qpvuntsm e8849ae1 (empty) (no description set)
+    main: qpvuntsm e8849ae1 (empty) (no description set)
+      @upstream: qpvuntsm e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+    Ok(())
+}
+
 #[test]
 fn test_git_import_export_stats_color() -> TestResult {
     let test_env = TestEnvironment::default();
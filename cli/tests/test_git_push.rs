@@ -2578,6 +2578,102 @@ fn test_git_push_sign_on_push() {
     ");
 }
 
+#[test]
+fn test_git_push_sign_policy() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+    work_dir
+        .run_jj(["new", "bookmark2", "-m", "commit 1"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "set", "bookmark2", "-r@"])
+        .success();
+    test_env.add_config(
+        r#"
+    signing.backend = "test"
+    signing.key = "impeccable"
+    signing.require = "push"
+    signing.trusted-keys = ["impeccable"]
+    "#,
+    );
+
+    // Unsigned commits are rejected
+    let output = work_dir.run_jj(["git", "push"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Won't push commit 077255570cef since it is not signed
+    Hint: Rejected commit: vruxwmqv 07725557 bookmark2* | (empty) commit 1
+    Hint: Sign the commit with `jj sign`, or set `git.sign-on-push = true` to sign your commits when pushing.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Unless they would be signed on push, which is reported in dry-run mode
+    let output = work_dir.run_jj(["git", "push", "--dry-run", "--config=git.sign-on-push=true"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Warning: Not checking the signatures of 1 commits which would be signed on push.
+    Changes to push to origin:
+      Move forward bookmark bookmark2 from 38a204733702 to 077255570cef
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+
+    // Commits signed by untrusted keys are rejected, and not re-signed on push
+    work_dir.run_jj(["sign", "-r@", "--key", "rogue"]).success();
+    let output = work_dir.run_jj(["git", "push", "--config=git.sign-on-push=true"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Won't push commit c7194f21d3b1 since it is signed by untrusted key rogue
+    Hint: Rejected commit: vruxwmqv c7194f21 bookmark2* | (empty) commit 1
+    Hint: Trusted keys are configured by `signing.trusted-keys`.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Commits signed by trusted keys are pushed
+    work_dir.run_jj(["sign", "-r@"]).success();
+    let output = work_dir.run_jj(["git", "push"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Changes to push to origin:
+      Move forward bookmark bookmark2 from 38a204733702 to dd97c3e29f60
+    [EOF]
+    ");
+
+    // Commits signed on push are checked after signing
+    work_dir.run_jj(["new", "-m", "commit 2"]).success();
+    work_dir
+        .run_jj(["bookmark", "set", "bookmark2", "-r@"])
+        .success();
+    let output = work_dir.run_jj([
+        "git",
+        "push",
+        "--config=git.sign-on-push=true",
+        "--config=signing.key=rogue",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Updated signatures of 1 commits
+    Error: Won't push commit 16139076d950 since it is signed by untrusted key rogue
+    Hint: Rejected commit: nkmrtpmo 16139076 bookmark2* | (empty) commit 2
+    Hint: Trusted keys are configured by `signing.trusted-keys`.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["git", "push", "--config=git.sign-on-push=true"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Updated signatures of 1 commits
+    Changes to push to origin:
+      Move forward bookmark bookmark2 from dd97c3e29f60 to 4c2879c93675
+    Working copy  (@) now at: nkmrtpmo 4c2879c9 bookmark2 | (empty) commit 2
+    Parent commit (@-)      : vruxwmqv dd97c3e2 (empty) commit 1
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_push_rejected_by_remote() -> TestResult {
     let test_env = TestEnvironment::default();
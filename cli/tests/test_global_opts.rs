@@ -676,6 +676,86 @@ fn test_color_ui_messages() {
     "#);
 }
 
+#[test]
+fn test_output_json() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m=first"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "main"])
+        .success();
+    work_dir.run_jj(["tag", "set", "-r@", "v1"]).success();
+
+    // Graph is omitted
+    let output = work_dir.run_jj(["log", "--output=json"]);
+    insta::assert_snapshot!(output, @r#"
+    {"commit_id":"21f6daaf806152e790e822e4f935c03ad45d962b","parents":["68a505386f936fff6d718f55005e77ea72589bc1"],"change_id":"zsuskulnrvyrovkzqrwmxqlsskqntxvp","description":"","author":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:10+07:00"},"committer":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:10+07:00"}}
+    {"commit_id":"68a505386f936fff6d718f55005e77ea72589bc1","parents":["0000000000000000000000000000000000000000"],"change_id":"qpvuntsmwlqtpsluzzsnyyzlmlwvmlnu","description":"first\n","author":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:08+07:00"},"committer":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:08+07:00"}}
+    {"commit_id":"0000000000000000000000000000000000000000","parents":[],"change_id":"zzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz","description":"","author":{"name":"","email":"","timestamp":"1970-01-01T00:00:00Z"},"committer":{"name":"","email":"","timestamp":"1970-01-01T00:00:00Z"}}
+    [EOF]
+    "#);
+    let output = work_dir.run_jj(["op", "log", "--output=json", "--limit=1"]);
+    insta::assert_snapshot!(output, @r#"
+    {"id":"e179f4f78a7c1d67e60342b01cdec1d9efdcc6b203b8cb27dc6631363c3dde339419f4e870d4a96cd4e181d59765fe3887e563f3aeb05394acfff368e0bf9919","parents":["efdcb55e4e60a261feb2adf32c56a9d331f968cc40af0bfe7bf8d3fbb034a2c144827af68c9ce280f92b4e636b316215ddf0f76bbd3e92eb66cb12c5fbccac39"],"time":{"start":"2001-02-03T04:05:10+07:00","end":"2001-02-03T04:05:10+07:00"},"description":"set tag v1 to commit 68a505386f936fff6d718f55005e77ea72589bc1","hostname":"host.example.com","username":"test-username","is_snapshot":false,"workspace_name":"default","tags":{"args":"jj tag set -r@ v1"}}
+    [EOF]
+    "#);
+    let output = work_dir.run_jj(["bookmark", "list", "--output=json"]);
+    insta::assert_snapshot!(output, @r#"
+    {"name":"main","target":["68a505386f936fff6d718f55005e77ea72589bc1"]}
+    [EOF]
+    "#);
+    let output = work_dir.run_jj(["tag", "list", "--output=json"]);
+    insta::assert_snapshot!(output, @r#"
+    {"name":"v1","target":["68a505386f936fff6d718f55005e77ea72589bc1"]}
+    [EOF]
+    "#);
+    let output = work_dir.run_jj(["workspace", "list", "--output=json"]);
+    insta::assert_snapshot!(output, @r#"
+    {"name":"default","target":{"commit_id":"21f6daaf806152e790e822e4f935c03ad45d962b","parents":["68a505386f936fff6d718f55005e77ea72589bc1"],"change_id":"zsuskulnrvyrovkzqrwmxqlsskqntxvp","description":"","author":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:10+07:00"},"committer":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:10+07:00"}}}
+    [EOF]
+    "#);
+
+    // `--output=text` is the default
+    let output = work_dir.run_jj(["bookmark", "list", "--output=text"]);
+    insta::assert_snapshot!(output, @"
+    main: qpvuntsm 68a50538 (empty) first
+    [EOF]
+    ");
+
+    // Conflicting options
+    let output = work_dir.run_jj(["log", "--output=json", "-Tbuiltin_log_oneline"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: `--output=json` cannot be used with `--template`
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["log", "--output=json", "--patch"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: `--output=json` cannot be used with diff options
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["op", "log", "--output=json", "--op-diff"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: `--output=json` cannot be used with diff options
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Unsupported command
+    let output = work_dir.run_jj(["diff", "--output=json"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: `--output=json` is not supported by this command
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_quiet() {
     let test_env = TestEnvironment::default();
@@ -1154,6 +1234,8 @@ fn test_help() {
           --ignore-working-copy          Don't snapshot the working copy, and don't update it
           --ignore-immutable             Allow rewriting immutable commits
           --at-operation <AT_OPERATION>  Operation to load the repo at [aliases: --at-op]
+          --output <FORMAT>              Output format of list-like commands [possible values: text,
+                                         json]
           --debug                        Enable debug logging
           --color <WHEN>                 When to colorize output [possible values: always, never, debug,
                                          auto]
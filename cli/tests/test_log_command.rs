@@ -70,6 +70,7 @@ fn test_log_with_no_template() {
     - email_placeholder
     - empty_commit_marker
     - git_format_patch_email_headers
+    - json
     - name_placeholder
     [EOF]
     [exit status: 2]
@@ -1868,3 +1869,38 @@ fn test_log_count() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_log_interactive_requires_terminal() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["log", "--interactive"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: `jj log --interactive` requires a terminal
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["log", "--interactive", "--count"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: the argument '--interactive' cannot be used with '--count'
+
+    Usage: jj log --interactive [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+
+    let output = work_dir.run_jj(["log", "--interactive", "--output=json"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: `--output=json` cannot be used with `--interactive`
+    [EOF]
+    [exit status: 1]
+    ");
+}
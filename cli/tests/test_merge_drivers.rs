@@ -0,0 +1,211 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use indoc::formatdoc;
+use testutils::TestResult;
+
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+use crate::common::create_commit_with_files;
+use crate::common::fake_editor_path;
+use crate::common::to_toml_value;
+
+/// Configures a merge driver for `file` which runs the fake editor on
+/// `$output`, passing the other paths as arguments.
+fn set_up_merge_driver(test_env: &mut TestEnvironment) -> std::path::PathBuf {
+    let editor_path = to_toml_value(fake_editor_path());
+    test_env.add_config(formatdoc! {r#"
+        [merge-drivers.fake]
+        command = [{editor_path}, "$output", "$path", "$base", "$left", "$right"]
+        patterns = ["glob:'file*'"]
+    "#});
+    let edit_script = test_env.env_root().join("edit_script");
+    std::fs::write(&edit_script, "").unwrap();
+    test_env.add_env_var("EDIT_SCRIPT", &edit_script);
+    edit_script
+}
+
+fn create_conflicting_commits(work_dir: &TestWorkDir) {
+    create_commit_with_files(
+        work_dir,
+        "base",
+        &[],
+        &[("file", "base\n"), ("other", "base\n")],
+    );
+    create_commit_with_files(
+        work_dir,
+        "a",
+        &["base"],
+        &[("file", "a\n"), ("other", "a\n")],
+    );
+    create_commit_with_files(
+        work_dir,
+        "b",
+        &["base"],
+        &[("file", "b\n"), ("other", "b\n")],
+    );
+}
+
+#[test]
+fn test_merge_driver_new_merge() -> TestResult {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = set_up_merge_driver(&mut test_env);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_conflicting_commits(&work_dir);
+
+    // The driver is invoked with the file paths and the contents of the left
+    // side in the output file
+    std::fs::write(
+        &edit_script,
+        ["expect\na\n", "expect-arg 0\nfile", "write\nmerged\n"].join("\0"),
+    )?;
+    let output = work_dir.run_jj(["new", "a", "b"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Working copy  (@) now at: vruxwmqv 3e47049e (conflict) (no description set)
+    Parent commit (@-)      : zsuskuln 4066fb2d a | a
+    Parent commit (@-)      : royxmykx 91d14785 b | b
+    Added 0 files, modified 2 files, removed 0 files
+    Warning: There are unresolved conflicts at these paths:
+    other    2-sided conflict
+    New conflicts appeared in 1 commits:
+      vruxwmqv 3e47049e (conflict) (no description set)
+    Hint: To resolve the conflicts, start by creating a commit on top of
+    the conflicted commit:
+      jj new vruxwmqv
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file"), @"merged");
+    insta::assert_snapshot!(work_dir.run_jj(["resolve", "--list"]), @"
+    other    2-sided conflict
+    [EOF]
+    ");
+    Ok(())
+}
+
+#[test]
+fn test_merge_driver_rebase() -> TestResult {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = set_up_merge_driver(&mut test_env);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_conflicting_commits(&work_dir);
+
+    std::fs::write(&edit_script, "write\nmerged\n")?;
+    let output = work_dir.run_jj(["rebase", "-r", "b", "-d", "a"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Rebased 1 commits to destination
+    Working copy  (@) now at: royxmykx cf665241 b | (conflict) b
+    Parent commit (@-)      : zsuskuln 4066fb2d a | a
+    Added 0 files, modified 2 files, removed 0 files
+    Warning: There are unresolved conflicts at these paths:
+    other    2-sided conflict
+    New conflicts appeared in 1 commits:
+      royxmykx cf665241 b | (conflict) b
+    Hint: To resolve the conflicts, start by creating a commit on top of
+    the conflicted commit:
+      jj new royxmykx
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["file", "show", "-r", "b", "file"]), @"
+    merged
+    [EOF]
+    ");
+    Ok(())
+}
+
+#[test]
+fn test_merge_driver_failure() -> TestResult {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = set_up_merge_driver(&mut test_env);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_conflicting_commits(&work_dir);
+
+    // A failed driver leaves the conflict
+    std::fs::write(&edit_script, "fail")?;
+    let output = work_dir.run_jj(["new", "a", "b"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Warning: Failed to merge file with merge driver.
+    Caused by: Merge driver fake exited with exit status: 1
+    Working copy  (@) now at: vruxwmqv c6b746e9 (conflict) (empty) (no description set)
+    Parent commit (@-)      : zsuskuln 4066fb2d a | a
+    Parent commit (@-)      : royxmykx 91d14785 b | b
+    Added 0 files, modified 2 files, removed 0 files
+    Warning: There are unresolved conflicts at these paths:
+    file     2-sided conflict
+    other    2-sided conflict
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["resolve", "--list"]), @"
+    file     2-sided conflict
+    other    2-sided conflict
+    [EOF]
+    ");
+    Ok(())
+}
+
+#[test]
+fn test_merge_driver_read_only_command() -> TestResult {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = set_up_merge_driver(&mut test_env);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_conflicting_commits(&work_dir);
+    std::fs::write(&edit_script, "fail")?;
+    work_dir.run_jj(["new", "a", "b"]).success();
+
+    // Merging the parents to show the diff shouldn't run the driver
+    std::fs::write(&edit_script, "write\nmerged\n")?;
+    let output = work_dir.run_jj(["diff", "--git"]);
+    insta::assert_snapshot!(output, @"");
+    Ok(())
+}
+
+#[test]
+fn test_merge_driver_invalid_config() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(formatdoc! {r#"
+        [merge-drivers.bad]
+        command = ["true"]
+        patterns = ["bad:pattern"]
+    "#});
+    let output = work_dir.run_jj(["log"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Config error: Invalid type or value for merge-drivers.bad
+    Caused by:
+    1:  --> 1:1
+      |
+    1 | bad:pattern
+      | ^---------^
+      |
+      = Invalid file pattern
+    2: Invalid file pattern kind `bad:`
+    For help, see https://docs.jj-vcs.dev/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
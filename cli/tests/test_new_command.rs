@@ -313,6 +313,62 @@ fn test_new_merge_same_change() {
     "#);
 }
 
+#[test]
+fn test_new_merge_strategy() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "1", &[], &[("file", "a\nb\n")]);
+    create_commit_with_files(&work_dir, "2", &["1"], &[("file", "a\n2\n")]);
+    create_commit_with_files(&work_dir, "3", &["1"], &[("file", "a\n3\n")]);
+
+    let output = work_dir.run_jj(["new", "2", "3", "--strategy=ours"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Working copy  (@) now at: vruxwmqv 291dc734 (no description set)
+    Parent commit (@-)      : zsuskuln 4feaf3e0 2 | 2
+    Parent commit (@-)      : royxmykx 11ec2b06 3 | 3
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file"), @"
+    a
+    2
+    ");
+
+    work_dir
+        .run_jj(["new", "2", "3", "--strategy=theirs"])
+        .success();
+    insta::assert_snapshot!(work_dir.read_file("file"), @"
+    a
+    3
+    ");
+
+    work_dir
+        .run_jj(["new", "2", "3", "--strategy=union"])
+        .success();
+    insta::assert_snapshot!(work_dir.read_file("file"), @"
+    a
+    2
+    3
+    ");
+
+    // The default strategy leaves the conflict
+    work_dir.run_jj(["new", "2", "3"]).success();
+    insta::assert_snapshot!(work_dir.read_file("file"), @r#"
+    a
+    <<<<<<< conflict 1 of 1
+    %%%%%%% diff from: rlvkpnrz 6953e1a0 "1"
+    \\\\\\\        to: zsuskuln 4feaf3e0 "2"
+    -b
+    +2
+    +++++++ royxmykx 11ec2b06 "3"
+    3
+    >>>>>>> conflict 1 of 1 ends
+    "#);
+}
+
 #[test]
 fn test_new_description_template() {
     let test_env = TestEnvironment::default();
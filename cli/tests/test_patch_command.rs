@@ -0,0 +1,181 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use regex::Regex;
+
+use crate::common::CommandOutput;
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+
+fn normalize_version(output: String) -> String {
+    let regex = Regex::new(r"(?m)^jj .*$").unwrap();
+    regex.replace_all(&output, "jj [VERSION]").into_owned()
+}
+
+#[test]
+fn test_patch_export() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\nb\nc\n");
+    work_dir
+        .run_jj(["commit", "-m", "first: add file"])
+        .success();
+    work_dir.write_file("file", "a\nB\nc\n");
+    work_dir.write_file("new", "new\n");
+    work_dir
+        .run_jj(["describe", "-m", "second\n\nwith a body\n"])
+        .success();
+
+    // Export outside of the working copy so the patches (which include the
+    // version string) aren't snapshotted.
+    let output = work_dir.run_jj(["patch", "export", "-r", "::@ ~ root()", "-o", "../out"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @"
+    ------- stderr -------
+    ../out/0001-first-add-file.patch
+    ../out/0002-second.patch
+    [EOF]
+    ");
+    let patch = std::fs::read_to_string(test_env.env_root().join("out/0002-second.patch")).unwrap();
+    insta::assert_snapshot!(normalize_version(patch), @"
+    From bd1ec05a02ba11934fed4f75f06f25d139b450dd Mon Sep 17 00:00:00 2001
+    From: Test User <test.user@example.com>
+    Date: Sat, 3 Feb 2001 04:05:09 +0700
+    Subject: [PATCH] second
+
+    with a body
+    ---
+     file | 2 +-
+     new  | 1 +
+     2 files changed, 2 insertions(+), 1 deletion(-)
+
+    diff --git a/file b/file
+    index de980441c3..7be73ce3c1 100644
+    --- a/file
+    +++ b/file
+    @@ -1,3 +1,3 @@
+     a
+    -b
+    +B
+     c
+    diff --git a/new b/new
+    new file mode 100644
+    index 0000000000..3e757656cf
+    --- /dev/null
+    +++ b/new
+    @@ -0,0 +1,1 @@
+    +new
+    -- 
+    jj [VERSION]
+    ");
+
+    // Merge commits can't be exported
+    work_dir.run_jj(["new", "@", "@-"]).success();
+    let output = work_dir.run_jj(["patch", "export", "--stdout"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Cannot export merge commit 77840633fb11 as a patch
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_patch_import() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\nb\nc\n");
+    work_dir.run_jj(["commit", "-m", "base"]).success();
+    work_dir.write_file("file", "a\nB\nc\n");
+    work_dir.run_jj(["commit", "-m", "modify file"]).success();
+    work_dir.remove_file("file");
+    work_dir.write_file("renamed", "a\nB\nc\nd\n");
+    work_dir
+        .run_jj([
+            "describe",
+            "-m",
+            "rename file",
+            "--config=user.name=Someone Else",
+            "--config=user.email=someone@example.com",
+        ])
+        .success();
+    work_dir
+        .run_jj([
+            "metaedit",
+            "--update-author",
+            "--config=user.name=Someone Else",
+            "--config=user.email=someone@example.com",
+        ])
+        .success();
+    let output = work_dir.run_jj(["patch", "export", "-r", "subject(base)+::@", "--stdout"]);
+    std::fs::write(test_env.env_root().join("series.mbox"), output.stdout.raw()).unwrap();
+    let original_tip = work_dir
+        .run_jj(["log", "--no-graph", "-r@", "-Tcommit_id"])
+        .success()
+        .stdout
+        .into_raw();
+
+    // Apply the series onto the base revision
+    work_dir.run_jj(["new", "subject(base)"]).success();
+    let output = work_dir.run_jj(["patch", "import", "../series.mbox"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Imported 2 patches:
+      yostqsxw 328f1659 modify file
+      wmwvqwsz 41c6c4ef rename file
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @"
+    ○  wmwvqwszqlqy Someone Else 2001-02-03 04:05:09.000 +07:00 rename file
+    ○  yostqsxwqrlt Test User 2001-02-03 04:05:09.000 +07:00 modify file
+    @  vruxwmqvtpmx Test User 2001-02-03 04:05:14.000 +07:00
+    │ ○  kkmpptxzrspx Someone Else 2001-02-03 04:05:09.000 +07:00 rename file
+    │ ○  rlvkpnrzqnoo Test User 2001-02-03 04:05:09.000 +07:00 modify file
+    ├─╯
+    ○  qpvuntsmwlqt Test User 2001-02-03 04:05:08.000 +07:00 base
+    ◆  zzzzzzzzzzzz 1970-01-01 00:00:00.000 +00:00
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--from", &original_tip, "--to", "heads(@::)"]);
+    insta::assert_snapshot!(output, @"");
+
+    // The patch doesn't apply onto the root commit
+    let output = work_dir.run_jj(["patch", "import", "../series.mbox", "--onto", "root()"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Failed to apply patch 1: modify file
+    Caused by: Cannot patch file: file does not exist
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // The error message from the OS is platform-dependent
+    let output = work_dir.run_jj(["patch", "import", "nonexistent.patch"]);
+    let output = output.normalize_stderr_with(|s| s.lines().next().unwrap().to_owned() + "\n");
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Failed to read nonexistent.patch
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[must_use]
+fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
+    let template = r#"separate(" ", change_id.short(), author.name(), author.timestamp(), description.first_line()) ++ "\n""#;
+    work_dir.run_jj(["log", "-T", template])
+}
@@ -704,7 +704,7 @@ fn test_rebase_revision_onto_descendant() {
     let output = work_dir.run_jj(["op", "restore", &setup_opid]);
     insta::assert_snapshot!(output, @"
     ------- stderr -------
-    Restored to operation: 445e5af39d91 (2001-02-03 08:05:15) create bookmark merge pointing to commit 08c0951bf69d0362708a5223a78446d664823b50
+    Restored to operation: 945eff3eb457 (2001-02-03 08:05:15) create bookmark merge pointing to commit 08c0951bf69d0362708a5223a78446d664823b50
     Working copy  (@) now at: vruxwmqv 08c0951b merge | merge
     Parent commit (@-)      : royxmykx 6a7081ef b | b
     Parent commit (@-)      : zsuskuln 68fbc443 a | a
@@ -3076,6 +3076,57 @@ fn test_rebase_simplify_parents() {
     ");
 }
 
+#[test]
+fn test_rebase_strategy() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "a", &[], &[("file", "a\nb\n")]);
+    create_commit_with_files(&work_dir, "b", &["a"], &[("file", "a\nb1\n")]);
+    create_commit_with_files(&work_dir, "c", &["a"], &[("file", "a\nc1\n")]);
+    let setup_opid = work_dir.current_operation_id();
+
+    // "ours" takes the destination side
+    let output = work_dir.run_jj(["rebase", "-r", "c", "-o", "b", "--strategy=ours"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Rebased 1 commits to destination
+    Working copy  (@) now at: royxmykx 2a6ed0ac c | (empty) c
+    Parent commit (@-)      : zsuskuln ecc3576c b | b
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["file", "show", "-r", "c", "file"]), @"
+    a
+    b1
+    [EOF]
+    ");
+
+    // "theirs" takes the rebased side
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    work_dir
+        .run_jj(["rebase", "-r", "c", "-o", "b", "--strategy=theirs"])
+        .success();
+    insta::assert_snapshot!(work_dir.run_jj(["file", "show", "-r", "c", "file"]), @"
+    a
+    c1
+    [EOF]
+    ");
+
+    // "union" keeps the lines from both
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    work_dir
+        .run_jj(["rebase", "-r", "c", "-o", "b", "--strategy=union"])
+        .success();
+    insta::assert_snapshot!(work_dir.run_jj(["file", "show", "-r", "c", "file"]), @"
+    a
+    b1
+    c1
+    [EOF]
+    ");
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     let template = "bookmarks ++ surround(': ', '', parents.map(|c| c.bookmarks()))";
@@ -743,13 +743,13 @@ fn test_too_many_parents() -> TestResult {
     [EOF]
     ");
 
-    let output = work_dir.run_jj(["resolve"]);
+    // Only the builtin tool can resolve this kind of conflict
+    let output = work_dir.run_jj(["resolve", "--tool=:ours"]);
     insta::assert_snapshot!(output, @r#"
     ------- stderr -------
-    Hint: Using default editor ':builtin'; run `jj config set --user ui.merge-editor :builtin` to disable this message.
     Error: Failed to resolve conflicts
     Caused by: The conflict at "file" has 3 sides. At most 2 sides are supported.
-    Hint: Edit the conflict markers manually to resolve this.
+    Hint: Edit the conflict markers manually or use `jj resolve --tool=:builtin` to resolve this.
     [EOF]
     [exit status: 1]
     "#);
@@ -951,16 +951,17 @@ fn test_file_vs_dir() -> TestResult {
     file    2-sided conflict including a directory
     [EOF]
     ");
-    let output = work_dir.run_jj(["resolve"]);
+    // Only the builtin tool can resolve this kind of conflict
+    let output = work_dir.run_jj(["resolve", "--tool=:ours"]);
     insta::assert_snapshot!(output, @r#"
     ------- stderr -------
-    Hint: Using default editor ':builtin'; run `jj config set --user ui.merge-editor :builtin` to disable this message.
     Error: Failed to resolve conflicts
     Caused by: Only conflicts that involve normal files (not symlinks, etc.) are supported. Conflict summary for "file":
     Conflict:
       Removing file with id df967b96a579e45a18b8251732d16804b2e56a55 (rlvkpnrz 1792382a "base")
       Adding file with id 78981922613b2afb6025042ff6bd878ac1994e85 (zsuskuln 45537d53 "a")
       Adding tree with id 133bb38fc4e4bf6b551f1f04db7e48f04cac2877 (royxmykx 0950b959 "b")
+    Hint: Use `jj resolve --tool=:builtin` to pick one of the sides.
     [EOF]
     [exit status: 1]
     "#);
@@ -1005,10 +1006,10 @@ fn test_description_with_dir_and_deletion() -> TestResult {
     file    [38;5;1m3-sided[38;5;3m conflict including 1 deletion and [38;5;1ma directory[39m
     [EOF]
     ");
-    let output = work_dir.run_jj(["resolve"]);
+    // Only the builtin tool can resolve this kind of conflict
+    let output = work_dir.run_jj(["resolve", "--tool=:ours"]);
     insta::assert_snapshot!(output, @r#"
     ------- stderr -------
-    Hint: Using default editor ':builtin'; run `jj config set --user ui.merge-editor :builtin` to disable this message.
     Error: Failed to resolve conflicts
     Caused by: Only conflicts that involve normal files (not symlinks, etc.) are supported. Conflict summary for "file":
     Conflict:
@@ -1016,6 +1017,7 @@ fn test_description_with_dir_and_deletion() -> TestResult {
       Removing file with id df967b96a579e45a18b8251732d16804b2e56a55 (rlvkpnrz 1792382a "base")
       Adding file with id 61780798228d17af2d34fce4cfbdf35556832472 (zsuskuln 6c74ed1d "edit")
       Adding tree with id 133bb38fc4e4bf6b551f1f04db7e48f04cac2877 (royxmykx 09c02334 "dir")
+    Hint: Use `jj resolve --tool=:builtin` to pick one of the sides.
     [EOF]
     [exit status: 1]
     "#);
@@ -2314,3 +2316,152 @@ fn test_resolve_with_contents_of_side() -> TestResult {
     ");
     Ok(())
 }
+
+#[test]
+fn test_resolve_export_import_json() -> TestResult {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "base", &[], &[("file", "a\nb\nc\n")]);
+    create_commit_with_files(&work_dir, "left", &["base"], &[("file", "a1\nb\nc1\n")]);
+    create_commit_with_files(&work_dir, "right", &["base"], &[("file", "a2\nb\nc2\n")]);
+    create_commit_with_files(&work_dir, "conflict", &["left", "right"], &[]);
+    let setup_opid = work_dir.current_operation_id();
+
+    let output = work_dir.run_jj(["resolve", "--export-json", "-"]);
+    insta::assert_snapshot!(output, @r#"
+    {
+      "files": [
+        {
+          "path": "file",
+          "labels": {
+            "bases": [
+              "rlvkpnrz 64200d06 \"base\""
+            ],
+            "sides": [
+              "zsuskuln 9b62adcf \"left\"",
+              "royxmykx 0505bfb0 \"right\""
+            ]
+          },
+          "hunks": [
+            {
+              "conflict": {
+                "bases": [
+                  "a\n"
+                ],
+                "sides": [
+                  "a1\n",
+                  "a2\n"
+                ]
+              }
+            },
+            {
+              "resolved": "b\n"
+            },
+            {
+              "conflict": {
+                "bases": [
+                  "c\n"
+                ],
+                "sides": [
+                  "c1\n",
+                  "c2\n"
+                ]
+              }
+            }
+          ]
+        }
+      ]
+    }
+    [EOF]
+    "#);
+    let exported_path = test_env.env_root().join("conflicts.json");
+    work_dir
+        .run_jj(["resolve", "--export-json", exported_path.to_str().unwrap()])
+        .success();
+    assert_eq!(
+        std::fs::read_to_string(&exported_path)?,
+        output.stdout.raw()
+    );
+
+    // Resolve one of the conflicted hunks
+    let mut exported: serde_json::Value = serde_json::from_str(output.stdout.raw())?;
+    exported["files"][0]["hunks"][0]["resolved"] = "a12\n".into();
+    let partial_path = test_env.env_root().join("partial.json");
+    std::fs::write(&partial_path, serde_json::to_string(&exported)?)?;
+    let output = work_dir.run_jj(["resolve", "--import-json", partial_path.to_str().unwrap()]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Working copy  (@) now at: vruxwmqv 59405082 conflict | (conflict) conflict
+    Parent commit (@-)      : zsuskuln 9b62adcf left | left
+    Parent commit (@-)      : royxmykx 0505bfb0 right | right
+    Added 0 files, modified 1 files, removed 0 files
+    Warning: There are unresolved conflicts at these paths:
+    file    2-sided conflict
+    New conflicts appeared in 1 commits:
+      vruxwmqv 59405082 conflict | (conflict) conflict
+    Hint: To resolve the conflicts, start by creating a commit on top of
+    the conflicted commit:
+      jj new vruxwmqv
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file"), @r#"
+    a12
+    b
+    <<<<<<< conflict 1 of 1
+    %%%%%%% diff from: rlvkpnrz 64200d06 "base"
+    \\\\\\\        to: zsuskuln 9b62adcf "left"
+    -c
+    +c1
+    +++++++ royxmykx 0505bfb0 "right"
+    c2
+    >>>>>>> conflict 1 of 1 ends
+    "#);
+
+    // The remaining hunks don't match the exported conflict
+    let output = work_dir.run_jj(["resolve", "--import-json", partial_path.to_str().unwrap()]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Conflict in file has changed since it was exported
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Resolve all hunks
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    exported["files"][0]["hunks"][2]["resolved"] = "c12\n".into();
+    let resolved_path = test_env.env_root().join("resolved.json");
+    std::fs::write(&resolved_path, serde_json::to_string(&exported)?)?;
+    let output = work_dir.run_jj(["resolve", "--import-json", resolved_path.to_str().unwrap()]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Working copy  (@) now at: vruxwmqv f17edab3 conflict | conflict
+    Parent commit (@-)      : zsuskuln 9b62adcf left | left
+    Parent commit (@-)      : royxmykx 0505bfb0 right | right
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file"), @"
+    a12
+    b
+    c12
+    ");
+
+    // Invalid JSON
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    let invalid_path = test_env.env_root().join("invalid.json");
+    std::fs::write(&invalid_path, "{")?;
+    let output = work_dir.run_jj(["resolve", "--import-json", invalid_path.to_str().unwrap()]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Failed to parse $TEST_ENV/invalid.json
+    Caused by: EOF while parsing an object at line 1 column 1
+    [EOF]
+    [exit status: 1]
+    ");
+    Ok(())
+}
@@ -0,0 +1,272 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+#[cfg(unix)]
+use crate::common::create_commit;
+
+#[test]
+fn test_run_missing_command() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["run"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      <SHELL_COMMAND>
+
+    Usage: jj run <SHELL_COMMAND>
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_read_only_command() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+
+    let output = work_dir.run_jj([
+        "run",
+        "-j1",
+        "-r",
+        "a|b",
+        "cat a; if test -f b; then cat b; fi",
+    ]);
+    insta::assert_snapshot!(output, @"
+    a
+    a
+    b
+    [EOF]
+    ------- stderr -------
+    Ran on rlvkpnrz 7d980be7 a | a
+    Ran on zsuskuln 123b4d91 b | b
+    Modified 0 commits of 2 checked, rebased 0 descendant commits.
+    Nothing changed.
+    [EOF]
+    ");
+
+    // Nothing was rewritten
+    let output = work_dir.run_jj(["op", "log", "-n1", "-T", "description"]);
+    insta::assert_snapshot!(output, @"
+    @  create bookmark b pointing to commit 123b4d91f6e5e39bfed39bae3bacf9380dc79078
+    [EOF]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_modifies_files() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+
+    // Only "b" is run, "c" should be rebased on top of the modified "b"
+    let output = work_dir.run_jj(["run", "-r", "b", "echo generated > new-file"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Ran on zsuskuln 123b4d91 b | b
+    Modified 1 commits of 1 checked, rebased 1 descendant commits.
+    Working copy  (@) now at: royxmykx b6e42b2e c | c
+    Parent commit (@-)      : zsuskuln 5132dafe b | b
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-T", "description", "--summary"]);
+    insta::assert_snapshot!(output, @"
+    @  c
+    │  A c
+    ○  b
+    │  A b
+    │  A new-file
+    ○  a
+    │  A a
+    ◆
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r", "c", "new-file"]);
+    insta::assert_snapshot!(output, @"
+    generated
+    [EOF]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_stack_in_parallel() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+
+    // Uppercase every file, as a formatter would. Running on each commit
+    // independently must produce the same result as rewriting them in order.
+    let output = work_dir.run_jj([
+        "run",
+        "-j3",
+        "-r",
+        "a::c",
+        "for f in *; do tr a-z A-Z < $f > $f.tmp && mv $f.tmp $f; done",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Ran on rlvkpnrz 7d980be7 a | a
+    Ran on zsuskuln 123b4d91 b | b
+    Ran on royxmykx dffaa0d4 c | c
+    Modified 3 commits of 3 checked, rebased 0 descendant commits.
+    Working copy  (@) now at: royxmykx 2be716d2 c | c
+    Parent commit (@-)      : zsuskuln 409cbdc3 b | b
+    Added 0 files, modified 3 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "-r", "a::c", "--git"]);
+    insta::assert_snapshot!(output, @"
+    diff --git a/a b/a
+    new file mode 100644
+    index 0000000000..f70f10e4db
+    --- /dev/null
+    +++ b/a
+    @@ -0,0 +1,1 @@
+    +A
+    diff --git a/b b/b
+    new file mode 100644
+    index 0000000000..223b7836fb
+    --- /dev/null
+    +++ b/b
+    @@ -0,0 +1,1 @@
+    +B
+    diff --git a/c b/c
+    new file mode 100644
+    index 0000000000..3cc58df837
+    --- /dev/null
+    +++ b/c
+    @@ -0,0 +1,1 @@
+    +C
+    [EOF]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_failing_command() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+
+    // The changes of the failing command are discarded, the others are kept
+    let output = work_dir.run_jj(["run", "-j1", "-r", "a|b", "echo modified > a; test ! -f b"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Ran on rlvkpnrz 7d980be7 a | a
+    Ran on zsuskuln 123b4d91 b | b
+    Warning: Command `echo modified > a; test ! -f b` failed on revision 123b4d91f6e5 with exit status: 1
+    Modified 1 commits of 2 checked, rebased 1 descendant commits.
+    Working copy  (@) now at: zsuskuln 5e7b9c4b b | b
+    Parent commit (@-)      : rlvkpnrz 852008d8 a | a
+    Added 0 files, modified 1 files, removed 0 files
+    Error: Command `echo modified > a; test ! -f b` failed on 1 revisions
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_immutable() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_commit(&work_dir, "a", &[]);
+    test_env.add_config(r#"revset-aliases."immutable_heads()" = "a""#);
+
+    // Read-only commands can be run on immutable revisions
+    let output = work_dir.run_jj(["run", "-r", "a", "cat a"]);
+    insta::assert_snapshot!(output, @"
+    a
+    [EOF]
+    ------- stderr -------
+    Ran on rlvkpnrz 7d980be7 a | a
+    Modified 0 commits of 1 checked, rebased 0 descendant commits.
+    Nothing changed.
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["run", "-r", "a", "echo modified > a"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Ran on rlvkpnrz 7d980be7 a | a
+    Error: Commit 7d980be7a1d4 is immutable
+    Hint: Could not modify commit: rlvkpnrz 7d980be7 a | a
+    Hint: Immutable commits are used to protect shared history.
+    Hint: For more information, see:
+          - https://docs.jj-vcs.dev/latest/config/#set-of-immutable-commits
+          - `jj help -k config`, "Set of immutable commits"
+    Hint: This operation would rewrite 1 immutable commits.
+    [EOF]
+    [exit status: 1]
+    "#);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_clean() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_commit(&work_dir, "a", &[]);
+
+    // Untracked files are kept between runs
+    work_dir
+        .run_jj([
+            "run",
+            "-r",
+            "a",
+            "echo build > build.log; echo build.log > .gitignore",
+        ])
+        .success();
+    let output = work_dir.run_jj(["run", "-r", "a", "cat build.log"]);
+    insta::assert_snapshot!(output, @"
+    build
+    [EOF]
+    ------- stderr -------
+    Ran on rlvkpnrz eb8e6ff8 a | a
+    Modified 0 commits of 1 checked, rebased 0 descendant commits.
+    Nothing changed.
+    [EOF]
+    ");
+    assert!(work_dir.root().join(".jj").join("run").exists());
+
+    let output = work_dir.run_jj(["run", "--clean"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Removed working copies in $TEST_ENV/repo/.jj/run
+    [EOF]
+    ");
+    assert!(!work_dir.root().join(".jj").join("run").exists());
+}
@@ -359,6 +359,7 @@ fn test_show_with_no_template() {
     - email_placeholder
     - empty_commit_marker
     - git_format_patch_email_headers
+    - json
     - name_placeholder
     [EOF]
     [exit status: 2]
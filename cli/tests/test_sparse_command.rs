@@ -195,6 +195,227 @@ fn test_sparse_manage_patterns() {
     "#);
 }
 
+#[test]
+fn test_sparse_fileset_patterns() {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = test_env.set_up_fake_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("services/api/main.rs", "contents");
+    work_dir.write_file("services/api/tests/fixtures/data.json", "contents");
+    work_dir.write_file("services/web/main.rs", "contents");
+    work_dir.write_file("docs/README.md", "contents");
+
+    // Can set patterns as a fileset expression
+    let output = work_dir.run_jj([
+        "sparse",
+        "set",
+        "--fileset",
+        r#"services/api ~ glob:"services/api/**/fixtures/**""#,
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Added 0 files, modified 0 files, removed 3 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["sparse", "list"]);
+    insta::assert_snapshot!(output, @r#"
+    services/api ~ glob:"services/api/**/fixtures/**"
+    [EOF]
+    "#);
+    assert!(work_dir.root().join("services/api/main.rs").exists());
+    assert!(!work_dir.root().join("services/api/tests").exists());
+    assert!(!work_dir.root().join("services/web").exists());
+    assert!(!work_dir.root().join("docs").exists());
+
+    // Can add and remove directories
+    let output = work_dir.run_jj(["sparse", "set", "--add", "docs", "--remove", "services/api"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Added 1 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["sparse", "list"]);
+    insta::assert_snapshot!(output, @r#"
+    ((services/api ~ glob:"services/api/**/fixtures/**") | root:"docs") ~ (root:"services/api")
+    [EOF]
+    "#);
+    assert!(!work_dir.root().join("services").exists());
+    assert!(work_dir.root().join("docs/README.md").exists());
+
+    // Can edit the expression with editor
+    std::fs::write(&edit_script, "write\nservices/web | docs").unwrap();
+    let output = work_dir.run_jj(["sparse", "edit"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["sparse", "list"]);
+    insta::assert_snapshot!(output, @"
+    services/web | docs
+    [EOF]
+    ");
+
+    // Invalid expressions are rejected
+    let output = work_dir.run_jj(["sparse", "set", "--fileset", "services/api |"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Failed to parse fileset: Syntax error
+    Caused by:  --> 1:15
+      |
+    1 | services/api |
+      |               ^---
+      |
+      = expected `~` or <primary>
+    Hint: See https://docs.jj-vcs.dev/latest/filesets/ or use `jj help -k filesets` for filesets syntax and how to match file paths.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Cannot be combined with other pattern updates
+    let output = work_dir.run_jj(["sparse", "set", "--fileset", "docs", "--add", "services"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: the argument '--fileset <FILESET>' cannot be used with '--add <ADD>'
+
+    Usage: jj sparse set --fileset <FILESET>
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+
+    // Reset goes back to plain prefixes
+    let output = work_dir.run_jj(["sparse", "reset"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Added 2 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["sparse", "list"]);
+    insta::assert_snapshot!(output, @"
+    .
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_sparse_profile() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file(
+        ".jjsparse/backend.toml",
+        "include = ['common']\npaths = ['services/api']\n",
+    );
+    work_dir.write_file(".jjsparse/common.toml", "paths = ['docs']\n");
+    work_dir.write_file("services/api/main.rs", "contents");
+    work_dir.write_file("services/web/main.rs", "contents");
+    work_dir.write_file("docs/README.md", "contents");
+    work_dir.run_jj(["commit", "-m", "first"]).success();
+
+    // Can use a profile with includes
+    let output = work_dir.run_jj(["sparse", "profile", "use", "backend"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["sparse", "list"]);
+    insta::assert_snapshot!(output, @"
+    .jjsparse
+    docs
+    services/api
+    [EOF]
+    ------- stderr -------
+    Patterns from sparse profile backend:
+    [EOF]
+    ");
+    assert!(!work_dir.root().join("services/web").exists());
+
+    // The profile is re-applied when it changes on checkout
+    work_dir.write_file(
+        ".jjsparse/backend.toml",
+        "include = ['common']\npaths = ['services']\n",
+    );
+    let output = work_dir.run_jj(["new"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Working copy  (@) now at: mzvwutvl baabfc60 (empty) (no description set)
+    Parent commit (@-)      : rlvkpnrz 44f7dd70 (no description set)
+    Updated sparse patterns from profile backend
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    assert!(work_dir.root().join("services/web/main.rs").exists());
+    let output = work_dir.run_jj(["new", "subject(first)"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Working copy  (@) now at: royxmykx ca08df40 (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm 03df74b1 first
+    Updated sparse patterns from profile backend
+    Added 0 files, modified 1 files, removed 1 files
+    [EOF]
+    ");
+    assert!(!work_dir.root().join("services/web").exists());
+
+    // Nothing happens if the profile didn't change
+    let output = work_dir.run_jj(["new"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Working copy  (@) now at: yqosqzyt 5fd62a05 (empty) (no description set)
+    Parent commit (@-)      : royxmykx ca08df40 (empty) (no description set)
+    [EOF]
+    ");
+
+    // The current patterns are kept if the profile can't be resolved
+    work_dir.remove_file(".jjsparse/backend.toml");
+    let output = work_dir.run_jj(["new"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Working copy  (@) now at: vruxwmqv 9d820257 (empty) (no description set)
+    Parent commit (@-)      : yqosqzyt 6e4536d0 (no description set)
+    Warning: Sparse profile backend not found
+    Hint: The working copy keeps the patterns from the previous version of the profile.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["sparse", "list"]);
+    insta::assert_snapshot!(output, @"
+    .jjsparse
+    docs
+    services/api
+    [EOF]
+    ------- stderr -------
+    Patterns from sparse profile backend:
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["sparse", "profile", "use", "backend"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Sparse profile backend not found
+    Hint: Sparse profiles are read from `.jjsparse/<name>.toml` in the working-copy commit.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Setting patterns stops using the profile
+    let output = work_dir.run_jj(["sparse", "reset"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["sparse", "list"]);
+    insta::assert_snapshot!(output, @"
+    .
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_sparse_editor_avoids_unc() -> TestResult {
     use std::path::PathBuf;
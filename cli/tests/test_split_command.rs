@@ -419,16 +419,16 @@ fn test_split_with_descendants() -> TestResult {
     insta::assert_snapshot!(evolog_1, @"
     ○  qpvuntsm test.user@example.com 2001-02-03 08:05:12 74306e35
     │  Add file1
-    │  -- operation ec53c39a72a8 split commit 1d2499e72cefc8a2b87ebb47569140857b96189f
+    │  -- operation b867816ba998 split commit 1d2499e72cefc8a2b87ebb47569140857b96189f
     ○  qpvuntsm/1 test.user@example.com 2001-02-03 08:05:08 1d2499e7 (hidden)
     │  Add file1 & file2
-    │  -- operation 8185a834cefe commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
+    │  -- operation a5cd7a6de1d8 commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
     ○  qpvuntsm/2 test.user@example.com 2001-02-03 08:05:08 f5700f8e (hidden)
     │  (no description set)
-    │  -- operation d36e968aa414 snapshot working copy
+    │  -- operation 6d5cd26b7f5d snapshot working copy
     ○  qpvuntsm/3 test.user@example.com 2001-02-03 08:05:07 e8849ae1 (hidden)
        (empty) (no description set)
-       -- operation 90267f31f904 add workspace 'default'
+       -- operation e39dc288903d add workspace 'default'
     [EOF]
     ");
 
@@ -438,16 +438,16 @@ fn test_split_with_descendants() -> TestResult {
     insta::assert_snapshot!(evolog_2, @"
     ○  royxmykx test.user@example.com 2001-02-03 08:05:12 0a37745e
     │  Add file2
-    │  -- operation ec53c39a72a8 split commit 1d2499e72cefc8a2b87ebb47569140857b96189f
+    │  -- operation b867816ba998 split commit 1d2499e72cefc8a2b87ebb47569140857b96189f
     ○  qpvuntsm/1 test.user@example.com 2001-02-03 08:05:08 1d2499e7 (hidden)
     │  Add file1 & file2
-    │  -- operation 8185a834cefe commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
+    │  -- operation a5cd7a6de1d8 commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
     ○  qpvuntsm/2 test.user@example.com 2001-02-03 08:05:08 f5700f8e (hidden)
     │  (no description set)
-    │  -- operation d36e968aa414 snapshot working copy
+    │  -- operation 6d5cd26b7f5d snapshot working copy
     ○  qpvuntsm/3 test.user@example.com 2001-02-03 08:05:07 e8849ae1 (hidden)
        (empty) (no description set)
-       -- operation 90267f31f904 add workspace 'default'
+       -- operation e39dc288903d add workspace 'default'
     [EOF]
     ");
     Ok(())
@@ -575,13 +575,13 @@ fn test_split_parallel_no_descendants() -> TestResult {
     insta::assert_snapshot!(evolog_1, @"
     ○  qpvuntsm test.user@example.com 2001-02-03 08:05:09 7bcd474c
     │  TESTED=TODO
-    │  -- operation 90307a87dc57 split commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
+    │  -- operation 7b9cdc9d8406 split commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
     ○  qpvuntsm/1 test.user@example.com 2001-02-03 08:05:08 f5700f8e (hidden)
     │  (no description set)
-    │  -- operation f07456070284 snapshot working copy
+    │  -- operation 03684cf61546 snapshot working copy
     ○  qpvuntsm/2 test.user@example.com 2001-02-03 08:05:07 e8849ae1 (hidden)
        (empty) (no description set)
-       -- operation 90267f31f904 add workspace 'default'
+       -- operation e39dc288903d add workspace 'default'
     [EOF]
     ");
 
@@ -591,13 +591,13 @@ fn test_split_parallel_no_descendants() -> TestResult {
     insta::assert_snapshot!(evolog_2, @"
     @  kkmpptxz test.user@example.com 2001-02-03 08:05:09 431886f6
     │  (no description set)
-    │  -- operation 90307a87dc57 split commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
+    │  -- operation 7b9cdc9d8406 split commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
     ○  qpvuntsm/1 test.user@example.com 2001-02-03 08:05:08 f5700f8e (hidden)
     │  (no description set)
-    │  -- operation f07456070284 snapshot working copy
+    │  -- operation 03684cf61546 snapshot working copy
     ○  qpvuntsm/2 test.user@example.com 2001-02-03 08:05:07 e8849ae1 (hidden)
        (empty) (no description set)
-       -- operation 90267f31f904 add workspace 'default'
+       -- operation e39dc288903d add workspace 'default'
     [EOF]
     ");
     Ok(())
@@ -1293,6 +1293,168 @@ fn test_split_with_message() -> TestResult {
     Ok(())
 }
 
+#[test]
+fn test_split_into_parts() -> TestResult {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("README", "readme\n");
+    work_dir.write_file("docs/guide.md", "guide\n");
+    work_dir.write_file("src/lib.rs", "lib\n");
+    work_dir.write_file("src/main.rs", "main\n");
+    work_dir.run_jj(["describe", "-m", "generated"]).success();
+    work_dir.run_jj(["new", "-m", "child"]).success();
+    let setup_opid = work_dir.current_operation_id();
+
+    // Parts are stacked in order, with the remaining changes last. Changes
+    // matching several parts go into the first of them.
+    let output = work_dir.run_jj([
+        "split",
+        "-r@-",
+        "--part",
+        "glob:docs/**",
+        "--part",
+        "src/main.rs",
+        "--part",
+        "glob:src/**",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Rebased 1 descendant commits
+    Part 1           : qpvuntsm da509fcf generated
+    Part 2           : zsuskuln 975b2514 generated
+    Part 3           : rzvqmyuk f69fc393 generated
+    Remaining changes: luqtuptr e0d5ee6e generated
+    Working copy  (@) now at: kkmpptxz 2a4da911 (empty) child
+    Parent commit (@-)      : luqtuptr e0d5ee6e generated
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_with_summary(&work_dir), @"
+    @  kkmpptxzrspx child
+    ○  luqtuptruqys generated
+    │  A README
+    ○  rzvqmyukvvwz generated
+    │  A src/lib.rs
+    ○  zsuskulnrvyr generated
+    │  A src/main.rs
+    ○  qpvuntsmwlqt generated
+    │  A docs/guide.md
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    // A part matching no changes creates an empty revision
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    let output = work_dir.run_jj([
+        "split",
+        "-r@-",
+        "-m",
+        "part",
+        "--part",
+        "nonexistent",
+        "--part",
+        "README",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Warning: No matching entries for paths: nonexistent
+    Warning: No changes matched part 1, so its revision will be empty
+    Rebased 1 descendant commits
+    Part 1           : qpvuntsm f3d57421 (empty) part
+    Part 2           : yqosqzyt b03eb03a part
+    Remaining changes: spxsnpux d024008e generated
+    Working copy  (@) now at: kkmpptxz 1137a7ac (empty) child
+    Parent commit (@-)      : spxsnpux d024008e generated
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_with_summary(&work_dir), @"
+    @  kkmpptxzrspx child
+    ○  spxsnpuxtvxq generated
+    │  A docs/guide.md
+    │  A src/lib.rs
+    │  A src/main.rs
+    ○  yqosqzytrlsw part
+    │  A README
+    ○  qpvuntsmwlqt part
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    // Parts can't be combined with interactive selection
+    let output = work_dir.run_jj(["split", "--part", "README", "README"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: the argument '--part <FILESET>' cannot be used with '[FILESETS]...'
+
+    Usage: jj split --part <FILESET> [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+    Ok(())
+}
+
+#[test]
+fn test_split_by_directory() -> TestResult {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("README", "readme\n");
+    work_dir.write_file("src/lib.rs", "lib\n");
+    work_dir.write_file("src/cli/main.rs", "main\n");
+    work_dir.write_file("docs/guide.md", "guide\n");
+    work_dir.run_jj(["new", "-m", "generated"]).success();
+    work_dir.write_file("README", "updated readme\n");
+    work_dir.remove_file("docs/guide.md");
+    work_dir.write_file("src/lib.rs", "updated lib\n");
+    work_dir.write_file("src/cli/main.rs", "updated main\n");
+    work_dir.write_file("src/cli/args.rs", "args\n");
+
+    let output = work_dir.run_jj(["split", "--by-directory"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Part 1           : rlvkpnrz ea183caf generated
+    Part 2           : kkmpptxz 42518875 generated
+    Part 3           : pmmvwywv dd33d2b8 generated
+    Remaining changes: yxszmlut 74f95efe generated
+    Working copy  (@) now at: yxszmlut 74f95efe generated
+    Parent commit (@-)      : pmmvwywv dd33d2b8 generated
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_with_summary(&work_dir), @"
+    @  yxszmlutytwo generated
+    │  M README
+    ○  pmmvwywvzvvn generated
+    │  A src/cli/args.rs
+    │  M src/cli/main.rs
+    ○  kkmpptxzrspx generated
+    │  M src/lib.rs
+    ○  rlvkpnrzqnoo generated
+    │  D docs/guide.md
+    ○  qpvuntsmwlqtA README
+    │  A docs/guide.md
+    │  A src/cli/main.rs
+    │  A src/lib.rs
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    // Nothing to split if there are only changes at the root
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("README", "readme\n");
+    let output = work_dir.run_jj(["split", "--by-directory"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: No changes in subdirectories to split into separate revisions
+    [EOF]
+    [exit status: 1]
+    ");
+    Ok(())
+}
+
 #[test]
 fn test_split_move_first_commit() -> TestResult {
     let test_env = TestEnvironment::default();
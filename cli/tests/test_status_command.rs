@@ -41,6 +41,34 @@ fn test_status_copies() {
     ");
 }
 
+#[test]
+fn test_status_json() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"snapshot.auto-track = "none()""#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "1");
+    work_dir.write_file("rename-source", "rename");
+    work_dir
+        .run_jj(["file", "track", "file", "rename-source"])
+        .success();
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file", "2");
+    work_dir.remove_file("rename-source");
+    work_dir.write_file("rename-target", "rename");
+    work_dir.write_file("untracked", "...");
+    work_dir
+        .run_jj(["file", "track", "rename-target"])
+        .success();
+
+    let output = work_dir.run_jj(["status", "--output=json"]);
+    insta::assert_snapshot!(output, @r#"
+    {"working_copy":{"commit_id":"82b0f8f408a560dcee3a671a066f6d1f8248a8da","parents":["f2a49b5b97892f906e35f1df77f99a7d4842d111"],"change_id":"kkmpptxzrspxrzommnulwmwkkqwworpl","description":"","author":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:10+07:00"},"committer":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:11+07:00"}},"parents":[{"commit_id":"f2a49b5b97892f906e35f1df77f99a7d4842d111","parents":["0000000000000000000000000000000000000000"],"change_id":"qpvuntsmwlqtpsluzzsnyyzlmlwvmlnu","description":"","author":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:09+07:00"},"committer":{"name":"Test User","email":"test.user@example.com","timestamp":"2001-02-03T04:05:09+07:00"}}],"changes":[{"status":"modified","path":"file","source":null},{"status":"renamed","path":"rename-target","source":"rename-source"}],"untracked":["untracked"],"conflicts":[],"conflicted_bookmarks":[],"conflicted_remote_bookmarks":[]}
+    [EOF]
+    "#);
+}
+
 #[test]
 fn test_status_merge() {
     let test_env = TestEnvironment::default();
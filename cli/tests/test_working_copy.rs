@@ -363,7 +363,7 @@ fn test_conflict_marker_length_stored_in_working_copy() -> TestResult {
     // Working copy should contain conflict marker length
     let output = work_dir.run_jj(["debug", "local-working-copy"]);
     insta::assert_snapshot!(output.normalize_stdout_with(redact_output), @r#"
-    Current operation: OperationId("072342f0123bbb53824fe316de828240ec981d9d11608e8a31c12253e99cd504d5a0fae3851f6a83941863a85b68954c90b7d6ad2fd4f7da469b7d8a6482c003")
+    Current operation: OperationId("03fde379ea4a6c7267f99f342c6ceb0a33479432ac84cf561ec672b223e27d8deb3ba598665e7f0a50c757fc15a9900e6973b99a7cd3df0c5ac6cb5ccd9adbf7")
     Current tree: MergedTree { tree_ids: Conflicted([TreeId("381273b50cf73f8c81b3f1502ee89e9bbd6c1518"), TreeId("771f3d31c4588ea40a8864b2a981749888e596c2"), TreeId("f56b8223da0dab22b03b8323ced4946329aeb4e0")]), labels: Labeled(["rlvkpnrz ccf9527c \"side-a\"", "qpvuntsm 2205b3ac \"base\"", "zsuskuln d7acaf48 \"side-b\""]), .. }
     Normal { exec_bit: ExecBit(false) }           313 <timestamp> Some(MaterializedConflictData { conflict_marker_len: 11 }) "file"
     [EOF]
@@ -426,7 +426,7 @@ fn test_conflict_marker_length_stored_in_working_copy() -> TestResult {
     // Working copy should still contain conflict marker length
     let output = work_dir.run_jj(["debug", "local-working-copy"]);
     insta::assert_snapshot!(output.normalize_stdout_with(redact_output), @r#"
-    Current operation: OperationId("19bcba55afcdec47b31cf0217b2a4b20dd9d90fe7dd7b4cdf0c52b74735c5407a8fddd1d3b43f37c09223b8b8053289f49bcba58c0af68869f891f582460ad4a")
+    Current operation: OperationId("27be51e35a02b5b526684006790fcef67a631d40e94a2d7adc5cdb09ae83d48f9eb6d1b93d36452c09196e2026d2f7337d32cac42e0767b5a866bd2d90bc204c")
     Current tree: MergedTree { tree_ids: Conflicted([TreeId("381273b50cf73f8c81b3f1502ee89e9bbd6c1518"), TreeId("771f3d31c4588ea40a8864b2a981749888e596c2"), TreeId("3329c18c95f7b7a55c278c2259e9c4ce711fae59")]), labels: Labeled(["rlvkpnrz ccf9527c \"side-a\"", "qpvuntsm 2205b3ac \"base\"", "zsuskuln d7acaf48 \"side-b\""]), .. }
     Normal { exec_bit: ExecBit(false) }           274 <timestamp> Some(MaterializedConflictData { conflict_marker_len: 11 }) "file"
     [EOF]
@@ -461,7 +461,7 @@ fn test_conflict_marker_length_stored_in_working_copy() -> TestResult {
     // working copy
     let output = work_dir.run_jj(["debug", "local-working-copy"]);
     insta::assert_snapshot!(output.normalize_stdout_with(redact_output), @r#"
-    Current operation: OperationId("ed741c71397533330b01669ac13c3407ed89ba782a65ef038cefef27f95f3e2a513f0d3b04a2f96e79ac8b9e050ea080b06dd6ad2b10fc4e3f6978100b8ec8c9")
+    Current operation: OperationId("c3508fd3f287d136a73e7eb67f456d4498ce3bbf3382a770213bbfb91661ce6a6ca5149b7bed2b4ed169c85dda985ed3eaf7b5af9438bc4a52f415306715c302")
     Current tree: MergedTree { tree_ids: Resolved(TreeId("6120567b3cb2472d549753ed3e4b84183d52a650")), labels: Unlabeled, .. }
     Normal { exec_bit: ExecBit(false) }           130 <timestamp> None "file"
     [EOF]
@@ -527,12 +527,11 @@ fn test_submodule_ignored() {
 
     // This should be empty. We shouldn't track the submodule itself.
     let output = work_dir.run_jj(["diff", "--summary"]);
-    insta::assert_snapshot!(output, @r#"
+    insta::assert_snapshot!(output, @"
     ------- stderr -------
-    ignoring git submodule at "sub"
     Done importing changes from the underlying Git repo.
     [EOF]
-    "#);
+    ");
 
     // Switch to a historical commit before the submodule was checked in.
     work_dir.run_jj(["prev"]).success();
@@ -542,6 +541,136 @@ fn test_submodule_ignored() {
     insta::assert_snapshot!(output, @"");
 }
 
+#[test]
+fn test_submodule_checkout_and_snapshot() {
+    let test_env = TestEnvironment::default();
+
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "submodule"])
+        .success();
+    let submodule_dir = test_env.work_dir("submodule");
+    submodule_dir.write_file("sub", "sub1\n");
+    submodule_dir.run_jj(["commit", "-m", "First"]).success();
+    submodule_dir.write_file("sub", "sub2\n");
+    submodule_dir.run_jj(["commit", "-m", "Second"]).success();
+
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let work_dir = test_env.work_dir("repo");
+    let run_git = |args: &[&str]| {
+        let mut full_args = vec![
+            "util",
+            "exec",
+            "--",
+            "git",
+            "-c",
+            "protocol.file.allow=always",
+            "-c",
+            "user.email=test@example.com",
+            "-c",
+            "user.name=Test user",
+        ];
+        full_args.extend(args);
+        // Fix the commit dates so the imported commit IDs are stable
+        work_dir
+            .run_jj_with(|cmd| {
+                cmd.args(full_args)
+                    .env("GIT_AUTHOR_DATE", "2001-02-03T04:05:06+07:00")
+                    .env("GIT_COMMITTER_DATE", "2001-02-03T04:05:06+07:00")
+            })
+            .success();
+    };
+    // The URL is relative to the superproject so the tree is stable
+    run_git(&["submodule", "--quiet", "add", "../submodule", "sub"]);
+    run_git(&["commit", "--quiet", "-m", "Add submodule"]);
+    work_dir.run_jj(["new"]).success();
+
+    // Moving the submodule HEAD is recorded in the working copy
+    run_git(&["-C", "sub", "checkout", "--quiet", "HEAD~"]);
+    let output = work_dir.run_jj(["diff", "--git"]);
+    insta::assert_snapshot!(output, @"
+    diff --git a/sub b/sub
+    index 5d28d5b26a..8999a4fbea 160000
+    --- a/sub
+    +++ b/sub
+    @@ -1,1 +1,1 @@
+    -Subproject commit 5d28d5b26a5bf30b18b0bf1981eae98a4d9d7227
+    +Subproject commit 8999a4fbeaf58e2fd153ddc9f2406853301216ca
+    [EOF]
+    ");
+
+    // Submodules aren't checked out by default
+    let op_id = work_dir
+        .run_jj(["op", "log", "--no-graph", "-n1", "-Tid"])
+        .success()
+        .stdout
+        .into_raw();
+    work_dir.run_jj(["new", "@--"]).success();
+    insta::assert_snapshot!(work_dir.read_file("sub/sub"), @"sub1");
+    work_dir.run_jj(["op", "restore", &op_id]).success();
+
+    // Checking out another commit updates the submodule
+    test_env.add_config("git.check-out-submodules = true");
+    work_dir.run_jj(["new", "@--"]).success();
+    insta::assert_snapshot!(work_dir.read_file("sub/sub"), @"sub2");
+
+    // A missing submodule is cloned into the submodule store
+    work_dir.run_jj(["new", "root()"]).success();
+    std::fs::remove_dir_all(work_dir.root().join("sub")).unwrap();
+    work_dir
+        .run_jj(["new", r#"description(substring:"Add submodule")"#])
+        .success();
+    insta::assert_snapshot!(work_dir.read_file("sub/sub"), @"sub2");
+    assert!(
+        work_dir
+            .root()
+            .join(".jj/repo/submodule_store/repos/sub")
+            .is_dir()
+    );
+    let output = work_dir.run_jj(["diff", "--summary"]);
+    insta::assert_snapshot!(output, @"");
+
+    // Another workspace doesn't share HEAD and index with the first one
+    work_dir
+        .run_jj([
+            "workspace",
+            "add",
+            "../second",
+            "-r",
+            r#"description(substring:"Add submodule")"#,
+        ])
+        .success();
+    let second_dir = test_env.work_dir("second");
+    insta::assert_snapshot!(second_dir.read_file("sub/sub"), @"sub2");
+    assert_ne!(
+        work_dir.read_file("sub/.git"),
+        second_dir.read_file("sub/.git")
+    );
+    work_dir.run_jj(["workspace", "forget", "second"]).success();
+
+    // A submodule that can't be fetched is reported, but the rest of the
+    // working copy is updated
+    work_dir.run_jj(["new", "root()"]).success();
+    std::fs::remove_dir_all(work_dir.root().join("sub")).unwrap();
+    std::fs::remove_dir_all(work_dir.root().join(".jj/repo/submodule_store/repos/sub")).unwrap();
+    std::fs::rename(
+        test_env.env_root().join("submodule"),
+        test_env.env_root().join("moved"),
+    )
+    .unwrap();
+    let output = work_dir.run_jj(["new", r#"description(substring:"Add submodule")"#]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Working copy  (@) now at: nmzmmopx a772c105 (empty) (no description set)
+    Parent commit (@-)      : rturskzz aaffce89 master | Add submodule
+    Added 2 files, modified 0 files, removed 0 files
+    Warning: Failed to check out submodule sub: Git command failed with exit status: 128: fatal: repository '$TEST_ENV/submodule' does not exist
+    [EOF]
+    ");
+    assert!(work_dir.root().join(".gitmodules").is_file());
+}
+
 #[test]
 fn test_snapshot_jjconflict_trees() -> TestResult {
     let test_env = TestEnvironment::default();
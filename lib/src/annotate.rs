@@ -44,6 +44,7 @@ use crate::files::FileMergeHunkLevel;
 use crate::fileset::FilesetExpression;
 use crate::graph::GraphEdge;
 use crate::merge::SameChange;
+use crate::merge_driver::MergeDrivers;
 use crate::merged_tree::MergedTree;
 use crate::repo::Repo;
 use crate::repo_path::RepoPath;
@@ -54,6 +55,7 @@ use crate::revset::RevsetExpression;
 use crate::revset::RevsetFilterPredicate;
 use crate::store::Store;
 use crate::tree_merge::MergeOptions;
+use crate::tree_merge::MergeStrategy;
 
 /// Annotation results for a specific file
 #[derive(Clone, Debug)]
@@ -456,6 +458,8 @@ async fn get_file_contents(
                 merge: MergeOptions {
                     hunk_level: FileMergeHunkLevel::Line,
                     same_change: SameChange::Accept,
+                    merge_drivers: MergeDrivers::default(),
+                    strategy: MergeStrategy::Conflict,
                 },
             };
             Ok(materialize_merge_result_to_bytes(
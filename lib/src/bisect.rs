@@ -44,7 +44,7 @@ pub enum BisectionError {
 
 /// Indicates whether a given commit was good, bad, or if it could not be
 /// determined.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Evaluation {
     /// The commit was good
     Good,
@@ -207,6 +207,32 @@ impl<'repo> Bisector<'repo> {
         Ok(self.candidates().evaluate(self.repo)?)
     }
 
+    /// Finds up to `count` commits which can be evaluated in parallel.
+    ///
+    /// The commits are spread evenly over the remaining candidates, so the
+    /// range is split into `count + 1` parts once all of them have been
+    /// evaluated. Returns an empty list if there are no more commits to
+    /// evaluate, in which case [`Self::next_step()`] returns the result.
+    pub async fn next_candidates(&self, count: usize) -> Result<Vec<Commit>, BisectionError> {
+        if self.aborted {
+            return Ok(vec![]);
+        }
+        let candidate_ids: Vec<CommitId> = self
+            .candidates()
+            .evaluate(self.repo)?
+            .stream()
+            .try_collect()
+            .await?;
+        let num_candidates = candidate_ids.len();
+        let count = count.min(num_candidates);
+        let mut commits = Vec::with_capacity(count);
+        for i in 1..=count {
+            let commit_id = &candidate_ids[i * num_candidates / (count + 1)];
+            commits.push(self.repo.store().get_commit_async(commit_id).await?);
+        }
+        Ok(commits)
+    }
+
     /// Find the next commit to evaluate, or determine that there are no more
     /// steps.
     pub async fn next_step(&mut self) -> Result<NextStep, BisectionError> {
@@ -39,6 +39,7 @@ use crate::merged_tree::MergedTree;
 use crate::repo::Repo;
 use crate::rewrite::merge_commit_trees;
 use crate::signing::SignResult;
+use crate::signing::Signer;
 use crate::signing::Verification;
 use crate::store::Store;
 
@@ -243,6 +244,49 @@ pub fn conflict_label_for_commits(commits: &[Commit]) -> String {
     }
 }
 
+/// Verifies the signatures of multiple commits at once. Returns one result per
+/// commit, which is `None` if the commit isn't signed.
+///
+/// The verifications are cached, so `Commit::verification()` of these commits
+/// is cheap afterwards. All commits must belong to the same store.
+pub fn verify_commit_signatures(commits: &[Commit]) -> Vec<SignResult<Option<Verification>>> {
+    verify_commit_signatures_with(commits, Signer::verify_batch)
+}
+
+/// Like [`verify_commit_signatures()`], but verifies the signatures again
+/// instead of using and updating the cached verifications.
+pub fn verify_commit_signatures_uncached(
+    commits: &[Commit],
+) -> Vec<SignResult<Option<Verification>>> {
+    verify_commit_signatures_with(commits, Signer::verify_batch_uncached)
+}
+
+fn verify_commit_signatures_with(
+    commits: &[Commit],
+    verify_batch: impl FnOnce(&Signer, &[(&CommitId, &[u8], &[u8])]) -> Vec<SignResult<Verification>>,
+) -> Vec<SignResult<Option<Verification>>> {
+    let Some(first) = commits.first() else {
+        return vec![];
+    };
+    let signatures = commits
+        .iter()
+        .filter_map(|commit| {
+            let sig = commit.data.secure_sig.as_ref()?;
+            Some((&commit.id, &*sig.data, &*sig.sig))
+        })
+        .collect_vec();
+    let mut results = verify_batch(first.store.signer(), &signatures).into_iter();
+    commits
+        .iter()
+        .map(|commit| {
+            commit
+                .is_signed()
+                .then(|| results.next().unwrap())
+                .transpose()
+        })
+        .collect()
+}
+
 pub(crate) async fn is_backend_commit_empty(
     repo: &dyn Repo,
     store: &Arc<Store>,
@@ -1083,7 +1083,29 @@ pub async fn update_from_content(
         let file_id = store.write_file(path, &mut &content[..]).await?;
         return Ok(Merge::normal(file_id));
     };
+    update_from_hunks(file_ids, store, path, &hunks).await
+}
 
+/// Concatenates partially-resolved `hunks` and returns an updated version of
+/// `file_ids` with the new contents. The conflict hunks must have the same
+/// arity as the simplified `file_ids`. If all hunks are resolved, a single
+/// resolved `FileId` will be returned.
+pub async fn update_from_hunks(
+    file_ids: &Merge<Option<FileId>>,
+    store: &Store,
+    path: &RepoPath,
+    hunks: &[Merge<BString>],
+) -> BackendResult<Merge<Option<FileId>>> {
+    if hunks.iter().all(|hunk| hunk.is_resolved()) {
+        let mut content = vec![];
+        for hunk in hunks {
+            content.extend_from_slice(hunk.first());
+        }
+        let file_id = store.write_file(path, &mut content.as_slice()).await?;
+        return Ok(Merge::normal(file_id));
+    }
+
+    let simplified_file_ids = file_ids.simplify();
     let mut contents = simplified_file_ids.map(|_| vec![]);
     for hunk in hunks {
         if let Some(slice) = hunk.as_resolved() {
@@ -1091,8 +1113,8 @@ pub async fn update_from_content(
                 content.extend_from_slice(slice);
             }
         } else {
-            for (content, slice) in zip(&mut contents, hunk) {
-                content.extend(Vec::from(slice));
+            for (content, slice) in zip(&mut contents, hunk.iter()) {
+                content.extend_from_slice(slice);
             }
         }
     }
@@ -1134,6 +1156,8 @@ mod tests {
 
     use super::*;
     use crate::files::FileMergeHunkLevel;
+    use crate::merge_driver::MergeDrivers;
+    use crate::tree_merge::MergeStrategy;
 
     #[test]
     fn test_resolve_file_executable() {
@@ -1363,6 +1387,8 @@ mod tests {
             merge: MergeOptions {
                 hunk_level: FileMergeHunkLevel::Line,
                 same_change: SameChange::Accept,
+                merge_drivers: MergeDrivers::default(),
+                strategy: MergeStrategy::Conflict,
             },
         };
         let actual_contents = String::from_utf8(
@@ -1533,8 +1533,10 @@ mod tests {
     use crate::default_index::readonly::FieldLengths;
     use crate::files::FileMergeHunkLevel;
     use crate::merge::SameChange;
+    use crate::merge_driver::MergeDrivers;
     use crate::str_util::StringPattern;
     use crate::tests::TestResult;
+    use crate::tree_merge::MergeStrategy;
 
     const TEST_FIELD_LENGTHS: FieldLengths = FieldLengths {
         commit_id: 3,
@@ -1922,6 +1924,8 @@ mod tests {
         let options = MergeOptions {
             hunk_level: FileMergeHunkLevel::Line,
             same_change: SameChange::Accept,
+            merge_drivers: MergeDrivers::default(),
+            strategy: MergeStrategy::Conflict,
         };
         let diff = |needle: &str| {
             let matcher = StringPattern::substring(needle).to_matcher();
@@ -1987,6 +1991,8 @@ mod tests {
         let options = MergeOptions {
             hunk_level: FileMergeHunkLevel::Line,
             same_change: SameChange::Accept,
+            merge_drivers: MergeDrivers::default(),
+            strategy: MergeStrategy::Conflict,
         };
         let diff = |needle: &str| {
             let matcher = StringPattern::substring(needle).to_matcher();
@@ -2077,6 +2083,8 @@ mod tests {
         let options = MergeOptions {
             hunk_level: FileMergeHunkLevel::Line,
             same_change: SameChange::Accept,
+            merge_drivers: MergeDrivers::default(),
+            strategy: MergeStrategy::Conflict,
         };
         let diff = |needle: &str| {
             let matcher = StringPattern::substring(needle).to_matcher();
@@ -2151,6 +2159,8 @@ mod tests {
         let options = MergeOptions {
             hunk_level: FileMergeHunkLevel::Line,
             same_change: SameChange::Accept,
+            merge_drivers: MergeDrivers::default(),
+            strategy: MergeStrategy::Conflict,
         };
         let diff = |needle: &str| {
             let matcher = StringPattern::substring(needle).to_matcher();
@@ -14,38 +14,166 @@
 
 #![expect(missing_docs)]
 
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Component;
 use std::path::Path;
 use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
 
+use crate::backend::CommitId;
+use crate::config::ConfigGetError;
+use crate::file_util::IoResultExt as _;
+use crate::object_id::ObjectId as _;
+use crate::settings::UserSettings;
 use crate::submodule_store::SubmoduleStore;
+use crate::submodule_store::SubmoduleStoreError;
 
+/// Stores a bare clone of each submodule repository in `<store>/repos/<name>`,
+/// using the Git executable to clone, fetch, and check out. Submodule
+/// directories are linked worktrees of these repositories.
 #[derive(Debug)]
 pub struct DefaultSubmoduleStore {
-    #[expect(dead_code)]
     path: PathBuf,
+    git_executable: PathBuf,
 }
 
 impl DefaultSubmoduleStore {
     /// Load an existing SubmoduleStore
-    pub fn load(store_path: &Path) -> Self {
-        Self {
+    pub fn load(settings: &UserSettings, store_path: &Path) -> Result<Self, ConfigGetError> {
+        Ok(Self {
             path: store_path.to_path_buf(),
-        }
+            git_executable: settings.get("git.executable-path")?,
+        })
     }
 
-    pub fn init(store_path: &Path) -> Self {
-        Self {
-            path: store_path.to_path_buf(),
-        }
+    pub fn init(settings: &UserSettings, store_path: &Path) -> Result<Self, ConfigGetError> {
+        Self::load(settings, store_path)
     }
 
     pub fn name() -> &'static str {
         "default"
     }
+
+    fn repo_path(&self, name: &str) -> Result<PathBuf, SubmoduleStoreError> {
+        let relative_path = Path::new(name);
+        let is_valid = !name.is_empty()
+            && relative_path
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+        if is_valid {
+            Ok(self.path.join("repos").join(relative_path))
+        } else {
+            Err(SubmoduleStoreError::InvalidName(name.to_owned()))
+        }
+    }
+
+    fn run_git(&self, args: &[&OsStr]) -> Result<(), SubmoduleStoreError> {
+        let output = Command::new(&self.git_executable)
+            .args(args)
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|source| SubmoduleStoreError::SpawnGit {
+                program: self.git_executable.clone(),
+                source,
+            })?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(SubmoduleStoreError::GitCommand {
+                status: output.status,
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+            })
+        }
+    }
+
+    fn has_commit(&self, repo_path: &Path, commit_id: &CommitId) -> bool {
+        let object = format!("{}^{{commit}}", commit_id.hex());
+        self.run_git(&[
+            "--git-dir".as_ref(),
+            repo_path.as_ref(),
+            "cat-file".as_ref(),
+            "-e".as_ref(),
+            object.as_ref(),
+        ])
+        .is_ok()
+    }
 }
 
 impl SubmoduleStore for DefaultSubmoduleStore {
     fn name(&self) -> &str {
         Self::name()
     }
+
+    fn get_submodule_repo(&self, name: &str) -> Result<Option<PathBuf>, SubmoduleStoreError> {
+        let repo_path = self.repo_path(name)?;
+        Ok(repo_path.is_dir().then_some(repo_path))
+    }
+
+    fn fetch_submodule(
+        &self,
+        name: &str,
+        url: &str,
+        commit_id: &CommitId,
+    ) -> Result<PathBuf, SubmoduleStoreError> {
+        let repo_path = self.repo_path(name)?;
+        if !repo_path.is_dir() {
+            self.run_git(&[
+                "clone".as_ref(),
+                "--bare".as_ref(),
+                "--quiet".as_ref(),
+                "--".as_ref(),
+                url.as_ref(),
+                repo_path.as_ref(),
+            ])?;
+        }
+        if !self.has_commit(&repo_path, commit_id) {
+            self.run_git(&[
+                "--git-dir".as_ref(),
+                repo_path.as_ref(),
+                "fetch".as_ref(),
+                "--quiet".as_ref(),
+                "--force".as_ref(),
+                "--".as_ref(),
+                url.as_ref(),
+                "refs/heads/*:refs/heads/*".as_ref(),
+                "refs/tags/*:refs/tags/*".as_ref(),
+            ])?;
+        }
+        Ok(repo_path)
+    }
+
+    fn check_out_submodule(
+        &self,
+        name: &str,
+        disk_path: &Path,
+        commit_id: &CommitId,
+    ) -> Result<(), SubmoduleStoreError> {
+        if !disk_path.join(".git").exists() {
+            // Each workspace gets its own worktree so it has its own HEAD and
+            // index, while the objects are shared through the store.
+            let repo_path = self.repo_path(name)?;
+            fs::create_dir_all(disk_path).context(disk_path)?;
+            let disk_path = dunce::canonicalize(disk_path).context(disk_path)?;
+            return self.run_git(&[
+                "--git-dir".as_ref(),
+                repo_path.as_ref(),
+                "worktree".as_ref(),
+                "add".as_ref(),
+                "--quiet".as_ref(),
+                "--detach".as_ref(),
+                disk_path.as_ref(),
+                commit_id.hex().as_ref(),
+            ]);
+        }
+        self.run_git(&[
+            "-C".as_ref(),
+            disk_path.as_ref(),
+            "checkout".as_ref(),
+            "--quiet".as_ref(),
+            "--detach".as_ref(),
+            commit_id.hex().as_ref(),
+        ])
+    }
 }
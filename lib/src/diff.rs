@@ -76,6 +76,184 @@ pub fn find_nonword_ranges(text: &[u8]) -> Vec<Range<usize>> {
         .collect()
 }
 
+/// Language whose lexical rules are used by [`find_syntax_token_ranges()`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenSyntax {
+    /// `//` and `/* */` comments, and string, raw string, and char literals.
+    Rust,
+    /// `#` comments, and single- or triple-quoted string literals which may
+    /// have prefixes such as `r` or `f`.
+    Python,
+    /// `//` and `/* */` comments, and string and template literals. Also
+    /// applicable to TypeScript.
+    #[serde(rename = "javascript")]
+    JavaScript,
+}
+
+/// Splits `text` into identifiers (including numbers and keywords), string
+/// literals, comments, and punctuation characters.
+///
+/// Whitespace outside of literals and comments isn't included in any token.
+pub fn find_syntax_token_ranges(text: &[u8], syntax: TokenSyntax) -> Vec<Range<usize>> {
+    let mut token_ranges = vec![];
+    let mut pos = 0;
+    while pos < text.len() {
+        if text[pos].is_ascii_whitespace() {
+            pos += 1;
+            continue;
+        }
+        let end = scan_comment(text, pos, syntax)
+            .or_else(|| scan_string_literal(text, pos, syntax))
+            .unwrap_or_else(|| {
+                let word_len = text[pos..].iter().take_while(|b| is_word_byte(**b)).count();
+                pos + word_len.max(1)
+            });
+        token_ranges.push(pos..end);
+        pos = end;
+    }
+    token_ranges
+}
+
+/// Splits whitespace between syntax tokens into single-byte ranges.
+fn find_syntax_whitespace_ranges(text: &[u8], syntax: TokenSyntax) -> Vec<Range<usize>> {
+    let token_ranges = find_syntax_token_ranges(text, syntax);
+    let gap_starts = iter::once(0).chain(token_ranges.iter().map(|range| range.end));
+    let gap_ends = token_ranges
+        .iter()
+        .map(|range| range.start)
+        .chain(iter::once(text.len()));
+    iter::zip(gap_starts, gap_ends)
+        .flat_map(|(start, end)| start..end)
+        .map(|i| i..i + 1)
+        .collect()
+}
+
+/// Returns the end position of the comment starting at `start`.
+fn scan_comment(text: &[u8], start: usize, syntax: TokenSyntax) -> Option<usize> {
+    let rest = &text[start..];
+    let line_end = || {
+        let len = rest.iter().position(|b| *b == b'\n').unwrap_or(rest.len());
+        start + len
+    };
+    match syntax {
+        TokenSyntax::Rust | TokenSyntax::JavaScript => {
+            if rest.starts_with(b"//") {
+                Some(line_end())
+            } else if rest.starts_with(b"/*") {
+                // Unlike JavaScript, Rust block comments can be nested.
+                let nested = syntax == TokenSyntax::Rust;
+                let mut depth = 0;
+                let mut pos = start;
+                while pos < text.len() {
+                    if text[pos..].starts_with(b"/*") && (nested || depth == 0) {
+                        depth += 1;
+                        pos += 2;
+                    } else if text[pos..].starts_with(b"*/") {
+                        depth -= 1;
+                        pos += 2;
+                        if depth == 0 {
+                            return Some(pos);
+                        }
+                    } else {
+                        pos += 1;
+                    }
+                }
+                Some(text.len())
+            } else {
+                None
+            }
+        }
+        TokenSyntax::Python => rest.starts_with(b"#").then(line_end),
+    }
+}
+
+/// Returns the end position of the string (or char) literal starting at
+/// `start`, including its prefix.
+fn scan_string_literal(text: &[u8], start: usize, syntax: TokenSyntax) -> Option<usize> {
+    let prefix_len = text[start..]
+        .iter()
+        .take_while(|b| b.is_ascii_alphabetic())
+        .count();
+    let prefix = &text[start..start + prefix_len];
+    let body_start = start + prefix_len;
+    let body = &text[body_start..];
+    match syntax {
+        TokenSyntax::Rust => match (prefix, *body.first()?) {
+            (b"" | b"b" | b"c", b'"') => Some(scan_quoted(text, body_start + 1, b"\"", true)),
+            (b"r" | b"br" | b"cr", b'"' | b'#') => {
+                let num_hashes = body.iter().take_while(|b| **b == b'#').count();
+                if body.get(num_hashes) != Some(&b'"') {
+                    return None;
+                }
+                let mut pos = body_start + num_hashes + 1;
+                while pos < text.len() {
+                    if text[pos] == b'"'
+                        && text[pos + 1..].iter().take_while(|b| **b == b'#').count() >= num_hashes
+                    {
+                        return Some(pos + 1 + num_hashes);
+                    }
+                    pos += 1;
+                }
+                Some(text.len())
+            }
+            (b"" | b"b", b'\'') => {
+                // 'a' and '\n' are char literals, but 'a in 'a: is a lifetime.
+                let char_len = match *body.get(1)? {
+                    b'\\' => return Some(scan_quoted(text, body_start + 1, b"'", false)),
+                    0x00..=0x7f => 1,
+                    0xc0..=0xdf => 2,
+                    0xe0..=0xef => 3,
+                    _ => 4,
+                };
+                (body.get(1 + char_len) == Some(&b'\'')).then_some(body_start + char_len + 2)
+            }
+            _ => None,
+        },
+        TokenSyntax::Python => {
+            let valid_prefix = prefix.len() <= 2
+                && prefix
+                    .iter()
+                    .all(|b| b"bfru".contains(&b.to_ascii_lowercase()))
+                && prefix.iter().map(u8::to_ascii_lowercase).all_unique()
+                && !(prefix.len() == 2 && prefix.iter().any(|b| b.eq_ignore_ascii_case(&b'u')));
+            let quote = *body.first().filter(|b| matches!(b, b'"' | b'\''))?;
+            if !valid_prefix {
+                return None;
+            }
+            let triple_quote = [quote; 3];
+            if body.starts_with(&triple_quote) {
+                Some(scan_quoted(text, body_start + 3, &triple_quote, true))
+            } else {
+                Some(scan_quoted(text, body_start + 1, &[quote], false))
+            }
+        }
+        TokenSyntax::JavaScript => match (prefix, *body.first()?) {
+            (b"", quote @ (b'"' | b'\'')) => Some(scan_quoted(text, start + 1, &[quote], false)),
+            (b"", b'`') => Some(scan_quoted(text, start + 1, b"`", true)),
+            _ => None,
+        },
+    }
+}
+
+/// Returns the position after the closing `quote`, skipping backslash escapes
+/// from `pos`. Unterminated literal ends at the end of the line unless
+/// `multiline`.
+fn scan_quoted(text: &[u8], mut pos: usize, quote: &[u8], multiline: bool) -> usize {
+    while pos < text.len() {
+        if text[pos] == b'\\' {
+            pos += 2;
+        } else if text[pos..].starts_with(quote) {
+            return pos + quote.len();
+        } else if !multiline && text[pos] == b'\n' {
+            return pos;
+        } else {
+            pos += 1;
+        }
+    }
+    text.len()
+}
+
 fn bytes_ignore_all_whitespace(text: &[u8]) -> impl Iterator<Item = u8> {
     text.iter().copied().filter(|b| !b.is_ascii_whitespace())
 }
@@ -767,6 +945,28 @@ impl<'input> ContentDiff<'input> {
         diff
     }
 
+    /// Compares `inputs` token by token, where tokens are split by the lexical
+    /// rules of the given `syntax`.
+    ///
+    /// Unlike [`ContentDiff::by_word()`], string literals and comments are
+    /// compared as units, and whitespace between tokens is only compared
+    /// within changed regions.
+    pub fn by_syntax_token<T: AsRef<[u8]> + ?Sized + 'input>(
+        inputs: impl IntoIterator<Item = &'input T>,
+        syntax: TokenSyntax,
+    ) -> Self {
+        let mut diff = ContentDiff::for_tokenizer(
+            inputs,
+            |text| find_syntax_token_ranges(text, syntax),
+            CompareBytesExactly,
+        );
+        diff.refine_changed_regions(
+            |text| find_syntax_whitespace_ranges(text, syntax),
+            CompareBytesExactly,
+        );
+        diff
+    }
+
     /// Returns iterator over matching and different texts.
     pub fn hunks(&self) -> DiffHunkIterator<'_, 'input> {
         let ranges = self.hunk_ranges();
@@ -1143,6 +1343,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_find_syntax_token_ranges_rust() {
+        let tokens = |text: &'static str| {
+            find_syntax_token_ranges(text.as_bytes(), TokenSyntax::Rust)
+                .into_iter()
+                .map(|range| &text[range])
+                .collect_vec()
+        };
+        assert_eq!(tokens(""), Vec::<&str>::new());
+        assert_eq!(
+            tokens(r#"let x = foo("a b", 1);"#),
+            ["let", "x", "=", "foo", "(", r#""a b""#, ",", "1", ")", ";"]
+        );
+        assert_eq!(
+            tokens(r###"r#"a "b" c"# b"\"" "x""###),
+            [r##"r#"a "b" c"#"##, r#"b"\"""#, r#""x""#]
+        );
+        assert_eq!(
+            tokens(r"'a' '\'' '⊢' &'a str"),
+            ["'a'", r"'\''", "'⊢'", "&", "'", "a", "str"]
+        );
+        assert_eq!(
+            tokens("a // x y\n/* p /* q */ r */ b"),
+            ["a", "// x y", "/* p /* q */ r */", "b"]
+        );
+        // Unterminated literal and comment
+        assert_eq!(tokens("x \"a\nb"), ["x", "\"a\nb"]);
+        assert_eq!(tokens("x /* a"), ["x", "/* a"]);
+    }
+
+    #[test]
+    fn test_find_syntax_token_ranges_python() {
+        let tokens = |text: &'static str| {
+            find_syntax_token_ranges(text.as_bytes(), TokenSyntax::Python)
+                .into_iter()
+                .map(|range| &text[range])
+                .collect_vec()
+        };
+        assert_eq!(
+            tokens("print(f'{x} y') # done"),
+            ["print", "(", "f'{x} y'", ")", "# done"]
+        );
+        assert_eq!(
+            tokens("Rb\"a\" xr\"b\" '''a\n' b'''"),
+            ["Rb\"a\"", "xr", "\"b\"", "'''a\n' b'''"]
+        );
+        // Unterminated single-quoted literal ends at newline
+        assert_eq!(tokens("'a\nx'"), ["'a", "x", "'"]);
+        assert_eq!(tokens("a // b"), ["a", "/", "/", "b"]);
+    }
+
+    #[test]
+    fn test_find_syntax_token_ranges_javascript() {
+        let tokens = |text: &'static str| {
+            find_syntax_token_ranges(text.as_bytes(), TokenSyntax::JavaScript)
+                .into_iter()
+                .map(|range| &text[range])
+                .collect_vec()
+        };
+        assert_eq!(
+            tokens(
+                r"f('a\'b', `x
+${y}`) /* c /* */"
+            ),
+            ["f", "(", r"'a\'b'", ",", "`x\n${y}`", ")", "/* c /* */"]
+        );
+        assert_eq!(tokens("a # b"), ["a", "#", "b"]);
+    }
+
     #[test]
     fn test_compare_bytes_ignore_all_whitespace() {
         let comp = WordComparator::new(CompareBytesIgnoreAllWhitespace);
@@ -1558,6 +1827,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_diff_by_syntax_token() {
+        fn diff(inputs: [&str; 2]) -> Vec<DiffHunk<'_>> {
+            let diff = ContentDiff::by_syntax_token(inputs, TokenSyntax::Rust);
+            diff.hunks().collect()
+        }
+
+        // String literal is compared as a unit
+        assert_eq!(
+            diff([r#"f("a b")"#, r#"f("a c")"#]),
+            vec![
+                DiffHunk::matching(["f("].repeat(2)),
+                DiffHunk::different([r#""a b""#, r#""a c""#]),
+                DiffHunk::matching([")"].repeat(2)),
+            ]
+        );
+        // Whitespace between changed tokens can be matched
+        assert_eq!(
+            diff(["a + b", "a - c"]),
+            vec![
+                DiffHunk::matching(["a "].repeat(2)),
+                DiffHunk::different(["+", "-"]),
+                DiffHunk::matching([" "].repeat(2)),
+                DiffHunk::different(["b", "c"]),
+            ]
+        );
+    }
+
     #[test]
     fn test_diff_hunk_iterator() {
         let diff = ContentDiff::by_word(["a b c", "a XX c", "a b "]);
@@ -492,6 +492,8 @@ mod tests {
     use indoc::indoc;
 
     use super::*;
+    use crate::merge_driver::MergeDrivers;
+    use crate::tree_merge::MergeStrategy;
 
     fn conflict<const N: usize>(values: [&[u8]; N]) -> Merge<BString> {
         Merge::from_vec(values.map(hunk).to_vec())
@@ -855,6 +857,8 @@ mod tests {
         let options = MergeOptions {
             hunk_level: FileMergeHunkLevel::Line,
             same_change: SameChange::Accept,
+            merge_drivers: MergeDrivers::default(),
+            strategy: MergeStrategy::Conflict,
         };
         let merge_hunks = |inputs: &_| merge_hunks(inputs, &options);
         // Unchanged and empty on all sides
@@ -971,6 +975,8 @@ mod tests {
         let options = MergeOptions {
             hunk_level: FileMergeHunkLevel::Line,
             same_change: SameChange::Accept,
+            merge_drivers: MergeDrivers::default(),
+            strategy: MergeStrategy::Conflict,
         };
         let merge_hunks = |inputs: &_| merge_hunks(inputs, &options);
         let merge = |inputs: &_| merge(inputs, &options);
@@ -1083,6 +1089,8 @@ mod tests {
         let options = MergeOptions {
             hunk_level: FileMergeHunkLevel::Word,
             same_change: SameChange::Accept,
+            merge_drivers: MergeDrivers::default(),
+            strategy: MergeStrategy::Conflict,
         };
         let merge = |inputs: &_| merge(inputs, &options);
         // No context line in between, but "\n" is a context word
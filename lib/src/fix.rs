@@ -17,21 +17,35 @@
 
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
 use std::sync::mpsc::channel;
+use std::time::SystemTime;
 
+use blake2::Blake2b512;
+use digest::Digest as _;
 use futures::StreamExt as _;
 use futures::TryStreamExt as _;
 use jj_lib::backend::BackendError;
 use jj_lib::backend::CommitId;
 use jj_lib::backend::FileId;
 use jj_lib::backend::TreeValue;
+use jj_lib::content_hash::ContentHash as _;
+use jj_lib::file_util::IoResultExt as _;
+use jj_lib::file_util::PathError;
 use jj_lib::matchers::Matcher;
 use jj_lib::merged_tree::TreeDiffEntry;
 use jj_lib::merged_tree_builder::MergedTreeBuilder;
+use jj_lib::object_id::ObjectId as _;
 use jj_lib::repo::MutableRepo;
 use jj_lib::repo::Repo as _;
 use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::revset::RevsetExpression;
+use jj_lib::stacked_table::ReadonlyTable;
+use jj_lib::stacked_table::TableSegment as _;
+use jj_lib::stacked_table::TableStore;
+use jj_lib::stacked_table::TableStoreError;
 use jj_lib::store::Store;
 use rayon::iter::IntoParallelIterator as _;
 use rayon::prelude::ParallelIterator as _;
@@ -70,6 +84,117 @@ pub enum FixError {
     /// Error occurred while processing the file content.
     #[error(transparent)]
     FixContent(Box<dyn std::error::Error + Send + Sync>),
+    /// Error occurred while setting up the cache directory.
+    #[error(transparent)]
+    CachePath(#[from] PathError),
+    /// Error occurred while reading/writing the cache.
+    #[error("Failed to access the fix cache")]
+    Cache(#[source] TableStoreError),
+}
+
+// BLAKE2b-512 hash length in bytes
+const CACHE_KEY_LENGTH: usize = 64;
+
+// Once the cache grows beyond this many entries, saving starts over from a
+// table containing only the new entries.
+const MAX_CACHE_ENTRIES: usize = 100_000;
+
+/// Key identifying an invocation of a tool on some file content.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FixCacheKey(Vec<u8>);
+
+impl FixCacheKey {
+    /// Creates a key for running the tool identified by `tool_args` (typically
+    /// the command line after variable substitution) on the `input` content.
+    pub fn new(tool_args: &[Vec<u8>], input: &FileId) -> Self {
+        let mut hasher = Blake2b512::default();
+        tool_args.hash(&mut hasher);
+        input.hash(&mut hasher);
+        Self(hasher.finalize().to_vec())
+    }
+}
+
+/// Persistent cache of tool outputs, so tools don't have to be run again on
+/// content they have already processed, even across invocations.
+///
+/// Only successful tool invocations should be recorded. The tools are assumed
+/// to be deterministic, as is already required by [`fix_files()`].
+pub struct FixCache {
+    table_store: TableStore,
+    table: Arc<ReadonlyTable>,
+    new_entries: Mutex<HashMap<FixCacheKey, FileId>>,
+}
+
+impl FixCache {
+    /// Loads the cache stored in `dir`, creating it if it doesn't exist.
+    pub fn load(dir: PathBuf) -> Result<Self, FixError> {
+        // Another process may be creating the cache concurrently, so this
+        // must not fail if the directory already exists.
+        let heads_dir = dir.join("heads");
+        std::fs::create_dir_all(&heads_dir).context(&heads_dir)?;
+        let table_store = TableStore::load(dir, CACHE_KEY_LENGTH);
+        let table = table_store.get_head().map_err(FixError::Cache)?;
+        Ok(Self {
+            table_store,
+            table,
+            new_entries: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Looks up the output recorded for the given invocation.
+    pub fn get(&self, key: &FixCacheKey) -> Option<FileId> {
+        if let Some(id) = self.new_entries.lock().unwrap().get(key) {
+            return Some(id.clone());
+        }
+        self.table.get_value(&key.0).map(FileId::from_bytes)
+    }
+
+    /// Records the output of the given invocation. The entry isn't persisted
+    /// until [`FixCache::save()`] is called.
+    pub fn insert(&self, key: FixCacheKey, output: FileId) {
+        self.new_entries.lock().unwrap().insert(key, output);
+    }
+
+    /// Persists the entries recorded since the cache was loaded.
+    pub fn save(self) -> Result<(), FixError> {
+        let new_entries = self.new_entries.into_inner().unwrap();
+        if new_entries.is_empty() {
+            return Ok(());
+        }
+        let (table, _lock) = self
+            .table_store
+            .get_head_locked()
+            .map_err(FixError::Cache)?;
+        if table.num_entries() + new_entries.len() > MAX_CACHE_ENTRIES {
+            let mut mut_table = self.table_store.start_empty_table();
+            for (key, output) in new_entries {
+                mut_table.add_entry(key.0, output.to_bytes());
+            }
+            self.table_store
+                .replace_head(&table, mut_table)
+                .map_err(FixError::Cache)?;
+        } else {
+            let mut mut_table = table.start_mutation();
+            for (key, output) in new_entries {
+                mut_table.add_entry(key.0, output.to_bytes());
+            }
+            self.table_store
+                .save_table(mut_table)
+                .map_err(FixError::Cache)?;
+        }
+        Ok(())
+    }
+
+    /// Removes cache segments that are no longer reachable from the current
+    /// cache contents, unless they were created after `keep_newer`.
+    pub fn gc(&self, keep_newer: SystemTime) -> Result<(), FixError> {
+        let (table, _lock) = self
+            .table_store
+            .get_head_locked()
+            .map_err(FixError::Cache)?;
+        self.table_store.gc(&table, keep_newer)?;
+        Ok(())
+    }
 }
 
 /// Fixes a set of files.
@@ -40,6 +40,10 @@ pub enum FsmonitorSettings {
     /// The Watchman filesystem monitor (<https://facebook.github.io/watchman/>).
     Watchman(WatchmanConfig),
 
+    /// The built-in filesystem monitor based on Linux's inotify API. Changes
+    /// are recorded by a daemon process running in the background.
+    Inotify,
+
     /// Only used in tests.
     Test {
         /// The set of changed files to pretend that the filesystem monitor is
@@ -63,6 +67,7 @@ impl FsmonitorSettings {
                 register_trigger: settings
                     .get_bool("fsmonitor.watchman.register-snapshot-trigger")?,
             })),
+            "inotify" => Ok(Self::Inotify),
             "test" => Err(ConfigGetError::Type {
                 name: name.to_owned(),
                 error: "Cannot use test fsmonitor in real repository".into(),
@@ -78,6 +83,811 @@ impl FsmonitorSettings {
     }
 }
 
+/// Filesystem monitor integration using inotify. Requires Linux.
+///
+/// A daemon process watches the working copy and appends the paths of changed
+/// files to a journal stored next to the working-copy state. Queries read the
+/// journal since the position recorded by the previous snapshot. The daemon
+/// isn't started by this module; see [`run_daemon()`](inotify::run_daemon).
+///
+/// Directories ignored by `.gitignore` aren't watched. Their paths are
+/// published by the daemon so that snapshots can still check the files
+/// tracked in them.
+#[cfg(target_os = "linux")]
+pub mod inotify {
+    use std::collections::BTreeSet;
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+    use std::ffi::OsStr;
+    use std::fs;
+    use std::fs::File;
+    use std::io;
+    use std::io::BufRead as _;
+    use std::io::BufReader;
+    use std::io::Read as _;
+    use std::io::Seek as _;
+    use std::io::SeekFrom;
+    use std::io::Write as _;
+    use std::mem::MaybeUninit;
+    use std::os::fd::AsFd as _;
+    use std::os::fd::BorrowedFd;
+    use std::os::fd::OwnedFd;
+    use std::os::unix::ffi::OsStrExt as _;
+    use std::path::Path;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+    use std::time::Instant;
+    use std::time::SystemTime;
+
+    use rustix::event::PollFd;
+    use rustix::event::PollFlags;
+    use rustix::event::Timespec;
+    use rustix::fs::FlockOperation;
+    use rustix::fs::inotify;
+    use rustix::fs::inotify::CreateFlags;
+    use rustix::fs::inotify::ReadFlags;
+    use rustix::fs::inotify::WatchFlags;
+    use thiserror::Error;
+    use tracing::instrument;
+
+    use crate::gitignore::GitIgnoreFile;
+
+    const JOURNAL_HEADER: &str = "jj-inotify-journal ";
+    /// The journal is started over once it grows beyond this size, which
+    /// forces clients to do a full scan once.
+    const MAX_JOURNAL_SIZE: u64 = 1 << 20;
+    /// How long to wait for the daemon to catch up with the filesystem.
+    const SYNC_TIMEOUT: Duration = Duration::from_secs(2);
+    /// How long a starting daemon waits for clients checking whether a daemon
+    /// is running to release their lock.
+    const LOCK_TIMEOUT: Duration = Duration::from_secs(1);
+    /// Name of the cookie asking the daemon to exit.
+    const STOP_COOKIE: &str = "stop";
+    /// Directories that aren't watched.
+    const IGNORED_DIR_NAMES: &[&str] = &[".git", ".jj"];
+    /// Name of the file listing the directories ignored by `.gitignore`.
+    const IGNORED_DIRS_FILE: &str = "ignored-dirs";
+    /// Name of the file left by a daemon that ran out of inotify watches. It
+    /// contains the limit at that time.
+    const WATCH_LIMIT_FILE: &str = "watch-limit";
+    /// The per-user limit on the number of inotify watches.
+    const MAX_USER_WATCHES_PATH: &str = "/proc/sys/fs/inotify/max_user_watches";
+
+    #[expect(missing_docs)]
+    #[derive(Debug, Error)]
+    pub enum InotifyError {
+        #[error("The inotify daemon is not running")]
+        NotRunning,
+        #[error("The inotify daemon is already running")]
+        AlreadyRunning,
+        #[error("Timed out waiting for the inotify daemon")]
+        Timeout,
+        #[error("Failed to access {}", path.display())]
+        Io { path: PathBuf, source: io::Error },
+        #[error("Failed to watch {}", path.display())]
+        Watch { path: PathBuf, source: io::Error },
+        #[error(
+            "Reached the limit on the number of inotify watches while watching {}",
+            path.display()
+        )]
+        WatchLimit { path: PathBuf },
+    }
+
+    /// Represents a position in the journal of a daemon.
+    ///
+    /// Passing the clock returned by a query to the next query only reports
+    /// the files changed in between.
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct Clock {
+        session: String,
+        offset: u64,
+    }
+
+    impl From<crate::protos::local_working_copy::InotifyClock> for Clock {
+        fn from(clock: crate::protos::local_working_copy::InotifyClock) -> Self {
+            Self {
+                session: clock.session,
+                offset: clock.offset,
+            }
+        }
+    }
+
+    impl From<Clock> for crate::protos::local_working_copy::InotifyClock {
+        fn from(clock: Clock) -> Self {
+            Self {
+                session: clock.session,
+                offset: clock.offset,
+            }
+        }
+    }
+
+    /// Returns the directory where the daemon for the working copy with the
+    /// given state directory keeps its journal.
+    pub fn daemon_dir(state_path: &Path) -> PathBuf {
+        state_path.join("inotify")
+    }
+
+    /// Returns the file where the errors of a daemon started in the background
+    /// should be written.
+    pub fn daemon_log_path(dir: &Path) -> PathBuf {
+        dir.join("daemon.log")
+    }
+
+    fn io_error(path: &Path) -> impl FnOnce(io::Error) -> InotifyError {
+        move |source| InotifyError::Io {
+            path: path.to_owned(),
+            source,
+        }
+    }
+
+    /// Returns whether a daemon is watching the working copy.
+    pub fn is_daemon_running(dir: &Path) -> Result<bool, InotifyError> {
+        let lock_path = dir.join("daemon.lock");
+        let file = match File::open(&lock_path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(err) => return Err(io_error(&lock_path)(err)),
+        };
+        // The daemon holds an exclusive lock for as long as it runs.
+        match rustix::fs::flock(&file, FlockOperation::NonBlockingLockShared) {
+            Ok(()) => Ok(false),
+            Err(rustix::io::Errno::WOULDBLOCK) => Ok(true),
+            Err(errno) => Err(io_error(&lock_path)(errno.into())),
+        }
+    }
+
+    /// Returns whether the last daemon exited because it ran out of inotify
+    /// watches, and the limit hasn't been raised since. Starting another
+    /// daemon would then fail the same way.
+    pub fn is_watch_limit_reached(dir: &Path) -> bool {
+        fs::read_to_string(dir.join(WATCH_LIMIT_FILE))
+            .is_ok_and(|limit| limit == max_user_watches())
+    }
+
+    fn max_user_watches() -> String {
+        fs::read_to_string(MAX_USER_WATCHES_PATH)
+            .map(|limit| limit.trim().to_owned())
+            .unwrap_or_default()
+    }
+
+    /// Asks the daemon to exit. Returns `false` if it wasn't running.
+    pub fn stop_daemon(dir: &Path) -> Result<bool, InotifyError> {
+        if !is_daemon_running(dir)? {
+            return Ok(false);
+        }
+        let cookie_path = dir.join("cookies").join(STOP_COOKIE);
+        fs::write(&cookie_path, b"").map_err(io_error(&cookie_path))?;
+        Ok(true)
+    }
+
+    /// Queries the daemon for the files changed since `previous_clock`.
+    ///
+    /// Returns the clock to pass to the next query. The changed files are
+    /// relative to the working-copy root, and are `None` if all files should
+    /// be considered changed, such as on the first query.
+    #[instrument]
+    pub fn query_changed_files(
+        dir: &Path,
+        previous_clock: Option<&Clock>,
+    ) -> Result<(Clock, Option<Vec<PathBuf>>), InotifyError> {
+        // The journal is created once the daemon is ready to record cookies.
+        let journal_path = dir.join("journal");
+        if !is_daemon_running(dir)? || !journal_path.exists() {
+            return Err(InotifyError::NotRunning);
+        }
+        // Watch the journal before reading it so no append is missed.
+        let watcher = inotify::init(CreateFlags::CLOEXEC | CreateFlags::NONBLOCK)
+            .map_err(|errno| io_error(dir)(errno.into()))?;
+        inotify::add_watch(&watcher, dir, WatchFlags::MODIFY | WatchFlags::MOVED_TO)
+            .map_err(|errno| io_error(dir)(errno.into()))?;
+        let mut journal = JournalTail::open(&journal_path)?;
+        match previous_clock {
+            Some(previous) if previous.session == journal.session => {
+                journal.skip_to(previous.offset)?;
+                journal.is_continued = true;
+            }
+            _ => journal.skip_to(u64::MAX)?,
+        }
+
+        // The daemon records the creation of the cookie after all the
+        // filesystem events that happened before it.
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let cookie_name = format!("{}-{nanos}", std::process::id());
+        let cookie_path = dir.join("cookies").join(&cookie_name);
+        fs::write(&cookie_path, b"").map_err(io_error(&cookie_path))?;
+        let result = wait_for_cookie(&watcher, &mut journal, &cookie_name);
+        fs::remove_file(&cookie_path).ok();
+        let (cookie_start, cookie_end) = result?;
+
+        let clock = Clock {
+            session: journal.session.clone(),
+            offset: journal.offset + cookie_end as u64,
+        };
+        let changed_files = if journal.is_continued {
+            parse_changed_files(&journal.data[..cookie_start])
+        } else {
+            None
+        };
+        Ok((clock, changed_files))
+    }
+
+    /// Returns the directories the daemon doesn't watch because they're
+    /// ignored, relative to the working-copy root. Files tracked in them may
+    /// have changed without being reported by [`query_changed_files()`].
+    pub fn ignored_dirs(dir: &Path) -> Result<Vec<PathBuf>, InotifyError> {
+        let path = dir.join(IGNORED_DIRS_FILE);
+        let data = match fs::read(&path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(io_error(&path)(err)),
+        };
+        Ok(data
+            .split(|&b| b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| PathBuf::from(OsStr::from_bytes(line)))
+            .collect())
+    }
+
+    /// Reads the entries appended to the journal from some offset on.
+    struct JournalTail {
+        path: PathBuf,
+        file: File,
+        session: String,
+        header_end: u64,
+        /// The offset of `data` within the journal.
+        offset: u64,
+        data: Vec<u8>,
+        /// The number of bytes of `data` that were searched for cookies.
+        searched: usize,
+        /// Whether `data` starts where the previous query ended.
+        is_continued: bool,
+    }
+
+    impl JournalTail {
+        fn open(path: &Path) -> Result<Self, InotifyError> {
+            let file = File::open(path).map_err(io_error(path))?;
+            let mut header = vec![];
+            BufReader::new(&file)
+                .read_until(b'\n', &mut header)
+                .map_err(io_error(path))?;
+            let session = header
+                .strip_prefix(JOURNAL_HEADER.as_bytes())
+                .map(|session| String::from_utf8_lossy(session.trim_ascii_end()).into_owned())
+                .unwrap_or_default();
+            let mut journal = Self {
+                path: path.to_owned(),
+                file,
+                session,
+                header_end: header.len() as u64,
+                offset: 0,
+                data: vec![],
+                searched: 0,
+                is_continued: false,
+            };
+            journal.skip_to(0)?;
+            Ok(journal)
+        }
+
+        /// Starts reading at `offset`, which is clamped to the entries
+        /// currently in the journal.
+        fn skip_to(&mut self, offset: u64) -> Result<(), InotifyError> {
+            let len = self.file.metadata().map_err(io_error(&self.path))?.len();
+            self.offset = offset.clamp(self.header_end, len.max(self.header_end));
+            self.file
+                .seek(SeekFrom::Start(self.offset))
+                .map_err(io_error(&self.path))?;
+            self.data.clear();
+            self.searched = 0;
+            Ok(())
+        }
+
+        /// Reads the entries appended since the last call, and returns the
+        /// range of the line recording the cookie within `data` if found.
+        fn read_cookie(
+            &mut self,
+            cookie_name: &str,
+        ) -> Result<Option<(usize, usize)>, InotifyError> {
+            self.file
+                .read_to_end(&mut self.data)
+                .map_err(io_error(&self.path))?;
+            let marker = format!("c {cookie_name}\n");
+            while let Some(line_len) = self.data[self.searched..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map(|pos| pos + 1)
+            {
+                let line_start = self.searched;
+                self.searched += line_len;
+                if self.data[line_start..self.searched] == *marker.as_bytes() {
+                    return Ok(Some((line_start, self.searched)));
+                }
+            }
+            Ok(None)
+        }
+    }
+
+    fn wait_for_cookie(
+        watcher: &OwnedFd,
+        journal: &mut JournalTail,
+        cookie_name: &str,
+    ) -> Result<(usize, usize), InotifyError> {
+        let deadline = Instant::now() + SYNC_TIMEOUT;
+        let mut buf = vec![MaybeUninit::uninit(); 4096];
+        loop {
+            if let Some(range) = journal.read_cookie(cookie_name)? {
+                return Ok(range);
+            }
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            let timeout = Timespec::try_from(timeout).map_err(|_| InotifyError::Timeout)?;
+            let mut fds = [PollFd::new(watcher, PollFlags::IN)];
+            match rustix::event::poll(&mut fds, Some(&timeout)) {
+                Ok(0) => return Err(InotifyError::Timeout),
+                Ok(_) | Err(rustix::io::Errno::INTR) => {}
+                Err(errno) => return Err(io_error(&journal.path)(errno.into())),
+            }
+            // Start over if the daemon replaced the journal.
+            let mut replaced = false;
+            let mut reader = inotify::Reader::new(watcher, &mut buf);
+            loop {
+                match reader.next() {
+                    Ok(event) => {
+                        replaced |= event.events().contains(ReadFlags::MOVED_TO)
+                            && event
+                                .file_name()
+                                .is_some_and(|name| name.to_bytes() == b"journal");
+                    }
+                    Err(rustix::io::Errno::WOULDBLOCK | rustix::io::Errno::INTR) => break,
+                    Err(errno) => return Err(io_error(&journal.path)(errno.into())),
+                }
+                if reader.is_buffer_empty() {
+                    break;
+                }
+            }
+            if replaced {
+                *journal = JournalTail::open(&journal.path)?;
+            }
+        }
+    }
+
+    fn parse_changed_files(entries: &[u8]) -> Option<Vec<PathBuf>> {
+        let mut changed_files = BTreeSet::new();
+        for line in entries.split(|&b| b == b'\n') {
+            if let Some(path) = line.strip_prefix(b"f ") {
+                changed_files.insert(PathBuf::from(OsStr::from_bytes(path)));
+            } else if line == b"o" {
+                return None;
+            }
+        }
+        Some(changed_files.into_iter().collect())
+    }
+
+    /// Watches the working copy at `workspace_root` until it's deleted or the
+    /// daemon is asked to stop, recording changes in a journal in `dir`.
+    /// Directories matching `base_ignores` or the `.gitignore` files in the
+    /// working copy aren't watched.
+    ///
+    /// Fails with [`InotifyError::AlreadyRunning`] if another daemon is
+    /// already watching the working copy, and with
+    /// [`InotifyError::WatchLimit`] if the working copy has more directories
+    /// than inotify watches are available. In the latter case,
+    /// [`is_watch_limit_reached()`] returns `true` until the limit is raised.
+    pub fn run_daemon(
+        workspace_root: &Path,
+        dir: &Path,
+        base_ignores: Arc<GitIgnoreFile>,
+    ) -> Result<(), InotifyError> {
+        let cookies_dir = dir.join("cookies");
+        fs::create_dir_all(&cookies_dir).map_err(io_error(&cookies_dir))?;
+        let lock_path = dir.join("daemon.lock");
+        let lock_file = File::create(&lock_path).map_err(io_error(&lock_path))?;
+        lock_daemon(&lock_path, &lock_file)?;
+        let watch_limit_path = dir.join(WATCH_LIMIT_FILE);
+        fs::remove_file(&watch_limit_path).ok();
+        // Remove stale cookies of queries that timed out.
+        if let Ok(entries) = fs::read_dir(&cookies_dir) {
+            for entry in entries.flatten() {
+                fs::remove_file(entry.path()).ok();
+            }
+        }
+
+        let fd = inotify::init(CreateFlags::CLOEXEC).map_err(|errno| InotifyError::Watch {
+            path: workspace_root.to_owned(),
+            source: errno.into(),
+        })?;
+        let cookies_wd =
+            inotify::add_watch(&fd, &cookies_dir, WatchFlags::CREATE).map_err(|errno| {
+                InotifyError::Watch {
+                    path: cookies_dir.clone(),
+                    source: errno.into(),
+                }
+            })?;
+        let mut daemon = Daemon {
+            workspace_root: workspace_root.to_owned(),
+            journal_path: dir.join("journal"),
+            journal: None,
+            journal_size: 0,
+            fd: fd.as_fd(),
+            cookies_wd,
+            base_ignores,
+            dirs: HashMap::new(),
+            ignored_dirs_path: dir.join(IGNORED_DIRS_FILE),
+            ignored_dirs: BTreeSet::new(),
+            ignored_dirs_changed: true,
+        };
+        daemon.start_journal()?;
+        let result = daemon
+            .refresh_dir(Path::new(""))
+            .and_then(|()| daemon.write_ignored_dirs())
+            .and_then(|()| daemon.run());
+        if let Err(InotifyError::WatchLimit { .. }) = &result {
+            // Let the next command do a normal snapshot instead of starting
+            // a daemon that would fail the same way.
+            fs::write(&watch_limit_path, max_user_watches()).ok();
+        }
+        result
+    }
+
+    /// Takes the exclusive lock held by the daemon while it runs.
+    fn lock_daemon(lock_path: &Path, lock_file: &File) -> Result<(), InotifyError> {
+        let deadline = Instant::now() + LOCK_TIMEOUT;
+        loop {
+            match rustix::fs::flock(lock_file, FlockOperation::NonBlockingLockExclusive) {
+                Ok(()) => return Ok(()),
+                Err(rustix::io::Errno::WOULDBLOCK) => {}
+                Err(errno) => return Err(io_error(lock_path)(errno.into())),
+            }
+            // Clients checking whether a daemon is running hold a shared lock
+            // for a moment. If a shared lock can't be taken either, the
+            // exclusive lock is held by another daemon.
+            match rustix::fs::flock(lock_file, FlockOperation::NonBlockingLockShared) {
+                Ok(()) => {
+                    rustix::fs::flock(lock_file, FlockOperation::Unlock)
+                        .map_err(|errno| io_error(lock_path)(errno.into()))?;
+                }
+                Err(rustix::io::Errno::WOULDBLOCK) => return Err(InotifyError::AlreadyRunning),
+                Err(errno) => return Err(io_error(lock_path)(errno.into())),
+            }
+            if Instant::now() > deadline {
+                return Err(InotifyError::AlreadyRunning);
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    struct Daemon<'a> {
+        workspace_root: PathBuf,
+        journal_path: PathBuf,
+        journal: Option<File>,
+        journal_size: u64,
+        fd: BorrowedFd<'a>,
+        cookies_wd: i32,
+        base_ignores: Arc<GitIgnoreFile>,
+        /// Watched directories, relative to the workspace root.
+        dirs: HashMap<i32, WatchedDir>,
+        ignored_dirs_path: PathBuf,
+        /// Directories not watched because they're ignored, relative to the
+        /// workspace root.
+        ignored_dirs: BTreeSet<PathBuf>,
+        /// Whether `ignored_dirs` has changed since it was last written.
+        ignored_dirs_changed: bool,
+    }
+
+    struct WatchedDir {
+        path: PathBuf,
+        /// The ignore rules for the entries of the directory, including its
+        /// own `.gitignore`.
+        git_ignore: Arc<GitIgnoreFile>,
+    }
+
+    impl Daemon<'_> {
+        fn start_journal(&mut self) -> Result<(), InotifyError> {
+            let nanos = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            let header = format!("{JOURNAL_HEADER}{:x}-{nanos:x}\n", std::process::id());
+            // Replace the journal atomically so clients never see it empty.
+            let temp_path = self.journal_path.with_extension("tmp");
+            fs::write(&temp_path, &header).map_err(io_error(&temp_path))?;
+            fs::rename(&temp_path, &self.journal_path).map_err(io_error(&self.journal_path))?;
+            let journal = File::options()
+                .append(true)
+                .open(&self.journal_path)
+                .map_err(io_error(&self.journal_path))?;
+            self.journal = Some(journal);
+            self.journal_size = header.len() as u64;
+            Ok(())
+        }
+
+        fn write_journal(&mut self, entries: &[u8]) -> Result<(), InotifyError> {
+            if entries.is_empty() {
+                return Ok(());
+            }
+            let journal = self.journal.as_mut().unwrap();
+            journal
+                .write_all(entries)
+                .map_err(io_error(&self.journal_path))?;
+            self.journal_size += entries.len() as u64;
+            Ok(())
+        }
+
+        /// Writes the ignored directories for clients if they changed.
+        fn write_ignored_dirs(&mut self) -> Result<(), InotifyError> {
+            if !self.ignored_dirs_changed {
+                return Ok(());
+            }
+            let mut data = vec![];
+            for path in &self.ignored_dirs {
+                data.extend_from_slice(path.as_os_str().as_bytes());
+                data.push(b'\n');
+            }
+            let temp_path = self.ignored_dirs_path.with_extension("tmp");
+            fs::write(&temp_path, &data).map_err(io_error(&temp_path))?;
+            fs::rename(&temp_path, &self.ignored_dirs_path)
+                .map_err(io_error(&self.ignored_dirs_path))?;
+            self.ignored_dirs_changed = false;
+            Ok(())
+        }
+
+        /// Watches `dir` and its subdirectories again after its `.gitignore`
+        /// changed, and stops watching the subdirectories that became
+        /// ignored.
+        fn refresh_dir(&mut self, dir: &Path) -> Result<(), InotifyError> {
+            let git_ignore = match dir.parent() {
+                None => self.base_ignores.clone(),
+                Some(parent) => {
+                    let Some(parent) = self.dirs.values().find(|watched| watched.path == parent)
+                    else {
+                        // The directory itself isn't watched.
+                        return Ok(());
+                    };
+                    parent.git_ignore.clone()
+                }
+            };
+            let old_wds = self
+                .dirs
+                .iter()
+                .filter(|(_, watched)| watched.path.starts_with(dir))
+                .map(|(&wd, _)| wd)
+                .collect::<Vec<_>>();
+            for wd in &old_wds {
+                self.dirs.remove(wd);
+            }
+            self.ignored_dirs.retain(|path| !path.starts_with(dir));
+            self.ignored_dirs_changed = true;
+            // Watching a directory again returns the same descriptor.
+            let mut changed = BTreeSet::new();
+            self.watch_dir(dir.to_owned(), &git_ignore, &mut changed, false)?;
+            for wd in old_wds {
+                if !self.dirs.contains_key(&wd) {
+                    inotify::remove_watch(self.fd, wd).ok();
+                }
+            }
+            Ok(())
+        }
+
+        /// Watches the directory `dir` and its subdirectories that aren't
+        /// ignored by `git_ignore` or their `.gitignore` files. If
+        /// `report_files` is set, the paths found are added to `changed`
+        /// since they may have been created before the watch was added.
+        fn watch_dir(
+            &mut self,
+            dir: PathBuf,
+            git_ignore: &Arc<GitIgnoreFile>,
+            changed: &mut BTreeSet<PathBuf>,
+            report_files: bool,
+        ) -> Result<(), InotifyError> {
+            let disk_dir = self.workspace_root.join(&dir);
+            let flags = WatchFlags::CREATE
+                | WatchFlags::DELETE
+                | WatchFlags::MODIFY
+                | WatchFlags::ATTRIB
+                | WatchFlags::MOVED_FROM
+                | WatchFlags::MOVED_TO
+                | WatchFlags::ONLYDIR
+                | WatchFlags::DONT_FOLLOW
+                | WatchFlags::EXCL_UNLINK;
+            let wd = match inotify::add_watch(self.fd, &disk_dir, flags) {
+                Ok(wd) => wd,
+                // The directory was removed or replaced in the meantime
+                Err(rustix::io::Errno::NOENT | rustix::io::Errno::NOTDIR) => return Ok(()),
+                // The snapshot reports unreadable directories itself
+                Err(rustix::io::Errno::ACCESS) => return Ok(()),
+                Err(rustix::io::Errno::NOSPC | rustix::io::Errno::NOMEM) => {
+                    return Err(InotifyError::WatchLimit { path: disk_dir });
+                }
+                Err(errno) => {
+                    return Err(InotifyError::Watch {
+                        path: disk_dir,
+                        source: errno.into(),
+                    });
+                }
+            };
+            let prefix = dir
+                .to_str()
+                .filter(|dir| !dir.is_empty())
+                .map(|dir| format!("{dir}/"))
+                .unwrap_or_default();
+            let git_ignore = git_ignore
+                .chain_with_file(&prefix, disk_dir.join(".gitignore"))
+                .unwrap_or_else(|err| {
+                    tracing::warn!(?err, "Failed to read .gitignore");
+                    git_ignore.clone()
+                });
+            self.dirs.insert(
+                wd,
+                WatchedDir {
+                    path: dir.clone(),
+                    git_ignore: git_ignore.clone(),
+                },
+            );
+            let Ok(entries) = fs::read_dir(&disk_dir) else {
+                return Ok(());
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name();
+                let path = dir.join(&name);
+                if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                    if is_ignored_dir_name(&name) {
+                    } else if is_ignored_dir(&git_ignore, &path) {
+                        self.ignored_dirs.insert(path);
+                        self.ignored_dirs_changed = true;
+                    } else {
+                        self.watch_dir(path, &git_ignore, changed, report_files)?;
+                    }
+                } else if report_files {
+                    changed.insert(path);
+                }
+            }
+            Ok(())
+        }
+
+        fn run(&mut self) -> Result<(), InotifyError> {
+            let mut buf = vec![MaybeUninit::uninit(); 64 * 1024];
+            let mut reader = inotify::Reader::new(self.fd, &mut buf);
+            loop {
+                let mut changed = BTreeSet::new();
+                let mut new_dirs = vec![];
+                let mut gitignore_dirs = HashSet::new();
+                let mut cookies = vec![];
+                let mut overflow = false;
+                let mut stop = false;
+                // Block for the first event, then process the ones already
+                // read together.
+                loop {
+                    let event = match reader.next() {
+                        Ok(event) => event,
+                        Err(rustix::io::Errno::INTR) => continue,
+                        Err(errno) => {
+                            return Err(InotifyError::Watch {
+                                path: self.workspace_root.clone(),
+                                source: errno.into(),
+                            });
+                        }
+                    };
+                    let flags = event.events();
+                    let name = event
+                        .file_name()
+                        .map(|name| OsStr::from_bytes(name.to_bytes()));
+                    if flags.contains(ReadFlags::QUEUE_OVERFLOW) {
+                        overflow = true;
+                    } else if event.wd() == self.cookies_wd {
+                        if flags.contains(ReadFlags::IGNORED) {
+                            // The working-copy state was deleted
+                            stop = true;
+                        } else if let Some(name) = name.and_then(OsStr::to_str) {
+                            stop |= name == STOP_COOKIE;
+                            cookies.push(name.to_owned());
+                        }
+                    } else if flags.contains(ReadFlags::IGNORED) {
+                        if let Some(dir) = self.dirs.remove(&event.wd()) {
+                            stop |= dir.path.as_os_str().is_empty();
+                        }
+                    } else if let Some(dir) = self.dirs.get(&event.wd())
+                        && let Some(name) = name
+                    {
+                        let path = dir.path.join(name);
+                        if !flags.contains(ReadFlags::ISDIR) {
+                            if name == ".gitignore" {
+                                gitignore_dirs.insert(dir.path.clone());
+                            }
+                            changed.insert(path);
+                        } else if is_ignored_dir_name(name) {
+                        } else if flags.contains(ReadFlags::MOVED_FROM) {
+                            // The files in the moved directory can't be
+                            // reported individually anymore.
+                            self.unwatch_dir(&path);
+                            overflow = true;
+                        } else if flags.intersects(ReadFlags::CREATE | ReadFlags::MOVED_TO) {
+                            new_dirs.push((path, event.wd()));
+                        }
+                    }
+                    if reader.is_buffer_empty() {
+                        break;
+                    }
+                }
+
+                // Clients rescan the directories whose .gitignore changed, so
+                // the files in newly watched directories needn't be reported.
+                for dir in gitignore_dirs {
+                    self.refresh_dir(&dir)?;
+                }
+                for (dir, parent_wd) in new_dirs {
+                    let Some(parent) = self.dirs.get(&parent_wd) else {
+                        continue;
+                    };
+                    let git_ignore = parent.git_ignore.clone();
+                    if is_ignored_dir(&git_ignore, &dir) {
+                        self.ignored_dirs.insert(dir);
+                        self.ignored_dirs_changed = true;
+                    } else {
+                        self.watch_dir(dir, &git_ignore, &mut changed, true)?;
+                    }
+                }
+                self.write_ignored_dirs()?;
+                let mut entries = vec![];
+                for path in &changed {
+                    let path = path.as_os_str().as_bytes();
+                    if path.contains(&b'\n') {
+                        overflow = true;
+                    } else {
+                        entries.extend_from_slice(b"f ");
+                        entries.extend_from_slice(path);
+                        entries.push(b'\n');
+                    }
+                }
+                if overflow {
+                    entries.extend_from_slice(b"o\n");
+                }
+                self.write_journal(&entries)?;
+                if self.journal_size > MAX_JOURNAL_SIZE {
+                    self.start_journal()?;
+                }
+                let cookie_entries = cookies
+                    .iter()
+                    .flat_map(|cookie| [b"c ", cookie.as_bytes(), b"\n"].concat())
+                    .collect::<Vec<_>>();
+                self.write_journal(&cookie_entries)?;
+                if stop {
+                    return Ok(());
+                }
+            }
+        }
+
+        fn unwatch_dir(&mut self, dir: &Path) {
+            let wds = self
+                .dirs
+                .iter()
+                .filter(|(_, watched)| watched.path.starts_with(dir))
+                .map(|(&wd, _)| wd)
+                .collect::<Vec<_>>();
+            for wd in wds {
+                inotify::remove_watch(self.fd, wd).ok();
+                self.dirs.remove(&wd);
+            }
+            self.ignored_dirs.retain(|path| !path.starts_with(dir));
+            self.ignored_dirs_changed = true;
+        }
+    }
+
+    fn is_ignored_dir_name(name: &OsStr) -> bool {
+        IGNORED_DIR_NAMES
+            .iter()
+            .any(|ignored| name == OsStr::new(ignored))
+    }
+
+    fn is_ignored_dir(git_ignore: &GitIgnoreFile, path: &Path) -> bool {
+        // Paths that can't be listed in the ignored-dirs file are watched.
+        path.to_str()
+            .is_some_and(|path| !path.contains('\n') && git_ignore.matches(&format!("{path}/")))
+    }
+}
+
 /// Filesystem monitor integration using Watchman
 /// (<https://facebook.github.io/watchman/>). Requires `watchman` to already be
 /// installed on the system.
@@ -16,6 +16,7 @@
 
 use std::borrow::Borrow;
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::default::Default;
@@ -643,6 +644,10 @@ async fn import_refs_inner(
             target: new_target.clone(),
             state: if old_remote_ref != RemoteRef::absent_ref() {
                 old_remote_ref.state
+            } else if is_bookmark_deleted_from_other_remote(mut_repo.view(), symbol) {
+                // Don't resurrect the local bookmark which has been deleted
+                // from another remote.
+                RemoteRefState::New
             } else {
                 default_remote_ref_state_for(GitRefKind::Bookmark, symbol, options)
             },
@@ -652,8 +657,9 @@ async fn import_refs_inner(
         }
         // Remote-tracking branch is the last known state of the branch in the remote.
         // It shouldn't diverge even if we had inconsistent view.
-        mut_repo.set_remote_bookmark(symbol, new_remote_ref);
+        set_remote_bookmark_or_tombstone(mut_repo, symbol, new_remote_ref);
     }
+    prune_bookmark_tombstones(mut_repo);
     for (symbol, (old_remote_ref, new_target)) in &changed_remote_tags {
         let symbol = symbol.as_ref();
         let base_target = old_remote_ref.tracked_target();
@@ -939,6 +945,58 @@ fn collect_changed_remote_tags_to_import(
     Ok(())
 }
 
+/// Returns true if the local bookmark doesn't exist and the bookmark is known
+/// to have been deleted from a remote other than `symbol.remote`.
+fn is_bookmark_deleted_from_other_remote(view: &View, symbol: RemoteRefSymbol<'_>) -> bool {
+    symbol.remote != REMOTE_NAME_FOR_LOCAL_GIT_REPO
+        && view.get_local_bookmark(symbol.name).is_absent()
+        && view.remote_views().any(|(remote, remote_view)| {
+            remote != symbol.remote && remote_view.bookmark_tombstones.contains(symbol.name)
+        })
+}
+
+/// Sets the remote bookmark, or records tombstone if the tracked remote
+/// bookmark has been deleted from the remote. If the local bookmark still
+/// exists, the absent ref is kept tracked by the local bookmark instead.
+fn set_remote_bookmark_or_tombstone(
+    mut_repo: &mut MutableRepo,
+    symbol: RemoteRefSymbol<'_>,
+    remote_ref: RemoteRef,
+) {
+    if remote_ref.is_absent()
+        && remote_ref.is_tracked()
+        && symbol.remote != REMOTE_NAME_FOR_LOCAL_GIT_REPO
+        && mut_repo.get_local_bookmark(symbol.name).is_absent()
+    {
+        mut_repo.add_remote_bookmark_tombstone(symbol);
+    } else {
+        mut_repo.set_remote_bookmark(symbol, remote_ref);
+    }
+}
+
+/// Removes tombstones of bookmarks which can't be resurrected from another
+/// remote. That's the case if the local bookmark exists, or if there's no
+/// other remote which hasn't seen the deletion of the bookmark.
+fn prune_bookmark_tombstones(mut_repo: &mut MutableRepo) {
+    let view = mut_repo.view();
+    let can_be_resurrected = |symbol: RemoteRefSymbol<'_>| {
+        view.get_local_bookmark(symbol.name).is_absent()
+            && view.remote_views().any(|(remote, remote_view)| {
+                remote != symbol.remote
+                    && remote != REMOTE_NAME_FOR_LOCAL_GIT_REPO
+                    && !remote_view.bookmark_tombstones.contains(symbol.name)
+            })
+    };
+    let stale_tombstones = view
+        .all_remote_bookmark_tombstones()
+        .filter(|&symbol| !can_be_resurrected(symbol))
+        .map(|symbol| symbol.to_owned())
+        .collect_vec();
+    for symbol in stale_tombstones {
+        mut_repo.set_remote_bookmark(symbol.as_ref(), RemoteRef::absent());
+    }
+}
+
 fn default_remote_ref_state_for(
     kind: GitRefKind,
     symbol: RemoteRefSymbol<'_>,
@@ -992,50 +1050,88 @@ fn remotely_pinned_commit_ids(view: &View) -> Vec<CommitId> {
 
 /// Imports HEAD from the underlying Git repo.
 ///
+/// The HEADs of the linked worktrees are imported as well. They are recorded
+/// separately by worktree name, and don't affect the working-copy commit.
+///
 /// Unlike `import_refs()`, the old HEAD branch is not abandoned because HEAD
 /// move doesn't always mean the old HEAD branch has been rewritten.
 ///
 /// Unlike `reset_head()`, this function doesn't move the working-copy commit to
 /// the child of the new HEAD revision.
 pub async fn import_head(mut_repo: &mut MutableRepo) -> Result<(), GitImportError> {
-    let store = mut_repo.store();
-    let git_backend = get_git_backend(store)?;
-    let git_repo = git_backend.git_repo();
+    let git_repo = get_git_repo(mut_repo.store())?;
 
-    let old_git_head = mut_repo.view().git_head();
-    let new_git_head_id = if let Ok(oid) = git_repo.head_id() {
-        Some(CommitId::from_bytes(oid.as_bytes()))
-    } else {
-        None
-    };
-    if old_git_head.as_resolved() == Some(&new_git_head_id) {
-        return Ok(());
+    let new_git_head_id = git_repo
+        .head_id()
+        .ok()
+        .map(|oid| CommitId::from_bytes(oid.as_bytes()));
+    if mut_repo.view().git_head().as_resolved() != Some(&new_git_head_id) {
+        if let Some(head_id) = &new_git_head_id {
+            import_head_commit(mut_repo, head_id).await?;
+        }
+        mut_repo.set_git_head_target(RefTarget::resolved(new_git_head_id));
+    }
+
+    let mut new_worktree_heads = BTreeMap::new();
+    for worktree in git_repo.worktrees().map_err(GitImportError::from_git)? {
+        let name = worktree.id().to_string();
+        // The worktree directory may have been deleted without pruning.
+        let head_id = worktree
+            .into_repo_with_possibly_inaccessible_worktree()
+            .ok()
+            .and_then(|repo| {
+                let oid = repo.head_id().ok()?;
+                Some(CommitId::from_bytes(oid.as_bytes()))
+            });
+        if let Some(head_id) = head_id {
+            new_worktree_heads.insert(name, head_id);
+        }
     }
-
-    // Import new head
-    if let Some(head_id) = &new_git_head_id {
-        let index = mut_repo.index();
-        if !index.has_id(head_id)? {
-            git_backend.import_head_commits([head_id]).map_err(|err| {
-                GitImportError::MissingHeadTarget {
-                    id: head_id.clone(),
-                    err,
-                }
-            })?;
+    let old_worktree_names = mut_repo
+        .view()
+        .git_worktree_heads()
+        .keys()
+        .filter(|name| !new_worktree_heads.contains_key(*name))
+        .cloned()
+        .collect_vec();
+    for name in old_worktree_names {
+        mut_repo.set_git_worktree_head_target(&name, RefTarget::absent());
+    }
+    for (name, head_id) in new_worktree_heads {
+        if mut_repo.view().get_git_worktree_head(&name).as_normal() == Some(&head_id) {
+            continue;
         }
-        // It's unlikely the imported commits were missing, but I/O-related
-        // error can still occur.
-        let commit = store
-            .get_commit_async(head_id)
-            .await
-            .map_err(GitImportError::Backend)?;
-        mut_repo
-            .add_head(&commit)
-            .await
-            .map_err(GitImportError::Backend)?;
+        import_head_commit(mut_repo, &head_id).await?;
+        mut_repo.set_git_worktree_head_target(&name, RefTarget::normal(head_id));
     }
+    Ok(())
+}
 
-    mut_repo.set_git_head_target(RefTarget::resolved(new_git_head_id));
+async fn import_head_commit(
+    mut_repo: &mut MutableRepo,
+    head_id: &CommitId,
+) -> Result<(), GitImportError> {
+    let store = mut_repo.store();
+    let git_backend = get_git_backend(store)?;
+    let index = mut_repo.index();
+    if !index.has_id(head_id)? {
+        git_backend.import_head_commits([head_id]).map_err(|err| {
+            GitImportError::MissingHeadTarget {
+                id: head_id.clone(),
+                err,
+            }
+        })?;
+    }
+    // It's unlikely the imported commits were missing, but I/O-related
+    // error can still occur.
+    let commit = store
+        .get_commit_async(head_id)
+        .await
+        .map_err(GitImportError::Backend)?;
+    mut_repo
+        .add_head(&commit)
+        .await
+        .map_err(GitImportError::Backend)?;
     Ok(())
 }
 
@@ -1075,6 +1171,9 @@ pub enum FailedRefExportReason {
     /// We wanted to modify it, but Git had deleted it
     #[error("Modified ref had been deleted in Git")]
     ModifiedInJjDeletedInGit,
+    /// The branch is checked out in the linked Git worktree of the given name
+    #[error("Branch is checked out in Git worktree {0}")]
+    CheckedOutInWorktree(String),
     /// Failed to delete the ref from the Git repo
     #[error("Failed to delete")]
     FailedToDelete(#[source] Box<dyn std::error::Error + Send + Sync>),
@@ -1113,6 +1212,19 @@ struct RefsToExport {
     failed: Vec<(RemoteRefSymbolBuf, FailedRefExportReason)>,
 }
 
+impl RefsToExport {
+    /// Moves the ref to the failed list if it is to be updated or deleted.
+    fn reject(&mut self, symbol: RemoteRefSymbol<'_>, reason: FailedRefExportReason) {
+        let old_len = self.to_update.len() + self.to_delete.len();
+        self.to_update.retain(|(s, _)| s.as_ref() != symbol);
+        self.to_delete.retain(|(s, _)| s.as_ref() != symbol);
+        if self.to_update.len() + self.to_delete.len() != old_len {
+            self.failed.push((symbol.to_owned(), reason));
+            self.failed.sort_by(|(s1, _), (s2, _)| s1.cmp(s2));
+        }
+    }
+}
+
 /// Export changes to bookmarks and tags made in the Jujutsu repo compared to
 /// our last seen view of the Git repo in `mut_repo.view().git_refs()`.
 ///
@@ -1136,11 +1248,34 @@ pub fn export_some_refs(
         Some(value)
     }
 
-    let AllRefsToExport { bookmarks, tags } = diff_refs_to_export(
+    let AllRefsToExport {
+        mut bookmarks,
+        tags,
+    } = diff_refs_to_export(
         mut_repo.view(),
         mut_repo.store().root_commit_id(),
         &git_ref_filter,
     );
+    let git_repo = get_git_repo(mut_repo.store())?;
+
+    // Moving a branch checked out in a linked worktree would leave the
+    // worktree in inconsistent state, and Git refuses to do that as well.
+    // Unlike the colocated HEAD, the worktree HEAD isn't ours to detach.
+    for worktree in git_repo.worktrees().map_err(GitExportError::from_git)? {
+        let name = worktree.id().to_string();
+        let Ok(worktree_repo) = worktree.into_repo_with_possibly_inaccessible_worktree() else {
+            continue;
+        };
+        let Ok(Some(head_name)) = worktree_repo.head_name() else {
+            continue;
+        };
+        if let Some((GitRefKind::Bookmark, symbol)) = str::from_utf8(head_name.as_bstr())
+            .ok()
+            .and_then(|name| parse_git_ref(name.as_ref()))
+        {
+            bookmarks.reject(symbol, FailedRefExportReason::CheckedOutInWorktree(name));
+        }
+    }
 
     let check_and_detach_head = |git_repo: &gix::Repository| -> Result<(), GitExportError> {
         let Ok(head_ref) = git_repo.find_reference("HEAD") else {
@@ -1187,14 +1322,7 @@ pub fn export_some_refs(
         Ok(())
     };
 
-    let git_repo = get_git_repo(mut_repo.store())?;
-
     check_and_detach_head(&git_repo)?;
-    for worktree in git_repo.worktrees().map_err(GitExportError::from_git)? {
-        if let Ok(worktree_repo) = worktree.into_repo_with_possibly_inaccessible_worktree() {
-            check_and_detach_head(&worktree_repo)?;
-        }
-    }
 
     let failed_bookmarks = export_refs_to_git(mut_repo, &git_repo, GitRefKind::Bookmark, bookmarks);
     let failed_tags = export_refs_to_git(mut_repo, &git_repo, GitRefKind::Tag, tags);
@@ -2906,6 +3034,7 @@ impl<'a> GitFetch<'a> {
         }: ExpandedFetchRefSpecs,
         callback: &mut dyn GitSubprocessCallback,
         depth: Option<NonZeroU32>,
+        filter: Option<GitFetchFilter>,
         fetch_tags_override: Option<FetchTagsOverride>,
     ) -> Result<(), GitFetchError> {
         validate_remote_name(remote_name)?;
@@ -2939,6 +3068,7 @@ impl<'a> GitFetch<'a> {
                 &negative_refspecs,
                 callback,
                 depth,
+                filter,
                 fetch_tags_override,
             )?;
             let failing_refspec = match status {
@@ -3124,12 +3254,14 @@ pub fn push_refs(
             .is_err()
     };
     for (name, update) in pushed_bookmark_updates().filter(|(name, _)| is_exported_bookmark(name)) {
+        let symbol = name.to_remote_symbol(remote);
         let new_remote_ref = RemoteRef {
             target: RefTarget::resolved(update.after.clone()),
             state: RemoteRefState::Tracked,
         };
-        mut_repo.set_remote_bookmark(name.to_remote_symbol(remote), new_remote_ref);
+        set_remote_bookmark_or_tombstone(mut_repo, symbol, new_remote_ref);
     }
+    prune_bookmark_tombstones(mut_repo);
 
     // TODO: Maybe we can add new stats type which stores RemoteRefSymbol in
     // place of GitRefName, and remove unexported_bookmarks from the original
@@ -3236,6 +3368,27 @@ pub enum FetchTagsOverride {
     NoTags,
 }
 
+/// Object filter to create a partial clone with
+///
+/// Objects omitted by the filter are fetched from the remote on demand.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum GitFetchFilter {
+    /// Omit all blobs (`blob:none`)
+    BlobNone,
+    /// Omit all trees and blobs (`tree:0`)
+    TreeZero,
+}
+
+impl GitFetchFilter {
+    /// Returns the filter spec as understood by `git fetch --filter`.
+    pub fn to_git_format(self) -> &'static str {
+        match self {
+            Self::BlobNone => "blob:none",
+            Self::TreeZero => "tree:0",
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
@@ -31,12 +31,15 @@ use std::str::Utf8Error;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::MutexGuard;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::time::SystemTime;
 
 use async_trait::async_trait;
 use futures::StreamExt as _;
 use futures::stream::BoxStream;
 use gix::bstr::BString;
+use gix::bstr::ByteSlice as _;
 use gix::objs::CommitRefIter;
 use gix::objs::WriteTo as _;
 use itertools::Itertools as _;
@@ -77,11 +80,16 @@ use crate::file_util::BadPathEncoding;
 use crate::file_util::IoResultExt as _;
 use crate::file_util::PathError;
 use crate::git::GitSettings;
+use crate::git::GitSubprocessOptions;
+use crate::git_subprocess::GitSubprocessContext;
+use crate::git_subprocess::GitSubprocessError;
 use crate::index::Index;
 use crate::lock::FileLock;
 use crate::merge::Merge;
 use crate::merge::MergeBuilder;
 use crate::object_id::ObjectId;
+use crate::ref_name::RemoteName;
+use crate::ref_name::RemoteNameBuf;
 use crate::repo_path::RepoPath;
 use crate::repo_path::RepoPathBuf;
 use crate::repo_path::RepoPathComponentBuf;
@@ -148,6 +156,26 @@ pub enum GitBackendError {
     ReadMetadata(#[source] TableStoreError),
     #[error("Failed to write non-git metadata")]
     WriteMetadata(#[source] TableStoreError),
+    #[error(
+        "Object is missing from the partial clone and could not be fetched from remote \
+         '{remote}'",
+        remote = remote.as_symbol()
+    )]
+    FetchMissingObject {
+        remote: RemoteNameBuf,
+        #[source]
+        source: GitSubprocessError,
+    },
+    #[error(
+        "Object is missing from the partial clone and remote '{remote}' can't be reached to \
+         fetch it",
+        remote = remote.as_symbol()
+    )]
+    FetchMissingObjectOffline {
+        remote: RemoteNameBuf,
+        #[source]
+        source: GitSubprocessError,
+    },
 }
 
 impl From<GitBackendError> for BackendError {
@@ -179,6 +207,9 @@ pub struct GitBackend {
     cached_extra_metadata: Mutex<Option<Arc<ReadonlyTable>>>,
     git_executable: PathBuf,
     write_change_id_header: bool,
+    promisor_remote: Mutex<Option<RemoteNameBuf>>,
+    // Set when prefetching failed, so we don't keep trying while offline.
+    prefetch_failed: AtomicBool,
 }
 
 impl GitBackend {
@@ -195,6 +226,7 @@ impl GitBackend {
         let root_commit_id = CommitId::from_bytes(&[0; HASH_LENGTH]);
         let root_change_id = ChangeId::from_bytes(&[0; CHANGE_ID_LENGTH]);
         let empty_tree_id = TreeId::from_hex("4b825dc642cb6eb9a060e54bf8d69288fbee4904");
+        let promisor_remote = Mutex::new(read_promisor_remote(base_repo.path()));
         Self {
             base_repo,
             repo,
@@ -206,6 +238,8 @@ impl GitBackend {
             cached_extra_metadata: Mutex::new(None),
             git_executable: git_settings.executable_path,
             write_change_id_header: git_settings.write_change_id_header,
+            promisor_remote,
+            prefetch_failed: AtomicBool::new(false),
         }
     }
 
@@ -450,12 +484,100 @@ impl GitBackend {
         self.save_extra_metadata_table(mut_table, &table_lock)
     }
 
+    /// Looks up the object by `git_id`, fetching it from the promisor remote
+    /// if it is missing from a partial clone.
+    fn find_object<'repo>(
+        &self,
+        repo: &'repo gix::Repository,
+        git_id: gix::ObjectId,
+        id: &impl ObjectId,
+    ) -> BackendResult<gix::Object<'repo>> {
+        let err = match repo.find_object(git_id) {
+            Ok(object) => return Ok(object),
+            Err(err) => err,
+        };
+        if !matches!(err, gix::object::find::existing::Error::NotFound { .. }) {
+            return Err(to_read_object_err(err, id));
+        }
+        let Some(remote) = self.promisor_remote() else {
+            return Err(map_not_found_err(err, id));
+        };
+        self.fetch_missing_objects(&remote, &[git_id])
+            .map_err(|err| to_read_object_err(err, id))?;
+        repo.find_object(git_id)
+            .map_err(|err| map_not_found_err(err, id))
+    }
+
+    /// Fetches the missing objects referenced by `git_tree` in one go, so they
+    /// won't be fetched one at a time when the tree entries are read.
+    ///
+    /// This is a best-effort optimization. If the objects can't be fetched,
+    /// the error is reported when they are read.
+    fn prefetch_tree_entries(&self, repo: &gix::Repository, git_tree: &gix::Tree<'_>) {
+        // Don't reread the config file for each tree. If the repository has
+        // been turned into a partial clone after it was loaded, the remote
+        // will be found by find_object() instead.
+        let Some(remote) = self.promisor_remote.lock().unwrap().clone() else {
+            return;
+        };
+        if self.prefetch_failed.load(Ordering::Relaxed) {
+            return;
+        }
+        let missing_ids = git_tree
+            .iter()
+            .filter_map(Result::ok)
+            .filter(|entry| !entry.mode().is_commit())
+            .map(|entry| entry.oid().to_owned())
+            .filter(|oid| !repo.has_object(oid))
+            .collect_vec();
+        if let Err(err) = self.fetch_missing_objects(&remote, &missing_ids) {
+            tracing::debug!(?err, "failed to prefetch missing objects");
+            self.prefetch_failed.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn fetch_missing_objects(
+        &self,
+        remote: &RemoteName,
+        object_ids: &[gix::ObjectId],
+    ) -> Result<(), GitBackendError> {
+        let options = GitSubprocessOptions {
+            executable_path: self.git_executable.clone(),
+            environment: Default::default(),
+        };
+        GitSubprocessContext::from_git_backend(self, options)
+            .spawn_fetch_missing_objects(remote, object_ids)
+            .map_err(|source| {
+                let remote = remote.to_owned();
+                match source {
+                    GitSubprocessError::NoSuchRepository(_)
+                    | GitSubprocessError::RemoteUnreachable(_) => {
+                        GitBackendError::FetchMissingObjectOffline { remote, source }
+                    }
+                    _ => GitBackendError::FetchMissingObject { remote, source },
+                }
+            })
+    }
+
+    /// Returns the remote to fetch missing objects from if the repository is
+    /// a partial clone.
+    ///
+    /// If the repository wasn't a partial clone, the config file is reread
+    /// from disk because it might have been turned into one by
+    /// `git fetch --filter` after it was loaded.
+    fn promisor_remote(&self) -> Option<RemoteNameBuf> {
+        let mut promisor_remote = self.promisor_remote.lock().unwrap();
+        if promisor_remote.is_none() {
+            *promisor_remote = read_promisor_remote(self.git_repo_path());
+        }
+        promisor_remote.clone()
+    }
+
     fn read_file_sync(&self, id: &FileId) -> BackendResult<Vec<u8>> {
         let git_blob_id = validate_git_object_id(id)?;
         let locked_repo = self.lock_git_repo();
-        let mut blob = locked_repo
-            .find_object(git_blob_id)
-            .map_err(|err| map_not_found_err(err, id))?
+        let mut blob = self
+            .find_object(&locked_repo, git_blob_id, id)?
             .try_into_blob()
             .map_err(|err| to_read_object_err(err, id))?;
         Ok(blob.take_data())
@@ -497,8 +619,7 @@ impl GitBackend {
         // TODO(kfm): probably want to do something here if it is a merge
         let tree_id = tree.first().clone();
         let gix_id = validate_git_object_id(&tree_id)?;
-        repo.find_object(gix_id)
-            .map_err(|err| map_not_found_err(err, &tree_id))?
+        self.find_object(repo, gix_id, &tree_id)?
             .try_into_tree()
             .map_err(|err| to_read_object_err(err, &tree_id))
     }
@@ -890,6 +1011,30 @@ fn run_git_gc(program: &OsStr, git_dir: &Path, keep_newer: SystemTime) -> Result
     Ok(())
 }
 
+/// Reads the name of the remote to fetch missing objects from if the
+/// repository at `git_repo_path` is a partial clone.
+fn read_promisor_remote(git_repo_path: &Path) -> Option<RemoteNameBuf> {
+    let config = gix::config::File::from_path_no_includes(
+        git_repo_path.join("config"),
+        gix::config::Source::Local,
+    )
+    .ok()?;
+    if let Some(name) = config.string("extensions.partialClone") {
+        return Some(name.to_str_lossy().into_owned().into());
+    }
+    config
+        .sections_by_name("remote")?
+        .filter_map(|section| section.header().subsection_name())
+        .map(|name| name.to_str_lossy().into_owned())
+        .find(|name| {
+            let key = format!("remote.{name}.promisor");
+            config
+                .boolean(key.as_str())
+                .is_some_and(|value| value == Ok(true))
+        })
+        .map(RemoteNameBuf::from)
+}
+
 fn validate_git_object_id(id: &impl ObjectId) -> BackendResult<gix::ObjectId> {
     if id.as_bytes().len() != HASH_LENGTH {
         return Err(BackendError::InvalidHashLength {
@@ -1032,9 +1177,8 @@ impl Backend for GitBackend {
     async fn read_symlink(&self, _path: &RepoPath, id: &SymlinkId) -> BackendResult<String> {
         let git_blob_id = validate_git_object_id(id)?;
         let locked_repo = self.lock_git_repo();
-        let mut blob = locked_repo
-            .find_object(git_blob_id)
-            .map_err(|err| map_not_found_err(err, id))?
+        let mut blob = self
+            .find_object(&locked_repo, git_blob_id, id)?
             .try_into_blob()
             .map_err(|err| to_read_object_err(err, id))?;
         let target = String::from_utf8(blob.take_data())
@@ -1079,11 +1223,11 @@ impl Backend for GitBackend {
         let git_tree_id = validate_git_object_id(id)?;
 
         let locked_repo = self.lock_git_repo();
-        let git_tree = locked_repo
-            .find_object(git_tree_id)
-            .map_err(|err| map_not_found_err(err, id))?
+        let git_tree = self
+            .find_object(&locked_repo, git_tree_id, id)?
             .try_into_tree()
             .map_err(|err| to_read_object_err(err, id))?;
+        self.prefetch_tree_entries(&locked_repo, &git_tree);
         let mut entries: Vec<_> = git_tree
             .iter()
             .map(|entry| -> BackendResult<_> {
@@ -1200,9 +1344,7 @@ impl Backend for GitBackend {
 
         let mut commit = {
             let locked_repo = self.lock_git_repo();
-            let git_object = locked_repo
-                .find_object(git_commit_id)
-                .map_err(|err| map_not_found_err(err, id))?;
+            let git_object = self.find_object(&locked_repo, git_commit_id, id)?;
             let is_shallow = self.shallow_root_ids(&locked_repo)?.contains(id);
             commit_from_git_without_root_parent(id, &git_object, is_shallow)?
         };
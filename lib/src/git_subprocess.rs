@@ -29,6 +29,7 @@ use itertools::Itertools as _;
 use thiserror::Error;
 
 use crate::git::FetchTagsOverride;
+use crate::git::GitFetchFilter;
 use crate::git::GitPushOptions;
 use crate::git::GitPushStats;
 use crate::git::GitSubprocessOptions;
@@ -71,6 +72,8 @@ pub enum GitSubprocessError {
          {MINIMUM_GIT_VERSION})"
     )]
     UnsupportedGitOption(String),
+    #[error("Could not reach the remote: {0}")]
+    RemoteUnreachable(String),
     #[error("Git process failed: {0}")]
     External(String),
 }
@@ -163,6 +166,7 @@ impl GitSubprocessContext {
     ///
     /// [`GitFetchStatus::NoRemoteRef`] is returned if ref doesn't exist. Note
     /// that `git` only returns one failed ref at a time.
+    #[expect(clippy::too_many_arguments)]
     pub(crate) fn spawn_fetch(
         &self,
         remote_name: &RemoteName,
@@ -170,6 +174,7 @@ impl GitSubprocessContext {
         negative_refspecs: &[NegativeRefSpec],
         callback: &mut dyn GitSubprocessCallback,
         depth: Option<NonZeroU32>,
+        filter: Option<GitFetchFilter>,
         fetch_tags_override: Option<FetchTagsOverride>,
     ) -> Result<GitFetchStatus, GitSubprocessError> {
         if refspecs.is_empty() {
@@ -186,6 +191,9 @@ impl GitSubprocessContext {
         if let Some(d) = depth {
             command.arg(format!("--depth={d}"));
         }
+        if let Some(filter) = filter {
+            command.arg(format!("--filter={}", filter.to_git_format()));
+        }
         match fetch_tags_override {
             Some(FetchTagsOverride::AllTags) => {
                 command.arg("--tags");
@@ -230,6 +238,40 @@ impl GitSubprocessContext {
         Ok(())
     }
 
+    /// Fetch the given objects missing from a partial clone
+    ///
+    /// This is what Git does internally when it hits a missing object in a
+    /// partial clone: the objects are requested by id from the promisor
+    /// remote without negotiating or updating any refs.
+    pub(crate) fn spawn_fetch_missing_objects(
+        &self,
+        remote_name: &RemoteName,
+        object_ids: &[gix::ObjectId],
+    ) -> Result<(), GitSubprocessError> {
+        if object_ids.is_empty() {
+            return Ok(());
+        }
+        tracing::debug!(?object_ids, "fetching missing objects");
+        let mut command = self.create_command();
+        command.stdout(Stdio::null());
+        command.args(["-c", "fetch.negotiationAlgorithm=noop"]);
+        // Don't fetch blobs reachable from the requested trees
+        command.args([
+            "fetch",
+            "--no-tags",
+            "--no-write-fetch-head",
+            "--recurse-submodules=no",
+            "--filter=blob:none",
+            "--",
+            remote_name.as_str(),
+        ]);
+        command.args(object_ids.iter().map(|id| id.to_string()));
+
+        let output = wait_with_output(self.spawn_cmd(command)?)?;
+
+        parse_git_fetch_missing_objects_output(output)
+    }
+
     /// How we retrieve the remote's default branch:
     ///
     /// `git remote show <remote_name>`
@@ -346,6 +388,22 @@ fn parse_no_such_remote(stderr: &[u8]) -> Option<String> {
         .map(|remote| remote.to_str_lossy().into_owned())
 }
 
+/// Returns the error message if the remote couldn't be reached, e.g. because
+/// the network is down
+///
+/// Depending on the transport, git prints out either a first line of the form:
+/// `fatal: unable to access '<url>': <reason>`
+/// or the reason followed by:
+/// `fatal: Could not read from remote repository.`
+fn parse_remote_unreachable(stderr: &[u8]) -> Option<String> {
+    let first_line = stderr.lines().next()?;
+    let is_unreachable = first_line.starts_with(b"fatal: unable to access '")
+        || stderr
+            .lines()
+            .any(|line| line == b"fatal: Could not read from remote repository.");
+    is_unreachable.then(|| first_line.to_str_lossy().into_owned())
+}
+
 /// Parse error from refspec not present on the remote
 ///
 /// This returns
@@ -520,6 +578,27 @@ fn parse_git_branch_prune_output(output: Output) -> Result<(), GitSubprocessErro
     Err(external_git_error(&output.stderr))
 }
 
+fn parse_git_fetch_missing_objects_output(output: Output) -> Result<(), GitSubprocessError> {
+    if output.status.success() {
+        return Ok(());
+    }
+
+    // There are some git errors we want to parse out
+    if let Some(option) = parse_unknown_option(&output.stderr) {
+        return Err(GitSubprocessError::UnsupportedGitOption(option));
+    }
+
+    if let Some(remote) = parse_no_such_remote(&output.stderr) {
+        return Err(GitSubprocessError::NoSuchRepository(remote));
+    }
+
+    if let Some(message) = parse_remote_unreachable(&output.stderr) {
+        return Err(GitSubprocessError::RemoteUnreachable(message));
+    }
+
+    Err(external_git_error(&output.stderr))
+}
+
 fn parse_git_remote_show_output(output: Output) -> Result<Output, GitSubprocessError> {
     if output.status.success() {
         return Ok(output);
@@ -1031,6 +1110,34 @@ Done";
         assert_eq!(parse_no_such_remote(SAMPLE_OK_STDERR), None);
     }
 
+    #[test]
+    fn test_parse_remote_unreachable() {
+        assert_eq!(
+            parse_remote_unreachable(
+                b"fatal: unable to access 'https://example.com/repo.git/': Could not resolve host: \
+                  example.com\n"
+            ),
+            Some(
+                "fatal: unable to access 'https://example.com/repo.git/': Could not resolve \
+                 host: example.com"
+                    .to_owned()
+            )
+        );
+        assert_eq!(
+            parse_remote_unreachable(
+                b"ssh: Could not resolve hostname example.com: Name or service not known\n\
+                  fatal: Could not read from remote repository.\n\n\
+                  Please make sure you have the correct access rights\n\
+                  and the repository exists.\n"
+            ),
+            Some(
+                "ssh: Could not resolve hostname example.com: Name or service not known".to_owned()
+            )
+        );
+        assert_eq!(parse_remote_unreachable(SAMPLE_NO_REMOTE_REF_ERROR), None);
+        assert_eq!(parse_remote_unreachable(SAMPLE_OK_STDERR), None);
+    }
+
     #[test]
     fn test_parse_no_remote_ref() {
         assert_eq!(parse_no_remote_ref(SAMPLE_NO_SUCH_REPOSITORY_ERROR), None);
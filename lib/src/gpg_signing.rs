@@ -17,12 +17,26 @@
 use std::ffi::OsString;
 use std::fmt::Debug;
 use std::io;
+use std::io::BufRead as _;
+use std::io::BufReader;
 use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
 use std::process;
+use std::process::Child;
+use std::process::ChildStdin;
+use std::process::ChildStdout;
 use std::process::Command;
 use std::process::ExitStatus;
 use std::process::Stdio;
-
+use std::sync::Mutex;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use blake2::Blake2b512;
+use blake2::Digest as _;
+use rayon::iter::IntoParallelRefIterator as _;
+use rayon::iter::ParallelIterator as _;
 use thiserror::Error;
 
 use crate::config::ConfigGetError;
@@ -31,6 +45,7 @@ use crate::signing::SigStatus;
 use crate::signing::SignError;
 use crate::signing::SigningBackend;
 use crate::signing::Verification;
+use crate::signing::hash_trust_files;
 
 /// Search for one of these in the output from `--status-fd=1`.
 ///
@@ -128,6 +143,56 @@ fn run_verify_command(command: &mut Command, input: &[u8]) -> Result<process::Ou
     }
 }
 
+/// Returns the GnuPG home directory, which contains the keyrings and the trust
+/// database.
+fn gnupg_home_dir(extra_args: &[OsString]) -> Option<PathBuf> {
+    let mut args = extra_args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--homedir" {
+            return args.next().map(PathBuf::from);
+        } else if let Some(dir) = arg.to_str().and_then(|arg| arg.strip_prefix("--homedir=")) {
+            return Some(dir.into());
+        }
+    }
+    if let Some(dir) = std::env::var_os("GNUPGHOME") {
+        Some(dir.into())
+    } else if cfg!(windows) {
+        std::env::var_os("APPDATA").map(|dir| PathBuf::from(dir).join("gnupg"))
+    } else {
+        etcetera::home_dir().ok().map(|dir| dir.join(".gnupg"))
+    }
+}
+
+/// Hashes the given files in the GnuPG home directory.
+fn hash_gnupg_files(extra_args: &[OsString], file_names: &[&str]) -> Option<Vec<u8>> {
+    let home_dir = gnupg_home_dir(extra_args)?;
+    let paths = file_names
+        .iter()
+        .map(|name| home_dir.join(name))
+        .collect::<Vec<_>>();
+    Some(hash_trust_files(paths.iter().map(PathBuf::as_path)))
+}
+
+/// Hashes the given files in the GnuPG home directory, and the output of the
+/// key listing `command`. The listing includes the validity of each key as of
+/// now, so the digest changes once a key expires.
+fn hash_gnupg_state(
+    extra_args: &[OsString],
+    file_names: &[&str],
+    command: &mut Command,
+) -> Option<Vec<u8>> {
+    let files_hash = hash_gnupg_files(extra_args, file_names)?;
+    tracing::info!(?command, "running GPG key listing command");
+    let output = command.stderr(Stdio::null()).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let mut hasher = Blake2b512::new();
+    hasher.update(files_hash);
+    hasher.update(&output.stdout);
+    Some(hasher.finalize().to_vec())
+}
+
 fn write_temp_file(prefix: &str, content: &[u8]) -> io::Result<tempfile::TempPath> {
     let mut file = tempfile::Builder::new().prefix(prefix).tempfile()?;
     file.write_all(content)?;
@@ -135,12 +200,110 @@ fn write_temp_file(prefix: &str, content: &[u8]) -> io::Result<tempfile::TempPat
     Ok(file.into_temp_path())
 }
 
+/// Decodes an ASCII-armored OpenPGP signature.
+fn dearmor_signature(signature: &[u8]) -> Option<Vec<u8>> {
+    let mut lines = str::from_utf8(signature).ok()?.lines().map(str::trim);
+    if lines.next()? != "-----BEGIN PGP SIGNATURE-----" {
+        return None;
+    }
+    let mut encoded = String::new();
+    for line in lines.skip_while(|line| line.contains(": ")) {
+        if line == "-----END PGP SIGNATURE-----" {
+            return BASE64_STANDARD.decode(encoded).ok();
+        } else if !line.starts_with('=') {
+            // Skip the checksum, which gpg verifies as part of the packets
+            // anyway.
+            encoded.push_str(line);
+        }
+    }
+    None
+}
+
+/// Combines a detached signature and the signed data into an OpenPGP signed
+/// message: the signature packets followed by a literal data packet.
+fn signed_message(data: &[u8], signature: &[u8]) -> Option<Vec<u8>> {
+    let mut message = dearmor_signature(signature)?;
+    // The literal data is binary ('b'), without file name and date.
+    let header = [b'b', 0, 0, 0, 0, 0];
+    let len = u32::try_from(header.len() + data.len()).ok()?;
+    // New-format packet with tag 11 and a five-octet length
+    message.extend_from_slice(&[0xc0 | 11, 0xff]);
+    message.extend_from_slice(&len.to_be_bytes());
+    message.extend_from_slice(&header);
+    message.extend_from_slice(data);
+    Some(message)
+}
+
+/// Long-lived `gpg --multifile --verify` process, which verifies the signed
+/// messages whose paths are written to its stdin one by one. The status
+/// lines of each message are reported between `FILE_START` and `FILE_DONE`.
+#[derive(Debug)]
+struct GpgVerifier {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl GpgVerifier {
+    fn spawn(command: &mut Command) -> io::Result<Self> {
+        tracing::info!(?command, "starting GPG verification process");
+        let mut child = command
+            .args(["--batch", "--keyid-format=long", "--status-fd=1"])
+            .args(["--multifile", "--verify"])
+            .stderr(Stdio::null())
+            .spawn()?;
+        let stdin = child.stdin.take();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        Ok(Self {
+            child,
+            stdin,
+            stdout,
+        })
+    }
+
+    /// Verifies the signed message at `path`. Returns the status lines, and
+    /// whether the process can verify more messages. gpg exits after a bad
+    /// signature.
+    fn verify_file(&mut self, path: &Path) -> io::Result<(Vec<u8>, bool)> {
+        let stdin = self.stdin.as_mut().unwrap();
+        stdin.write_all(path.as_os_str().as_encoded_bytes())?;
+        stdin.write_all(b"\n")?;
+        stdin.flush()?;
+        let mut output = vec![];
+        let mut started = false;
+        let mut line = vec![];
+        loop {
+            line.clear();
+            if self.stdout.read_until(b'\n', &mut line)? == 0 {
+                return Ok((output, false));
+            }
+            if line.starts_with(b"[GNUPG:] FILE_START ") {
+                started = true;
+            } else if line.starts_with(b"[GNUPG:] FILE_DONE") {
+                return Ok((output, true));
+            } else if started {
+                output.extend_from_slice(&line);
+            }
+        }
+    }
+}
+
+impl Drop for GpgVerifier {
+    fn drop(&mut self) {
+        // gpg exits once its input is closed
+        drop(self.stdin.take());
+        self.child.wait().ok();
+    }
+}
+
 #[derive(Debug)]
 pub struct GpgBackend {
     program: OsString,
     allow_expired_keys: bool,
     extra_args: Vec<OsString>,
     default_key: String,
+    /// Verification process kept running across calls to `verify_batch()`.
+    verifier: Mutex<Option<GpgVerifier>>,
 }
 
 #[derive(Debug, Error)]
@@ -167,6 +330,7 @@ impl GpgBackend {
             allow_expired_keys,
             extra_args: vec![],
             default_key,
+            verifier: Mutex::new(None),
         }
     }
 
@@ -199,6 +363,35 @@ impl GpgBackend {
             .args(&self.extra_args);
         command
     }
+
+    /// Verifies a signature with the long-lived verification process, which
+    /// is started if needed. Returns `None` if the process couldn't tell the
+    /// result, in which case the signature should be verified on its own.
+    fn verify_with(
+        &self,
+        verifier: &mut Option<GpgVerifier>,
+        data: &[u8],
+        signature: &[u8],
+    ) -> Option<Result<Verification, SignError>> {
+        let message = signed_message(data, signature)?;
+        let message_path = write_temp_file(".jj-gpg-msg-tmp-", &message).ok()?;
+        if verifier.is_none() {
+            *verifier = GpgVerifier::spawn(&mut self.create_command())
+                .inspect_err(|err| tracing::warn!(?err, "failed to start GPG verification"))
+                .ok();
+        }
+        let (output, is_alive) = match verifier.as_mut()?.verify_file(&message_path) {
+            Ok(result) => result,
+            Err(err) => {
+                tracing::warn!(?err, "GPG verification process failed");
+                (vec![], false)
+            }
+        };
+        if !is_alive {
+            *verifier = None;
+        }
+        parse_gpg_verify_output(&output, self.allow_expired_keys)
+    }
 }
 
 impl SigningBackend for GpgBackend {
@@ -232,6 +425,28 @@ impl SigningBackend for GpgBackend {
         parse_gpg_verify_output(&output.stdout, self.allow_expired_keys)
             .unwrap_or_else(|| Err(make_command_error(&output).into()))
     }
+
+    fn verify_batch(&self, signatures: &[(&[u8], &[u8])]) -> Vec<Result<Verification, SignError>> {
+        // Feed the signatures to a single gpg process instead of spawning one
+        // per signature.
+        let mut verifier = self.verifier.lock().unwrap();
+        signatures
+            .iter()
+            .map(|(data, signature)| {
+                self.verify_with(&mut verifier, data, signature)
+                    .unwrap_or_else(|| self.verify(data, signature))
+            })
+            .collect()
+    }
+
+    fn trust_state(&self) -> Option<Vec<u8>> {
+        hash_gnupg_state(
+            &self.extra_args,
+            &["pubring.kbx", "pubring.gpg", "trustdb.gpg"],
+            self.create_command()
+                .args(["--with-colons", "--fixed-list-mode", "--list-keys"]),
+        )
+    }
 }
 
 #[derive(Debug)]
@@ -316,6 +531,22 @@ impl SigningBackend for GpgsmBackend {
         parse_gpg_verify_output(&output.stdout, self.allow_expired_keys)
             .unwrap_or_else(|| Err(make_command_error(&output).into()))
     }
+
+    fn verify_batch(&self, signatures: &[(&[u8], &[u8])]) -> Vec<Result<Verification, SignError>> {
+        // Each verification spawns a gpgsm process, so run them in parallel.
+        signatures
+            .par_iter()
+            .map(|(data, signature)| self.verify(data, signature))
+            .collect()
+    }
+
+    fn trust_state(&self) -> Option<Vec<u8>> {
+        hash_gnupg_state(
+            &self.extra_args,
+            &["pubring.kbx", "trustlist.txt"],
+            self.create_command().args(["--with-colons", "--list-keys"]),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -72,6 +72,7 @@ pub mod local_working_copy;
 pub mod lock;
 pub mod matchers;
 pub mod merge;
+pub mod merge_driver;
 pub mod merged_tree;
 pub mod merged_tree_builder;
 pub mod object_id;
@@ -79,10 +80,14 @@ pub mod op_heads_store;
 pub mod op_store;
 pub mod op_walk;
 pub mod operation;
+pub mod opset;
+mod opset_parser;
+pub mod patch;
 #[expect(missing_docs)]
 pub mod protos;
 pub mod ref_name;
 pub mod refs;
+pub mod remote_backend;
 pub mod repo;
 pub mod repo_path;
 pub mod revset;
@@ -100,6 +105,8 @@ pub mod tree_merge;
 pub mod simple_backend;
 pub mod simple_op_heads_store;
 pub mod simple_op_store;
+pub mod sparse;
+pub mod ssh_agent_signing;
 pub mod ssh_signing;
 pub mod stacked_table;
 pub mod store;
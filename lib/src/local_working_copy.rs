@@ -85,9 +85,12 @@ use crate::file_util::check_symlink_support;
 use crate::file_util::copy_async_to_sync;
 use crate::file_util::persist_temp_file;
 use crate::file_util::symlink_file;
+use crate::fileset::FilesetParseError;
 use crate::fsmonitor::FsmonitorSettings;
 #[cfg(feature = "watchman")]
 use crate::fsmonitor::WatchmanConfig;
+#[cfg(target_os = "linux")]
+use crate::fsmonitor::inotify;
 #[cfg(feature = "watchman")]
 use crate::fsmonitor::watchman;
 use crate::gitignore::GitIgnoreFile;
@@ -114,7 +117,12 @@ use crate::repo_path::RepoPath;
 use crate::repo_path::RepoPathBuf;
 use crate::repo_path::RepoPathComponent;
 use crate::settings::UserSettings;
+use crate::sparse::SparseFileset;
+use crate::sparse::SparsePatterns;
+use crate::sparse::SparseProfile;
+use crate::sparse::resolve_sparse_profile;
 use crate::store::Store;
+use crate::submodule_store::read_submodule_head;
 use crate::working_copy::CheckoutError;
 use crate::working_copy::CheckoutStats;
 use crate::working_copy::LockedWorkingCopy;
@@ -123,6 +131,7 @@ use crate::working_copy::SnapshotError;
 use crate::working_copy::SnapshotOptions;
 use crate::working_copy::SnapshotProgress;
 use crate::working_copy::SnapshotStats;
+use crate::working_copy::SparseProfileFailure;
 use crate::working_copy::UntrackedReason;
 use crate::working_copy::WorkingCopy;
 use crate::working_copy::WorkingCopyFactory;
@@ -650,18 +659,45 @@ fn is_file_state_entries_proto_unique_and_sorted(
 
 fn sparse_patterns_from_proto(
     proto: Option<&crate::protos::local_working_copy::SparsePatterns>,
-) -> Vec<RepoPathBuf> {
-    let mut sparse_patterns = vec![];
+) -> Result<SparsePatterns, FilesetParseError> {
     if let Some(proto_sparse_patterns) = proto {
-        for prefix in &proto_sparse_patterns.prefixes {
-            sparse_patterns.push(RepoPathBuf::from_internal_string(prefix).unwrap());
+        if let Some(fileset) = &proto_sparse_patterns.fileset {
+            return Ok(SparsePatterns::Fileset(SparseFileset::parse(fileset)?));
         }
+        let prefixes = proto_sparse_patterns
+            .prefixes
+            .iter()
+            .map(|prefix| RepoPathBuf::from_internal_string(prefix).unwrap())
+            .collect();
+        Ok(SparsePatterns::Prefixes(prefixes))
     } else {
         // For compatibility with old working copies.
         // TODO: Delete this is late 2022 or so.
-        sparse_patterns.push(RepoPathBuf::root());
+        Ok(SparsePatterns::all())
+    }
+}
+
+fn sparse_patterns_to_proto(
+    sparse_patterns: &SparsePatterns,
+    sparse_profile: Option<&str>,
+) -> crate::protos::local_working_copy::SparsePatterns {
+    let profile = sparse_profile.map(str::to_owned);
+    match sparse_patterns {
+        SparsePatterns::Prefixes(prefixes) => crate::protos::local_working_copy::SparsePatterns {
+            prefixes: prefixes
+                .iter()
+                .map(|path| path.as_internal_file_string().to_owned())
+                .collect(),
+            fileset: None,
+            profile,
+        },
+        SparsePatterns::Fileset(fileset) => crate::protos::local_working_copy::SparsePatterns {
+            // Older versions would check out nothing if this were empty.
+            prefixes: vec![RepoPath::root().as_internal_file_string().to_owned()],
+            fileset: Some(fileset.as_str().to_owned()),
+            profile,
+        },
     }
-    sparse_patterns
 }
 
 /// Creates intermediate directories from the `working_copy_path` to the
@@ -936,6 +972,7 @@ fn file_state(metadata: &Metadata) -> Result<Option<FileState>, MtimeOutOfRange>
 struct FsmonitorMatcher {
     matcher: Option<Box<dyn Matcher>>,
     watchman_clock: Option<crate::protos::local_working_copy::WatchmanClock>,
+    inotify_clock: Option<crate::protos::local_working_copy::InotifyClock>,
 }
 
 /// Settings specific to the tree state of the [`LocalWorkingCopy`] backend.
@@ -972,8 +1009,9 @@ pub struct TreeState {
     state_path: PathBuf,
     tree: MergedTree,
     file_states: FileStatesMap,
-    // Currently only path prefixes
-    sparse_patterns: Vec<RepoPathBuf>,
+    sparse_patterns: SparsePatterns,
+    /// Name of the sparse profile `sparse_patterns` were resolved from.
+    sparse_profile: Option<String>,
     own_mtime: MillisSinceEpoch,
     symlink_support: bool,
 
@@ -981,6 +1019,9 @@ pub struct TreeState {
     /// the repo is configured to use the Watchman filesystem monitor and
     /// Watchman has been queried at least once.
     watchman_clock: Option<crate::protos::local_working_copy::WatchmanClock>,
+    /// The position in the journal of the inotify daemon at the last snapshot.
+    /// Will only be set if the repo is configured to use the inotify monitor.
+    inotify_clock: Option<crate::protos::local_working_copy::InotifyClock>,
 
     conflict_marker_style: ConflictMarkerStyle,
     exec_policy: ExecChangePolicy,
@@ -1001,6 +1042,11 @@ pub enum TreeStateError {
     WriteTreeState { path: PathBuf, source: io::Error },
     #[error("Persisting tree state to file {path}")]
     PersistTreeState { path: PathBuf, source: io::Error },
+    #[error("Parsing sparse patterns in tree state from {path}")]
+    ParseSparsePatterns {
+        path: PathBuf,
+        source: FilesetParseError,
+    },
     #[error("Filesystem monitor error")]
     Fsmonitor(#[source] Box<dyn Error + Send + Sync>),
 }
@@ -1018,12 +1064,16 @@ impl TreeState {
         self.file_states.all()
     }
 
-    pub fn sparse_patterns(&self) -> &Vec<RepoPathBuf> {
+    pub fn sparse_patterns(&self) -> &SparsePatterns {
         &self.sparse_patterns
     }
 
+    pub fn sparse_profile(&self) -> Option<&str> {
+        self.sparse_profile.as_deref()
+    }
+
     fn sparse_matcher(&self) -> Box<dyn Matcher> {
-        Box::new(PrefixMatcher::new(&self.sparse_patterns))
+        self.sparse_patterns.to_matcher()
     }
 
     pub fn init(
@@ -1055,10 +1105,12 @@ impl TreeState {
             state_path,
             tree: store.empty_merged_tree(),
             file_states: FileStatesMap::new(),
-            sparse_patterns: vec![RepoPathBuf::root()],
+            sparse_patterns: SparsePatterns::all(),
+            sparse_profile: None,
             own_mtime: MillisSinceEpoch(0),
             symlink_support: check_symlink_support().unwrap_or(false),
             watchman_clock: None,
+            inotify_clock: None,
             conflict_marker_style: *conflict_marker_style,
             exec_policy,
             fsmonitor_settings: fsmonitor_settings.clone(),
@@ -1135,8 +1187,18 @@ impl TreeState {
         }
         self.file_states =
             FileStatesMap::from_proto(proto.file_states, proto.is_file_states_sorted);
-        self.sparse_patterns = sparse_patterns_from_proto(proto.sparse_patterns.as_ref());
+        self.sparse_patterns =
+            sparse_patterns_from_proto(proto.sparse_patterns.as_ref()).map_err(|err| {
+                TreeStateError::ParseSparsePatterns {
+                    path: tree_state_path.to_owned(),
+                    source: err,
+                }
+            })?;
+        self.sparse_profile = proto
+            .sparse_patterns
+            .and_then(|proto_sparse_patterns| proto_sparse_patterns.profile);
         self.watchman_clock = proto.watchman_clock;
+        self.inotify_clock = proto.inotify_clock;
         Ok(())
     }
 
@@ -1153,14 +1215,12 @@ impl TreeState {
         proto.file_states = self.file_states.data.clone();
         // `FileStatesMap` is guaranteed to be sorted.
         proto.is_file_states_sorted = true;
-        let mut sparse_patterns = crate::protos::local_working_copy::SparsePatterns::default();
-        for path in &self.sparse_patterns {
-            sparse_patterns
-                .prefixes
-                .push(path.as_internal_file_string().to_owned());
-        }
-        proto.sparse_patterns = Some(sparse_patterns);
+        proto.sparse_patterns = Some(sparse_patterns_to_proto(
+            &self.sparse_patterns,
+            self.sparse_profile(),
+        ));
         proto.watchman_clock = self.watchman_clock.clone();
+        proto.inotify_clock = self.inotify_clock.clone();
 
         let wrap_write_err = |source| TreeStateError::WriteTreeState {
             path: self.state_path.clone(),
@@ -1190,6 +1250,25 @@ impl TreeState {
         self.watchman_clock.take();
     }
 
+    #[cfg(target_os = "linux")]
+    #[instrument(skip(self))]
+    pub fn query_inotify(&self) -> Result<(inotify::Clock, Option<Vec<PathBuf>>), TreeStateError> {
+        let previous_clock = self.inotify_clock.clone().map(inotify::Clock::from);
+        inotify::query_changed_files(
+            &inotify::daemon_dir(&self.state_path),
+            previous_clock.as_ref(),
+        )
+        .map_err(|err| TreeStateError::Fsmonitor(Box::new(err)))
+    }
+
+    /// Returns the directories the inotify daemon doesn't watch because
+    /// they're ignored.
+    #[cfg(target_os = "linux")]
+    pub fn inotify_ignored_dirs(&self) -> Result<Vec<PathBuf>, TreeStateError> {
+        inotify::ignored_dirs(&inotify::daemon_dir(&self.state_path))
+            .map_err(|err| TreeStateError::Fsmonitor(Box::new(err)))
+    }
+
     #[cfg(feature = "watchman")]
     #[instrument(skip(self))]
     pub async fn query_watchman(
@@ -1273,6 +1352,7 @@ impl TreeState {
         let FsmonitorMatcher {
             matcher: fsmonitor_matcher,
             watchman_clock,
+            inotify_clock,
         } = self
             .make_fsmonitor_matcher(&self.fsmonitor_settings)
             .await?;
@@ -1288,6 +1368,7 @@ impl TreeState {
         if matcher.visit(RepoPath::root()).is_nothing() {
             // No need to load the current tree, set up channels, etc.
             self.watchman_clock = watchman_clock;
+            self.inotify_clock = inotify_clock;
             return Ok((is_dirty, SnapshotStats::default()));
         }
 
@@ -1379,6 +1460,11 @@ impl TreeState {
         } else {
             tracing::info!("not updating watchman clock because there are untracked files");
         }
+        if stats.untracked_paths.is_empty() || inotify_clock.is_none() {
+            self.inotify_clock = inotify_clock;
+        } else {
+            tracing::info!("not updating inotify clock because there are untracked files");
+        }
         Ok((is_dirty, stats))
     }
 
@@ -1387,15 +1473,21 @@ impl TreeState {
         &self,
         fsmonitor_settings: &FsmonitorSettings,
     ) -> Result<FsmonitorMatcher, SnapshotError> {
-        let (watchman_clock, changed_files) = match fsmonitor_settings {
-            FsmonitorSettings::None => (None, None),
-            FsmonitorSettings::Test { changed_files } => (None, Some(changed_files.clone())),
+        // Directories the monitor doesn't watch are rescanned along with the
+        // changed files.
+        #[cfg_attr(not(target_os = "linux"), expect(unused_mut))]
+        let mut unwatched_dirs: Vec<PathBuf> = vec![];
+        let (watchman_clock, inotify_clock, changed_files) = match fsmonitor_settings {
+            FsmonitorSettings::None => (None, None, None),
+            FsmonitorSettings::Test { changed_files } => (None, None, Some(changed_files.clone())),
             #[cfg(feature = "watchman")]
             FsmonitorSettings::Watchman(config) => match self.query_watchman(config).await {
-                Ok((watchman_clock, changed_files)) => (Some(watchman_clock.into()), changed_files),
+                Ok((watchman_clock, changed_files)) => {
+                    (Some(watchman_clock.into()), None, changed_files)
+                }
                 Err(err) => {
                     tracing::warn!(?err, "Failed to query filesystem monitor");
-                    (None, None)
+                    (None, None, None)
                 }
             },
             #[cfg(not(feature = "watchman"))]
@@ -1407,6 +1499,29 @@ impl TreeState {
                         .into(),
                 });
             }
+            #[cfg(target_os = "linux")]
+            FsmonitorSettings::Inotify => match self
+                .query_inotify()
+                .and_then(|result| Ok((result, self.inotify_ignored_dirs()?)))
+            {
+                Ok(((inotify_clock, changed_files), ignored_dirs)) => {
+                    unwatched_dirs = ignored_dirs;
+                    (None, Some(inotify_clock.into()), changed_files)
+                }
+                Err(err) => {
+                    tracing::warn!(?err, "Failed to query filesystem monitor");
+                    (None, None, None)
+                }
+            },
+            #[cfg(not(target_os = "linux"))]
+            FsmonitorSettings::Inotify => {
+                return Err(SnapshotError::Other {
+                    message: "Failed to query the filesystem monitor".to_string(),
+                    err: "The inotify filesystem monitor is only supported on Linux (consider \
+                          disabling `fsmonitor.backend`)"
+                        .into(),
+                });
+            }
         };
         let matcher: Option<Box<dyn Matcher>> = match changed_files {
             None => None,
@@ -1426,6 +1541,11 @@ impl TreeState {
                                 (basename.as_internal_str() == ".gitignore")
                                     .then(|| parent.to_owned())
                             })
+                            .chain(
+                                unwatched_dirs
+                                    .iter()
+                                    .filter_map(|path| RepoPathBuf::from_relative_path(path).ok()),
+                            )
                             .collect_vec();
                         (repo_paths, gitignore_prefixes)
                     });
@@ -1445,6 +1565,7 @@ impl TreeState {
         Ok(FsmonitorMatcher {
             matcher,
             watchman_clock,
+            inotify_clock,
         })
     }
 }
@@ -1575,6 +1696,9 @@ impl FileSnapshotter<'_> {
         if let Some(file_state) = &maybe_current_file_state
             && file_state.file_type == FileType::GitSubmodule
         {
+            if self.matcher.matches(&path) {
+                self.process_git_submodule(path, &entry.path()).await?;
+            }
             return Ok(None);
         }
 
@@ -1678,13 +1802,15 @@ impl FileSnapshotter<'_> {
     /// Visits only paths we're already tracking.
     async fn visit_tracked_files(&self, file_states: FileStates<'_>) -> Result<(), SnapshotError> {
         for (tracked_path, current_file_state) in file_states {
-            if current_file_state.file_type == FileType::GitSubmodule {
-                continue;
-            }
             if !self.matcher.matches(tracked_path) {
                 continue;
             }
             let disk_path = tracked_path.to_fs_path(&self.tree_state.working_copy_path)?;
+            if current_file_state.file_type == FileType::GitSubmodule {
+                self.process_git_submodule(tracked_path.to_owned(), &disk_path)
+                    .await?;
+                continue;
+            }
             let metadata = match disk_path.symlink_metadata() {
                 Ok(metadata) => Some(metadata),
                 Err(err) if err.kind() == io::ErrorKind::NotFound => None,
@@ -1739,6 +1865,28 @@ impl FileSnapshotter<'_> {
         Ok(())
     }
 
+    /// Records the commit checked out in the submodule if it moved. The
+    /// submodule is left unchanged if it isn't a Git repository on disk.
+    async fn process_git_submodule(
+        &self,
+        path: RepoPathBuf,
+        disk_path: &Path,
+    ) -> Result<(), SnapshotError> {
+        let head = read_submodule_head(disk_path).map_err(|err| SnapshotError::Other {
+            message: format!("Failed to read submodule HEAD in {}", disk_path.display()),
+            err: err.into(),
+        })?;
+        let Some(commit_id) = head else {
+            return Ok(());
+        };
+        let current_tree_values = self.current_tree.path_value(&path).await?;
+        let new_tree_values = Merge::normal(TreeValue::GitSubmodule(commit_id));
+        if new_tree_values != current_tree_values {
+            self.tree_entries_tx.send((path, new_tree_values)).ok();
+        }
+        Ok(())
+    }
+
     /// Emits file paths that don't exist in the `present_entries`.
     fn emit_deleted_files(
         &self,
@@ -2136,20 +2284,28 @@ impl TreeState {
         Ok(stats)
     }
 
-    pub fn set_sparse_patterns(
+    /// Checks out `new_tree` while changing the sparse patterns. Each file is
+    /// updated at most once. The sparse profile in use, if any, is kept.
+    pub fn check_out_with_sparse_patterns(
         &mut self,
-        sparse_patterns: Vec<RepoPathBuf>,
+        new_tree: &MergedTree,
+        sparse_patterns: SparsePatterns,
     ) -> Result<CheckoutStats, CheckoutError> {
-        let tree = self.tree.clone();
-        let old_matcher = PrefixMatcher::new(&self.sparse_patterns);
-        let new_matcher = PrefixMatcher::new(&sparse_patterns);
-        let added_matcher = DifferenceMatcher::new(&new_matcher, &old_matcher);
-        let removed_matcher = DifferenceMatcher::new(&old_matcher, &new_matcher);
+        let old_tree = self.tree.clone();
+        let old_matcher = self.sparse_patterns.to_matcher();
+        let new_matcher = sparse_patterns.to_matcher();
+        let kept_matcher = IntersectionMatcher::new(old_matcher.as_ref(), new_matcher.as_ref());
+        let added_matcher = DifferenceMatcher::new(new_matcher.as_ref(), old_matcher.as_ref());
+        let removed_matcher = DifferenceMatcher::new(old_matcher.as_ref(), new_matcher.as_ref());
         let empty_tree = self.store.empty_merged_tree();
-        let added_stats = self.update(&empty_tree, &tree, &added_matcher).block_on()?;
+        let kept_stats = self.update(&old_tree, new_tree, &kept_matcher).block_on()?;
+        let added_stats = self
+            .update(&empty_tree, new_tree, &added_matcher)
+            .block_on()?;
         let removed_stats = self
-            .update(&tree, &empty_tree, &removed_matcher)
+            .update(&old_tree, &empty_tree, &removed_matcher)
             .block_on()?;
+        self.tree = new_tree.clone();
         self.sparse_patterns = sparse_patterns;
         assert_eq!(added_stats.updated_files, 0);
         assert_eq!(added_stats.removed_files, 0);
@@ -2157,13 +2313,37 @@ impl TreeState {
         assert_eq!(removed_stats.added_files, 0);
         assert_eq!(removed_stats.skipped_files, 0);
         Ok(CheckoutStats {
-            updated_files: 0,
-            added_files: added_stats.added_files,
-            removed_files: removed_stats.removed_files,
-            skipped_files: added_stats.skipped_files,
+            updated_files: kept_stats.updated_files,
+            added_files: kept_stats.added_files + added_stats.added_files,
+            removed_files: kept_stats.removed_files + removed_stats.removed_files,
+            skipped_files: kept_stats.skipped_files + added_stats.skipped_files,
+            failed_submodules: vec![],
+            updated_sparse_profile: None,
+            failed_sparse_profile: None,
         })
     }
 
+    pub fn set_sparse_patterns(
+        &mut self,
+        sparse_patterns: SparsePatterns,
+    ) -> Result<CheckoutStats, CheckoutError> {
+        let tree = self.tree.clone();
+        let stats = self.check_out_with_sparse_patterns(&tree, sparse_patterns)?;
+        self.sparse_profile = None;
+        Ok(stats)
+    }
+
+    pub fn set_sparse_profile(
+        &mut self,
+        profile: SparseProfile,
+    ) -> Result<CheckoutStats, CheckoutError> {
+        let (name, sparse_patterns) = profile.into_parts();
+        let tree = self.tree.clone();
+        let stats = self.check_out_with_sparse_patterns(&tree, sparse_patterns)?;
+        self.sparse_profile = Some(name);
+        Ok(stats)
+    }
+
     async fn update(
         &mut self,
         old_tree: &MergedTree,
@@ -2177,6 +2357,9 @@ impl TreeState {
             added_files: 0,
             removed_files: 0,
             skipped_files: 0,
+            failed_submodules: vec![],
+            updated_sparse_profile: None,
+            failed_sparse_profile: None,
         };
         let mut changed_file_states = Vec::new();
         let mut deleted_files = HashSet::new();
@@ -2195,7 +2378,8 @@ impl TreeState {
             }
 
             // Existing Git submodule can be a non-empty directory on disk. We
-            // shouldn't attempt to manage it as a tracked path.
+            // shouldn't attempt to manage it as a tracked path. The submodule
+            // itself is checked out by the SubmoduleStore.
             //
             // TODO: It might be better to add general support for paths not
             // tracked by jj than processing submodules specially. For example,
@@ -2204,7 +2388,6 @@ impl TreeState {
             if matches!(before.as_normal(), Some(TreeValue::GitSubmodule(_)))
                 && matches!(after, MaterializedTreeValue::GitSubmodule(_))
             {
-                eprintln!("ignoring git submodule at {path:?}");
                 // Not updating the file state as if there were no diffs. Leave
                 // the state type as FileType::GitSubmodule if it was before.
                 return Ok(());
@@ -2307,10 +2490,7 @@ impl TreeState {
                             .await?
                     }
                 }
-                MaterializedTreeValue::GitSubmodule(_) => {
-                    eprintln!("ignoring git submodule at {path:?}");
-                    FileState::for_gitsubmodule()
-                }
+                MaterializedTreeValue::GitSubmodule(_) => FileState::for_gitsubmodule(),
                 MaterializedTreeValue::Tree(_) => {
                     panic!("unexpected tree entry in diff at {path:?}");
                 }
@@ -2431,10 +2611,7 @@ impl TreeState {
                             FileType::Normal { exec_bit }
                         }
                         TreeValue::Symlink(_id) => FileType::Symlink,
-                        TreeValue::GitSubmodule(_id) => {
-                            eprintln!("ignoring git submodule at {path:?}");
-                            FileType::GitSubmodule
-                        }
+                        TreeValue::GitSubmodule(_id) => FileType::GitSubmodule,
                         TreeValue::Tree(_id) => {
                             panic!("unexpected tree entry in diff at {path:?}");
                         }
@@ -2560,10 +2737,14 @@ impl WorkingCopy for LocalWorkingCopy {
         Ok(self.tree_state()?.current_tree())
     }
 
-    fn sparse_patterns(&self) -> Result<&[RepoPathBuf], WorkingCopyStateError> {
+    fn sparse_patterns(&self) -> Result<&SparsePatterns, WorkingCopyStateError> {
         Ok(self.tree_state()?.sparse_patterns())
     }
 
+    fn sparse_profile(&self) -> Result<Option<&str>, WorkingCopyStateError> {
+        Ok(self.tree_state()?.sparse_profile())
+    }
+
     fn start_mutation(&self) -> Result<Box<dyn LockedWorkingCopy>, WorkingCopyStateError> {
         let lock_path = self.state_path.join("working_copy.lock");
         let lock = FileLock::lock(lock_path).map_err(|err| WorkingCopyStateError {
@@ -2796,13 +2977,36 @@ impl LockedWorkingCopy for LockedLocalWorkingCopy {
         // continue an interrupted update if we find such a file.
         let new_tree = commit.tree();
         let tree_state = self.wc.tree_state_mut()?;
-        if tree_state.tree.tree_ids_and_labels() != new_tree.tree_ids_and_labels() {
+        let mut new_sparse_patterns = None;
+        let mut failed_sparse_profile = None;
+        if let Some(name) = tree_state.sparse_profile.clone() {
+            match resolve_sparse_profile(&new_tree, &name).await {
+                Ok(profile) if profile.patterns() != &tree_state.sparse_patterns => {
+                    new_sparse_patterns = Some((name, profile.into_parts().1));
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    let message =
+                        iter::successors(Some(&err as &dyn Error), |&err| err.source()).join(": ");
+                    failed_sparse_profile = Some(SparseProfileFailure { name, message });
+                }
+            }
+        }
+        let mut stats = if let Some((name, sparse_patterns)) = new_sparse_patterns {
+            let mut stats =
+                tree_state.check_out_with_sparse_patterns(&new_tree, sparse_patterns)?;
+            stats.updated_sparse_profile = Some(name);
+            self.tree_state_dirty = true;
+            stats
+        } else if tree_state.tree.tree_ids_and_labels() != new_tree.tree_ids_and_labels() {
             let stats = tree_state.check_out(&new_tree)?;
             self.tree_state_dirty = true;
-            Ok(stats)
+            stats
         } else {
-            Ok(CheckoutStats::default())
-        }
+            CheckoutStats::default()
+        };
+        stats.failed_sparse_profile = failed_sparse_profile;
+        Ok(stats)
     }
 
     fn rename_workspace(&mut self, new_name: WorkspaceNameBuf) {
@@ -2823,13 +3027,17 @@ impl LockedWorkingCopy for LockedLocalWorkingCopy {
         Ok(())
     }
 
-    fn sparse_patterns(&self) -> Result<&[RepoPathBuf], WorkingCopyStateError> {
+    fn sparse_patterns(&self) -> Result<&SparsePatterns, WorkingCopyStateError> {
         self.wc.sparse_patterns()
     }
 
+    fn sparse_profile(&self) -> Result<Option<&str>, WorkingCopyStateError> {
+        self.wc.sparse_profile()
+    }
+
     async fn set_sparse_patterns(
         &mut self,
-        new_sparse_patterns: Vec<RepoPathBuf>,
+        new_sparse_patterns: SparsePatterns,
     ) -> Result<CheckoutStats, CheckoutError> {
         // TODO: Write a "pending_checkout" file with new sparse patterns so we can
         // continue an interrupted update if we find such a file.
@@ -2841,6 +3049,15 @@ impl LockedWorkingCopy for LockedLocalWorkingCopy {
         Ok(stats)
     }
 
+    async fn set_sparse_profile(
+        &mut self,
+        profile: SparseProfile,
+    ) -> Result<CheckoutStats, CheckoutError> {
+        let stats = self.wc.tree_state_mut()?.set_sparse_profile(profile)?;
+        self.tree_state_dirty = true;
+        Ok(stats)
+    }
+
     #[instrument(skip_all)]
     async fn finish(
         mut self: Box<Self>,
@@ -3098,4 +3315,16 @@ mod tests {
             assert_eq!(system_time_to_millis(time), None);
         }
     }
+
+    #[test]
+    fn test_sparse_patterns_proto_fileset() {
+        let sparse_patterns = SparsePatterns::Fileset(SparseFileset::parse("glob:'*.rs'").unwrap());
+        let proto = sparse_patterns_to_proto(&sparse_patterns, None);
+        // Versions without fileset support read the prefixes as all files
+        assert_eq!(proto.prefixes, [""]);
+        assert_eq!(
+            sparse_patterns_from_proto(Some(&proto)).unwrap(),
+            sparse_patterns
+        );
+    }
 }
@@ -0,0 +1,309 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! External commands which merge file contents the built-in merge can't
+//! resolve.
+//!
+//! Merge drivers are configured in the `merge-drivers` table. Each driver has
+//! a `command` and a list of fileset `patterns` selecting the files it can
+//! merge:
+//!
+//! ```toml
+//! [merge-drivers.cargo-lock]
+//! command = ["cargo-lock-merge", "$base", "$left", "$right", "-o", "$output"]
+//! patterns = ["glob:'**/Cargo.lock'"]
+//! ```
+//!
+//! The driver is invoked for 3-way conflicts in matching files, with the
+//! contents of the base and the two sides written to temporary files. If the
+//! command exits successfully, the contents of the `$output` file replace the
+//! conflict.
+//!
+//! Drivers aren't loaded by default. Callers which rewrite commits should set
+//! [`MergeDrivers`] in the merge options of the transaction, and report the
+//! recorded failures to the user.
+
+use std::fs;
+use std::io;
+use std::mem;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::thread;
+
+use futures::channel::oneshot;
+use regex::Captures;
+use regex::Regex;
+use thiserror::Error;
+
+use crate::config::ConfigGetError;
+use crate::fileset;
+use crate::fileset::FilesetAliasesMap;
+use crate::fileset::FilesetDiagnostics;
+use crate::fileset::FilesetExpression;
+use crate::fileset::FilesetParseContext;
+use crate::matchers::Matcher;
+use crate::merge::Merge;
+use crate::repo_path::RepoPath;
+use crate::repo_path::RepoPathBuf;
+use crate::repo_path::RepoPathUiConverter;
+use crate::settings::UserSettings;
+
+/// Error that may occur while running a merge driver.
+#[derive(Debug, Error)]
+pub enum MergeDriverError {
+    /// Failed to prepare the input files or to read the output file.
+    #[error("Failed to set up files for merge driver {name}")]
+    Io {
+        /// Driver name.
+        name: String,
+        /// Source error.
+        #[source]
+        source: io::Error,
+    },
+    /// Failed to spawn the command.
+    #[error("Failed to run merge driver {name}")]
+    Spawn {
+        /// Driver name.
+        name: String,
+        /// Source error.
+        #[source]
+        source: io::Error,
+    },
+    /// The command exited with non-zero status.
+    #[error("Merge driver {name} exited with {status}")]
+    Failed {
+        /// Driver name.
+        name: String,
+        /// Exit status of the command.
+        status: std::process::ExitStatus,
+    },
+}
+
+/// An external command which merges file contents.
+#[derive(Clone, Debug)]
+pub struct MergeDriver {
+    /// Name of the driver in the `merge-drivers` table.
+    pub name: String,
+    /// Command name and arguments. `$base`, `$left`, `$right`, `$output`, and
+    /// `$path` are substituted.
+    pub command: Vec<String>,
+    /// Files this driver is used for.
+    pub matcher: Arc<dyn Matcher>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+struct RawMergeDriver {
+    command: Vec<String>,
+    patterns: Vec<String>,
+}
+
+impl MergeDriver {
+    /// Loads merge drivers from the `merge-drivers` table of `settings`. The
+    /// drivers are sorted by name.
+    pub fn all_from_settings(settings: &UserSettings) -> Result<Vec<Self>, ConfigGetError> {
+        // Patterns are relative to the workspace root.
+        let path_converter = RepoPathUiConverter::Fs {
+            cwd: "".into(),
+            base: "".into(),
+        };
+        let context = FilesetParseContext {
+            aliases_map: &FilesetAliasesMap::new(),
+            path_converter: &path_converter,
+        };
+        let mut names = settings.table_keys("merge-drivers").collect::<Vec<_>>();
+        names.sort_unstable();
+        names
+            .into_iter()
+            .map(|name| {
+                let config_name = ["merge-drivers", name];
+                let raw: RawMergeDriver = settings.get(config_name)?;
+                let to_config_error =
+                    |error: Box<dyn std::error::Error + Send + Sync>| ConfigGetError::Type {
+                        name: format!("merge-drivers.{name}"),
+                        error,
+                        source_path: None,
+                    };
+                if raw.command.is_empty() {
+                    return Err(to_config_error("Command must not be empty".into()));
+                }
+                let expressions = raw
+                    .patterns
+                    .iter()
+                    .map(|pattern| {
+                        fileset::parse(&mut FilesetDiagnostics::new(), pattern, &context)
+                            .map_err(|err| to_config_error(err.into()))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Self {
+                    name: name.to_owned(),
+                    command: raw.command,
+                    matcher: FilesetExpression::union_all(expressions)
+                        .to_matcher()
+                        .into(),
+                })
+            })
+            .collect()
+    }
+
+    /// Runs the driver to merge 3-way conflict `contents` of the file at
+    /// `path`. Returns the merged contents.
+    ///
+    /// The command is run in a separate thread, so it doesn't stall the async
+    /// executor.
+    pub async fn run(
+        &self,
+        path: &RepoPath,
+        contents: &Merge<Vec<u8>>,
+    ) -> Result<Vec<u8>, MergeDriverError> {
+        assert_eq!(contents.num_sides(), 2);
+        let (sender, receiver) = oneshot::channel();
+        let driver = self.clone();
+        let path = path.to_owned();
+        let contents = contents.clone();
+        thread::spawn(move || {
+            sender.send(driver.run_blocking(&path, &contents)).ok();
+        });
+        receiver.await.expect("merge driver thread shouldn't panic")
+    }
+
+    fn run_blocking(
+        &self,
+        path: &RepoPath,
+        contents: &Merge<Vec<u8>>,
+    ) -> Result<Vec<u8>, MergeDriverError> {
+        let io_error = |source| MergeDriverError::Io {
+            name: self.name.clone(),
+            source,
+        };
+        let temp_dir = tempfile::Builder::new()
+            .prefix("jj-merge-driver-")
+            .tempdir()
+            .map_err(io_error)?;
+        let base_path = temp_dir.path().join("base");
+        let left_path = temp_dir.path().join("left");
+        let right_path = temp_dir.path().join("right");
+        let output_path = temp_dir.path().join("output");
+        fs::write(&base_path, contents.get_remove(0).unwrap()).map_err(io_error)?;
+        fs::write(&left_path, contents.get_add(0).unwrap()).map_err(io_error)?;
+        fs::write(&right_path, contents.get_add(1).unwrap()).map_err(io_error)?;
+        // The output starts with the left contents so it can be updated in place.
+        fs::write(&output_path, contents.get_add(0).unwrap()).map_err(io_error)?;
+
+        let substitute = |name: &str| -> Option<String> {
+            let path = match name {
+                "base" => &base_path,
+                "left" => &left_path,
+                "right" => &right_path,
+                "output" => &output_path,
+                "path" => return Some(path.as_internal_file_string().to_owned()),
+                _ => return None,
+            };
+            Some(path.to_string_lossy().into_owned())
+        };
+        let args = self
+            .command
+            .iter()
+            .map(|arg| interpolate_variables(arg, substitute))
+            .collect::<Vec<_>>();
+        tracing::info!(name = self.name, ?args, "running merge driver");
+        let status = Command::new(&args[0])
+            .args(&args[1..])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .status()
+            .map_err(|source| MergeDriverError::Spawn {
+                name: self.name.clone(),
+                source,
+            })?;
+        if !status.success() {
+            return Err(MergeDriverError::Failed {
+                name: self.name.clone(),
+                status,
+            });
+        }
+        fs::read(&output_path).map_err(io_error)
+    }
+}
+
+/// File which a merge driver failed to merge.
+#[derive(Debug)]
+pub struct MergeDriverFailure {
+    /// Path of the conflicted file.
+    pub path: RepoPathBuf,
+    /// Why the driver failed.
+    pub error: MergeDriverError,
+}
+
+/// Merge drivers to be used when merging trees.
+///
+/// Failures are recorded instead of being propagated, since the conflict can
+/// still be left in the tree. Clones share the recorded failures.
+#[derive(Clone, Debug, Default)]
+pub struct MergeDrivers {
+    drivers: Vec<MergeDriver>,
+    failures: Arc<Mutex<Vec<MergeDriverFailure>>>,
+}
+
+impl MergeDrivers {
+    /// Creates a set of `drivers` with no recorded failures.
+    pub fn new(drivers: Vec<MergeDriver>) -> Self {
+        Self {
+            drivers,
+            failures: Default::default(),
+        }
+    }
+
+    /// Merges 3-way conflict `contents` by the first driver configured for
+    /// `path`. Returns `None` if there's no such driver or if the driver
+    /// failed.
+    pub async fn try_merge(&self, path: &RepoPath, contents: &Merge<Vec<u8>>) -> Option<Vec<u8>> {
+        if contents.num_sides() != 2 {
+            return None;
+        }
+        let driver = self
+            .drivers
+            .iter()
+            .find(|driver| driver.matcher.matches(path))?;
+        match driver.run(path, contents).await {
+            Ok(content) => Some(content),
+            Err(error) => {
+                tracing::warn!(?error, ?path, "merge driver failed");
+                let failure = MergeDriverFailure {
+                    path: path.to_owned(),
+                    error,
+                };
+                self.failures.lock().unwrap().push(failure);
+                None
+            }
+        }
+    }
+
+    /// Takes the failures recorded so far.
+    pub fn take_failures(&self) -> Vec<MergeDriverFailure> {
+        mem::take(&mut self.failures.lock().unwrap())
+    }
+}
+
+fn interpolate_variables(arg: &str, substitute: impl Fn(&str) -> Option<String>) -> String {
+    static VARIABLE_REGEX: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"\$([a-z0-9_]+)\b").unwrap());
+    VARIABLE_REGEX
+        .replace_all(arg, |caps: &Captures| {
+            substitute(&caps[1]).unwrap_or_else(|| caps[0].to_owned())
+        })
+        .into_owned()
+}
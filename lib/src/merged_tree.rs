@@ -56,7 +56,8 @@ use crate::repo_path::RepoPathBuf;
 use crate::repo_path::RepoPathComponent;
 use crate::store::Store;
 use crate::tree::Tree;
-use crate::tree_merge::merge_trees;
+use crate::tree_merge::MergeOptions;
+use crate::tree_merge::merge_trees_with_options;
 
 /// Presents a view of a merged set of trees at the root directory, as well as
 /// conflict labels.
@@ -165,8 +166,15 @@ impl MergedTree {
     /// Tries to resolve any conflicts, resolving any conflicts that can be
     /// automatically resolved and leaving the rest unresolved.
     pub async fn resolve(self) -> BackendResult<Self> {
-        let merged = merge_trees(&self.store, self.tree_ids).await?;
-        // If the result can be resolved, then `merge_trees()` above would have returned
+        let store = self.store.clone();
+        self.resolve_with_options(store.merge_options()).await
+    }
+
+    /// Like [`Self::resolve()`], but resolves file conflicts with the given
+    /// `options` instead of the store's default options.
+    pub async fn resolve_with_options(self, options: &MergeOptions) -> BackendResult<Self> {
+        let merged = merge_trees_with_options(&self.store, self.tree_ids, options).await?;
+        // If the result can be resolved, then `merge_trees_with_options()` above would have returned
         // a resolved merge. However, that function will always preserve the arity of
         // conflicts it cannot resolve. So we simplify the conflict again
         // here to possibly reduce a complex conflict to a simpler one.
@@ -179,7 +187,9 @@ impl MergedTree {
         // particular, that this last simplification doesn't enable further automatic
         // resolutions
         if cfg!(debug_assertions) {
-            let re_merged = merge_trees(&self.store, simplified.clone()).await.unwrap();
+            let re_merged = merge_trees_with_options(&self.store, simplified.clone(), options)
+                .await
+                .unwrap();
             debug_assert_eq!(re_merged, simplified);
         }
         Ok(Self {
@@ -337,6 +347,17 @@ impl MergedTree {
         Self::merge_no_resolve(merge).resolve().await
     }
 
+    /// Like [`Self::merge()`], but resolves file conflicts with the given
+    /// `options` instead of the store's default options.
+    pub async fn merge_with_options(
+        merge: Merge<(Self, String)>,
+        options: &MergeOptions,
+    ) -> BackendResult<Self> {
+        Self::merge_no_resolve(merge)
+            .resolve_with_options(options)
+            .await
+    }
+
     /// Merges the provided trees into a single `MergedTree`, without attempting
     /// to resolve file conflicts.
     pub fn merge_no_resolve(merge: Merge<(Self, String)>) -> Self {
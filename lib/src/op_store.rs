@@ -16,6 +16,7 @@
 
 use std::any::Any;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Debug;
@@ -31,6 +32,7 @@ use crate::backend::CommitId;
 use crate::backend::MillisSinceEpoch;
 use crate::backend::Timestamp;
 use crate::content_hash::ContentHash;
+use crate::content_hash::DigestUpdate;
 use crate::merge::Merge;
 use crate::object_id::HexPrefix;
 use crate::object_id::ObjectId as _;
@@ -255,9 +257,11 @@ pub struct View {
     pub remote_views: BTreeMap<RemoteNameBuf, RemoteView>,
     pub git_refs: BTreeMap<GitRefNameBuf, RefTarget>,
     /// The commit the Git HEAD points to.
-    // TODO: Support multiple Git worktrees?
     // TODO: Do we want to store the current bookmark name too?
     pub git_head: RefTarget,
+    /// The commits the HEADs of the linked Git worktrees point to, keyed by
+    /// worktree name.
+    pub git_worktree_heads: BTreeMap<String, RefTarget>,
     // The commit that *should be* checked out in the workspace. Note that the working copy
     // (.jj/working_copy/) has the source of truth about which commit *is* checked out (to be
     // precise: the commit to which we most recently completed an update to).
@@ -274,20 +278,43 @@ impl View {
             remote_views: BTreeMap::new(),
             git_refs: BTreeMap::new(),
             git_head: RefTarget::absent(),
+            git_worktree_heads: BTreeMap::new(),
             wc_commit_ids: BTreeMap::new(),
         }
     }
 }
 
 /// Represents the state of the remote repo.
-#[derive(ContentHash, Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct RemoteView {
-    // TODO: Do we need to support tombstones for remote bookmarks? For example, if the bookmark
-    // has been deleted locally and you pull from a remote, maybe it should make a difference
-    // whether the bookmark is known to have existed on the remote. We may not want to resurrect
-    // the bookmark if the bookmark's state on the remote was just not known.
     pub bookmarks: BTreeMap<RefNameBuf, RemoteRef>,
     pub tags: BTreeMap<RefNameBuf, RemoteRef>,
+    /// Names of the bookmarks known to have been deleted from the remote.
+    ///
+    /// Unlike a missing entry, a tombstone tells that the bookmark shouldn't
+    /// be recreated locally from another remote which hasn't seen the deletion
+    /// yet. A name should never be in both `bookmarks` and this set.
+    pub bookmark_tombstones: BTreeSet<RefNameBuf>,
+}
+
+// Implemented manually so the hash of a view without tombstones stays the same
+// as before tombstones were introduced.
+impl ContentHash for RemoteView {
+    fn hash(&self, state: &mut impl DigestUpdate) {
+        let Self {
+            bookmarks,
+            tags,
+            bookmark_tombstones,
+        } = self;
+        bookmarks.hash(state);
+        tags.hash(state);
+        if !bookmark_tombstones.is_empty() {
+            state.update(&(bookmark_tombstones.len() as u64).to_le_bytes());
+            for name in bookmark_tombstones {
+                name.hash(state);
+            }
+        }
+    }
 }
 
 /// Iterates pair of local and remote refs by name.
@@ -501,6 +528,7 @@ impl dyn OpStore {
 #[cfg(test)]
 mod tests {
     use maplit::btreemap;
+    use maplit::btreeset;
 
     use super::*;
 
@@ -530,18 +558,21 @@ mod tests {
                     "bookmark2".into() => git_bookmark2_remote_ref.clone(),
                 },
                 tags: btreemap! {},
+                bookmark_tombstones: btreeset! {},
             },
             "remote1".into() => RemoteView {
                 bookmarks: btreemap! {
                     "bookmark1".into() => remote1_bookmark1_remote_ref.clone(),
                 },
                 tags: btreemap! {},
+                bookmark_tombstones: btreeset! {},
             },
             "remote2".into() => RemoteView {
                 bookmarks: btreemap! {
                     "bookmark2".into() => remote2_bookmark2_remote_ref.clone(),
                 },
                 tags: btreemap! {},
+                bookmark_tombstones: btreeset! {},
             },
         };
         assert_eq!(
@@ -596,6 +627,7 @@ mod tests {
                     "bookmark1".into() => remote1_bookmark1_remote_ref.clone(),
                 },
                 tags: btreemap! {},
+                bookmark_tombstones: btreeset! {},
             },
         };
         assert_eq!(
@@ -15,8 +15,10 @@
 //! Utility for operation id resolution and traversal.
 
 use std::cmp::Ordering;
+use std::cmp::Reverse;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::iter;
 use std::slice;
 use std::sync::Arc;
 
@@ -31,6 +33,8 @@ use pollster::FutureExt as _;
 use thiserror::Error;
 
 use crate::dag_walk;
+use crate::graph::GraphEdge;
+use crate::graph::GraphNode;
 use crate::object_id::HexPrefix;
 use crate::object_id::PrefixResolution;
 use crate::op_heads_store;
@@ -42,13 +46,25 @@ use crate::op_store::OpStoreError;
 use crate::op_store::OpStoreResult;
 use crate::op_store::OperationId;
 use crate::operation::Operation;
+use crate::opset;
+use crate::opset::OpsetExpression;
+use crate::opset::OpsetParseContext;
+use crate::opset::OpsetParseError;
+use crate::opset_parser;
+use crate::opset_parser::ExpressionKind;
+use crate::opset_parser::ExpressionNode;
+use crate::opset_parser::UnaryOp;
 use crate::repo::ReadonlyRepo;
 use crate::repo::Repo as _;
 use crate::repo::RepoLoader;
+use crate::settings::UserSettings;
 
 /// Error that may occur during evaluation of operation set expression.
 #[derive(Debug, Error)]
 pub enum OpsetEvaluationError {
+    /// Failed to parse operation set expression.
+    #[error(transparent)]
+    OpsetParse(#[from] OpsetParseError),
     /// Failed to resolve operation set expression.
     #[error(transparent)]
     OpsetResolution(#[from] OpsetResolutionError),
@@ -89,8 +105,20 @@ pub enum OpsetResolutionError {
     /// Operation ID prefix matches multiple operations.
     #[error(r#"Operation ID prefix "{0}" is ambiguous"#)]
     AmbiguousIdPrefix(String),
+    /// Expression would have to be evaluated against too many operations.
+    #[error(r#"The "{expr}" expression needs more than {limit} operations to be resolved"#)]
+    TooManyOperations {
+        /// Source expression.
+        expr: String,
+        /// Maximum number of operations to walk.
+        limit: usize,
+    },
 }
 
+/// Maximum number of operations to walk when resolving an expression which
+/// isn't a plain operation id followed by parents and children operators.
+const SINGLE_OP_WALK_LIMIT: usize = 100_000;
+
 /// Resolves operation set expression without loading a repo.
 pub async fn resolve_op_for_load(
     repo_loader: &RepoLoader,
@@ -109,7 +137,14 @@ pub async fn resolve_op_for_load(
         .await
     };
     let get_head_ops = async || get_current_head_ops(op_store, op_heads_store).await;
-    resolve_single_op(op_store, get_current_op, get_head_ops, op_str).await
+    resolve_single_op(
+        repo_loader.settings(),
+        op_store,
+        get_current_op,
+        get_head_ops,
+        op_str,
+    )
+    .await
 }
 
 /// Resolves operation set expression against the loaded repo.
@@ -119,11 +154,18 @@ pub async fn resolve_op_with_repo(
     repo: &ReadonlyRepo,
     op_str: &str,
 ) -> Result<Operation, OpsetEvaluationError> {
-    resolve_op_at(repo.op_store(), slice::from_ref(repo.operation()), op_str).await
+    resolve_op_at(
+        repo.settings(),
+        repo.op_store(),
+        slice::from_ref(repo.operation()),
+        op_str,
+    )
+    .await
 }
 
 /// Resolves operation set expression at the given head operations.
 pub async fn resolve_op_at(
+    settings: &UserSettings,
     op_store: &Arc<dyn OpStore>,
     head_ops: &[Operation],
     op_str: &str,
@@ -138,32 +180,101 @@ pub async fn resolve_op_at(
         .into()),
     };
     let get_head_ops = async || Ok(head_ops.to_vec());
-    resolve_single_op(op_store, get_current_op, get_head_ops, op_str).await
+    resolve_single_op(settings, op_store, get_current_op, get_head_ops, op_str).await
 }
 
 /// Resolves operation set expression with the given "@" symbol resolution
 /// callbacks.
+///
+/// An expression consisting only of an operation id or "@" followed by parents
+/// and children operators is resolved without walking the operation history.
+/// Each step of such an expression must resolve to exactly one operation.
+/// Other expressions are evaluated against a bounded number of operations.
 async fn resolve_single_op(
+    settings: &UserSettings,
     op_store: &Arc<dyn OpStore>,
     get_current_op: impl AsyncFnOnce() -> Result<Operation, OpsetEvaluationError>,
     get_head_ops: impl AsyncFnOnce() -> Result<Vec<Operation>, OpsetEvaluationError>,
     op_str: &str,
 ) -> Result<Operation, OpsetEvaluationError> {
-    let op_symbol = op_str.trim_end_matches(['-', '+']);
-    let op_postfix = &op_str[op_symbol.len()..];
-    let head_ops = if op_postfix.contains('+') {
+    // An empty string would otherwise be reported as a syntax error.
+    if op_str.is_empty() {
+        return Err(OpsetResolutionError::InvalidIdPrefix(op_str.to_owned()).into());
+    }
+    let node = opset_parser::parse_program(op_str)?;
+    if is_neighbors_expression(&node) {
+        return resolve_neighbors_expression(op_store, get_current_op, get_head_ops, &node).await;
+    }
+    let context = OpsetParseContext {
+        date_pattern_context: settings.date_pattern_context(),
+    };
+    let expression = opset::lower_expression(&node, &context)?;
+    let head_ops = get_head_ops().await?;
+    let ops: Vec<Operation> = walk_ancestors(&head_ops)
+        .take(SINGLE_OP_WALK_LIMIT + 1)
+        .try_collect()
+        .await?;
+    if ops.len() > SINGLE_OP_WALK_LIMIT {
+        return Err(OpsetResolutionError::TooManyOperations {
+            expr: op_str.to_owned(),
+            limit: SINGLE_OP_WALK_LIMIT,
+        }
+        .into());
+    }
+    let mut operations = evaluate_opset_in(op_store, get_current_op, &ops, &expression).await?;
+    match operations.len() {
+        0 => Err(OpsetResolutionError::EmptyOperations(op_str.to_owned()).into()),
+        1 => Ok(operations.pop().unwrap()),
+        _ => Err(OpsetResolutionError::MultipleOperations {
+            expr: op_str.to_owned(),
+            candidates: operations.iter().map(|op| op.id().clone()).collect(),
+        }
+        .into()),
+    }
+}
+
+/// Returns true if the `node` is a symbol followed by parents and children
+/// operators.
+fn is_neighbors_expression(node: &ExpressionNode) -> bool {
+    match &node.kind {
+        ExpressionKind::Identifier(_)
+        | ExpressionKind::String(_)
+        | ExpressionKind::AtCurrentOperation => true,
+        ExpressionKind::Unary(UnaryOp::Parents | UnaryOp::Children, arg) => {
+            is_neighbors_expression(arg)
+        }
+        _ => false,
+    }
+}
+
+async fn resolve_neighbors_expression(
+    op_store: &Arc<dyn OpStore>,
+    get_current_op: impl AsyncFnOnce() -> Result<Operation, OpsetEvaluationError>,
+    get_head_ops: impl AsyncFnOnce() -> Result<Vec<Operation>, OpsetEvaluationError>,
+    node: &ExpressionNode<'_>,
+) -> Result<Operation, OpsetEvaluationError> {
+    // Unwrap the postfix operators from the outermost one.
+    let mut postfix_nodes = vec![];
+    let mut symbol_node = node;
+    while let ExpressionKind::Unary(op, arg) = &symbol_node.kind {
+        postfix_nodes.push((*op, symbol_node));
+        symbol_node = arg;
+    }
+    let head_ops = if postfix_nodes.iter().any(|(op, _)| *op == UnaryOp::Children) {
         Some(get_head_ops().await?)
     } else {
         None
     };
-    let mut operation = match op_symbol {
-        "@" => get_current_op().await,
-        s => resolve_single_op_from_store(op_store, s).await,
+    let mut operation = match &symbol_node.kind {
+        ExpressionKind::AtCurrentOperation => get_current_op().await,
+        ExpressionKind::Identifier(s) => resolve_single_op_from_store(op_store, s).await,
+        ExpressionKind::String(s) => resolve_single_op_from_store(op_store, s).await,
+        _ => unreachable!(),
     }?;
-    for (i, c) in op_postfix.chars().enumerate() {
-        let mut neighbor_ops = match c {
-            '-' => operation.parents().await?,
-            '+' => find_child_ops(head_ops.as_ref().unwrap(), operation.id()).await?,
+    for (op, node) in postfix_nodes.into_iter().rev() {
+        let mut neighbor_ops = match op {
+            UnaryOp::Parents => operation.parents().await?,
+            UnaryOp::Children => find_child_ops(head_ops.as_ref().unwrap(), operation.id()).await?,
             _ => unreachable!(),
         };
         operation = match neighbor_ops.len() {
@@ -175,12 +286,14 @@ async fn resolve_single_op(
             //
             // The full op set expression is guaranteed to be empty in this case,
             // because ancestors/descendants of an empty operation are empty.
-            0 => Err(OpsetResolutionError::EmptyOperations(op_str.to_owned()))?,
+            0 => Err(OpsetResolutionError::EmptyOperations(
+                node.span.get_input().to_owned(),
+            ))?,
             1 => neighbor_ops.pop().unwrap(),
             // Returns the exact subexpression that resolves to multiple operations,
             // rather than the full expression provided by the user.
             _ => Err(OpsetResolutionError::MultipleOperations {
-                expr: op_str[..=op_symbol.len() + i].to_owned(),
+                expr: node.span.as_str().to_owned(),
                 candidates: neighbor_ops.iter().map(|op| op.id().clone()).collect(),
             })?,
         };
@@ -211,6 +324,260 @@ async fn resolve_single_op_from_store(
     }
 }
 
+/// Evaluates operation set expression at the given head operations.
+///
+/// The "@" symbol will be resolved to the head operation if there's exactly
+/// one. Operations which aren't reachable from the `head_ops` are never
+/// included. The returned operations are sorted in reverse topological order.
+pub async fn resolve_ops_at(
+    op_store: &Arc<dyn OpStore>,
+    head_ops: &[Operation],
+    expression: &OpsetExpression,
+) -> Result<Vec<Operation>, OpsetEvaluationError> {
+    let get_current_op = async || match head_ops {
+        [head_op] => Ok(head_op.clone()),
+        [] => Err(OpsetResolutionError::EmptyOperations("@".to_owned()).into()),
+        _ => Err(OpsetResolutionError::MultipleOperations {
+            expr: "@".to_owned(),
+            candidates: head_ops.iter().map(|op| op.id().clone()).collect(),
+        }
+        .into()),
+    };
+    let ops: Vec<Operation> = walk_ancestors(head_ops).try_collect().await?;
+    evaluate_opset_in(op_store, get_current_op, &ops, expression).await
+}
+
+/// Evaluates expression over the `ops` sorted in reverse topological order,
+/// which must include all ancestors of the operations in it.
+async fn evaluate_opset_in(
+    op_store: &Arc<dyn OpStore>,
+    get_current_op: impl AsyncFnOnce() -> Result<Operation, OpsetEvaluationError>,
+    ops: &[Operation],
+    expression: &OpsetExpression,
+) -> Result<Vec<Operation>, OpsetEvaluationError> {
+    let mut id_prefixes = vec![];
+    let mut uses_current_op = false;
+    collect_symbols(expression, &mut id_prefixes, &mut uses_current_op);
+    let current_op_id = if uses_current_op {
+        Some(get_current_op().await?.id().clone())
+    } else {
+        None
+    };
+    let mut resolved_ids = HashMap::new();
+    for prefix in id_prefixes {
+        let op = resolve_single_op_from_store(op_store, prefix).await?;
+        resolved_ids.insert(prefix, op.id().clone());
+    }
+
+    let evaluator = OpsetEvaluator {
+        positions: ops.iter().enumerate().map(|(i, op)| (op.id(), i)).collect(),
+        ops,
+        root_op_id: op_store.root_operation_id(),
+        current_op_id: current_op_id.as_ref(),
+        resolved_ids: &resolved_ids,
+    };
+    let selected = evaluator.evaluate(expression);
+    Ok(itertools::zip_eq(ops, selected)
+        .filter(|(_, selected)| *selected)
+        .map(|(op, _)| op.clone())
+        .collect())
+}
+
+fn collect_symbols<'a>(
+    expression: &'a OpsetExpression,
+    id_prefixes: &mut Vec<&'a str>,
+    uses_current_op: &mut bool,
+) {
+    match expression {
+        OpsetExpression::None | OpsetExpression::All | OpsetExpression::Root => {}
+        OpsetExpression::CurrentOperation => *uses_current_op = true,
+        OpsetExpression::IdPrefix(prefix) => id_prefixes.push(prefix),
+        OpsetExpression::Parents(expr)
+        | OpsetExpression::Children(expr)
+        | OpsetExpression::Ancestors(expr)
+        | OpsetExpression::Descendants(expr)
+        | OpsetExpression::Latest {
+            candidates: expr,
+            count: _,
+        }
+        | OpsetExpression::NotIn(expr) => collect_symbols(expr, id_prefixes, uses_current_op),
+        OpsetExpression::Filter(_) => {}
+        OpsetExpression::Union(expr1, expr2)
+        | OpsetExpression::Intersection(expr1, expr2)
+        | OpsetExpression::Difference(expr1, expr2) => {
+            collect_symbols(expr1, id_prefixes, uses_current_op);
+            collect_symbols(expr2, id_prefixes, uses_current_op);
+        }
+    }
+}
+
+/// Evaluates expression over the operations sorted in reverse topological
+/// order. Each set is represented as a flag per operation.
+struct OpsetEvaluator<'a> {
+    ops: &'a [Operation],
+    positions: HashMap<&'a OperationId, usize>,
+    root_op_id: &'a OperationId,
+    current_op_id: Option<&'a OperationId>,
+    resolved_ids: &'a HashMap<&'a str, OperationId>,
+}
+
+impl OpsetEvaluator<'_> {
+    fn evaluate(&self, expression: &OpsetExpression) -> Vec<bool> {
+        match expression {
+            OpsetExpression::None => vec![false; self.ops.len()],
+            OpsetExpression::All => vec![true; self.ops.len()],
+            OpsetExpression::Root => self.single(self.root_op_id),
+            OpsetExpression::CurrentOperation => self.single(self.current_op_id.unwrap()),
+            OpsetExpression::IdPrefix(prefix) => self.single(&self.resolved_ids[prefix.as_str()]),
+            OpsetExpression::Parents(expr) => {
+                let set = self.evaluate(expr);
+                let mut parents = vec![false; self.ops.len()];
+                for (op, _) in iter::zip(self.ops, &set).filter(|(_, selected)| **selected) {
+                    for pos in self.parent_positions(op) {
+                        parents[pos] = true;
+                    }
+                }
+                parents
+            }
+            OpsetExpression::Children(expr) => {
+                let set = self.evaluate(expr);
+                self.ops
+                    .iter()
+                    .map(|op| self.parent_positions(op).any(|pos| set[pos]))
+                    .collect()
+            }
+            OpsetExpression::Ancestors(expr) => {
+                let mut set = self.evaluate(expr);
+                // Parents appear after their children.
+                for (i, op) in self.ops.iter().enumerate() {
+                    if set[i] {
+                        for pos in self.parent_positions(op) {
+                            set[pos] = true;
+                        }
+                    }
+                }
+                set
+            }
+            OpsetExpression::Descendants(expr) => {
+                let mut set = self.evaluate(expr);
+                for (i, op) in self.ops.iter().enumerate().rev() {
+                    if !set[i] && self.parent_positions(op).any(|pos| set[pos]) {
+                        set[i] = true;
+                    }
+                }
+                set
+            }
+            OpsetExpression::Latest { candidates, count } => {
+                let set = self.evaluate(candidates);
+                let mut latest = vec![false; self.ops.len()];
+                let sorted_positions = (0..self.ops.len())
+                    .filter(|&i| set[i])
+                    .sorted_by_key(|&i| Reverse(self.ops[i].metadata().time.end.timestamp));
+                for i in sorted_positions.take(*count) {
+                    latest[i] = true;
+                }
+                latest
+            }
+            OpsetExpression::Filter(predicate) => self
+                .ops
+                .iter()
+                .map(|op| predicate.matches(op.metadata()))
+                .collect(),
+            OpsetExpression::NotIn(expr) => {
+                let set = self.evaluate(expr);
+                set.into_iter().map(|selected| !selected).collect()
+            }
+            OpsetExpression::Union(expr1, expr2) => {
+                let set1 = self.evaluate(expr1);
+                let set2 = self.evaluate(expr2);
+                iter::zip(set1, set2).map(|(a, b)| a || b).collect()
+            }
+            OpsetExpression::Intersection(expr1, expr2) => {
+                let set1 = self.evaluate(expr1);
+                let set2 = self.evaluate(expr2);
+                iter::zip(set1, set2).map(|(a, b)| a && b).collect()
+            }
+            OpsetExpression::Difference(expr1, expr2) => {
+                let set1 = self.evaluate(expr1);
+                let set2 = self.evaluate(expr2);
+                iter::zip(set1, set2).map(|(a, b)| a && !b).collect()
+            }
+        }
+    }
+
+    fn single(&self, id: &OperationId) -> Vec<bool> {
+        let mut set = vec![false; self.ops.len()];
+        if let Some(&pos) = self.positions.get(id) {
+            set[pos] = true;
+        }
+        set
+    }
+
+    fn parent_positions(&self, op: &Operation) -> impl Iterator<Item = usize> {
+        op.parent_ids()
+            .iter()
+            .filter_map(|id| self.positions.get(id).copied())
+    }
+}
+
+/// Builds graph nodes of the given operations.
+///
+/// Each operation is connected to its nearest ancestors within the `ops`, which
+/// should be sorted in reverse topological order. Edges to operations outside
+/// of the set are indirect, or missing if there are no such ancestors.
+pub async fn build_graph_nodes(
+    ops: Vec<Operation>,
+) -> OpStoreResult<Vec<GraphNode<Operation, OperationId>>> {
+    let selected_ids: HashSet<OperationId> = ops.iter().map(|op| op.id().clone()).collect();
+    let all_ops: Vec<Operation> = walk_ancestors(&ops).try_collect().await?;
+    // Nearest selected ancestors of each unselected operation.
+    let mut reachable_ids: HashMap<&OperationId, Vec<&OperationId>> = HashMap::new();
+    for op in all_ops.iter().rev() {
+        if selected_ids.contains(op.id()) {
+            continue;
+        }
+        let ids = op
+            .parent_ids()
+            .iter()
+            .flat_map(|id| match reachable_ids.get(id) {
+                Some(ids) => ids.clone(),
+                None => vec![id],
+            })
+            .unique()
+            .collect();
+        reachable_ids.insert(op.id(), ids);
+    }
+    let nodes = ops
+        .iter()
+        .map(|op| {
+            let mut edges: Vec<GraphEdge<OperationId>> = vec![];
+            for id in op.parent_ids() {
+                if selected_ids.contains(id) {
+                    edges.push(GraphEdge::direct(id.clone()));
+                    continue;
+                }
+                let ids = &reachable_ids[id];
+                if ids.is_empty() {
+                    edges.push(GraphEdge::missing(id.clone()));
+                } else {
+                    // Direct edges take precedence.
+                    edges.extend(
+                        ids.iter()
+                            .filter(|&&id| !op.parent_ids().contains(id))
+                            .map(|&id| GraphEdge::indirect(id.clone())),
+                    );
+                }
+            }
+            let edges = edges
+                .into_iter()
+                .unique_by(|edge| edge.target.clone())
+                .collect();
+            (op.clone(), edges)
+        })
+        .collect();
+    Ok(nodes)
+}
+
 /// Loads the current head operations. The returned operations may contain
 /// redundant ones which are ancestors of the other heads.
 pub async fn get_current_head_ops(
@@ -424,3 +791,74 @@ pub async fn reparent_range(
         unreachable_count,
     })
 }
+
+/// Removes the `abandoned_ids` operations from the history of the `head_ops`.
+///
+/// Descendants of the abandoned operations are reparented onto the nearest
+/// ancestors which aren't abandoned. Returns the new head operation ids in
+/// order of the old `head_ops` as well as some stats. The root operation and
+/// the head operations can't be abandoned.
+pub async fn abandon_operations(
+    op_store: &dyn OpStore,
+    head_ops: &[Operation],
+    abandoned_ids: &HashSet<OperationId>,
+) -> OpStoreResult<ReparentStats> {
+    assert!(
+        !abandoned_ids.contains(op_store.root_operation_id()),
+        "root operation cannot be abandoned"
+    );
+    assert!(
+        head_ops.iter().all(|op| !abandoned_ids.contains(op.id())),
+        "head operations cannot be abandoned"
+    );
+    // Descendants precede ancestors, so no operations after the last abandoned
+    // one need to be rewritten.
+    let mut ops_to_visit = vec![];
+    let mut remaining_ids = abandoned_ids.clone();
+    let mut ancestors = walk_ancestors(head_ops).boxed();
+    while !remaining_ids.is_empty()
+        && let Some(op) = ancestors.try_next().await?
+    {
+        remaining_ids.remove(op.id());
+        ops_to_visit.push(op);
+    }
+
+    // Maps rewritten and abandoned operations to their replacements.
+    let mut replacement_ids: HashMap<OperationId, Vec<OperationId>> = HashMap::new();
+    let mut rewritten_count = 0;
+    for old_op in ops_to_visit.iter().rev() {
+        let new_parent_ids = old_op
+            .parent_ids()
+            .iter()
+            .flat_map(|id| {
+                replacement_ids
+                    .get(id)
+                    .cloned()
+                    .unwrap_or_else(|| vec![id.clone()])
+            })
+            .unique()
+            .collect_vec();
+        if abandoned_ids.contains(old_op.id()) {
+            replacement_ids.insert(old_op.id().clone(), new_parent_ids);
+        } else if new_parent_ids != old_op.parent_ids() {
+            let mut data = old_op.store_operation().clone();
+            data.parents = new_parent_ids;
+            let new_id = op_store.write_operation(&data).await?;
+            replacement_ids.insert(old_op.id().clone(), vec![new_id]);
+            rewritten_count += 1;
+        }
+    }
+
+    let new_head_ids = head_ops
+        .iter()
+        .map(|op| match replacement_ids.get(op.id()) {
+            Some(ids) => ids[0].clone(),
+            None => op.id().clone(),
+        })
+        .collect();
+    Ok(ReparentStats {
+        new_head_ids,
+        rewritten_count,
+        unreachable_count: abandoned_ids.len() - remaining_ids.len(),
+    })
+}
@@ -0,0 +1,423 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Operation set expressions.
+//!
+//! The operations are evaluated by [`crate::op_walk`].
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::LazyLock;
+
+use itertools::Itertools as _;
+
+use crate::dsl_util::collect_similar;
+use crate::op_store::OperationMetadata;
+use crate::opset_parser;
+use crate::opset_parser::BinaryOp;
+use crate::opset_parser::ExpressionKind;
+use crate::opset_parser::ExpressionNode;
+use crate::opset_parser::FunctionCallNode;
+pub use crate::opset_parser::OpsetParseError;
+pub use crate::opset_parser::OpsetParseErrorKind;
+pub use crate::opset_parser::OpsetParseResult;
+use crate::opset_parser::UnaryOp;
+use crate::str_util::StringPattern;
+use crate::time_util::DatePattern;
+use crate::time_util::DatePatternContext;
+
+/// Operation set expression.
+#[derive(Clone, Debug)]
+pub enum OpsetExpression {
+    /// Empty set.
+    None,
+    /// All operations reachable from the head operations.
+    All,
+    /// The root operation.
+    Root,
+    /// The current operation, `@`.
+    CurrentOperation,
+    /// Operation id prefix.
+    IdPrefix(String),
+    /// Parents of the operations, `x-`.
+    Parents(Arc<Self>),
+    /// Children of the operations, `x+`.
+    Children(Arc<Self>),
+    /// Ancestors of the operations including themselves, `::x`.
+    Ancestors(Arc<Self>),
+    /// Descendants of the operations including themselves, `x::`.
+    Descendants(Arc<Self>),
+    /// Most recent `count` operations among the `candidates`.
+    Latest {
+        /// Operations to select from.
+        candidates: Arc<Self>,
+        /// Maximum number of operations to select.
+        count: usize,
+    },
+    /// Operations filtered by their metadata.
+    Filter(OpsetPredicate),
+    /// `~x`
+    NotIn(Arc<Self>),
+    /// `x | y | ..`
+    Union(Arc<Self>, Arc<Self>),
+    /// `x & y`
+    Intersection(Arc<Self>, Arc<Self>),
+    /// `x ~ y`
+    Difference(Arc<Self>, Arc<Self>),
+}
+
+impl OpsetExpression {
+    /// Ancestors of `heads` excluding ancestors of `roots`, `roots..heads`.
+    pub fn range(roots: Arc<Self>, heads: Arc<Self>) -> Arc<Self> {
+        Arc::new(Self::Difference(
+            Arc::new(Self::Ancestors(heads)),
+            Arc::new(Self::Ancestors(roots)),
+        ))
+    }
+
+    /// Descendants of `roots` which are also ancestors of `heads`,
+    /// `roots::heads`.
+    pub fn dag_range(roots: Arc<Self>, heads: Arc<Self>) -> Arc<Self> {
+        Arc::new(Self::Intersection(
+            Arc::new(Self::Descendants(roots)),
+            Arc::new(Self::Ancestors(heads)),
+        ))
+    }
+}
+
+/// Predicate to filter operations by their metadata.
+#[derive(Clone, Debug)]
+pub enum OpsetPredicate {
+    /// Operations run in a workspace whose name matches the pattern.
+    Workspace(StringPattern),
+    /// Operations that only snapshot the working copy.
+    Snapshot,
+    /// Operations whose description matches the pattern.
+    Description(StringPattern),
+    /// Operations having a tag whose value matches the pattern.
+    Tag {
+        /// Tag name.
+        key: String,
+        /// Pattern to match the tag value against.
+        value: StringPattern,
+    },
+    /// Operations which ended within the date range.
+    Time(DatePattern),
+}
+
+impl OpsetPredicate {
+    /// Returns true if the operation `metadata` matches this predicate.
+    pub fn matches(&self, metadata: &OperationMetadata) -> bool {
+        match self {
+            Self::Workspace(pattern) => metadata
+                .workspace_name
+                .as_ref()
+                .is_some_and(|name| pattern.to_matcher().is_match(name.as_str())),
+            Self::Snapshot => metadata.is_snapshot,
+            Self::Description(pattern) => pattern.to_matcher().is_match(&metadata.description),
+            Self::Tag { key, value } => metadata
+                .tags
+                .get(key)
+                .is_some_and(|tag_value| value.to_matcher().is_match(tag_value)),
+            Self::Time(pattern) => pattern.matches(&metadata.time.end),
+        }
+    }
+}
+
+/// Information needed to parse operation set expression.
+#[derive(Clone, Debug)]
+pub struct OpsetParseContext {
+    /// Context to resolve relative dates.
+    pub date_pattern_context: DatePatternContext,
+}
+
+type OpsetFunction =
+    fn(&FunctionCallNode, &OpsetParseContext) -> OpsetParseResult<Arc<OpsetExpression>>;
+
+static BUILTIN_FUNCTION_MAP: LazyLock<HashMap<&str, OpsetFunction>> = LazyLock::new(|| {
+    // Not using maplit::hashmap!{} or custom declarative macro here because
+    // code completion inside macro is quite restricted.
+    let mut map: HashMap<&str, OpsetFunction> = HashMap::new();
+    map.insert("none", |function, _context| {
+        function.expect_no_arguments()?;
+        Ok(Arc::new(OpsetExpression::None))
+    });
+    map.insert("all", |function, _context| {
+        function.expect_no_arguments()?;
+        Ok(Arc::new(OpsetExpression::All))
+    });
+    map.insert("root", |function, _context| {
+        function.expect_no_arguments()?;
+        Ok(Arc::new(OpsetExpression::Root))
+    });
+    map.insert("latest", |function, context| {
+        let ([candidates_arg], [count_opt_arg]) = function.expect_arguments()?;
+        let candidates = lower_expression(candidates_arg, context)?;
+        let count = if let Some(count_arg) = count_opt_arg {
+            let value = opset_parser::expect_string_literal("integer", count_arg)?;
+            value
+                .parse()
+                .map_err(|_| OpsetParseError::expression("Expected integer", count_arg.span))?
+        } else {
+            1
+        };
+        Ok(Arc::new(OpsetExpression::Latest { candidates, count }))
+    });
+    map.insert("workspace", |function, _context| {
+        let [arg] = function.expect_exact_arguments()?;
+        let pattern = expect_string_pattern(arg, "exact")?;
+        Ok(filter(OpsetPredicate::Workspace(pattern)))
+    });
+    map.insert("snapshot", |function, _context| {
+        function.expect_no_arguments()?;
+        Ok(filter(OpsetPredicate::Snapshot))
+    });
+    map.insert("description", |function, _context| {
+        let [arg] = function.expect_exact_arguments()?;
+        let pattern = expect_string_pattern(arg, "substring")?;
+        Ok(filter(OpsetPredicate::Description(pattern)))
+    });
+    map.insert("tag", |function, _context| {
+        let ([key_arg], [value_opt_arg]) = function.expect_arguments()?;
+        let key = opset_parser::expect_string_literal("string", key_arg)?.to_owned();
+        let value = if let Some(value_arg) = value_opt_arg {
+            expect_string_pattern(value_arg, "exact")?
+        } else {
+            StringPattern::all()
+        };
+        Ok(filter(OpsetPredicate::Tag { key, value }))
+    });
+    map.insert("after", |function, context| {
+        let [arg] = function.expect_exact_arguments()?;
+        let pattern = expect_date(arg, "after", context)?;
+        Ok(filter(OpsetPredicate::Time(pattern)))
+    });
+    map.insert("before", |function, context| {
+        let [arg] = function.expect_exact_arguments()?;
+        let pattern = expect_date(arg, "before", context)?;
+        Ok(filter(OpsetPredicate::Time(pattern)))
+    });
+    map
+});
+
+fn filter(predicate: OpsetPredicate) -> Arc<OpsetExpression> {
+    Arc::new(OpsetExpression::Filter(predicate))
+}
+
+fn expect_string_pattern(
+    node: &ExpressionNode,
+    default_kind: &str,
+) -> OpsetParseResult<StringPattern> {
+    let (value, kind) = opset_parser::expect_string_pattern("string pattern", node)?;
+    StringPattern::from_str_kind(value, kind.unwrap_or(default_kind)).map_err(|err| {
+        OpsetParseError::expression("Invalid string pattern", node.span).with_source(err)
+    })
+}
+
+fn expect_date(
+    node: &ExpressionNode,
+    kind: &str,
+    context: &OpsetParseContext,
+) -> OpsetParseResult<DatePattern> {
+    let value = opset_parser::expect_string_literal("date", node)?;
+    context
+        .date_pattern_context
+        .parse_relative(value, kind)
+        .map_err(|err| {
+            OpsetParseError::expression("Invalid date pattern", node.span).with_source(err)
+        })
+}
+
+fn lower_function_call(
+    function: &FunctionCallNode,
+    context: &OpsetParseContext,
+) -> OpsetParseResult<Arc<OpsetExpression>> {
+    if let Some(func) = BUILTIN_FUNCTION_MAP.get(function.name) {
+        func(function, context)
+    } else {
+        Err(OpsetParseError::new(
+            OpsetParseErrorKind::NoSuchFunction {
+                name: function.name.to_owned(),
+                candidates: collect_similar(function.name, BUILTIN_FUNCTION_MAP.keys()),
+            },
+            function.name_span,
+        ))
+    }
+}
+
+pub(crate) fn lower_expression(
+    node: &ExpressionNode,
+    context: &OpsetParseContext,
+) -> OpsetParseResult<Arc<OpsetExpression>> {
+    match &node.kind {
+        ExpressionKind::Identifier(name) => {
+            Ok(Arc::new(OpsetExpression::IdPrefix((*name).to_owned())))
+        }
+        ExpressionKind::String(name) => Ok(Arc::new(OpsetExpression::IdPrefix(name.clone()))),
+        ExpressionKind::AtCurrentOperation => Ok(Arc::new(OpsetExpression::CurrentOperation)),
+        ExpressionKind::Pattern(_) => Err(OpsetParseError::expression(
+            "String patterns may not be used as operation",
+            node.span,
+        )),
+        ExpressionKind::DagRangeAll => Ok(Arc::new(OpsetExpression::All)),
+        ExpressionKind::RangeAll => Ok(OpsetExpression::range(
+            Arc::new(OpsetExpression::Root),
+            Arc::new(OpsetExpression::All),
+        )),
+        ExpressionKind::Unary(op, arg_node) => {
+            let arg = lower_expression(arg_node, context)?;
+            let expression = match op {
+                UnaryOp::Negate => OpsetExpression::NotIn(arg),
+                UnaryOp::DagRangePre => OpsetExpression::Ancestors(arg),
+                UnaryOp::DagRangePost => OpsetExpression::Descendants(arg),
+                UnaryOp::RangePre => {
+                    return Ok(OpsetExpression::range(Arc::new(OpsetExpression::Root), arg));
+                }
+                UnaryOp::RangePost => {
+                    return Ok(OpsetExpression::range(arg, Arc::new(OpsetExpression::All)));
+                }
+                UnaryOp::Parents => OpsetExpression::Parents(arg),
+                UnaryOp::Children => OpsetExpression::Children(arg),
+            };
+            Ok(Arc::new(expression))
+        }
+        ExpressionKind::Binary(op, lhs_node, rhs_node) => {
+            let lhs = lower_expression(lhs_node, context)?;
+            let rhs = lower_expression(rhs_node, context)?;
+            match op {
+                BinaryOp::Intersection => Ok(Arc::new(OpsetExpression::Intersection(lhs, rhs))),
+                BinaryOp::Difference => Ok(Arc::new(OpsetExpression::Difference(lhs, rhs))),
+                BinaryOp::DagRange => Ok(OpsetExpression::dag_range(lhs, rhs)),
+                BinaryOp::Range => Ok(OpsetExpression::range(lhs, rhs)),
+            }
+        }
+        ExpressionKind::UnionAll(nodes) => {
+            let expressions: Vec<_> = nodes
+                .iter()
+                .map(|node| lower_expression(node, context))
+                .try_collect()?;
+            Ok(expressions
+                .into_iter()
+                .reduce(|lhs, rhs| Arc::new(OpsetExpression::Union(lhs, rhs)))
+                .unwrap())
+        }
+        ExpressionKind::FunctionCall(function) => lower_function_call(function, context),
+    }
+}
+
+/// Parses text into `OpsetExpression`.
+pub fn parse(text: &str, context: &OpsetParseContext) -> OpsetParseResult<Arc<OpsetExpression>> {
+    let node = opset_parser::parse_program(text)?;
+    lower_expression(&node, context)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    fn parse_with_fixed_time(text: &str) -> OpsetParseResult<Arc<OpsetExpression>> {
+        let now = chrono::DateTime::parse_from_rfc3339("2024-03-10T12:00:00+09:00").unwrap();
+        let context = OpsetParseContext {
+            date_pattern_context: now.into(),
+        };
+        parse(text, &context)
+    }
+
+    #[test]
+    fn test_parse_functions() {
+        assert_matches!(
+            *parse_with_fixed_time("workspace(ci)").unwrap(),
+            OpsetExpression::Filter(OpsetPredicate::Workspace(ref pattern))
+                if pattern.as_exact() == Some("ci")
+        );
+        assert_matches!(
+            *parse_with_fixed_time(r#"description(glob:"snapshot*")"#).unwrap(),
+            OpsetExpression::Filter(OpsetPredicate::Description(StringPattern::Glob(_)))
+        );
+        assert_matches!(
+            *parse_with_fixed_time(r#"description("new")"#).unwrap(),
+            OpsetExpression::Filter(OpsetPredicate::Description(StringPattern::Substring(_)))
+        );
+        assert_matches!(
+            *parse_with_fixed_time("tag(args)").unwrap(),
+            OpsetExpression::Filter(OpsetPredicate::Tag { ref key, ref value })
+                if key == "args" && value.is_all()
+        );
+        assert_matches!(
+            *parse_with_fixed_time("after(yesterday)").unwrap(),
+            OpsetExpression::Filter(OpsetPredicate::Time(DatePattern::AtOrAfter(_)))
+        );
+        assert_matches!(
+            *parse_with_fixed_time(r#"before("2024-01-01")"#).unwrap(),
+            OpsetExpression::Filter(OpsetPredicate::Time(DatePattern::Before(_)))
+        );
+
+        let err = parse_with_fixed_time("snapshots()").unwrap_err();
+        assert_matches!(
+            err.kind(),
+            OpsetParseErrorKind::NoSuchFunction { name, candidates }
+                if name == "snapshots" && candidates == &["snapshot"]
+        );
+        assert_matches!(
+            parse_with_fixed_time("snapshot(a)").unwrap_err().kind(),
+            OpsetParseErrorKind::InvalidArguments { .. }
+        );
+        assert_matches!(
+            parse_with_fixed_time("after(not-a-date)")
+                .unwrap_err()
+                .kind(),
+            OpsetParseErrorKind::SyntaxError
+        );
+        assert_matches!(
+            parse_with_fixed_time("after(someday)").unwrap_err().kind(),
+            OpsetParseErrorKind::Expression(_)
+        );
+        assert_matches!(
+            parse_with_fixed_time("workspace(@)").unwrap_err().kind(),
+            OpsetParseErrorKind::Expression(_)
+        );
+        assert_matches!(
+            parse_with_fixed_time("glob:abc").unwrap_err().kind(),
+            OpsetParseErrorKind::Expression(_)
+        );
+    }
+
+    #[test]
+    fn test_parse_ranges() {
+        assert_matches!(
+            *parse_with_fixed_time("..@").unwrap(),
+            OpsetExpression::Difference(ref heads, ref roots)
+                if matches!(**heads, OpsetExpression::Ancestors(ref x)
+                            if matches!(**x, OpsetExpression::CurrentOperation))
+                && matches!(**roots, OpsetExpression::Ancestors(ref x)
+                            if matches!(**x, OpsetExpression::Root))
+        );
+        assert_matches!(
+            *parse_with_fixed_time("abc::").unwrap(),
+            OpsetExpression::Descendants(ref x)
+                if matches!(**x, OpsetExpression::IdPrefix(ref s) if s == "abc")
+        );
+        assert_matches!(*parse_with_fixed_time("::").unwrap(), OpsetExpression::All);
+        assert_matches!(
+            *parse_with_fixed_time("latest(snapshot(), 3)").unwrap(),
+            OpsetExpression::Latest { count: 3, .. }
+        );
+        assert_matches!(
+            parse_with_fixed_time("latest(@, x)").unwrap_err().kind(),
+            OpsetParseErrorKind::Expression(_)
+        );
+    }
+}
@@ -0,0 +1,590 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parser for the operation set language.
+
+use std::error;
+use std::sync::LazyLock;
+
+use itertools::Itertools as _;
+use pest::Parser as _;
+use pest::iterators::Pair;
+use pest::pratt_parser::Assoc;
+use pest::pratt_parser::Op;
+use pest::pratt_parser::PrattParser;
+use pest_derive::Parser;
+use thiserror::Error;
+
+use crate::dsl_util;
+use crate::dsl_util::InvalidArguments;
+use crate::dsl_util::StringLiteralParser;
+
+#[derive(Parser)]
+#[grammar = "opset.pest"]
+struct OpsetParser;
+
+const STRING_LITERAL_PARSER: StringLiteralParser<Rule> = StringLiteralParser {
+    content_rule: Rule::string_content,
+    escape_rule: Rule::string_escape,
+};
+
+impl Rule {
+    fn to_symbol(self) -> Option<&'static str> {
+        match self {
+            Self::EOI => None,
+            Self::whitespace => None,
+            Self::identifier => None,
+            Self::strict_identifier_part => None,
+            Self::strict_identifier => None,
+            Self::string_escape => None,
+            Self::string_content_char => None,
+            Self::string_content => None,
+            Self::string_literal => None,
+            Self::raw_string_content => None,
+            Self::raw_string_literal => None,
+            Self::at_op => Some("@"),
+            Self::pattern_kind_op => Some(":"),
+            Self::parents_op => Some("-"),
+            Self::children_op => Some("+"),
+            Self::dag_range_op => Some("::"),
+            Self::dag_range_pre_op => Some("::"),
+            Self::dag_range_post_op => Some("::"),
+            Self::dag_range_all_op => Some("::"),
+            Self::range_op => Some(".."),
+            Self::range_pre_op => Some(".."),
+            Self::range_post_op => Some(".."),
+            Self::range_all_op => Some(".."),
+            Self::range_ops => None,
+            Self::range_pre_ops => None,
+            Self::range_post_ops => None,
+            Self::range_all_ops => None,
+            Self::negate_op => Some("~"),
+            Self::union_op => Some("|"),
+            Self::intersection_op => Some("&"),
+            Self::difference_op => Some("~"),
+            Self::infix_op => None,
+            Self::function => None,
+            Self::function_name => None,
+            Self::function_arguments => None,
+            Self::pattern => None,
+            Self::pattern_value => None,
+            Self::primary => None,
+            Self::neighbors_expression => None,
+            Self::range_expression => None,
+            Self::expression => None,
+            Self::program => None,
+        }
+    }
+}
+
+/// Result of operation set parsing.
+pub type OpsetParseResult<T> = Result<T, OpsetParseError>;
+
+/// Error occurred during operation set parsing.
+#[derive(Debug, Error)]
+#[error("{pest_error}")]
+pub struct OpsetParseError {
+    kind: OpsetParseErrorKind,
+    pest_error: Box<pest::error::Error<Rule>>,
+    source: Option<Box<dyn error::Error + Send + Sync>>,
+}
+
+/// Categories of operation set parsing error.
+#[expect(missing_docs)]
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum OpsetParseErrorKind {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error("Function `{name}` doesn't exist")]
+    NoSuchFunction {
+        name: String,
+        candidates: Vec<String>,
+    },
+    #[error("Function `{name}`: {message}")]
+    InvalidArguments { name: String, message: String },
+    #[error("{0}")]
+    Expression(String),
+}
+
+impl OpsetParseError {
+    pub(super) fn new(kind: OpsetParseErrorKind, span: pest::Span<'_>) -> Self {
+        let message = kind.to_string();
+        let pest_error = Box::new(pest::error::Error::new_from_span(
+            pest::error::ErrorVariant::CustomError { message },
+            span,
+        ));
+        Self {
+            kind,
+            pest_error,
+            source: None,
+        }
+    }
+
+    pub(super) fn with_source(
+        mut self,
+        source: impl Into<Box<dyn error::Error + Send + Sync>>,
+    ) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Some other expression error.
+    pub(super) fn expression(message: impl Into<String>, span: pest::Span<'_>) -> Self {
+        Self::new(OpsetParseErrorKind::Expression(message.into()), span)
+    }
+
+    /// Category of the underlying error.
+    pub fn kind(&self) -> &OpsetParseErrorKind {
+        &self.kind
+    }
+}
+
+impl From<pest::error::Error<Rule>> for OpsetParseError {
+    fn from(err: pest::error::Error<Rule>) -> Self {
+        Self {
+            kind: OpsetParseErrorKind::SyntaxError,
+            pest_error: Box::new(rename_rules_in_pest_error(err)),
+            source: None,
+        }
+    }
+}
+
+impl From<InvalidArguments<'_>> for OpsetParseError {
+    fn from(err: InvalidArguments<'_>) -> Self {
+        let kind = OpsetParseErrorKind::InvalidArguments {
+            name: err.name.to_owned(),
+            message: err.message,
+        };
+        Self::new(kind, err.span)
+    }
+}
+
+fn rename_rules_in_pest_error(err: pest::error::Error<Rule>) -> pest::error::Error<Rule> {
+    err.renamed_rules(|rule| {
+        rule.to_symbol()
+            .map(|sym| format!("`{sym}`"))
+            .unwrap_or_else(|| format!("<{rule:?}>"))
+    })
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ExpressionKind<'i> {
+    /// Unquoted symbol, which is usually an operation id prefix.
+    Identifier(&'i str),
+    /// Quoted string.
+    String(String),
+    /// `@`
+    AtCurrentOperation,
+    /// `<kind>:<value>` where `<value>` is `Identifier` or `String`.
+    Pattern(Box<PatternNode<'i>>),
+    /// `::`
+    DagRangeAll,
+    /// `..`
+    RangeAll,
+    Unary(UnaryOp, Box<ExpressionNode<'i>>),
+    Binary(BinaryOp, Box<ExpressionNode<'i>>, Box<ExpressionNode<'i>>),
+    /// `x | y | ..`
+    UnionAll(Vec<ExpressionNode<'i>>),
+    FunctionCall(Box<FunctionCallNode<'i>>),
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum UnaryOp {
+    /// `~x`
+    Negate,
+    /// `::x`
+    DagRangePre,
+    /// `x::`
+    DagRangePost,
+    /// `..x`
+    RangePre,
+    /// `x..`
+    RangePost,
+    /// `x-`
+    Parents,
+    /// `x+`
+    Children,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BinaryOp {
+    /// `&`
+    Intersection,
+    /// `~`
+    Difference,
+    /// `::`
+    DagRange,
+    /// `..`
+    Range,
+}
+
+pub type ExpressionNode<'i> = dsl_util::ExpressionNode<'i, ExpressionKind<'i>>;
+pub type FunctionCallNode<'i> = dsl_util::FunctionCallNode<'i, ExpressionKind<'i>>;
+pub type PatternNode<'i> = dsl_util::PatternNode<'i, ExpressionKind<'i>>;
+
+fn union_nodes<'i>(lhs: ExpressionNode<'i>, rhs: ExpressionNode<'i>) -> ExpressionNode<'i> {
+    let span = lhs.span.start_pos().span(&rhs.span.end_pos());
+    let expr = match lhs.kind {
+        // Flatten "x | y | z" to save recursion stack.
+        ExpressionKind::UnionAll(mut nodes) => {
+            nodes.push(rhs);
+            ExpressionKind::UnionAll(nodes)
+        }
+        _ => ExpressionKind::UnionAll(vec![lhs, rhs]),
+    };
+    ExpressionNode::new(expr, span)
+}
+
+fn parse_function_call_node(pair: Pair<Rule>) -> OpsetParseResult<FunctionCallNode> {
+    assert_eq!(pair.as_rule(), Rule::function);
+    let [name_pair, args_pair] = pair.into_inner().collect_array().unwrap();
+    assert_eq!(name_pair.as_rule(), Rule::function_name);
+    assert_eq!(args_pair.as_rule(), Rule::function_arguments);
+    let name_span = name_pair.as_span();
+    let args_span = args_pair.as_span();
+    let name = name_pair.as_str();
+    let args = args_pair
+        .into_inner()
+        .map(parse_expression_node)
+        .try_collect()?;
+    Ok(FunctionCallNode {
+        name,
+        name_span,
+        args,
+        keyword_args: vec![], // unsupported
+        args_span,
+    })
+}
+
+fn parse_string_node(pair: Pair<Rule>) -> ExpressionNode {
+    let span = pair.as_span();
+    let expr = match pair.as_rule() {
+        Rule::identifier => ExpressionKind::Identifier(pair.as_str()),
+        Rule::string_literal => {
+            ExpressionKind::String(STRING_LITERAL_PARSER.parse(pair.into_inner()))
+        }
+        Rule::raw_string_literal => {
+            let [content] = pair.into_inner().collect_array().unwrap();
+            assert_eq!(content.as_rule(), Rule::raw_string_content);
+            ExpressionKind::String(content.as_str().to_owned())
+        }
+        r => panic!("unexpected string rule: {r:?}"),
+    };
+    ExpressionNode::new(expr, span)
+}
+
+fn parse_primary_node(pair: Pair<Rule>) -> OpsetParseResult<ExpressionNode> {
+    assert_eq!(pair.as_rule(), Rule::primary);
+    let span = pair.as_span();
+    let first = pair.into_inner().next().unwrap();
+    let expr = match first.as_rule() {
+        // Ignore inner span to preserve parenthesized expression as such.
+        Rule::expression => parse_expression_node(first)?.kind,
+        Rule::function => {
+            let function = Box::new(parse_function_call_node(first)?);
+            ExpressionKind::FunctionCall(function)
+        }
+        Rule::pattern => {
+            let [lhs, op, rhs] = first.into_inner().collect_array().unwrap();
+            assert_eq!(lhs.as_rule(), Rule::strict_identifier);
+            assert_eq!(op.as_rule(), Rule::pattern_kind_op);
+            let pattern = Box::new(PatternNode {
+                name: lhs.as_str(),
+                name_span: lhs.as_span(),
+                value: parse_string_node(rhs),
+            });
+            ExpressionKind::Pattern(pattern)
+        }
+        Rule::identifier | Rule::string_literal | Rule::raw_string_literal => {
+            parse_string_node(first).kind
+        }
+        Rule::at_op => ExpressionKind::AtCurrentOperation,
+        r => panic!("unexpected primary rule: {r:?}"),
+    };
+    Ok(ExpressionNode::new(expr, span))
+}
+
+fn parse_expression_node(pair: Pair<Rule>) -> OpsetParseResult<ExpressionNode> {
+    assert_eq!(pair.as_rule(), Rule::expression);
+    static PRATT: LazyLock<PrattParser<Rule>> = LazyLock::new(|| {
+        PrattParser::new()
+            .op(Op::infix(Rule::union_op, Assoc::Left))
+            .op(Op::infix(Rule::intersection_op, Assoc::Left)
+                | Op::infix(Rule::difference_op, Assoc::Left))
+            .op(Op::prefix(Rule::negate_op))
+            // Ranges can't be nested without parentheses. Associativity doesn't matter.
+            .op(Op::infix(Rule::dag_range_op, Assoc::Left) | Op::infix(Rule::range_op, Assoc::Left))
+            .op(Op::prefix(Rule::dag_range_pre_op) | Op::prefix(Rule::range_pre_op))
+            .op(Op::postfix(Rule::dag_range_post_op) | Op::postfix(Rule::range_post_op))
+            // Neighbors
+            .op(Op::postfix(Rule::parents_op) | Op::postfix(Rule::children_op))
+    });
+    PRATT
+        .map_primary(|primary| {
+            let expr = match primary.as_rule() {
+                Rule::primary => return parse_primary_node(primary),
+                Rule::dag_range_all_op => ExpressionKind::DagRangeAll,
+                Rule::range_all_op => ExpressionKind::RangeAll,
+                r => panic!("unexpected primary rule {r:?}"),
+            };
+            Ok(ExpressionNode::new(expr, primary.as_span()))
+        })
+        .map_prefix(|op, rhs| {
+            let op_kind = match op.as_rule() {
+                Rule::negate_op => UnaryOp::Negate,
+                Rule::dag_range_pre_op => UnaryOp::DagRangePre,
+                Rule::range_pre_op => UnaryOp::RangePre,
+                r => panic!("unexpected prefix operator rule {r:?}"),
+            };
+            let rhs = Box::new(rhs?);
+            let span = op.as_span().start_pos().span(&rhs.span.end_pos());
+            let expr = ExpressionKind::Unary(op_kind, rhs);
+            Ok(ExpressionNode::new(expr, span))
+        })
+        .map_postfix(|lhs, op| {
+            let op_kind = match op.as_rule() {
+                Rule::dag_range_post_op => UnaryOp::DagRangePost,
+                Rule::range_post_op => UnaryOp::RangePost,
+                Rule::parents_op => UnaryOp::Parents,
+                Rule::children_op => UnaryOp::Children,
+                r => panic!("unexpected postfix operator rule {r:?}"),
+            };
+            let lhs = Box::new(lhs?);
+            let span = lhs.span.start_pos().span(&op.as_span().end_pos());
+            let expr = ExpressionKind::Unary(op_kind, lhs);
+            Ok(ExpressionNode::new(expr, span))
+        })
+        .map_infix(|lhs, op, rhs| {
+            let op_kind = match op.as_rule() {
+                Rule::union_op => return Ok(union_nodes(lhs?, rhs?)),
+                Rule::intersection_op => BinaryOp::Intersection,
+                Rule::difference_op => BinaryOp::Difference,
+                Rule::dag_range_op => BinaryOp::DagRange,
+                Rule::range_op => BinaryOp::Range,
+                r => panic!("unexpected infix operator rule {r:?}"),
+            };
+            let lhs = Box::new(lhs?);
+            let rhs = Box::new(rhs?);
+            let span = lhs.span.start_pos().span(&rhs.span.end_pos());
+            let expr = ExpressionKind::Binary(op_kind, lhs, rhs);
+            Ok(ExpressionNode::new(expr, span))
+        })
+        .parse(pair.into_inner())
+}
+
+/// Parses text into expression tree. No name resolution is made at this stage.
+pub fn parse_program(text: &str) -> OpsetParseResult<ExpressionNode<'_>> {
+    let mut pairs = OpsetParser::parse(Rule::program, text)?;
+    let first = pairs.next().unwrap();
+    parse_expression_node(first)
+}
+
+pub(super) fn expect_string_pattern<'a>(
+    type_name: &str,
+    node: &'a ExpressionNode<'_>,
+) -> OpsetParseResult<(&'a str, Option<&'a str>)> {
+    match &node.kind {
+        ExpressionKind::Identifier(name) => Ok((*name, None)),
+        ExpressionKind::String(name) => Ok((name, None)),
+        ExpressionKind::Pattern(pattern) => {
+            let value = expect_string_literal("string", &pattern.value)?;
+            Ok((value, Some(pattern.name)))
+        }
+        _ => Err(OpsetParseError::expression(
+            format!("Expected {type_name}"),
+            node.span,
+        )),
+    }
+}
+
+pub(super) fn expect_string_literal<'a>(
+    type_name: &str,
+    node: &'a ExpressionNode<'_>,
+) -> OpsetParseResult<&'a str> {
+    match &node.kind {
+        ExpressionKind::Identifier(name) => Ok(*name),
+        ExpressionKind::String(name) => Ok(name),
+        _ => Err(OpsetParseError::expression(
+            format!("Expected {type_name}"),
+            node.span,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[derive(Debug, Eq, PartialEq)]
+    enum Tree<'i> {
+        Leaf(&'i str),
+        Unary(UnaryOp, Box<Self>),
+        Binary(BinaryOp, Box<Self>, Box<Self>),
+        Union(Vec<Self>),
+        Function(&'i str, Vec<Self>),
+        Pattern(&'i str, Box<Self>),
+    }
+
+    fn tree(node: ExpressionNode<'_>) -> Tree<'_> {
+        match node.kind {
+            ExpressionKind::Identifier(_)
+            | ExpressionKind::String(_)
+            | ExpressionKind::AtCurrentOperation
+            | ExpressionKind::DagRangeAll
+            | ExpressionKind::RangeAll => Tree::Leaf(node.span.as_str()),
+            ExpressionKind::Pattern(pattern) => {
+                Tree::Pattern(pattern.name, Box::new(tree(pattern.value)))
+            }
+            ExpressionKind::Unary(op, arg) => Tree::Unary(op, Box::new(tree(*arg))),
+            ExpressionKind::Binary(op, lhs, rhs) => {
+                Tree::Binary(op, Box::new(tree(*lhs)), Box::new(tree(*rhs)))
+            }
+            ExpressionKind::UnionAll(nodes) => Tree::Union(nodes.into_iter().map(tree).collect()),
+            ExpressionKind::FunctionCall(function) => {
+                Tree::Function(function.name, function.args.into_iter().map(tree).collect())
+            }
+        }
+    }
+
+    fn parse_tree(text: &str) -> Tree<'_> {
+        tree(parse_program(text).unwrap())
+    }
+
+    fn parse_into_kind(text: &str) -> Result<ExpressionKind<'_>, OpsetParseErrorKind> {
+        parse_program(text)
+            .map(|node| node.kind)
+            .map_err(|err| err.kind)
+    }
+
+    #[test]
+    fn test_parse_symbols() {
+        assert_eq!(parse_into_kind("@"), Ok(ExpressionKind::AtCurrentOperation));
+        assert_eq!(
+            parse_into_kind("abc012"),
+            Ok(ExpressionKind::Identifier("abc012"))
+        );
+        assert_eq!(
+            parse_into_kind(r#""a\tb""#),
+            Ok(ExpressionKind::String("a\tb".to_owned()))
+        );
+        assert_eq!(
+            parse_into_kind(r"'a\tb'"),
+            Ok(ExpressionKind::String(r"a\tb".to_owned()))
+        );
+        assert_eq!(parse_into_kind("::"), Ok(ExpressionKind::DagRangeAll));
+        assert_eq!(parse_into_kind(".."), Ok(ExpressionKind::RangeAll));
+        assert_eq!(parse_into_kind(""), Err(OpsetParseErrorKind::SyntaxError));
+        assert_eq!(parse_into_kind("@@"), Err(OpsetParseErrorKind::SyntaxError));
+        assert_eq!(
+            parse_into_kind("a.b"),
+            Err(OpsetParseErrorKind::SyntaxError)
+        );
+    }
+
+    #[test]
+    fn test_parse_operators() {
+        use Tree::*;
+        let leaf = Leaf;
+        assert_eq!(
+            parse_tree("@--+"),
+            Unary(
+                UnaryOp::Children,
+                Box::new(Unary(
+                    UnaryOp::Parents,
+                    Box::new(Unary(UnaryOp::Parents, Box::new(leaf("@"))))
+                ))
+            )
+        );
+        assert_eq!(
+            parse_tree("abc-..@"),
+            Binary(
+                BinaryOp::Range,
+                Box::new(Unary(UnaryOp::Parents, Box::new(leaf("abc")))),
+                Box::new(leaf("@"))
+            )
+        );
+        assert_eq!(
+            parse_tree("..@-"),
+            Unary(
+                UnaryOp::RangePre,
+                Box::new(Unary(UnaryOp::Parents, Box::new(leaf("@"))))
+            )
+        );
+        assert_eq!(
+            parse_tree("abc::"),
+            Unary(UnaryOp::DagRangePost, Box::new(leaf("abc")))
+        );
+        assert_eq!(
+            parse_tree("a | b & ~c"),
+            Union(vec![
+                leaf("a"),
+                Binary(
+                    BinaryOp::Intersection,
+                    Box::new(leaf("b")),
+                    Box::new(Unary(UnaryOp::Negate, Box::new(leaf("c"))))
+                )
+            ])
+        );
+        assert_eq!(
+            parse_tree("a | b | c"),
+            Union(vec![leaf("a"), leaf("b"), leaf("c")])
+        );
+        assert_eq!(
+            parse_tree("(a | b)-"),
+            Unary(
+                UnaryOp::Parents,
+                Box::new(Union(vec![leaf("a"), leaf("b")]))
+            )
+        );
+        // Ranges can't be nested without parentheses
+        assert_matches!(
+            parse_into_kind("a..b..c"),
+            Err(OpsetParseErrorKind::SyntaxError)
+        );
+        assert_matches!(
+            parse_into_kind("a::b::"),
+            Err(OpsetParseErrorKind::SyntaxError)
+        );
+    }
+
+    #[test]
+    fn test_parse_function_call() {
+        use Tree::*;
+        assert_eq!(parse_tree("snapshot()"), Function("snapshot", vec![]));
+        assert_eq!(
+            parse_tree(r#"tag(key, glob:"a*")"#),
+            Function(
+                "tag",
+                vec![Leaf("key"), Pattern("glob", Box::new(Leaf(r#""a*""#)))]
+            )
+        );
+        assert_eq!(
+            parse_tree("workspace(default) & snapshot()::"),
+            Binary(
+                BinaryOp::Intersection,
+                Box::new(Function("workspace", vec![Leaf("default")])),
+                Box::new(Unary(
+                    UnaryOp::DagRangePost,
+                    Box::new(Function("snapshot", vec![]))
+                ))
+            )
+        );
+        assert_matches!(
+            parse_into_kind("description(a=b)"),
+            Err(OpsetParseErrorKind::SyntaxError)
+        );
+    }
+}
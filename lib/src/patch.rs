@@ -0,0 +1,938 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing and applying patches in the format produced by `git format-patch`.
+
+#![expect(missing_docs)]
+
+use bstr::BString;
+use bstr::ByteSlice as _;
+use thiserror::Error;
+use tokio::io::AsyncReadExt as _;
+
+use crate::backend::BackendError;
+use crate::backend::CopyId;
+use crate::backend::Timestamp;
+use crate::backend::TreeValue;
+use crate::diff_presentation::unified::DiffLineType;
+use crate::merge::Merge;
+use crate::merged_tree::MergedTree;
+use crate::merged_tree_builder::MergedTreeBuilder;
+use crate::repo_path::RepoPath;
+use crate::repo_path::RepoPathBuf;
+
+/// Error while parsing a patch.
+#[derive(Debug, Error)]
+#[error("Invalid patch at line {line}: {message}")]
+pub struct PatchParseError {
+    /// 1-based line number in the input.
+    pub line: usize,
+    pub message: String,
+}
+
+/// Error while applying a patch to a tree.
+#[derive(Debug, Error)]
+pub enum PatchApplyError {
+    #[error("Hunk #{hunk} does not apply to {path}")]
+    HunkMismatch { path: String, hunk: usize },
+    #[error("Cannot patch {path}: file does not exist")]
+    MissingFile { path: String },
+    #[error("Cannot create {path}: file already exists")]
+    FileExists { path: String },
+    #[error("Cannot patch {path}: {reason}")]
+    Unsupported { path: String, reason: &'static str },
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+}
+
+/// Author of a patch as specified by the email headers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatchAuthor {
+    pub name: String,
+    pub email: String,
+    /// Timestamp from the `Date` header, if valid.
+    pub timestamp: Option<Timestamp>,
+}
+
+/// One patch of a series, usually one email message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatchMessage {
+    /// Author from the `From` header.
+    pub author: Option<PatchAuthor>,
+    /// Commit description made from the subject and the message body.
+    pub description: String,
+    pub files: Vec<FilePatch>,
+}
+
+/// Mode of a file in a patch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PatchFileMode {
+    Normal,
+    Executable,
+    Symlink,
+    GitSubmodule,
+}
+
+impl PatchFileMode {
+    fn parse(mode: &str) -> Option<Self> {
+        match mode {
+            "100644" | "100664" => Some(Self::Normal),
+            "100755" => Some(Self::Executable),
+            "120000" => Some(Self::Symlink),
+            "160000" => Some(Self::GitSubmodule),
+            _ => None,
+        }
+    }
+}
+
+/// Changes to a single file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FilePatch {
+    /// Path before the change, or `None` if the file is created.
+    pub old_path: Option<RepoPathBuf>,
+    /// Path after the change, or `None` if the file is deleted.
+    pub new_path: Option<RepoPathBuf>,
+    pub old_mode: Option<PatchFileMode>,
+    pub new_mode: Option<PatchFileMode>,
+    /// Whether `new_path` is a copy of `old_path` (as opposed to a rename).
+    pub is_copy: bool,
+    /// Whether the content change couldn't be represented as text.
+    pub is_binary: bool,
+    pub hunks: Vec<PatchHunk>,
+}
+
+/// A hunk of a unified diff.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatchHunk {
+    /// 1-based line number of the hunk in the old file. If the hunk has no
+    /// old lines, this is the line after which the new lines are inserted.
+    pub old_start: usize,
+    /// Lines including their line terminator (if any).
+    pub lines: Vec<(DiffLineType, BString)>,
+}
+
+impl PatchHunk {
+    fn old_lines(&self) -> impl Iterator<Item = &[u8]> {
+        self.lines
+            .iter()
+            .filter(|(line_type, _)| *line_type != DiffLineType::Added)
+            .map(|(_, line)| line.as_ref())
+    }
+
+    fn new_lines(&self) -> impl Iterator<Item = &[u8]> {
+        self.lines
+            .iter()
+            .filter(|(line_type, _)| *line_type != DiffLineType::Removed)
+            .map(|(_, line)| line.as_ref())
+    }
+}
+
+/// Parses an mbox file or a single patch into messages.
+///
+/// Messages are separated by `From <commit id> Mon Sep 17 00:00:00 2001` lines
+/// as written by `git format-patch`. Input without email headers is parsed as a
+/// single message with an empty description.
+pub fn parse_mbox(input: &[u8]) -> Result<Vec<PatchMessage>, PatchParseError> {
+    let lines = input.lines_with_terminator().collect::<Vec<_>>();
+    let mut message_starts = vec![];
+    for (i, line) in lines.iter().enumerate() {
+        if is_message_separator(line) {
+            message_starts.push(i);
+        }
+    }
+    if message_starts.first() != Some(&0) {
+        message_starts.insert(0, 0);
+    }
+    let mut messages = vec![];
+    for (i, &start) in message_starts.iter().enumerate() {
+        let end = message_starts.get(i + 1).copied().unwrap_or(lines.len());
+        let mut parser = Parser {
+            lines: &lines[start..end],
+            pos: 0,
+            line_offset: start,
+        };
+        if is_message_separator(lines[start]) {
+            parser.pos += 1;
+        }
+        messages.push(parser.parse_message()?);
+    }
+    Ok(messages)
+}
+
+/// Returns true if the line is the `From ` line `git format-patch` writes at
+/// the start of each message. The date is fixed, so a line starting with
+/// `From ` in the message body isn't mistaken for it.
+fn is_message_separator(line: &[u8]) -> bool {
+    let Some(rest) = line.trim_ascii_end().strip_prefix(b"From ") else {
+        return false;
+    };
+    let Some((hash, date)) = rest.split_once_str(" ") else {
+        return false;
+    };
+    matches!(hash.len(), 40 | 64)
+        && hash.iter().all(u8::is_ascii_hexdigit)
+        && date == b"Mon Sep 17 00:00:00 2001"
+}
+
+struct Parser<'a> {
+    lines: &'a [&'a [u8]],
+    pos: usize,
+    /// Offset of `lines` in the whole input, for error reporting.
+    line_offset: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a [u8]> {
+        self.lines.get(self.pos).copied()
+    }
+
+    fn next_line(&mut self) -> Option<&'a [u8]> {
+        let line = self.peek()?;
+        self.pos += 1;
+        Some(line)
+    }
+
+    fn error(&self, message: impl Into<String>) -> PatchParseError {
+        PatchParseError {
+            line: self.line_offset + self.pos,
+            message: message.into(),
+        }
+    }
+
+    fn parse_message(&mut self) -> Result<PatchMessage, PatchParseError> {
+        let headers = self.parse_headers();
+        let author = headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("from"))
+            .and_then(|(_, value)| parse_author(value))
+            .map(|(name, email)| PatchAuthor {
+                name,
+                email,
+                timestamp: headers
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case("date"))
+                    .and_then(|(_, value)| parse_date(value)),
+            });
+        let subject = headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("subject"))
+            .map(|(_, value)| strip_subject_prefix(value))
+            .unwrap_or_default();
+
+        let mut body_lines = vec![];
+        while let Some(line) = self.peek() {
+            if line.trim_end() == b"---" || line.starts_with(b"diff --git ") {
+                break;
+            }
+            body_lines.push(line.to_str_lossy());
+            self.pos += 1;
+        }
+        let description = make_description(subject, &body_lines);
+
+        let mut files = vec![];
+        while let Some(line) = self.peek() {
+            if line.trim_end() == b"--" {
+                // Signature, e.g. the version of Git which generated the patch
+                break;
+            } else if line.starts_with(b"diff --git ") {
+                files.push(self.parse_file_patch()?);
+            } else {
+                // Diffstat or other text between the description and the diff
+                self.pos += 1;
+            }
+        }
+        Ok(PatchMessage {
+            author,
+            description,
+            files,
+        })
+    }
+
+    /// Parses RFC 2822 headers if any, unfolding continuation lines.
+    fn parse_headers(&mut self) -> Vec<(String, String)> {
+        let looks_like_header = |line: &[u8]| {
+            line.find_byte(b':').is_some_and(|colon| {
+                colon > 0 && !line[..colon].contains(&b' ') && !line.starts_with(b"diff ")
+            })
+        };
+        if !self.peek().is_some_and(looks_like_header) {
+            return vec![];
+        }
+        let mut headers: Vec<(String, String)> = vec![];
+        while let Some(line) = self.next_line() {
+            let line = line.to_str_lossy();
+            let line = line.trim_end_matches(['\n', '\r']);
+            if line.is_empty() {
+                break;
+            } else if line.starts_with([' ', '\t']) {
+                if let Some((_, value)) = headers.last_mut() {
+                    value.push(' ');
+                    value.push_str(line.trim_start());
+                }
+            } else if let Some((name, value)) = line.split_once(':') {
+                headers.push((name.to_owned(), value.trim().to_owned()));
+            }
+        }
+        for (_, value) in &mut headers {
+            *value = decode_encoded_words(value);
+        }
+        headers
+    }
+
+    fn parse_file_patch(&mut self) -> Result<FilePatch, PatchParseError> {
+        let header = self.next_line().unwrap();
+        let header = header.to_str_lossy();
+        let (mut old_path, mut new_path) = parse_diff_git_paths(header.trim_end())
+            .ok_or_else(|| self.error(format!("Cannot parse paths in {:?}", header.trim_end())))?;
+        let mut file_patch = FilePatch {
+            old_path: None,
+            new_path: None,
+            old_mode: None,
+            new_mode: None,
+            is_copy: false,
+            is_binary: false,
+            hunks: vec![],
+        };
+        let mut is_new = false;
+        let mut is_deleted = false;
+        while let Some(line) = self.peek() {
+            let line_str = line.to_str_lossy();
+            let line_str = line_str.trim_end_matches(['\n', '\r']);
+            let parse_mode = |mode: &str| {
+                PatchFileMode::parse(mode.trim())
+                    .ok_or_else(|| self.error(format!("Unsupported file mode {mode}")))
+            };
+            if let Some(mode) = line_str.strip_prefix("new file mode ") {
+                is_new = true;
+                file_patch.new_mode = Some(parse_mode(mode)?);
+            } else if let Some(mode) = line_str.strip_prefix("deleted file mode ") {
+                is_deleted = true;
+                file_patch.old_mode = Some(parse_mode(mode)?);
+            } else if let Some(mode) = line_str.strip_prefix("old mode ") {
+                file_patch.old_mode = Some(parse_mode(mode)?);
+            } else if let Some(mode) = line_str.strip_prefix("new mode ") {
+                file_patch.new_mode = Some(parse_mode(mode)?);
+            } else if let Some(path) = line_str.strip_prefix("rename from ") {
+                old_path = path.to_owned();
+            } else if let Some(path) = line_str.strip_prefix("rename to ") {
+                new_path = path.to_owned();
+            } else if let Some(path) = line_str.strip_prefix("copy from ") {
+                file_patch.is_copy = true;
+                old_path = path.to_owned();
+            } else if let Some(path) = line_str.strip_prefix("copy to ") {
+                file_patch.is_copy = true;
+                new_path = path.to_owned();
+            } else if let Some(index) = line_str.strip_prefix("index ") {
+                // "index <old>..<new> <mode>"
+                if let Some((_, mode)) = index.split_once(' ') {
+                    let mode = parse_mode(mode)?;
+                    file_patch.old_mode = Some(mode);
+                    file_patch.new_mode = Some(mode);
+                }
+            } else if line_str.starts_with("similarity index ")
+                || line_str.starts_with("dissimilarity index ")
+                || line_str.starts_with("--- ")
+                || line_str.starts_with("+++ ")
+            {
+                // Paths in "---" and "+++" lines are redundant with the
+                // header, but "/dev/null" is already implied by the modes.
+            } else if line_str.starts_with("Binary files ") || line_str == "GIT binary patch" {
+                file_patch.is_binary = true;
+                self.pos += 1;
+                // Skip the binary data, if any, up to the next file
+                while self.peek().is_some_and(|line| {
+                    !line.starts_with(b"diff --git ") && line.trim_end() != b"--"
+                }) {
+                    self.pos += 1;
+                }
+                break;
+            } else if line_str.starts_with("@@ ") {
+                file_patch.hunks.push(self.parse_hunk()?);
+                continue;
+            } else {
+                break;
+            }
+            self.pos += 1;
+        }
+        if is_new && is_deleted {
+            return Err(self.error("File cannot be both created and deleted"));
+        }
+        let to_repo_path = |path: String| {
+            RepoPathBuf::from_internal_string(path)
+                .map_err(|err| self.error(format!("Invalid path: {err}")))
+        };
+        file_patch.old_path = (!is_new).then(|| to_repo_path(old_path)).transpose()?;
+        file_patch.new_path = (!is_deleted).then(|| to_repo_path(new_path)).transpose()?;
+        Ok(file_patch)
+    }
+
+    fn parse_hunk(&mut self) -> Result<PatchHunk, PatchParseError> {
+        let header = self.next_line().unwrap().to_str_lossy();
+        let (old_start, mut old_count, mut new_count) = parse_hunk_header(&header)
+            .ok_or_else(|| self.error(format!("Invalid hunk header {:?}", header.trim_end())))?;
+        let mut lines: Vec<(DiffLineType, BString)> = vec![];
+        while old_count > 0 || new_count > 0 {
+            let line = self
+                .next_line()
+                .ok_or_else(|| self.error("Unexpected end of hunk"))?;
+            let (line_type, content) = match line.first() {
+                Some(b' ') => (DiffLineType::Context, &line[1..]),
+                Some(b'-') => (DiffLineType::Removed, &line[1..]),
+                Some(b'+') => (DiffLineType::Added, &line[1..]),
+                // Some mail clients strip the trailing space of empty context lines
+                Some(b'\n' | b'\r') => (DiffLineType::Context, line),
+                Some(b'\\') => {
+                    // "\ No newline at end of file" applies to the preceding line
+                    if let Some((_, line)) = lines.last_mut() {
+                        strip_line_terminator(line);
+                    }
+                    continue;
+                }
+                _ => return Err(self.error("Unexpected end of hunk")),
+            };
+            match line_type {
+                DiffLineType::Context if old_count > 0 && new_count > 0 => {
+                    old_count -= 1;
+                    new_count -= 1;
+                }
+                DiffLineType::Removed if old_count > 0 => old_count -= 1,
+                DiffLineType::Added if new_count > 0 => new_count -= 1,
+                _ => return Err(self.error("Hunk is longer than specified in its header")),
+            }
+            lines.push((line_type, content.into()));
+        }
+        if self.peek().is_some_and(|line| line.starts_with(b"\\")) {
+            self.pos += 1;
+            if let Some((_, line)) = lines.last_mut() {
+                strip_line_terminator(line);
+            }
+        }
+        Ok(PatchHunk { old_start, lines })
+    }
+}
+
+fn strip_line_terminator(line: &mut BString) {
+    if line.ends_with(b"\n") {
+        line.pop();
+        if line.ends_with(b"\r") {
+            line.pop();
+        }
+    }
+}
+
+/// Parses `diff --git a/<old> b/<new>`. Quoted paths are not supported.
+fn parse_diff_git_paths(line: &str) -> Option<(String, String)> {
+    let rest = line.strip_prefix("diff --git ")?;
+    let rest = rest.strip_prefix("a/")?;
+    // If the paths are the same, the line is symmetric, which allows paths
+    // containing " b/".
+    if rest.len() % 2 == 1 {
+        let half = (rest.len() - 3) / 2;
+        if rest.is_char_boundary(half)
+            && rest[half..].starts_with(" b/")
+            && rest[..half] == rest[half + 3..]
+        {
+            return Some((rest[..half].to_owned(), rest[half + 3..].to_owned()));
+        }
+    }
+    let (old, new) = rest.split_once(" b/")?;
+    Some((old.to_owned(), new.to_owned()))
+}
+
+/// Parses `@@ -<start>[,<count>] +<start>[,<count>] @@`.
+fn parse_hunk_header(line: &str) -> Option<(usize, usize, usize)> {
+    let rest = line.strip_prefix("@@ -")?;
+    let (ranges, _) = rest.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+    let parse_range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_count) = parse_range(old)?;
+    let (_, new_count) = parse_range(new)?;
+    Some((old_start, old_count, new_count))
+}
+
+/// Parses `Name <email>` or a bare email address.
+fn parse_author(value: &str) -> Option<(String, String)> {
+    if let Some((name, rest)) = value.split_once('<') {
+        let (email, _) = rest.split_once('>')?;
+        let name = name.trim().trim_matches('"').to_owned();
+        Some((name, email.trim().to_owned()))
+    } else if value.contains('@') {
+        Some((String::new(), value.trim().to_owned()))
+    } else {
+        None
+    }
+}
+
+fn parse_date(value: &str) -> Option<Timestamp> {
+    let datetime = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(Timestamp::from_datetime(datetime))
+}
+
+/// Removes `Re:` and `[PATCH ...]` prefixes from a subject.
+fn strip_subject_prefix(subject: &str) -> String {
+    let mut subject = subject.trim();
+    loop {
+        if let Some(rest) = subject
+            .strip_prefix("Re:")
+            .or_else(|| subject.strip_prefix("RE:"))
+        {
+            subject = rest.trim_start();
+        } else if subject.starts_with('[')
+            && let Some((_, rest)) = subject.split_once(']')
+        {
+            subject = rest.trim_start();
+        } else {
+            return subject.to_owned();
+        }
+    }
+}
+
+/// Decodes RFC 2047 encoded words using the "Q" encoding, as written by
+/// `git format-patch` for non-ASCII headers.
+fn decode_encoded_words(value: &str) -> String {
+    let mut decoded = Vec::new();
+    let mut rest = value;
+    let mut last_was_encoded = false;
+    while let Some(start) = rest.find("=?") {
+        let (before, encoded) = rest.split_at(start);
+        let Some((charset, encoding, text, after)) = (|| {
+            let mut parts = encoded[2..].splitn(3, '?');
+            let charset = parts.next()?;
+            let encoding = parts.next()?;
+            let remainder = parts.next()?;
+            let (text, after) = remainder.split_once("?=")?;
+            Some((charset, encoding, text, after))
+        })() else {
+            break;
+        };
+        if !encoding.eq_ignore_ascii_case("q")
+            || !(charset.eq_ignore_ascii_case("utf-8") || charset.eq_ignore_ascii_case("us-ascii"))
+        {
+            break;
+        }
+        // Whitespace between adjacent encoded words is ignored
+        if !(last_was_encoded && before.trim().is_empty()) {
+            decoded.extend_from_slice(before.as_bytes());
+        }
+        let mut bytes = text.bytes();
+        while let Some(b) = bytes.next() {
+            match b {
+                b'_' => decoded.push(b' '),
+                b'=' => {
+                    let hex = [bytes.next().unwrap_or(b'0'), bytes.next().unwrap_or(b'0')];
+                    let hex = std::str::from_utf8(&hex).unwrap_or("3F");
+                    decoded.push(u8::from_str_radix(hex, 16).unwrap_or(b'?'));
+                }
+                _ => decoded.push(b),
+            }
+        }
+        rest = after;
+        last_was_encoded = true;
+    }
+    decoded.extend_from_slice(rest.as_bytes());
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn make_description(subject: String, body_lines: &[std::borrow::Cow<'_, str>]) -> String {
+    let body = body_lines.concat();
+    let body = body.trim_matches(|c| c == '\n' || c == '\r');
+    let mut description = subject;
+    if !body.is_empty() {
+        description.push_str("\n\n");
+        description.push_str(body);
+    }
+    if !description.is_empty() {
+        description.push('\n');
+    }
+    description
+}
+
+/// Applies the `hunks` to the `old` content of the file at `path`.
+///
+/// Hunks are looked up near their expected position, so patches still apply
+/// when earlier parts of the file have changed. The context must match
+/// exactly.
+pub fn apply_hunks(
+    path: &RepoPath,
+    old: &[u8],
+    hunks: &[PatchHunk],
+) -> Result<Vec<u8>, PatchApplyError> {
+    let old_lines = old.lines_with_terminator().collect::<Vec<_>>();
+    let mut new_content = Vec::with_capacity(old.len());
+    // Index of the first old line not yet copied to the output
+    let mut cursor = 0;
+    // Difference between the actual and expected positions of the previous hunk
+    let mut offset: isize = 0;
+    for (i, hunk) in hunks.iter().enumerate() {
+        let hunk_old_lines = hunk.old_lines().collect::<Vec<_>>();
+        let expected = if hunk_old_lines.is_empty() {
+            hunk.old_start
+        } else {
+            hunk.old_start.saturating_sub(1)
+        };
+        let expected = expected.saturating_add_signed(offset).max(cursor);
+        let matches_at = |pos: usize| {
+            pos + hunk_old_lines.len() <= old_lines.len()
+                && old_lines[pos..pos + hunk_old_lines.len()] == hunk_old_lines[..]
+        };
+        let max_distance = old_lines.len().max(expected);
+        let pos = (0..=max_distance)
+            .flat_map(|distance| {
+                [
+                    expected.checked_add(distance),
+                    expected.checked_sub(distance),
+                ]
+            })
+            .flatten()
+            .find(|&pos| pos >= cursor && matches_at(pos))
+            .ok_or_else(|| PatchApplyError::HunkMismatch {
+                path: path.as_internal_file_string().to_owned(),
+                hunk: i + 1,
+            })?;
+        offset += pos as isize - expected as isize;
+        for line in &old_lines[cursor..pos] {
+            new_content.extend_from_slice(line);
+        }
+        for line in hunk.new_lines() {
+            new_content.extend_from_slice(line);
+        }
+        cursor = pos + hunk_old_lines.len();
+    }
+    for line in &old_lines[cursor..] {
+        new_content.extend_from_slice(line);
+    }
+    Ok(new_content)
+}
+
+/// Applies the file patches to `tree` and returns the resulting tree.
+pub async fn apply_file_patches(
+    tree: &MergedTree,
+    file_patches: &[FilePatch],
+) -> Result<MergedTree, PatchApplyError> {
+    let store = tree.store();
+    let mut tree_builder = MergedTreeBuilder::new(tree.clone());
+    // Files are looked up in the original tree so that the patches can be
+    // applied in any order, like `git apply` does.
+    for file_patch in file_patches {
+        let Some(display_path) = file_patch
+            .new_path
+            .as_ref()
+            .or(file_patch.old_path.as_ref())
+        else {
+            return Err(PatchApplyError::Unsupported {
+                path: String::new(),
+                reason: "the patch has neither an old nor a new path",
+            });
+        };
+        let display_path = display_path.as_internal_file_string().to_owned();
+        let unsupported = |reason| PatchApplyError::Unsupported {
+            path: display_path.clone(),
+            reason,
+        };
+        if file_patch.is_binary {
+            return Err(unsupported("binary patches are not supported"));
+        }
+        if [file_patch.old_mode, file_patch.new_mode].contains(&Some(PatchFileMode::GitSubmodule)) {
+            return Err(unsupported("Git submodules are not supported"));
+        }
+
+        let (old_content, old_mode, copy_id) = if let Some(old_path) = &file_patch.old_path {
+            let value = tree.path_value(old_path).await?;
+            let Some(value) = value.as_resolved() else {
+                return Err(unsupported("the file has conflicts"));
+            };
+            match value {
+                Some(TreeValue::File {
+                    id,
+                    executable,
+                    copy_id,
+                }) => {
+                    let mut content = vec![];
+                    store
+                        .read_file(old_path, id)
+                        .await?
+                        .read_to_end(&mut content)
+                        .await
+                        .map_err(|err| BackendError::ReadFile {
+                            path: old_path.clone(),
+                            id: id.clone(),
+                            source: err.into(),
+                        })?;
+                    let mode = if *executable {
+                        PatchFileMode::Executable
+                    } else {
+                        PatchFileMode::Normal
+                    };
+                    (content, mode, copy_id.clone())
+                }
+                Some(TreeValue::Symlink(id)) => {
+                    let target = store.read_symlink(old_path, id).await?;
+                    (
+                        target.into_bytes(),
+                        PatchFileMode::Symlink,
+                        CopyId::placeholder(),
+                    )
+                }
+                Some(_) => return Err(unsupported("not a file")),
+                None => {
+                    return Err(PatchApplyError::MissingFile {
+                        path: old_path.as_internal_file_string().to_owned(),
+                    });
+                }
+            }
+        } else {
+            (vec![], PatchFileMode::Normal, CopyId::placeholder())
+        };
+
+        let Some(new_path) = &file_patch.new_path else {
+            if let Some(old_path) = &file_patch.old_path {
+                tree_builder.set_or_remove(old_path.clone(), Merge::absent());
+            }
+            continue;
+        };
+        if file_patch.old_path.is_none() && tree.path_value(new_path).await?.is_present() {
+            return Err(PatchApplyError::FileExists {
+                path: new_path.as_internal_file_string().to_owned(),
+            });
+        }
+        let new_content = apply_hunks(new_path, &old_content, &file_patch.hunks)?;
+        let new_value = match file_patch.new_mode.unwrap_or(old_mode) {
+            PatchFileMode::Symlink => {
+                let target = String::from_utf8(new_content)
+                    .map_err(|_| unsupported("symlink target is not valid UTF-8"))?;
+                TreeValue::Symlink(store.write_symlink(new_path, &target).await?)
+            }
+            mode => TreeValue::File {
+                id: store
+                    .write_file(new_path, &mut new_content.as_slice())
+                    .await?,
+                executable: mode == PatchFileMode::Executable,
+                copy_id,
+            },
+        };
+        if let Some(old_path) = &file_patch.old_path
+            && old_path != new_path
+            && !file_patch.is_copy
+        {
+            tree_builder.set_or_remove(old_path.clone(), Merge::absent());
+        }
+        tree_builder.set_or_remove(new_path.clone(), Merge::normal(new_value));
+    }
+    Ok(tree_builder.write_tree().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    fn repo_path(value: &str) -> &RepoPath {
+        RepoPath::from_internal_string(value).unwrap()
+    }
+
+    #[test]
+    fn test_parse_mbox() {
+        let input = indoc! {b"
+            From 0123456789abcdef0123456789abcdef01234567 Mon Sep 17 00:00:00 2001
+            From: Some One <some.one@example.com>
+            Date: Sat, 3 Feb 2001 04:05:08 +0700
+            Subject: [PATCH 1/2] first line of
+             the subject
+
+            body
+
+            From the description, not a separator
+            ---
+             file | 2 +-
+             1 file changed, 1 insertion(+), 1 deletion(-)
+
+            diff --git a/file b/file
+            index 257cc5642c..3bd1f0e297 100644
+            --- a/file
+            +++ b/file
+            @@ -1,2 +1,2 @@
+             foo
+            -bar
+            +baz
+            --
+            2.40.0
+
+            From 0123456789abcdef0123456789abcdef01234568 Mon Sep 17 00:00:00 2001
+            From: =?UTF-8?q?J=C3=A9r=C3=B4me?= <j@example.com>
+            Subject: [PATCH 2/2] rename
+
+            ---
+            diff --git a/file b/new file
+            similarity index 100%
+            rename from file
+            rename to new file
+            diff --git a/link b/link
+            new file mode 120000
+            index 0000000000..1de565933b
+            --- /dev/null
+            +++ b/link
+            @@ -0,0 +1 @@
+            +target
+            \\ No newline at end of file
+        "};
+        let messages = parse_mbox(input).unwrap();
+        assert_eq!(messages.len(), 2);
+
+        let author = messages[0].author.as_ref().unwrap();
+        assert_eq!(author.name, "Some One");
+        assert_eq!(author.email, "some.one@example.com");
+        assert_eq!(author.timestamp.unwrap().tz_offset, 7 * 60);
+        assert_eq!(
+            messages[0].description,
+            "first line of the subject\n\nbody\n\nFrom the description, not a separator\n"
+        );
+        assert_eq!(
+            messages[0].files,
+            vec![FilePatch {
+                old_path: Some(repo_path("file").to_owned()),
+                new_path: Some(repo_path("file").to_owned()),
+                old_mode: Some(PatchFileMode::Normal),
+                new_mode: Some(PatchFileMode::Normal),
+                is_copy: false,
+                is_binary: false,
+                hunks: vec![PatchHunk {
+                    old_start: 1,
+                    lines: vec![
+                        (DiffLineType::Context, "foo\n".into()),
+                        (DiffLineType::Removed, "bar\n".into()),
+                        (DiffLineType::Added, "baz\n".into()),
+                    ],
+                }],
+            }]
+        );
+
+        let author = messages[1].author.as_ref().unwrap();
+        assert_eq!(author.name, "Jérôme");
+        assert_eq!(author.timestamp, None);
+        assert_eq!(messages[1].description, "rename\n");
+        let [rename, link] = messages[1].files.as_slice() else {
+            panic!("unexpected files: {:?}", messages[1].files);
+        };
+        assert_eq!(rename.old_path.as_deref(), Some(repo_path("file")));
+        assert_eq!(rename.new_path.as_deref(), Some(repo_path("new file")));
+        assert!(rename.hunks.is_empty());
+        assert_eq!(link.old_path, None);
+        assert_eq!(link.new_mode, Some(PatchFileMode::Symlink));
+        assert_eq!(
+            link.hunks[0].lines,
+            vec![(DiffLineType::Added, "target".into())]
+        );
+    }
+
+    #[test]
+    fn test_parse_mbox_plain_diff() {
+        let input = indoc! {b"
+            diff --git a/file b/file
+            deleted file mode 100644
+            index 257cc5642c..0000000000
+            --- a/file
+            +++ /dev/null
+            @@ -1 +0,0 @@
+            -foo
+        "};
+        let messages = parse_mbox(input).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].author, None);
+        assert_eq!(messages[0].description, "");
+        assert_eq!(messages[0].files[0].new_path, None);
+
+        let input = b"diff --git a/file b/file\n@@ -1,2 +1,2 @@\n foo\n";
+        assert!(parse_mbox(input).is_err());
+
+        let input = indoc! {b"
+            diff --git a/file b/file
+            new file mode 100644
+            deleted file mode 100644
+        "};
+        let err = parse_mbox(input).unwrap_err();
+        assert_eq!(err.message, "File cannot be both created and deleted");
+    }
+
+    #[test]
+    fn test_parse_diff_git_paths() {
+        assert_eq!(
+            parse_diff_git_paths("diff --git a/foo b/bar"),
+            Some(("foo".to_owned(), "bar".to_owned()))
+        );
+        assert_eq!(
+            parse_diff_git_paths("diff --git a/x b/y b/x b/y"),
+            Some(("x b/y".to_owned(), "x b/y".to_owned()))
+        );
+        assert_eq!(parse_diff_git_paths("diff --git foo bar"), None);
+    }
+
+    #[test]
+    fn test_strip_subject_prefix() {
+        assert_eq!(strip_subject_prefix("[PATCH] foo"), "foo");
+        assert_eq!(strip_subject_prefix("Re: [PATCH v2 3/4] foo"), "foo");
+        assert_eq!(strip_subject_prefix("[RFC][PATCH] foo [bar]"), "foo [bar]");
+    }
+
+    #[test]
+    fn test_apply_hunks() {
+        let hunk = |old_start, lines: &[(DiffLineType, &str)]| PatchHunk {
+            old_start,
+            lines: lines
+                .iter()
+                .map(|(line_type, line)| (*line_type, (*line).into()))
+                .collect(),
+        };
+        let path = repo_path("file");
+        let old = b"a\nb\nc\nd\ne\n";
+        let hunks = [
+            hunk(
+                2,
+                &[
+                    (DiffLineType::Context, "b\n"),
+                    (DiffLineType::Removed, "c\n"),
+                    (DiffLineType::Added, "C\n"),
+                ],
+            ),
+            hunk(5, &[(DiffLineType::Added, "f\n")]),
+        ];
+        assert_eq!(
+            apply_hunks(path, old, &hunks).unwrap(),
+            b"a\nb\nC\nd\ne\nf\n"
+        );
+
+        // Hunks apply at an offset
+        let old = b"x\ny\na\nb\nc\nd\ne\n";
+        assert_eq!(
+            apply_hunks(path, old, &hunks).unwrap(),
+            b"x\ny\na\nb\nC\nd\ne\nf\n"
+        );
+
+        // Context doesn't match
+        let old = b"a\nb\nX\nd\ne\n";
+        assert_matches::assert_matches!(
+            apply_hunks(path, old, &hunks),
+            Err(PatchApplyError::HunkMismatch { hunk: 1, .. })
+        );
+
+        // Insertion into an empty file
+        let hunks = [hunk(0, &[(DiffLineType::Added, "new")])];
+        assert_eq!(apply_hunks(path, b"", &hunks).unwrap(), b"new");
+    }
+}
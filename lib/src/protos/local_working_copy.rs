@@ -27,6 +27,14 @@ pub struct FileStateEntry {
 pub struct SparsePatterns {
     #[prost(string, repeated, tag = "1")]
     pub prefixes: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Fileset expression with workspace-relative paths. If set, `prefixes` is
+    /// ignored. `prefixes` then contains only the root so that older versions,
+    /// which don't know about filesets, check out all files.
+    #[prost(string, optional, tag = "2")]
+    pub fileset: ::core::option::Option<::prost::alloc::string::String>,
+    /// Name of the sparse profile the patterns were resolved from, if any.
+    #[prost(string, optional, tag = "3")]
+    pub profile: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct TreeState {
@@ -48,6 +56,8 @@ pub struct TreeState {
     pub sparse_patterns: ::core::option::Option<SparsePatterns>,
     #[prost(message, optional, tag = "4")]
     pub watchman_clock: ::core::option::Option<WatchmanClock>,
+    #[prost(message, optional, tag = "8")]
+    pub inotify_clock: ::core::option::Option<InotifyClock>,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct WatchmanClock {
@@ -65,6 +75,15 @@ pub mod watchman_clock {
     }
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct InotifyClock {
+    /// Identifies the journal written by one run of the inotify daemon.
+    #[prost(string, tag = "1")]
+    pub session: ::prost::alloc::string::String,
+    /// Position in the journal up to which changes have been seen.
+    #[prost(uint64, tag = "2")]
+    pub offset: u64,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct Checkout {
     /// The operation at which the working copy was updated.
     #[prost(bytes = "vec", tag = "2")]
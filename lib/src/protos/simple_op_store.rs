@@ -90,6 +90,14 @@ pub struct GitRef {
     pub target: ::core::option::Option<RefTarget>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GitWorktreeHead {
+    /// Name of the linked worktree (as in .git/worktrees/<name>).
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub target: ::core::option::Option<RefTarget>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RemoteRef {
     #[prost(string, tag = "1")]
     pub name: ::prost::alloc::string::String,
@@ -136,6 +144,9 @@ pub struct View {
     pub git_head_legacy: ::prost::alloc::vec::Vec<u8>,
     #[prost(message, optional, tag = "9")]
     pub git_head: ::core::option::Option<RefTarget>,
+    /// HEADs of the linked Git worktrees. Introduced in jj 0.40.
+    #[prost(message, repeated, tag = "13")]
+    pub git_worktree_heads: ::prost::alloc::vec::Vec<GitWorktreeHead>,
     /// Whether "@git" tags have been migrated to remote_views.
     #[prost(bool, tag = "12")]
     pub has_git_refs_migrated_to_remote_tags: bool,
@@ -148,6 +159,10 @@ pub struct RemoteView {
     pub bookmarks: ::prost::alloc::vec::Vec<RemoteRef>,
     #[prost(message, repeated, tag = "3")]
     pub tags: ::prost::alloc::vec::Vec<RemoteRef>,
+    /// Names of the bookmarks known to have been deleted from the remote. Stored
+    /// separately so older versions can ignore them. Introduced in jj 0.40.
+    #[prost(string, repeated, tag = "4")]
+    pub bookmark_tombstones: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Operation {
@@ -0,0 +1,1479 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Commit backend that stores objects in a remote content-addressed store.
+//!
+//! Objects are encoded and addressed the same way as in the
+//! [`SimpleBackend`](crate::simple_backend::SimpleBackend). They're exchanged
+//! with the object service over HTTP, and cached in the local store directory
+//! so each object is downloaded at most once.
+//!
+//! The service implements the following endpoints, where `<kind>` is one of
+//! `commits`, `trees`, `files`, and `symlinks`:
+//!
+//! * `GET /objects/<kind>/<hex>` returns the object content, or 404.
+//! * `PUT /objects/<kind>/<hex>` stores the object content.
+//! * `POST /batch` takes a list of `<kind>/<hex>` lines, and returns each
+//!   object that exists as a `<kind>/<hex> <length>` line followed by the
+//!   content.
+//!
+//! Objects received from the service are checked against their ids before
+//! they're cached, so a misbehaving service can't corrupt the local store.
+//!
+//! [`RemoteStoreServer`] is a reference implementation of the service backed by
+//! a local directory.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::io::BufRead as _;
+use std::io::BufReader;
+use std::io::Read as _;
+use std::io::Write as _;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::net::ToSocketAddrs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+use blake2::Blake2b512;
+use blake2::Digest as _;
+use futures::StreamExt as _;
+use futures::channel::oneshot;
+use futures::stream;
+use futures::stream::BoxStream;
+use pollster::FutureExt as _;
+use prost::Message as _;
+use tempfile::NamedTempFile;
+use thiserror::Error;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt as _;
+
+use crate::backend::Backend;
+use crate::backend::BackendError;
+use crate::backend::BackendInitError;
+use crate::backend::BackendLoadError;
+use crate::backend::BackendResult;
+use crate::backend::ChangeId;
+use crate::backend::Commit;
+use crate::backend::CommitId;
+use crate::backend::CopyHistory;
+use crate::backend::CopyId;
+use crate::backend::CopyRecord;
+use crate::backend::FileId;
+use crate::backend::RelatedCopy;
+use crate::backend::SecureSig;
+use crate::backend::SigningFn;
+use crate::backend::SymlinkId;
+use crate::backend::Tree;
+use crate::backend::TreeId;
+use crate::backend::TreeValue;
+use crate::backend::make_root_commit;
+use crate::content_hash::blake2b_hash;
+use crate::file_util::persist_content_addressed_temp_file;
+use crate::index::Index;
+use crate::object_id::ObjectId;
+use crate::repo_path::RepoPath;
+use crate::repo_path::RepoPathBuf;
+use crate::simple_backend::commit_from_proto;
+use crate::simple_backend::commit_to_proto;
+use crate::simple_backend::tree_from_proto;
+use crate::simple_backend::tree_to_proto;
+
+const COMMIT_ID_LENGTH: usize = 64;
+const CHANGE_ID_LENGTH: usize = 16;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const IO_TIMEOUT: Duration = Duration::from_secs(60);
+/// Maximum size of request bodies accepted by [`RemoteStoreServer`].
+const MAX_REQUEST_BODY_SIZE: usize = 256 << 20;
+/// Maximum size of response bodies accepted by the client. Batch responses
+/// may contain many objects, so this is larger than the request limit.
+const MAX_RESPONSE_BODY_SIZE: usize = 1 << 30;
+/// Maximum number of requests sent to the remote store at the same time, and
+/// of idle connections kept open for later requests.
+const MAX_CONNECTIONS: usize = 16;
+
+/// Kind of objects stored in the remote store.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum ObjectKind {
+    Commit,
+    Tree,
+    File,
+    Symlink,
+}
+
+impl ObjectKind {
+    const ALL: [Self; 4] = [Self::Commit, Self::Tree, Self::File, Self::Symlink];
+
+    fn dir_name(self) -> &'static str {
+        match self {
+            Self::Commit => "commits",
+            Self::Tree => "trees",
+            Self::File => "files",
+            Self::Symlink => "symlinks",
+        }
+    }
+
+    fn from_dir_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.dir_name() == name)
+    }
+
+    fn object_type(self) -> &'static str {
+        match self {
+            Self::Commit => "commit",
+            Self::Tree => "tree",
+            Self::File => "file",
+            Self::Symlink => "symlink",
+        }
+    }
+}
+
+/// Computes the id of the object encoded as `data`, or returns `None` if the
+/// data can't be decoded.
+fn object_id_of(kind: ObjectKind, data: &[u8]) -> Option<Vec<u8>> {
+    match kind {
+        ObjectKind::Commit => {
+            let proto = crate::protos::simple_store::Commit::decode(data).ok()?;
+            Some(blake2b_hash(&commit_from_proto(proto)).to_vec())
+        }
+        ObjectKind::Tree => {
+            let proto = crate::protos::simple_store::Tree::decode(data).ok()?;
+            Some(blake2b_hash(&tree_from_proto(proto)).to_vec())
+        }
+        ObjectKind::File | ObjectKind::Symlink => Some(Blake2b512::digest(data).to_vec()),
+    }
+}
+
+/// Checks that `data` is the content of the object with the given id.
+fn verify_object(kind: ObjectKind, hex: &str, data: &[u8]) -> Result<(), RemoteStoreError> {
+    match object_id_of(kind, data) {
+        Some(id) if crate::hex_util::encode_hex(&id) == hex => Ok(()),
+        _ => Err(RemoteStoreError::ObjectMismatch {
+            kind: kind.object_type(),
+            hash: hex.to_owned(),
+        }),
+    }
+}
+
+/// Parses `<kind>/<hex>` object name.
+fn parse_object_name(name: &str) -> Option<(ObjectKind, &str)> {
+    let (kind, hex) = name.split_once('/')?;
+    let kind = ObjectKind::from_dir_name(kind)?;
+    let is_valid_hex = !hex.is_empty() && hex.bytes().all(|b| b.is_ascii_hexdigit());
+    is_valid_hex.then_some((kind, hex))
+}
+
+/// Error that may occur while talking to the remote store.
+#[derive(Debug, Error)]
+pub enum RemoteStoreError {
+    /// The configured URL isn't supported.
+    #[error("Invalid remote store URL {0:?}, expected http://<host>:<port>[/<path>]")]
+    InvalidUrl(String),
+    /// The remote store couldn't be reached.
+    #[error("Failed to connect to remote store at {url}")]
+    Connect {
+        /// URL of the remote store.
+        url: String,
+        /// The source error.
+        source: io::Error,
+    },
+    /// The connection to the remote store failed.
+    #[error("I/O error while talking to remote store")]
+    Io(#[from] io::Error),
+    /// The remote store responded with an unexpected status code.
+    #[error("Remote store responded with HTTP status {0}")]
+    Status(u16),
+    /// The remote store sent something that isn't a valid response.
+    #[error("Malformed response from remote store")]
+    MalformedResponse,
+    /// The remote store sent a response larger than the client accepts.
+    #[error("Response from remote store is larger than {MAX_RESPONSE_BODY_SIZE} bytes")]
+    ResponseTooLarge,
+    /// The remote store sent an object whose content doesn't match its id.
+    #[error("Remote store sent invalid content for {kind} {hash}")]
+    ObjectMismatch {
+        /// Type of the object.
+        kind: &'static str,
+        /// Requested object id.
+        hash: String,
+    },
+}
+
+/// Minimal HTTP/1.1 client for the remote store protocol.
+///
+/// The requests are blocking. Use [`BlockingPool`] to call them from async
+/// code. Connections are kept open and reused by later requests.
+#[derive(Clone, Debug)]
+struct RemoteClient {
+    url: String,
+    authority: String,
+    path_prefix: String,
+    idle_connections: Arc<Mutex<Vec<BufReader<TcpStream>>>>,
+}
+
+impl RemoteClient {
+    fn new(url: &str) -> Result<Self, RemoteStoreError> {
+        let invalid_url = || RemoteStoreError::InvalidUrl(url.to_owned());
+        let rest = url.strip_prefix("http://").ok_or_else(invalid_url)?;
+        let (authority, path_prefix) = match rest.find('/') {
+            Some(pos) => rest.split_at(pos),
+            None => (rest, ""),
+        };
+        if authority.is_empty() {
+            return Err(invalid_url());
+        }
+        Ok(Self {
+            url: url.to_owned(),
+            authority: authority.to_owned(),
+            path_prefix: path_prefix.trim_end_matches('/').to_owned(),
+            idle_connections: Default::default(),
+        })
+    }
+
+    fn request(
+        &self,
+        method: &str,
+        path: &str,
+        body: &[u8],
+    ) -> Result<(u16, Vec<u8>), RemoteStoreError> {
+        let idle_connection = self.idle_connections.lock().unwrap().pop();
+        if let Some(connection) = idle_connection {
+            // The server may have closed the idle connection in the meantime.
+            // All requests are idempotent, so they can be sent again over a new
+            // connection.
+            match self.send_request(connection, method, path, body) {
+                Err(RemoteStoreError::Io(err)) if is_closed_connection_error(&err) => {}
+                result => return result,
+            }
+        }
+        let stream = self.connect().map_err(|source| RemoteStoreError::Connect {
+            url: self.url.clone(),
+            source,
+        })?;
+        stream.set_read_timeout(Some(IO_TIMEOUT))?;
+        stream.set_write_timeout(Some(IO_TIMEOUT))?;
+        self.send_request(BufReader::new(stream), method, path, body)
+    }
+
+    fn send_request(
+        &self,
+        mut connection: BufReader<TcpStream>,
+        method: &str,
+        path: &str,
+        body: &[u8],
+    ) -> Result<(u16, Vec<u8>), RemoteStoreError> {
+        let mut writer = io::BufWriter::new(connection.get_ref());
+        write!(
+            writer,
+            "{method} {prefix}{path} HTTP/1.1\r\nHost: {authority}\r\nContent-Length: \
+             {len}\r\n\r\n",
+            prefix = self.path_prefix,
+            authority = self.authority,
+            len = body.len(),
+        )?;
+        writer.write_all(body)?;
+        writer.flush()?;
+        drop(writer);
+
+        let reader = &mut connection;
+        if reader.fill_buf()?.is_empty() {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+        let head = read_message_head(reader)?;
+        let status = head
+            .start_line
+            .split(' ')
+            .nth(1)
+            .and_then(|code| code.parse().ok())
+            .ok_or(RemoteStoreError::MalformedResponse)?;
+        let mut body = vec![];
+        let mut keep_alive = !head.close;
+        if head.chunked {
+            read_chunked_body(reader, &mut body, MAX_RESPONSE_BODY_SIZE)?;
+        } else if let Some(len) = head.content_length {
+            if len > MAX_RESPONSE_BODY_SIZE {
+                return Err(RemoteStoreError::ResponseTooLarge);
+            }
+            reader.take(len as u64).read_to_end(&mut body)?;
+            if body.len() != len {
+                return Err(RemoteStoreError::MalformedResponse);
+            }
+        } else {
+            // The body is terminated by closing the connection.
+            reader
+                .take(MAX_RESPONSE_BODY_SIZE as u64 + 1)
+                .read_to_end(&mut body)?;
+            if body.len() > MAX_RESPONSE_BODY_SIZE {
+                return Err(RemoteStoreError::ResponseTooLarge);
+            }
+            keep_alive = false;
+        }
+        if keep_alive {
+            let mut idle_connections = self.idle_connections.lock().unwrap();
+            if idle_connections.len() < MAX_CONNECTIONS {
+                idle_connections.push(connection);
+            }
+        }
+        Ok((status, body))
+    }
+
+    fn connect(&self) -> io::Result<TcpStream> {
+        let mut last_err = None;
+        for addr in self.authority.to_socket_addrs()? {
+            match TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) {
+                Ok(stream) => return Ok(stream),
+                Err(err) => last_err = Some(err),
+            }
+        }
+        Err(last_err
+            .unwrap_or_else(|| io::Error::new(io::ErrorKind::NotFound, "host name didn't resolve")))
+    }
+
+    fn get(&self, kind: ObjectKind, hex: &str) -> Result<Option<Vec<u8>>, RemoteStoreError> {
+        let path = format!("/objects/{}/{hex}", kind.dir_name());
+        match self.request("GET", &path, &[])? {
+            (200, body) => {
+                verify_object(kind, hex, &body)?;
+                Ok(Some(body))
+            }
+            (404, _) => Ok(None),
+            (status, _) => Err(RemoteStoreError::Status(status)),
+        }
+    }
+
+    fn put(&self, kind: ObjectKind, hex: &str, data: &[u8]) -> Result<(), RemoteStoreError> {
+        let path = format!("/objects/{}/{hex}", kind.dir_name());
+        match self.request("PUT", &path, data)? {
+            (200 | 201, _) => Ok(()),
+            (status, _) => Err(RemoteStoreError::Status(status)),
+        }
+    }
+
+    /// Fetches the given objects in one request. Objects that don't exist in
+    /// the remote store are omitted from the result.
+    ///
+    /// Fails if the response contains objects that weren't requested or that
+    /// don't match their ids.
+    fn batch_get(
+        &self,
+        objects: &[(ObjectKind, String)],
+    ) -> Result<Vec<(ObjectKind, String, Vec<u8>)>, RemoteStoreError> {
+        let mut request = String::new();
+        for (kind, hex) in objects {
+            request.push_str(kind.dir_name());
+            request.push('/');
+            request.push_str(hex);
+            request.push('\n');
+        }
+        let body = match self.request("POST", "/batch", request.as_bytes())? {
+            (200, body) => body,
+            (status, _) => return Err(RemoteStoreError::Status(status)),
+        };
+
+        let requested: HashSet<(ObjectKind, &str)> = objects
+            .iter()
+            .map(|(kind, hex)| (*kind, hex.as_str()))
+            .collect();
+        let mut fetched = vec![];
+        let mut rest = body.as_slice();
+        while !rest.is_empty() {
+            let header_len = rest
+                .iter()
+                .position(|&b| b == b'\n')
+                .ok_or(RemoteStoreError::MalformedResponse)?;
+            let (header, tail) = (&rest[..header_len], &rest[header_len + 1..]);
+            let (name, len) = str::from_utf8(header)
+                .ok()
+                .and_then(|header| header.split_once(' '))
+                .ok_or(RemoteStoreError::MalformedResponse)?;
+            let (kind, hex) = parse_object_name(name).ok_or(RemoteStoreError::MalformedResponse)?;
+            let len: usize = len
+                .parse()
+                .map_err(|_| RemoteStoreError::MalformedResponse)?;
+            if tail.len() < len {
+                return Err(RemoteStoreError::MalformedResponse);
+            }
+            let (data, tail) = tail.split_at(len);
+            if !requested.contains(&(kind, hex)) {
+                return Err(RemoteStoreError::MalformedResponse);
+            }
+            verify_object(kind, hex, data)?;
+            fetched.push((kind, hex.to_owned(), data.to_vec()));
+            rest = tail;
+        }
+        Ok(fetched)
+    }
+}
+
+/// Start line and the relevant headers of an HTTP message.
+#[derive(Debug)]
+struct MessageHead {
+    start_line: String,
+    content_length: Option<usize>,
+    chunked: bool,
+    /// Whether the sender closes the connection after this message.
+    close: bool,
+}
+
+fn invalid_http_data() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "malformed HTTP message")
+}
+
+/// Returns true if the error means that the peer closed the connection.
+fn is_closed_connection_error(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::BrokenPipe
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::UnexpectedEof
+    )
+}
+
+/// Reads the start line and headers of an HTTP message.
+fn read_message_head(reader: &mut impl io::BufRead) -> io::Result<MessageHead> {
+    let invalid_data = invalid_http_data;
+    let mut start_line = String::new();
+    reader.read_line(&mut start_line)?;
+    let start_line = start_line.trim_end().to_owned();
+    if start_line.is_empty() {
+        return Err(invalid_data());
+    }
+    let mut content_length = None;
+    let mut chunked = false;
+    let mut close = false;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(invalid_data());
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(':').ok_or_else(invalid_data)?;
+        if name.eq_ignore_ascii_case("content-length") {
+            let len = value.trim().parse().map_err(|_| invalid_data())?;
+            content_length = Some(len);
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            chunked = value.trim().eq_ignore_ascii_case("chunked");
+        } else if name.eq_ignore_ascii_case("connection") {
+            close = value
+                .split(',')
+                .any(|option| option.trim().eq_ignore_ascii_case("close"));
+        }
+    }
+    Ok(MessageHead {
+        start_line,
+        content_length,
+        chunked,
+        close,
+    })
+}
+
+/// Reads a body in the chunked transfer encoding, which must not be larger
+/// than `max_size`.
+fn read_chunked_body(
+    reader: &mut impl io::BufRead,
+    body: &mut Vec<u8>,
+    max_size: usize,
+) -> Result<(), RemoteStoreError> {
+    loop {
+        let mut size_line = String::new();
+        reader.read_line(&mut size_line)?;
+        let size = size_line.trim_end().split(';').next().unwrap_or_default();
+        let size = usize::from_str_radix(size.trim(), 16).map_err(|_| invalid_http_data())?;
+        if size == 0 {
+            break;
+        }
+        if size > max_size - body.len() {
+            return Err(RemoteStoreError::ResponseTooLarge);
+        }
+        let start = body.len();
+        reader.take(size as u64).read_to_end(body)?;
+        if body.len() - start != size {
+            return Err(invalid_http_data().into());
+        }
+        let mut crlf = String::new();
+        reader.read_line(&mut crlf)?;
+        if !crlf.trim_end().is_empty() {
+            return Err(invalid_http_data().into());
+        }
+    }
+    // Skip trailer fields.
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            return Ok(());
+        }
+    }
+}
+
+type BlockingJob = Box<dyn FnOnce() + Send>;
+
+/// Threads that run blocking functions, so they don't stall the async
+/// executor.
+///
+/// Up to [`MAX_CONNECTIONS`] threads are started as needed. Further jobs are
+/// queued until a thread is available. The threads exit when the pool is
+/// dropped.
+#[derive(Debug)]
+struct BlockingPool {
+    sender: mpsc::Sender<BlockingJob>,
+    receiver: Arc<Mutex<mpsc::Receiver<BlockingJob>>>,
+    threads: Arc<Mutex<PoolThreads>>,
+}
+
+#[derive(Debug, Default)]
+struct PoolThreads {
+    started: usize,
+    /// Number of threads waiting for a job.
+    idle: usize,
+}
+
+impl BlockingPool {
+    fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            sender,
+            receiver: Arc::new(Mutex::new(receiver)),
+            threads: Default::default(),
+        }
+    }
+
+    async fn run<T: Send + 'static>(&self, f: impl FnOnce() -> T + Send + 'static) -> T {
+        let (sender, receiver) = oneshot::channel();
+        self.spawn_thread_if_busy();
+        self.sender
+            .send(Box::new(move || {
+                sender.send(f()).ok();
+            }))
+            .expect("pool threads should be running");
+        receiver.await.expect("blocking task shouldn't panic")
+    }
+
+    fn spawn_thread_if_busy(&self) {
+        let mut threads = self.threads.lock().unwrap();
+        if threads.idle > 0 || threads.started >= MAX_CONNECTIONS {
+            return;
+        }
+        threads.started += 1;
+        let receiver = self.receiver.clone();
+        let threads = self.threads.clone();
+        thread::spawn(move || {
+            loop {
+                threads.lock().unwrap().idle += 1;
+                let job = receiver.lock().unwrap().recv();
+                threads.lock().unwrap().idle -= 1;
+                match job {
+                    Ok(job) => job(),
+                    Err(mpsc::RecvError) => break,
+                }
+            }
+        });
+    }
+}
+
+fn to_other_err(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> BackendError {
+    BackendError::Other(err.into())
+}
+
+/// Commit backend that reads and writes objects from a remote
+/// content-addressed store.
+#[derive(Debug)]
+pub struct RemoteBackend {
+    client: RemoteClient,
+    pool: BlockingPool,
+    cache_path: PathBuf,
+    /// Contains an empty file for each object that was uploaded by this
+    /// client. Objects in the cache aren't necessarily in the remote store.
+    uploaded_path: PathBuf,
+    root_commit_id: CommitId,
+    root_change_id: ChangeId,
+    empty_tree_id: TreeId,
+}
+
+impl RemoteBackend {
+    /// "remote"
+    pub fn name() -> &'static str {
+        "remote"
+    }
+
+    /// Initializes the backend to store objects at the given `url`.
+    pub fn init(store_path: &Path, url: &str) -> Result<Self, BackendInitError> {
+        let client = RemoteClient::new(url).map_err(|err| BackendInitError(err.into()))?;
+        fs::write(store_path.join("remote_url"), url)
+            .map_err(|err| BackendInitError(err.into()))?;
+        let backend = Self::new(store_path, client).map_err(|err| BackendInitError(err.into()))?;
+        // Make sure the remote store is reachable.
+        backend
+            .write_tree(RepoPath::root(), &Tree::default())
+            .block_on()
+            .map_err(|err| BackendInitError(err.into()))?;
+        Ok(backend)
+    }
+
+    /// Loads the backend from the given store path.
+    pub fn load(store_path: &Path) -> Result<Self, BackendLoadError> {
+        let url = fs::read_to_string(store_path.join("remote_url"))
+            .map_err(|err| BackendLoadError(err.into()))?;
+        let client = RemoteClient::new(url.trim()).map_err(|err| BackendLoadError(err.into()))?;
+        Self::new(store_path, client).map_err(|err| BackendLoadError(err.into()))
+    }
+
+    fn new(store_path: &Path, client: RemoteClient) -> io::Result<Self> {
+        let cache_path = store_path.join("cache");
+        let uploaded_path = store_path.join("uploaded");
+        for kind in ObjectKind::ALL {
+            fs::create_dir_all(cache_path.join(kind.dir_name()))?;
+            fs::create_dir_all(uploaded_path.join(kind.dir_name()))?;
+        }
+        Ok(Self {
+            client,
+            pool: BlockingPool::new(),
+            cache_path,
+            uploaded_path,
+            root_commit_id: CommitId::from_bytes(&[0; COMMIT_ID_LENGTH]),
+            root_change_id: ChangeId::from_bytes(&[0; CHANGE_ID_LENGTH]),
+            empty_tree_id: TreeId::new(blake2b_hash(&Tree::default()).to_vec()),
+        })
+    }
+
+    /// URL of the remote store.
+    pub fn url(&self) -> &str {
+        &self.client.url
+    }
+
+    fn cached_object_path(&self, kind: ObjectKind, hex: &str) -> PathBuf {
+        self.cache_path.join(kind.dir_name()).join(hex)
+    }
+
+    fn write_cache(&self, kind: ObjectKind, hex: &str, data: &[u8]) -> io::Result<()> {
+        let temp_file = NamedTempFile::new_in(&self.cache_path)?;
+        temp_file.as_file().write_all(data)?;
+        persist_content_addressed_temp_file(temp_file, self.cached_object_path(kind, hex))?;
+        Ok(())
+    }
+
+    /// Returns the cached object, or downloads it from the remote store.
+    async fn read_object(&self, kind: ObjectKind, id: &impl ObjectId) -> BackendResult<Vec<u8>> {
+        let hex = id.hex();
+        let read_err = |err: Box<dyn std::error::Error + Send + Sync>| BackendError::ReadObject {
+            object_type: id.object_type(),
+            hash: id.hex(),
+            source: err,
+        };
+        match fs::read(self.cached_object_path(kind, &hex)) {
+            Ok(data) => return Ok(data),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(read_err(err.into())),
+        }
+        let client = self.client.clone();
+        let data = self
+            .pool
+            .run({
+                let hex = hex.clone();
+                move || client.get(kind, &hex)
+            })
+            .await
+            .map_err(|err| read_err(err.into()))?
+            .ok_or_else(|| BackendError::ObjectNotFound {
+                object_type: id.object_type(),
+                hash: id.hex(),
+                source: format!("Not found in remote store at {}", self.url()).into(),
+            })?;
+        self.write_cache(kind, &hex, &data)
+            .map_err(|err| read_err(err.into()))?;
+        Ok(data)
+    }
+
+    /// Uploads the object to the remote store unless it was uploaded before,
+    /// and caches it.
+    async fn write_object(
+        &self,
+        kind: ObjectKind,
+        id: &impl ObjectId,
+        data: Vec<u8>,
+    ) -> BackendResult<()> {
+        let hex = id.hex();
+        let write_err = |err: Box<dyn std::error::Error + Send + Sync>| BackendError::WriteObject {
+            object_type: kind.object_type(),
+            source: err,
+        };
+        if !self.cached_object_path(kind, &hex).exists() {
+            self.write_cache(kind, &hex, &data)
+                .map_err(|err| write_err(err.into()))?;
+        }
+        let uploaded_marker_path = self.uploaded_path.join(kind.dir_name()).join(&hex);
+        if uploaded_marker_path.exists() {
+            return Ok(());
+        }
+        let client = self.client.clone();
+        self.pool
+            .run(move || client.put(kind, &hex, &data))
+            .await
+            .map_err(|err| write_err(err.into()))?;
+        fs::write(uploaded_marker_path, b"").map_err(|err| write_err(err.into()))
+    }
+
+    /// Downloads the objects that aren't cached yet in one request.
+    ///
+    /// This is an optimization, so errors are only logged. The objects will be
+    /// downloaded again when they're read.
+    async fn prefetch(&self, objects: impl IntoIterator<Item = (ObjectKind, String)>) {
+        let missing = objects
+            .into_iter()
+            .filter(|(kind, hex)| !self.cached_object_path(*kind, hex).exists())
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            return;
+        }
+        tracing::debug!(
+            count = missing.len(),
+            "prefetching objects from remote store"
+        );
+        let client = self.client.clone();
+        let fetched = match self.pool.run(move || client.batch_get(&missing)).await {
+            Ok(fetched) => fetched,
+            Err(err) => {
+                tracing::warn!(?err, "failed to prefetch objects from remote store");
+                return;
+            }
+        };
+        for (kind, hex, data) in fetched {
+            if let Err(err) = self.write_cache(kind, &hex, &data) {
+                tracing::warn!(?err, "failed to cache prefetched object");
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Backend for RemoteBackend {
+    fn name(&self) -> &str {
+        Self::name()
+    }
+
+    fn commit_id_length(&self) -> usize {
+        COMMIT_ID_LENGTH
+    }
+
+    fn change_id_length(&self) -> usize {
+        CHANGE_ID_LENGTH
+    }
+
+    fn root_commit_id(&self) -> &CommitId {
+        &self.root_commit_id
+    }
+
+    fn root_change_id(&self) -> &ChangeId {
+        &self.root_change_id
+    }
+
+    fn empty_tree_id(&self) -> &TreeId {
+        &self.empty_tree_id
+    }
+
+    fn concurrency(&self) -> usize {
+        MAX_CONNECTIONS
+    }
+
+    async fn read_file(
+        &self,
+        _path: &RepoPath,
+        id: &FileId,
+    ) -> BackendResult<Pin<Box<dyn AsyncRead + Send>>> {
+        let data = self.read_object(ObjectKind::File, id).await?;
+        Ok(Box::pin(io::Cursor::new(data)))
+    }
+
+    async fn write_file(
+        &self,
+        _path: &RepoPath,
+        contents: &mut (dyn AsyncRead + Send + Unpin),
+    ) -> BackendResult<FileId> {
+        let mut data = vec![];
+        contents
+            .read_to_end(&mut data)
+            .await
+            .map_err(to_other_err)?;
+        let id = FileId::new(Blake2b512::digest(&data).to_vec());
+        self.write_object(ObjectKind::File, &id, data).await?;
+        Ok(id)
+    }
+
+    async fn read_symlink(&self, _path: &RepoPath, id: &SymlinkId) -> BackendResult<String> {
+        let data = self.read_object(ObjectKind::Symlink, id).await?;
+        String::from_utf8(data).map_err(|err| BackendError::InvalidUtf8 {
+            object_type: id.object_type(),
+            hash: id.hex(),
+            source: err.utf8_error(),
+        })
+    }
+
+    async fn write_symlink(&self, _path: &RepoPath, target: &str) -> BackendResult<SymlinkId> {
+        let id = SymlinkId::new(Blake2b512::digest(target.as_bytes()).to_vec());
+        self.write_object(ObjectKind::Symlink, &id, target.as_bytes().to_vec())
+            .await?;
+        Ok(id)
+    }
+
+    async fn read_copy(&self, _id: &CopyId) -> BackendResult<CopyHistory> {
+        Err(BackendError::Unsupported(
+            "The remote backend doesn't support copies".to_string(),
+        ))
+    }
+
+    async fn write_copy(&self, _contents: &CopyHistory) -> BackendResult<CopyId> {
+        Err(BackendError::Unsupported(
+            "The remote backend doesn't support copies".to_string(),
+        ))
+    }
+
+    async fn get_related_copies(&self, _copy_id: &CopyId) -> BackendResult<Vec<RelatedCopy>> {
+        Err(BackendError::Unsupported(
+            "The remote backend doesn't support copies".to_string(),
+        ))
+    }
+
+    async fn read_tree(&self, _path: &RepoPath, id: &TreeId) -> BackendResult<Tree> {
+        let data = self.read_object(ObjectKind::Tree, id).await?;
+        let proto = crate::protos::simple_store::Tree::decode(&*data).map_err(to_other_err)?;
+        let tree = tree_from_proto(proto);
+        // Subtrees are likely to be read next, so fetch them all at once.
+        self.prefetch(tree.entries().filter_map(|entry| match entry.value() {
+            TreeValue::Tree(id) => Some((ObjectKind::Tree, id.hex())),
+            _ => None,
+        }))
+        .await;
+        Ok(tree)
+    }
+
+    async fn write_tree(&self, _path: &RepoPath, tree: &Tree) -> BackendResult<TreeId> {
+        let data = tree_to_proto(tree).encode_to_vec();
+        let id = TreeId::new(blake2b_hash(tree).to_vec());
+        self.write_object(ObjectKind::Tree, &id, data).await?;
+        Ok(id)
+    }
+
+    async fn read_commit(&self, id: &CommitId) -> BackendResult<Commit> {
+        if *id == self.root_commit_id {
+            return Ok(make_root_commit(
+                self.root_change_id().clone(),
+                self.empty_tree_id.clone(),
+            ));
+        }
+
+        let data = self.read_object(ObjectKind::Commit, id).await?;
+        let proto = crate::protos::simple_store::Commit::decode(&*data).map_err(to_other_err)?;
+        let commit = commit_from_proto(proto);
+        // Commands like `jj log` will likely read the root trees and the
+        // parents next.
+        let trees = commit
+            .root_tree
+            .iter()
+            .map(|id| (ObjectKind::Tree, id.hex()));
+        let parents = commit
+            .parents
+            .iter()
+            .filter(|id| **id != self.root_commit_id)
+            .map(|id| (ObjectKind::Commit, id.hex()));
+        self.prefetch(trees.chain(parents)).await;
+        Ok(commit)
+    }
+
+    async fn write_commit(
+        &self,
+        mut commit: Commit,
+        sign_with: Option<&mut SigningFn>,
+    ) -> BackendResult<(CommitId, Commit)> {
+        assert!(commit.secure_sig.is_none(), "commit.secure_sig was set");
+
+        if commit.parents.is_empty() {
+            return Err(BackendError::Other(
+                "Cannot write a commit with no parents".into(),
+            ));
+        }
+
+        let mut proto = commit_to_proto(&commit);
+        if let Some(sign) = sign_with {
+            let data = proto.encode_to_vec();
+            let sig = sign(&data).map_err(to_other_err)?;
+            proto.secure_sig = Some(sig.clone());
+            commit.secure_sig = Some(SecureSig { data, sig });
+        }
+
+        let id = CommitId::new(blake2b_hash(&commit).to_vec());
+        self.write_object(ObjectKind::Commit, &id, proto.encode_to_vec())
+            .await?;
+        Ok((id, commit))
+    }
+
+    fn get_copy_records(
+        &self,
+        _paths: Option<&[RepoPathBuf]>,
+        _root: &CommitId,
+        _head: &CommitId,
+    ) -> BackendResult<BoxStream<'_, BackendResult<CopyRecord>>> {
+        Ok(stream::empty().boxed())
+    }
+
+    fn gc(&self, _index: &dyn Index, _keep_newer: SystemTime) -> BackendResult<()> {
+        Ok(())
+    }
+}
+
+/// Reference implementation of the remote store service, which stores objects
+/// in a local directory.
+///
+/// This is meant for testing. It trusts the clients to send objects with
+/// matching ids, and doesn't authenticate them.
+#[derive(Debug)]
+pub struct RemoteStoreServer {
+    listener: TcpListener,
+    store_path: PathBuf,
+}
+
+impl RemoteStoreServer {
+    /// Binds the server to the given address. Objects will be stored in
+    /// `store_path`, which is created if needed.
+    pub fn bind(addr: impl ToSocketAddrs, store_path: &Path) -> io::Result<Self> {
+        for kind in ObjectKind::ALL {
+            fs::create_dir_all(store_path.join(kind.dir_name()))?;
+        }
+        let listener = TcpListener::bind(addr)?;
+        Ok(Self {
+            listener,
+            store_path: store_path.to_owned(),
+        })
+    }
+
+    /// URL the clients should connect to.
+    pub fn url(&self) -> io::Result<String> {
+        Ok(format!("http://{}", self.listener.local_addr()?))
+    }
+
+    /// Serves requests until an error occurs while accepting connections.
+    pub fn serve(&self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let store_path = self.store_path.clone();
+            thread::spawn(move || {
+                if let Err(err) = handle_connection(&store_path, &stream) {
+                    tracing::warn!(?err, "failed to handle remote store request");
+                }
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Serves requests until the client closes the connection or stays idle for
+/// too long.
+fn handle_connection(store_path: &Path, stream: &TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let mut reader = BufReader::new(stream);
+    loop {
+        match reader.fill_buf() {
+            Ok([]) => return Ok(()),
+            Ok(_) => {}
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+                ) || is_closed_connection_error(&err) =>
+            {
+                return Ok(());
+            }
+            Err(err) => return Err(err),
+        }
+        if !handle_request(store_path, &mut reader)? {
+            return Ok(());
+        }
+    }
+}
+
+/// Handles one request. Returns whether the connection should be kept open.
+fn handle_request(store_path: &Path, reader: &mut BufReader<&TcpStream>) -> io::Result<bool> {
+    let stream = *reader.get_ref();
+    let head = read_message_head(reader)?;
+    let content_length = head.content_length.unwrap_or(0);
+    let mut body = vec![];
+    if head.chunked || content_length > MAX_REQUEST_BODY_SIZE {
+        // The body isn't read, so the connection can't be reused.
+        write_response(stream, 413, &[], true)?;
+        return Ok(false);
+    }
+    reader.take(content_length as u64).read_to_end(&mut body)?;
+    if body.len() != content_length {
+        return Err(invalid_http_data());
+    }
+
+    let mut parts = head.start_line.split(' ');
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+    let (status, response) = if let Some(name) = path.strip_prefix("/objects/") {
+        match (method, parse_object_name(name)) {
+            (_, None) => (404, vec![]),
+            ("GET", Some((kind, hex))) => {
+                match fs::read(store_path.join(kind.dir_name()).join(hex)) {
+                    Ok(data) => (200, data),
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => (404, vec![]),
+                    Err(err) => return Err(err),
+                }
+            }
+            ("PUT", Some((kind, hex))) => {
+                let temp_file = NamedTempFile::new_in(store_path)?;
+                temp_file.as_file().write_all(&body)?;
+                persist_content_addressed_temp_file(
+                    temp_file,
+                    store_path.join(kind.dir_name()).join(hex),
+                )?;
+                (201, vec![])
+            }
+            _ => (405, vec![]),
+        }
+    } else if path == "/batch" {
+        if method == "POST" {
+            let mut response = vec![];
+            for name in String::from_utf8_lossy(&body).lines() {
+                let Some((kind, hex)) = parse_object_name(name) else {
+                    continue;
+                };
+                match fs::read(store_path.join(kind.dir_name()).join(hex)) {
+                    Ok(data) => {
+                        writeln!(response, "{name} {}", data.len())?;
+                        response.extend(data);
+                    }
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            (200, response)
+        } else {
+            (405, vec![])
+        }
+    } else {
+        (404, vec![])
+    };
+
+    write_response(stream, status, &response, head.close)?;
+    Ok(!head.close)
+}
+
+fn write_response(stream: &TcpStream, status: u16, response: &[u8], close: bool) -> io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        201 => "Created",
+        404 => "Not Found",
+        413 => "Content Too Large",
+        _ => "Method Not Allowed",
+    };
+    let mut writer = io::BufWriter::new(stream);
+    write!(
+        writer,
+        "HTTP/1.1 {status} {reason}\r\nContent-Length: {len}\r\n",
+        len = response.len()
+    )?;
+    if close {
+        write!(writer, "Connection: close\r\n")?;
+    }
+    write!(writer, "\r\n")?;
+    writer.write_all(response)?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::backend::MillisSinceEpoch;
+    use crate::backend::Signature;
+    use crate::backend::Timestamp;
+    use crate::merge::Merge;
+    use crate::repo_path::RepoPathComponentBuf;
+    use crate::tests::TestResult;
+    use crate::tests::new_temp_dir;
+
+    fn start_server(store_path: &Path) -> String {
+        let server = RemoteStoreServer::bind("127.0.0.1:0", store_path).unwrap();
+        let url = server.url().unwrap();
+        thread::spawn(move || server.serve());
+        url
+    }
+
+    fn create_signature() -> Signature {
+        Signature {
+            name: "Someone".to_string(),
+            email: "someone@example.com".to_string(),
+            timestamp: Timestamp {
+                timestamp: MillisSinceEpoch(0),
+                tz_offset: 0,
+            },
+        }
+    }
+
+    fn write_test_tree(backend: &RemoteBackend) -> TestResult<TreeId> {
+        let path = RepoPath::root();
+        let file_id = backend
+            .write_file(path, &mut b"contents\n".as_slice())
+            .block_on()?;
+        let symlink_id = backend.write_symlink(path, "target").block_on()?;
+        let file_value = TreeValue::File {
+            id: file_id,
+            executable: false,
+            copy_id: CopyId::placeholder(),
+        };
+        let sub_tree = Tree::from_sorted_entries(vec![(
+            RepoPathComponentBuf::new("file")?,
+            file_value.clone(),
+        )]);
+        let sub_tree_id = backend.write_tree(path, &sub_tree).block_on()?;
+        let tree = Tree::from_sorted_entries(vec![
+            (
+                RepoPathComponentBuf::new("dir")?,
+                TreeValue::Tree(sub_tree_id),
+            ),
+            (RepoPathComponentBuf::new("file")?, file_value),
+            (
+                RepoPathComponentBuf::new("link")?,
+                TreeValue::Symlink(symlink_id),
+            ),
+        ]);
+        Ok(backend.write_tree(path, &tree).block_on()?)
+    }
+
+    #[test]
+    fn read_objects_written_by_other_client() -> TestResult {
+        let temp_dir = new_temp_dir();
+        let server_path = temp_dir.path().join("server");
+        let url = start_server(&server_path);
+        fs::create_dir(temp_dir.path().join("writer"))?;
+        fs::create_dir(temp_dir.path().join("reader"))?;
+        let writer = RemoteBackend::init(&temp_dir.path().join("writer"), &url)?;
+        let reader = RemoteBackend::init(&temp_dir.path().join("reader"), &url)?;
+        assert_eq!(reader.url(), url);
+
+        let tree_id = write_test_tree(&writer)?;
+        let commit = Commit {
+            parents: vec![writer.root_commit_id().clone()],
+            predecessors: vec![],
+            root_tree: Merge::resolved(tree_id.clone()),
+            conflict_labels: Merge::resolved(String::new()),
+            change_id: ChangeId::from_hex("abc123"),
+            description: "description".to_string(),
+            author: create_signature(),
+            committer: create_signature(),
+            secure_sig: None,
+        };
+        let (commit_id, commit) = writer.write_commit(commit, None).block_on()?;
+        assert!(server_path.join("commits").join(commit_id.hex()).exists());
+
+        // Reading the commit prefetches its root tree
+        assert_eq!(reader.read_commit(&commit_id).block_on()?, commit);
+        assert!(
+            reader
+                .cached_object_path(ObjectKind::Tree, &tree_id.hex())
+                .exists()
+        );
+
+        // Reading the tree prefetches the subtrees
+        let path = RepoPath::root();
+        let tree = reader.read_tree(path, &tree_id).block_on()?;
+        assert_eq!(tree, writer.read_tree(path, &tree_id).block_on()?);
+        let Some(TreeValue::Tree(sub_tree_id)) = tree.value(&RepoPathComponentBuf::new("dir")?)
+        else {
+            panic!("dir should be a tree");
+        };
+        assert!(
+            reader
+                .cached_object_path(ObjectKind::Tree, &sub_tree_id.hex())
+                .exists()
+        );
+
+        let Some(TreeValue::File { id: file_id, .. }) =
+            tree.value(&RepoPathComponentBuf::new("file")?)
+        else {
+            panic!("file should be a file");
+        };
+        let mut contents = vec![];
+        reader
+            .read_file(path, file_id)
+            .block_on()?
+            .read_to_end(&mut contents)
+            .block_on()?;
+        assert_eq!(contents, b"contents\n");
+        let Some(TreeValue::Symlink(symlink_id)) = tree.value(&RepoPathComponentBuf::new("link")?)
+        else {
+            panic!("link should be a symlink");
+        };
+        assert_eq!(reader.read_symlink(path, symlink_id).block_on()?, "target");
+        Ok(())
+    }
+
+    #[test]
+    fn read_cached_objects_offline() -> TestResult {
+        let temp_dir = new_temp_dir();
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let offline_url = format!("http://{}", listener.local_addr()?);
+        drop(listener);
+
+        let url = start_server(&temp_dir.path().join("server"));
+        let store_path = temp_dir.path().join("client");
+        fs::create_dir(&store_path)?;
+        let backend = RemoteBackend::init(&store_path, &url)?;
+        let tree_id = write_test_tree(&backend)?;
+
+        // Point the client to an unreachable server
+        fs::write(store_path.join("remote_url"), &offline_url)?;
+        let backend = RemoteBackend::load(&store_path)?;
+        backend.read_tree(RepoPath::root(), &tree_id).block_on()?;
+        let unknown_id = TreeId::new(vec![0xff; 64]);
+        assert_matches!(
+            backend.read_tree(RepoPath::root(), &unknown_id).block_on(),
+            Err(BackendError::ReadObject { source, .. })
+                if source.to_string().starts_with("Failed to connect to remote store")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn read_missing_object() -> TestResult {
+        let temp_dir = new_temp_dir();
+        let url = start_server(&temp_dir.path().join("server"));
+        let backend = RemoteBackend::init(temp_dir.path(), &url)?;
+        let unknown_id = CommitId::new(vec![0xff; 64]);
+        assert_matches!(
+            backend.read_commit(&unknown_id).block_on(),
+            Err(BackendError::ObjectNotFound { .. })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn read_cached_tree_offline_without_prefetch() -> TestResult {
+        let temp_dir = new_temp_dir();
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let offline_url = format!("http://{}", listener.local_addr()?);
+        drop(listener);
+
+        let url = start_server(&temp_dir.path().join("server"));
+        fs::create_dir(temp_dir.path().join("writer"))?;
+        let writer = RemoteBackend::init(&temp_dir.path().join("writer"), &url)?;
+        let tree_id = write_test_tree(&writer)?;
+        let store_path = temp_dir.path().join("reader");
+        fs::create_dir(&store_path)?;
+        let reader = RemoteBackend::init(&store_path, &url)?;
+        // Only the root tree is cached, not the subtrees
+        reader.read_object(ObjectKind::Tree, &tree_id).block_on()?;
+
+        fs::write(store_path.join("remote_url"), &offline_url)?;
+        let reader = RemoteBackend::load(&store_path)?;
+        reader.read_tree(RepoPath::root(), &tree_id).block_on()?;
+        Ok(())
+    }
+
+    #[test]
+    fn reject_corrupt_objects() -> TestResult {
+        let temp_dir = new_temp_dir();
+        let server_path = temp_dir.path().join("server");
+        let url = start_server(&server_path);
+        let backend = RemoteBackend::init(temp_dir.path(), &url)?;
+        let file_id = FileId::new(Blake2b512::digest(b"contents").to_vec());
+        fs::write(server_path.join("files").join(file_id.hex()), b"other")?;
+
+        assert_matches!(
+            backend.read_file(RepoPath::root(), &file_id).block_on().map(|_| ()),
+            Err(BackendError::ReadObject { source, .. })
+                if source.to_string().starts_with("Remote store sent invalid content")
+        );
+        assert_matches!(
+            backend
+                .client
+                .batch_get(&[(ObjectKind::File, file_id.hex())]),
+            Err(RemoteStoreError::ObjectMismatch { .. })
+        );
+        assert!(
+            !backend
+                .cached_object_path(ObjectKind::File, &file_id.hex())
+                .exists()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reject_unrequested_objects() -> TestResult {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let client = RemoteClient::new(&format!("http://{}", listener.local_addr()?))?;
+        thread::spawn(move || -> io::Result<()> {
+            let (stream, _) = listener.accept()?;
+            read_message_head(&mut BufReader::new(&stream))?;
+            let data = b"contents";
+            let name = format!(
+                "files/{}",
+                crate::hex_util::encode_hex(&Blake2b512::digest(data))
+            );
+            let body = [format!("{name} {}\n", data.len()).as_bytes(), data].concat();
+            // Respond in the chunked transfer encoding
+            write!(
+                &stream,
+                "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n",
+                body.len()
+            )?;
+            (&stream).write_all(&body)?;
+            write!(&stream, "\r\n0\r\n\r\n")
+        });
+        let requested = FileId::new(Blake2b512::digest(b"other").to_vec());
+        assert_matches!(
+            client.batch_get(&[(ObjectKind::File, requested.hex())]),
+            Err(RemoteStoreError::MalformedResponse)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reject_large_responses() -> TestResult {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let client = RemoteClient::new(&format!("http://{}", listener.local_addr()?))?;
+        thread::spawn(move || -> io::Result<()> {
+            let (stream, _) = listener.accept()?;
+            read_message_head(&mut BufReader::new(&stream))?;
+            write!(
+                &stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n",
+                MAX_RESPONSE_BODY_SIZE + 1
+            )?;
+            let (stream, _) = listener.accept()?;
+            read_message_head(&mut BufReader::new(&stream))?;
+            write!(
+                &stream,
+                "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n",
+                MAX_RESPONSE_BODY_SIZE + 1
+            )
+        });
+        let id = FileId::new(Blake2b512::digest(b"contents").to_vec());
+        assert_matches!(
+            client.get(ObjectKind::File, &id.hex()),
+            Err(RemoteStoreError::ResponseTooLarge)
+        );
+        assert_matches!(
+            client.batch_get(&[(ObjectKind::File, id.hex())]),
+            Err(RemoteStoreError::ResponseTooLarge)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reuse_connections() -> TestResult {
+        let temp_dir = new_temp_dir();
+        let url = start_server(temp_dir.path());
+        let client = RemoteClient::new(&url)?;
+        let id = FileId::new(Blake2b512::digest(b"contents").to_vec());
+        client.put(ObjectKind::File, &id.hex(), b"contents")?;
+        assert_eq!(client.idle_connections.lock().unwrap().len(), 1);
+        let stream_addr = |client: &RemoteClient| {
+            client.idle_connections.lock().unwrap()[0]
+                .get_ref()
+                .local_addr()
+                .unwrap()
+        };
+        let addr = stream_addr(&client);
+        assert_eq!(
+            client.get(ObjectKind::File, &id.hex())?,
+            Some(b"contents".to_vec())
+        );
+        assert_eq!(client.idle_connections.lock().unwrap().len(), 1);
+        assert_eq!(stream_addr(&client), addr);
+        Ok(())
+    }
+
+    #[test]
+    fn retry_on_closed_connection() -> TestResult {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let client = RemoteClient::new(&format!("http://{}", listener.local_addr()?))?;
+        // Close the connection after each response without telling the client
+        thread::spawn(move || -> io::Result<()> {
+            for _ in 0..2 {
+                let (stream, _) = listener.accept()?;
+                read_message_head(&mut BufReader::new(&stream))?;
+                write!(
+                    &stream,
+                    "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n"
+                )?;
+            }
+            Ok(())
+        });
+        let id = FileId::new(Blake2b512::digest(b"contents").to_vec());
+        assert_eq!(client.get(ObjectKind::File, &id.hex())?, None);
+        assert_eq!(client.get(ObjectKind::File, &id.hex())?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn upload_cached_objects() -> TestResult {
+        let temp_dir = new_temp_dir();
+        let url = start_server(&temp_dir.path().join("server1"));
+        let store_path = temp_dir.path().join("client");
+        fs::create_dir(&store_path)?;
+        let backend = RemoteBackend::init(&store_path, &url)?;
+        let tree_id = write_test_tree(&backend)?;
+        fs::create_dir(temp_dir.path().join("other"))?;
+        let other = RemoteBackend::init(&temp_dir.path().join("other"), &url)?;
+        let tree = other.read_tree(RepoPath::root(), &tree_id).block_on()?;
+
+        // The tree is cached by the other client, but was never uploaded by it
+        let server_path = temp_dir.path().join("server2");
+        let url = start_server(&server_path);
+        fs::write(temp_dir.path().join("other").join("remote_url"), &url)?;
+        let other = RemoteBackend::load(&temp_dir.path().join("other"))?;
+        other.write_tree(RepoPath::root(), &tree).block_on()?;
+        assert!(server_path.join("trees").join(tree_id.hex()).exists());
+        Ok(())
+    }
+
+    #[test]
+    fn server_rejects_large_requests() -> TestResult {
+        let temp_dir = new_temp_dir();
+        let url = start_server(temp_dir.path());
+        let stream = TcpStream::connect(url.strip_prefix("http://").unwrap())?;
+        write!(
+            &stream,
+            "PUT /objects/files/00 HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            usize::MAX
+        )?;
+        let head = read_message_head(&mut BufReader::new(&stream))?;
+        assert_eq!(head.start_line, "HTTP/1.1 413 Content Too Large");
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_url() {
+        let temp_dir = new_temp_dir();
+        assert_matches!(
+            RemoteBackend::init(temp_dir.path(), "https://example.com"),
+            Err(BackendInitError(err)) if err.to_string().starts_with("Invalid remote store URL")
+        );
+    }
+}
@@ -90,6 +90,7 @@ use crate::refs::diff_named_ref_targets;
 use crate::refs::diff_named_remote_refs;
 use crate::refs::merge_ref_targets;
 use crate::refs::merge_remote_refs;
+use crate::remote_backend::RemoteBackend;
 use crate::revset;
 use crate::revset::RevsetEvaluationError;
 use crate::revset::RevsetExpression;
@@ -121,6 +122,9 @@ pub trait Repo {
 
     fn store(&self) -> &Arc<Store>;
 
+    /// Options to be used when merging trees in this repo.
+    fn merge_options(&self) -> &MergeOptions;
+
     fn op_store(&self) -> &Arc<dyn OpStore>;
 
     fn index(&self) -> &dyn Index;
@@ -196,7 +200,11 @@ impl ReadonlyRepo {
     }
 
     pub fn default_submodule_store_initializer() -> &'static SubmoduleStoreInitializer<'static> {
-        &|_settings, store_path| Ok(Box::new(DefaultSubmoduleStore::init(store_path)))
+        &|settings, store_path| {
+            let store = DefaultSubmoduleStore::init(settings, store_path)
+                .map_err(|err| BackendInitError(err.into()))?;
+            Ok(Box::new(store))
+        }
     }
 
     #[expect(clippy::too_many_arguments)]
@@ -219,6 +227,7 @@ impl ReadonlyRepo {
         fs::write(&backend_path, backend.name()).context(&backend_path)?;
         let merge_options =
             MergeOptions::from_settings(settings).map_err(|err| BackendInitError(err.into()))?;
+        let signer = signer.with_persistent_cache(repo_path.join("signature_cache"));
         let store = Store::new(backend, signer, merge_options);
 
         let op_store_path = repo_path.join("op_store");
@@ -352,6 +361,10 @@ impl Repo for ReadonlyRepo {
         self.loader.store()
     }
 
+    fn merge_options(&self) -> &MergeOptions {
+        self.store().merge_options()
+    }
+
     fn op_store(&self) -> &Arc<dyn OpStore> {
         self.loader.op_store()
     }
@@ -435,6 +448,10 @@ impl Default for StoreFactories {
             SimpleBackend::name(),
             Box::new(|_settings, store_path| Ok(Box::new(SimpleBackend::load(store_path)))),
         );
+        factories.add_backend(
+            RemoteBackend::name(),
+            Box::new(|_settings, store_path| Ok(Box::new(RemoteBackend::load(store_path)?))),
+        );
         #[cfg(feature = "git")]
         factories.add_backend(
             crate::git_backend::GitBackend::name(),
@@ -477,7 +494,11 @@ impl Default for StoreFactories {
         // SubmoduleStores
         factories.add_submodule_store(
             DefaultSubmoduleStore::name(),
-            Box::new(|_settings, store_path| Ok(Box::new(DefaultSubmoduleStore::load(store_path)))),
+            Box::new(|settings, store_path| {
+                let store = DefaultSubmoduleStore::load(settings, store_path)
+                    .map_err(|err| BackendLoadError(err.into()))?;
+                Ok(Box::new(store))
+            }),
         );
 
         factories
@@ -706,7 +727,8 @@ impl RepoLoader {
             MergeOptions::from_settings(settings).map_err(|err| BackendLoadError(err.into()))?;
         let store = Store::new(
             store_factories.load_backend(settings, &repo_path.join("store"))?,
-            Signer::from_settings(settings)?,
+            Signer::from_settings(settings)?
+                .with_persistent_cache(repo_path.join("signature_cache")),
             merge_options,
         );
         let root_op_data = RootOperationData {
@@ -909,18 +931,21 @@ pub struct MutableRepo {
     //   commits. However, if the type is `Abandoned`, a new working-copy commit should be created
     //   on top of all of the new commits instead.
     parent_mapping: HashMap<CommitId, Rewrite>,
+    merge_options: MergeOptions,
 }
 
 impl MutableRepo {
     pub fn new(base_repo: Arc<ReadonlyRepo>, index: &dyn ReadonlyIndex, view: &View) -> Self {
         let mut_view = view.clone();
         let mut_index = index.start_modification();
+        let merge_options = base_repo.store().merge_options().clone();
         Self {
             base_repo,
             index: mut_index,
             view: DirtyCell::with_clean(mut_view),
             commit_predecessors: Default::default(),
             parent_mapping: Default::default(),
+            merge_options,
         }
     }
 
@@ -928,6 +953,12 @@ impl MutableRepo {
         &self.base_repo
     }
 
+    /// Overrides the options to be used when merging trees in this
+    /// transaction, e.g. when rebasing commits.
+    pub fn set_merge_options(&mut self, options: MergeOptions) {
+        self.merge_options = options;
+    }
+
     fn view_mut(&mut self) -> &mut View {
         self.view.get_mut()
     }
@@ -1743,6 +1774,10 @@ impl MutableRepo {
         self.view_mut().set_remote_bookmark(symbol, remote_ref);
     }
 
+    pub fn add_remote_bookmark_tombstone(&mut self, symbol: RemoteRefSymbol<'_>) {
+        self.view_mut().add_remote_bookmark_tombstone(symbol);
+    }
+
     fn merge_remote_bookmark(
         &mut self,
         symbol: RemoteRefSymbol<'_>,
@@ -1861,6 +1896,15 @@ impl MutableRepo {
         self.view_mut().set_git_head_target(target);
     }
 
+    pub fn get_git_worktree_head(&self, name: &str) -> RefTarget {
+        self.view
+            .with_ref(|v| v.get_git_worktree_head(name).clone())
+    }
+
+    pub fn set_git_worktree_head_target(&mut self, name: &str, target: RefTarget) {
+        self.view_mut().set_git_worktree_head_target(name, target);
+    }
+
     pub fn set_view(&mut self, data: op_store::View) {
         self.view_mut().set_view(data);
         self.view.mark_dirty();
@@ -1939,6 +1983,18 @@ impl MutableRepo {
         for (symbol, (base_ref, other_ref)) in changed_remote_bookmarks {
             self.merge_remote_bookmark(symbol, base_ref, other_ref)?;
         }
+        // Tombstones aren't included in the diff above. Keep the ones recorded
+        // by the other side unless the bookmark exists on this side.
+        let base_tombstones: HashSet<_> = base.all_remote_bookmark_tombstones().collect();
+        for symbol in other.all_remote_bookmark_tombstones() {
+            let view = self.view.get_mut();
+            if !base_tombstones.contains(&symbol)
+                && view.get_remote_bookmark(symbol) == RemoteRef::absent_ref()
+                && view.get_local_bookmark(symbol.name).is_absent()
+            {
+                view.add_remote_bookmark_tombstone(symbol);
+            }
+        }
 
         let changed_remote_tags =
             diff_named_remote_refs(base.all_remote_tags(), other.all_remote_tags());
@@ -1954,6 +2010,18 @@ impl MutableRepo {
         )?;
         self.set_git_head_target(new_git_head_target);
 
+        let changed_git_worktree_heads =
+            diff_named_ref_targets(base.git_worktree_heads(), other.git_worktree_heads());
+        for (name, (base_target, other_target)) in changed_git_worktree_heads {
+            let new_target = merge_ref_targets(
+                self.index(),
+                self.view().get_git_worktree_head(name),
+                base_target,
+                other_target,
+            )?;
+            self.set_git_worktree_head_target(name, new_target);
+        }
+
         Ok(())
     }
 
@@ -2029,6 +2097,10 @@ impl Repo for MutableRepo {
         self.base_repo.store()
     }
 
+    fn merge_options(&self) -> &MergeOptions {
+        &self.merge_options
+    }
+
     fn op_store(&self) -> &Arc<dyn OpStore> {
         self.base_repo.op_store()
     }
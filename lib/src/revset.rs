@@ -161,6 +161,7 @@ pub enum RevsetCommitRef {
     },
     GitRefs,
     GitHead,
+    GitWorktreeHeads(StringExpression),
 }
 
 /// String expressions to match `name@remote` bookmarks/tags.
@@ -465,6 +466,12 @@ impl<St: ExpressionState<CommitRef = RevsetCommitRef>> RevsetExpression<St> {
     pub fn git_head() -> Arc<Self> {
         Arc::new(Self::CommitRef(RevsetCommitRef::GitHead))
     }
+
+    pub fn git_worktree_heads(expression: StringExpression) -> Arc<Self> {
+        Arc::new(Self::CommitRef(RevsetCommitRef::GitWorktreeHeads(
+            expression,
+        )))
+    }
 }
 
 // Compound expression
@@ -987,13 +994,17 @@ static BUILTIN_FUNCTION_MAP: LazyLock<HashMap<&str, RevsetFunction>> = LazyLock:
         function.expect_no_arguments()?;
         Ok(RevsetExpression::git_refs())
     });
-    // TODO: Remove in jj 0.43+
-    map.insert("git_head", |diagnostics, function, _context| {
+    map.insert("git_head", |diagnostics, function, context| {
+        let ([], [opt_arg]) = function.expect_arguments()?;
+        if let Some(arg) = opt_arg {
+            let expr = expect_string_expression(diagnostics, arg, context)?;
+            return Ok(RevsetExpression::git_worktree_heads(expr));
+        }
+        // TODO: Remove in jj 0.43+
         diagnostics.add_warning(RevsetParseError::expression(
             "git_head() is deprecated; use first_parent(@) instead",
             function.name_span,
         ));
-        function.expect_no_arguments()?;
         Ok(RevsetExpression::git_head())
     });
     map.insert("latest", |diagnostics, function, context| {
@@ -3051,6 +3062,18 @@ fn resolve_commit_ref(
             Ok(commit_ids)
         }
         RevsetCommitRef::GitHead => Ok(repo.view().git_head().added_ids().cloned().collect()),
+        RevsetCommitRef::GitWorktreeHeads(expression) => {
+            let matcher = expression.to_matcher();
+            let commit_ids = repo
+                .view()
+                .git_worktree_heads()
+                .iter()
+                .filter(|(name, _)| matcher.is_match(name))
+                .flat_map(|(_, target)| target.added_ids())
+                .cloned()
+                .collect();
+            Ok(commit_ids)
+        }
     }
 }
 
@@ -62,7 +62,7 @@ pub async fn merge_commit_trees(repo: &dyn Repo, commits: &[Commit]) -> BackendR
     } else {
         merge_commit_trees_no_resolve_without_repo(repo.store(), repo.index(), commits)
             .await?
-            .resolve()
+            .resolve_with_options(repo.merge_options())
             .await
     }
 }
@@ -333,29 +333,30 @@ impl<'repo> CommitRewriter<'repo> {
             let new_base_tree_fut = merge_commit_trees(self.mut_repo, &new_parents);
             let old_tree = self.old_commit.tree();
             let (old_base_tree, new_base_tree) = try_join!(old_base_tree_fut, new_base_tree_fut)?;
-            (
-                old_base_tree.tree_ids() == self.old_commit.tree_ids(),
-                MergedTree::merge(Merge::from_vec(vec![
-                    (
-                        new_base_tree,
-                        format!(
-                            "{} (rebase destination)",
-                            conflict_label_for_commits(&new_parents)
-                        ),
-                    ),
-                    (
-                        old_base_tree,
-                        format!(
-                            "{} (parents of rebased revision)",
-                            conflict_label_for_commits(&old_parents)
-                        ),
+            let was_empty = old_base_tree.tree_ids() == self.old_commit.tree_ids();
+            let trees = Merge::from_vec(vec![
+                (
+                    new_base_tree,
+                    format!(
+                        "{} (rebase destination)",
+                        conflict_label_for_commits(&new_parents)
                     ),
-                    (
-                        old_tree,
-                        format!("{} (rebased revision)", self.old_commit.conflict_label()),
+                ),
+                (
+                    old_base_tree,
+                    format!(
+                        "{} (parents of rebased revision)",
+                        conflict_label_for_commits(&old_parents)
                     ),
-                ]))
-                .await?,
+                ),
+                (
+                    old_tree,
+                    format!("{} (rebased revision)", self.old_commit.conflict_label()),
+                ),
+            ]);
+            (
+                was_empty,
+                MergedTree::merge_with_options(trees, self.mut_repo.merge_options()).await?,
             )
         };
         // Ensure we don't abandon commits with multiple parents (merge commits), even
@@ -1391,13 +1392,16 @@ pub async fn squash_commits<'repo>(
             .map(|source| source.commit.commit.id().clone()),
     );
     // Apply the selected changes onto the destination
-    let destination_tree = MergedTree::merge(Merge::from_diffs(
-        (
-            rewritten_destination.tree(),
-            format!("{} (squash destination)", destination.conflict_label()),
+    let destination_tree = MergedTree::merge_with_options(
+        Merge::from_diffs(
+            (
+                rewritten_destination.tree(),
+                format!("{} (squash destination)", destination.conflict_label()),
+            ),
+            source_commits.into_iter().map(|source| source.diff),
         ),
-        source_commits.into_iter().map(|source| source.diff),
-    ))
+        repo.merge_options(),
+    )
     .await?;
 
     let commit_builder = repo
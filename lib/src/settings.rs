@@ -20,6 +20,7 @@ use std::sync::Arc;
 use std::sync::Mutex;
 
 use chrono::DateTime;
+use chrono::TimeZone as _;
 use itertools::Itertools as _;
 use rand::prelude::*;
 use rand_chacha::ChaCha20Rng;
@@ -38,6 +39,7 @@ use crate::config::ToConfigNamePath;
 use crate::fmt_util::binary_prefix;
 use crate::ref_name::RemoteNameBuf;
 use crate::signing::SignBehavior;
+use crate::time_util::DatePatternContext;
 
 #[derive(Debug, Clone)]
 pub struct UserSettings {
@@ -185,6 +187,19 @@ impl UserSettings {
         self.data.commit_timestamp
     }
 
+    /// Context for parsing date patterns relative to the current time, or to
+    /// the configured commit timestamp if any.
+    pub fn date_pattern_context(&self) -> DatePatternContext {
+        let now = if let Some(timestamp) = self.commit_timestamp() {
+            chrono::Local
+                .timestamp_millis_opt(timestamp.timestamp.0)
+                .unwrap()
+        } else {
+            chrono::Local::now()
+        };
+        now.into()
+    }
+
     pub fn operation_timestamp(&self) -> Option<Timestamp> {
         self.data.operation_timestamp
     }
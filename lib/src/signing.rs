@@ -17,17 +17,32 @@
 
 use std::fmt::Debug;
 use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::SystemTime;
 
+use blake2::Blake2b512;
 use clru::CLruCache;
+use digest::Digest as _;
 use thiserror::Error;
 
 use crate::backend::CommitId;
 use crate::config::ConfigGetError;
+use crate::file_util::create_or_reuse_dir;
 use crate::gpg_signing::GpgBackend;
 use crate::gpg_signing::GpgsmBackend;
+use crate::object_id::ObjectId as _;
 use crate::settings::UserSettings;
+use crate::ssh_agent_signing::SshAgentBackend;
 use crate::ssh_signing::SshBackend;
+use crate::stacked_table::ReadonlyTable;
+use crate::stacked_table::TableSegment as _;
+use crate::stacked_table::TableStore;
 use crate::store::COMMIT_CACHE_CAPACITY;
 #[cfg(feature = "testing")]
 use crate::test_signing_backend::TestSigningBackend;
@@ -114,6 +129,49 @@ pub trait SigningBackend: Debug + Send + Sync {
     /// verify(data, sign(data)?)?.status == SigStatus::Good
     /// ```
     fn verify(&self, data: &[u8], signature: &[u8]) -> SignResult<Verification>;
+
+    /// Verify multiple `(data, signature)` pairs at once. Returns one result
+    /// per pair, in the same order.
+    ///
+    /// Backends which run an external program can override this to verify the
+    /// signatures concurrently. The default implementation verifies the
+    /// signatures one by one.
+    fn verify_batch(&self, signatures: &[(&[u8], &[u8])]) -> Vec<SignResult<Verification>> {
+        signatures
+            .iter()
+            .map(|(data, signature)| self.verify(data, signature))
+            .collect()
+    }
+
+    /// Returns a digest of the inputs the verification results depend on,
+    /// such as the trusted keys and the revocation list.
+    ///
+    /// Results are only persisted across processes if all backends return
+    /// `Some`, and only reused while the digests stay the same. Backends must
+    /// return `None` if their results depend on the current time in a way that
+    /// the digest can't capture. The default implementation returns `None`.
+    fn trust_state(&self) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+/// Hashes the contents of the files listing trusted or revoked keys, to be
+/// returned by [`SigningBackend::trust_state()`]. Missing files are hashed as
+/// such.
+pub fn hash_trust_files<'a>(paths: impl IntoIterator<Item = &'a Path>) -> Vec<u8> {
+    let mut hasher = Blake2b512::new();
+    for path in paths {
+        hasher.update(path.as_os_str().as_encoded_bytes());
+        match fs::read(path) {
+            Ok(content) => {
+                hasher.update([1]);
+                hasher.update((content.len() as u64).to_le_bytes());
+                hasher.update(&content);
+            }
+            Err(_) => hasher.update([0]),
+        }
+    }
+    hasher.finalize().to_vec()
 }
 
 /// An error type for the signing/verifying operations
@@ -161,6 +219,110 @@ pub enum SignBehavior {
     Force,
 }
 
+/// A enum that describes when commits are required to be signed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SignRequirement {
+    /// Signatures are never required.
+    None,
+    /// Commits must have a good signature from a trusted key to be pushed.
+    Push,
+}
+
+/// Describes which signatures are acceptable on published commits.
+#[derive(Debug, Clone)]
+pub struct SignPolicy {
+    /// When the policy is enforced, see [SignRequirement].
+    pub require: SignRequirement,
+    /// Keys trusted to sign commits, as reported by [Verification::key]. If
+    /// empty, any signature the backend considers good is accepted.
+    pub trusted_keys: Vec<String>,
+}
+
+/// The reason why a commit is rejected by a [SignPolicy].
+#[derive(Debug, Error)]
+pub enum SignPolicyViolation {
+    /// The commit has no signature.
+    #[error("it is not signed")]
+    Unsigned,
+    /// The signature couldn't be verified or doesn't match the commit.
+    #[error("its signature is {0}")]
+    InvalidSignature(SigStatus),
+    /// The signature is good, but the backend didn't report the signing key.
+    #[error("its signing key is unknown")]
+    UnidentifiedKey,
+    /// The signature is good, but the key isn't one of the trusted keys.
+    #[error("it is signed by untrusted key {0}")]
+    UntrustedKey(String),
+}
+
+impl SignPolicy {
+    /// Loads the policy from `signing.require` and `signing.trusted-keys`.
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        Ok(Self {
+            require: settings.get("signing.require")?,
+            trusted_keys: settings.get("signing.trusted-keys")?,
+        })
+    }
+
+    /// Whether commits must be signed before they are pushed.
+    pub fn is_required_on_push(&self) -> bool {
+        self.require == SignRequirement::Push
+    }
+
+    /// Checks the verification result of a commit signature against the
+    /// policy. `None` means the commit is unsigned.
+    pub fn check(&self, verification: Option<&Verification>) -> Result<(), SignPolicyViolation> {
+        let verification = verification.ok_or(SignPolicyViolation::Unsigned)?;
+        if verification.status != SigStatus::Good {
+            return Err(SignPolicyViolation::InvalidSignature(verification.status));
+        }
+        if self.trusted_keys.is_empty() {
+            return Ok(());
+        }
+        let key = verification
+            .key
+            .as_ref()
+            .ok_or(SignPolicyViolation::UnidentifiedKey)?;
+        if self
+            .trusted_keys
+            .iter()
+            .any(|trusted_key| is_same_key(trusted_key, key))
+        {
+            Ok(())
+        } else {
+            Err(SignPolicyViolation::UntrustedKey(key.clone()))
+        }
+    }
+}
+
+/// Whether the configured `trusted_key` identifies the `key` reported by a
+/// backend.
+///
+/// Hexadecimal key ids are compared case-insensitively, ignoring whitespace.
+/// A long key id (at least 16 digits) also matches the fingerprint ending with
+/// it, since GnuPG may report either.
+fn is_same_key(trusted_key: &str, key: &str) -> bool {
+    if trusted_key == key {
+        return true;
+    }
+    let normalize_hex = |s: &str| -> Option<String> {
+        let hex: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        hex.chars()
+            .all(|c| c.is_ascii_hexdigit())
+            .then(|| hex.to_ascii_uppercase())
+    };
+    let (Some(trusted_key), Some(key)) = (normalize_hex(trusted_key), normalize_hex(key)) else {
+        return false;
+    };
+    let (shorter, longer) = if trusted_key.len() <= key.len() {
+        (&trusted_key, &key)
+    } else {
+        (&key, &trusted_key)
+    };
+    shorter.len() >= 16 && longer.ends_with(shorter.as_str())
+}
+
 /// Wraps low-level signing backends and adds caching, similar to `Store`.
 #[derive(Debug)]
 pub struct Signer {
@@ -172,6 +334,13 @@ pub struct Signer {
     /// for ownership reasons.
     backends: Vec<Box<dyn SigningBackend>>,
     cache: Mutex<CLruCache<CommitId, Verification>>,
+    /// Identifies the backends configuration. Persisted verifications are
+    /// only reused if the configuration doesn't change.
+    cache_namespace: String,
+    /// Digest of the configuration and the trust states of all backends, or
+    /// `None` if the results can't be persisted.
+    trust_namespace: OnceLock<Option<Vec<u8>>>,
+    persistent_cache: Option<VerificationTable>,
 }
 
 impl Signer {
@@ -182,6 +351,9 @@ impl Signer {
             Box::new(GpgBackend::from_settings(settings).map_err(SignInitError::BackendConfig)?),
             Box::new(GpgsmBackend::from_settings(settings).map_err(SignInitError::BackendConfig)?),
             Box::new(SshBackend::from_settings(settings).map_err(SignInitError::BackendConfig)?),
+            Box::new(
+                SshAgentBackend::from_settings(settings).map_err(SignInitError::BackendConfig)?,
+            ),
             #[cfg(feature = "testing")]
             Box::new(TestSigningBackend),
         ];
@@ -198,7 +370,12 @@ impl Signer {
             })
             .transpose()?;
 
-        Ok(Self::new(main_backend, backends))
+        let mut signer = Self::new(main_backend, backends);
+        signer.cache_namespace = settings
+            .get_table("signing.backends")
+            .map(|table| table.to_string())
+            .unwrap_or_default();
+        Ok(signer)
     }
 
     /// Creates a signer with the given backends.
@@ -210,9 +387,35 @@ impl Signer {
             main_backend,
             backends: other_backends,
             cache: Mutex::new(CLruCache::new(COMMIT_CACHE_CAPACITY.try_into().unwrap())),
+            cache_namespace: String::new(),
+            trust_namespace: OnceLock::new(),
+            persistent_cache: None,
         }
     }
 
+    /// Persists the verification results in the given directory, so they can
+    /// be reused by later processes. The directory is created on first write.
+    pub fn with_persistent_cache(mut self, dir: PathBuf) -> Self {
+        self.persistent_cache = Some(VerificationTable::new(dir));
+        self
+    }
+
+    fn trust_namespace(&self) -> Option<&[u8]> {
+        self.trust_namespace
+            .get_or_init(|| {
+                let mut hasher = Blake2b512::new();
+                hasher.update(self.cache_namespace.as_bytes());
+                for backend in self.main_backend.iter().chain(&self.backends) {
+                    let state = backend.trust_state()?;
+                    hasher.update(backend.name().as_bytes());
+                    hasher.update([0]);
+                    hasher.update(state);
+                }
+                Some(hasher.finalize().to_vec())
+            })
+            .as_deref()
+    }
+
     /// Checks if the signer can sign, i.e. if a main backend is configured.
     pub fn can_sign(&self) -> bool {
         self.main_backend.is_some()
@@ -235,46 +438,280 @@ impl Signer {
         data: &[u8],
         signature: &[u8],
     ) -> SignResult<Verification> {
-        let cached = self.cache.lock().unwrap().get(commit_id).cloned();
-        if let Some(check) = cached {
-            return Ok(check);
+        self.verify_batch(&[(commit_id, data, signature)])
+            .pop()
+            .unwrap()
+    }
+
+    /// Verifies multiple `(commit_id, data, signature)` entries at once.
+    /// Returns one result per entry, in the same order.
+    ///
+    /// The signatures that aren't cached are passed to the backends in
+    /// batches, which is usually much faster than verifying them one by one.
+    pub fn verify_batch(
+        &self,
+        signatures: &[(&CommitId, &[u8], &[u8])],
+    ) -> Vec<SignResult<Verification>> {
+        let mut results: Vec<Option<SignResult<Verification>>> = {
+            let mut cache = self.cache.lock().unwrap();
+            signatures
+                .iter()
+                .map(|(commit_id, _, _)| cache.get(*commit_id).cloned().map(Ok))
+                .collect()
+        };
+
+        if let Some(table) = &self.persistent_cache
+            && let Some(namespace) = self.trust_namespace()
+        {
+            let missing_ids: Vec<&CommitId> = signatures
+                .iter()
+                .zip(&results)
+                .filter(|(_, result)| result.is_none())
+                .map(|((commit_id, _, _), _)| *commit_id)
+                .collect();
+            let mut found = table.get(namespace, &missing_ids).into_iter();
+            let mut cache = self.cache.lock().unwrap();
+            for ((commit_id, _, _), result) in signatures.iter().zip(&mut results) {
+                if result.is_none()
+                    && let Some(verification) = found.next().unwrap()
+                {
+                    cache.put((*commit_id).clone(), verification.clone());
+                    *result = Some(Ok(verification));
+                }
+            }
+        }
+
+        let pending = self.verify_with_backends(signatures, &mut results);
+
+        let mut verified = vec![];
+        {
+            let mut cache = self.cache.lock().unwrap();
+            for i in pending {
+                // now here it's correct to cache unknowns, as we don't
+                // have a backend that knows how to handle this signature
+                //
+                // not sure about how much of an optimization this is
+                cache.put(signatures[i].0.clone(), Verification::unknown());
+                results[i] = Some(Ok(Verification::unknown()));
+            }
+            for ((commit_id, _, _), result) in signatures.iter().zip(&results) {
+                // a key might get imported before next call?.
+                // realistically this is unlikely, but technically
+                // it's correct to not cache unknowns here
+                if let Some(Ok(verification)) = result
+                    && verification.status != SigStatus::Unknown
+                    && cache
+                        .put((*commit_id).clone(), verification.clone())
+                        .is_none()
+                {
+                    verified.push((*commit_id, verification));
+                }
+            }
+        }
+        if let Some(table) = &self.persistent_cache
+            && !verified.is_empty()
+            && let Some(namespace) = self.trust_namespace()
+        {
+            table.insert(namespace, &verified);
+        }
+
+        results.into_iter().map(Option::unwrap).collect()
+    }
+
+    /// Verifies the signatures with the backends, bypassing the caches.
+    ///
+    /// Use this when the results must reflect the current trust state, e.g.
+    /// to enforce a [`SignPolicy`]. The results aren't cached either.
+    pub fn verify_batch_uncached(
+        &self,
+        signatures: &[(&CommitId, &[u8], &[u8])],
+    ) -> Vec<SignResult<Verification>> {
+        let mut results: Vec<_> = signatures.iter().map(|_| None).collect();
+        for i in self.verify_with_backends(signatures, &mut results) {
+            results[i] = Some(Ok(Verification::unknown()));
+        }
+        results.into_iter().map(Option::unwrap).collect()
+    }
+
+    /// Verifies the signatures whose `results` aren't known yet. Returns the
+    /// indices of the signatures that no backend could verify.
+    fn verify_with_backends(
+        &self,
+        signatures: &[(&CommitId, &[u8], &[u8])],
+        results: &mut [Option<SignResult<Verification>>],
+    ) -> Vec<usize> {
+        let mut pending: Vec<usize> = (0..signatures.len())
+            .filter(|&i| results[i].is_none())
+            .collect();
+        for backend in self.main_backend.iter().chain(self.backends.iter()) {
+            if pending.is_empty() {
+                break;
+            }
+            let (readable, unreadable): (Vec<usize>, Vec<usize>) = pending
+                .iter()
+                .partition(|&&i| backend.can_read(signatures[i].2));
+            if readable.is_empty() {
+                continue;
+            }
+            let batch: Vec<(&[u8], &[u8])> = readable
+                .iter()
+                .map(|&i| (signatures[i].1, signatures[i].2))
+                .collect();
+            pending = unreadable;
+            for (i, result) in readable.into_iter().zip(backend.verify_batch(&batch)) {
+                match result {
+                    // skip unknown and invalid sigs to allow other backends that can read to
+                    // try for example, we might have gpg and sq, both of which could read a
+                    // PGP signature
+                    Ok(check) if check.status == SigStatus::Unknown => pending.push(i),
+                    Err(SignError::InvalidSignatureFormat) => pending.push(i),
+                    result => results[i] = Some(result),
+                }
+            }
         }
 
-        let verification = self
-            .main_backend
+        pending
+    }
+}
+
+/// Key size of the persistent verification table. Keys are BLAKE2b-512
+/// hashes of the trust namespace and the commit id.
+const VERIFICATION_KEY_SIZE: usize = 64;
+
+/// How long persisted verification results are reused. Keys may expire or
+/// be revoked without any change to the inputs the backends know about.
+const VERIFICATION_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Persistent table of verification results, keyed by commit id.
+///
+/// Only good and bad verifications are stored, along with the time they were
+/// made. Failing to read or write the table isn't an error since the
+/// signatures can always be verified again.
+struct VerificationTable {
+    dir: PathBuf,
+    store: TableStore,
+    head: Mutex<Option<Arc<ReadonlyTable>>>,
+}
+
+impl Debug for VerificationTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VerificationTable")
+            .field("dir", &self.dir)
+            .finish_non_exhaustive()
+    }
+}
+
+impl VerificationTable {
+    fn new(dir: PathBuf) -> Self {
+        let store = TableStore::load(dir.clone(), VERIFICATION_KEY_SIZE);
+        Self {
+            dir,
+            store,
+            head: Mutex::new(None),
+        }
+    }
+
+    fn key(namespace: &[u8], commit_id: &CommitId) -> Vec<u8> {
+        let mut hasher = Blake2b512::new();
+        hasher.update(namespace);
+        hasher.update(commit_id.as_bytes());
+        hasher.finalize().to_vec()
+    }
+
+    fn get(&self, namespace: &[u8], commit_ids: &[&CommitId]) -> Vec<Option<Verification>> {
+        let head = if commit_ids.is_empty() || !self.dir.join("heads").is_dir() {
+            None
+        } else {
+            let mut locked_head = self.head.lock().unwrap();
+            if locked_head.is_none() {
+                *locked_head = self
+                    .store
+                    .get_head()
+                    .inspect_err(|err| tracing::warn!(?err, "failed to load verification cache"))
+                    .ok();
+            }
+            locked_head.clone()
+        };
+        let now = SystemTime::now();
+        commit_ids
             .iter()
-            .chain(self.backends.iter())
-            .filter(|b| b.can_read(signature))
-            // skip unknown and invalid sigs to allow other backends that can read to try
-            // for example, we might have gpg and sq, both of which could read a PGP signature
-            .find_map(|backend| match backend.verify(data, signature) {
-                Ok(check) if check.status == SigStatus::Unknown => None,
-                Err(SignError::InvalidSignatureFormat) => None,
-                e => Some(e),
+            .map(|commit_id| {
+                let value = head.as_ref()?.get_value(&Self::key(namespace, commit_id))?;
+                let (verified_at, verification) = decode_verification(value)?;
+                let age = now.duration_since(verified_at).ok()?;
+                (age < VERIFICATION_TTL).then_some(verification)
             })
-            .transpose()?;
+            .collect()
+    }
 
-        if let Some(verification) = verification {
-            // a key might get imported before next call?.
-            // realistically this is unlikely, but technically
-            // it's correct to not cache unknowns here
-            if verification.status != SigStatus::Unknown {
-                self.cache
-                    .lock()
-                    .unwrap()
-                    .put(commit_id.clone(), verification.clone());
+    fn insert(&self, namespace: &[u8], verifications: &[(&CommitId, &Verification)]) {
+        let save = || -> Result<(), Box<dyn std::error::Error>> {
+            create_or_reuse_dir(&self.dir)?;
+            create_or_reuse_dir(&self.dir.join("heads"))?;
+            let (table, _lock) = self.store.get_head_locked()?;
+            let mut mut_table = table.start_mutation();
+            let now = SystemTime::now();
+            for (commit_id, verification) in verifications {
+                mut_table.add_entry(
+                    Self::key(namespace, commit_id),
+                    encode_verification(now, verification),
+                );
             }
-            Ok(verification)
+            let table = self.store.save_table(mut_table)?;
+            *self.head.lock().unwrap() = Some(table);
+            Ok(())
+        };
+        if let Err(err) = save() {
+            tracing::warn!(?err, "failed to save verification cache");
+        }
+    }
+}
+
+fn encode_verification(verified_at: SystemTime, verification: &Verification) -> Vec<u8> {
+    let secs = verified_at
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let mut buf = secs.to_le_bytes().to_vec();
+    buf.push(match verification.status {
+        SigStatus::Good => 0,
+        SigStatus::Unknown => 1,
+        SigStatus::Bad => 2,
+    });
+    for field in [&verification.key, &verification.display] {
+        if let Some(value) = field {
+            buf.push(1);
+            buf.extend_from_slice(&u32::try_from(value.len()).unwrap().to_le_bytes());
+            buf.extend_from_slice(value.as_bytes());
         } else {
-            // now here it's correct to cache unknowns, as we don't
-            // have a backend that knows how to handle this signature
-            //
-            // not sure about how much of an optimization this is
-            self.cache
-                .lock()
-                .unwrap()
-                .put(commit_id.clone(), Verification::unknown());
-            Ok(Verification::unknown())
+            buf.push(0);
+        }
+    }
+    buf
+}
+
+fn decode_verification(buf: &[u8]) -> Option<(SystemTime, Verification)> {
+    fn read_field(buf: &mut &[u8]) -> Option<Option<String>> {
+        let (&is_present, rest) = buf.split_first()?;
+        *buf = rest;
+        if is_present == 0 {
+            return Some(None);
         }
+        let (len, rest) = buf.split_first_chunk::<4>()?;
+        let (value, rest) = rest.split_at_checked(u32::from_le_bytes(*len) as usize)?;
+        *buf = rest;
+        String::from_utf8(value.to_vec()).ok().map(Some)
     }
+
+    let (secs, rest) = buf.split_first_chunk::<8>()?;
+    let verified_at = SystemTime::UNIX_EPOCH + Duration::from_secs(u64::from_le_bytes(*secs));
+    let (&status, mut rest) = rest.split_first()?;
+    let status = match status {
+        0 => SigStatus::Good,
+        1 => SigStatus::Unknown,
+        2 => SigStatus::Bad,
+        _ => return None,
+    };
+    let key = read_field(&mut rest)?;
+    let display = read_field(&mut rest)?;
+    Some((verified_at, Verification::new(status, key, display)))
 }
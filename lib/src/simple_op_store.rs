@@ -573,6 +573,17 @@ fn view_to_proto(view: &View) -> crate::protos::simple_op_store::View {
 
     let git_head = ref_target_to_proto(&view.git_head);
 
+    let git_worktree_heads = view
+        .git_worktree_heads
+        .iter()
+        .map(
+            |(name, target)| crate::protos::simple_op_store::GitWorktreeHead {
+                name: name.clone(),
+                target: ref_target_to_proto(target),
+            },
+        )
+        .collect();
+
     #[expect(deprecated)]
     crate::protos::simple_op_store::View {
         head_ids,
@@ -584,6 +595,7 @@ fn view_to_proto(view: &View) -> crate::protos::simple_op_store::View {
         git_refs,
         git_head_legacy: Default::default(),
         git_head,
+        git_worktree_heads,
         // New/loaded view should have been migrated to the latest format
         has_git_refs_migrated_to_remote_tags: true,
     }
@@ -672,6 +684,12 @@ fn view_from_proto(proto: crate::protos::simple_op_store::View) -> Result<View,
         RefTarget::absent()
     };
 
+    let git_worktree_heads = proto
+        .git_worktree_heads
+        .into_iter()
+        .map(|head| (head.name, ref_target_from_proto(head.target)))
+        .collect();
+
     Ok(View {
         head_ids,
         local_bookmarks,
@@ -679,6 +697,7 @@ fn view_from_proto(proto: crate::protos::simple_op_store::View) -> Result<View,
         remote_views,
         git_refs,
         git_head,
+        git_worktree_heads,
         wc_commit_ids,
     })
 }
@@ -761,6 +780,11 @@ fn remote_views_to_proto(
             name: name.into(),
             bookmarks: remote_refs_to_proto(&view.bookmarks),
             tags: remote_refs_to_proto(&view.tags),
+            bookmark_tombstones: view
+                .bookmark_tombstones
+                .iter()
+                .map(|name| name.into())
+                .collect(),
         })
         .collect()
 }
@@ -775,6 +799,11 @@ fn remote_views_from_proto(
             let view = RemoteView {
                 bookmarks: remote_refs_from_proto(proto.bookmarks)?,
                 tags: remote_refs_from_proto(proto.tags)?,
+                bookmark_tombstones: proto
+                    .bookmark_tombstones
+                    .into_iter()
+                    .map(RefNameBuf::from)
+                    .collect(),
             };
             Ok((name, view))
         })
@@ -943,6 +972,7 @@ mod tests {
     use insta::assert_snapshot;
     use itertools::Itertools as _;
     use maplit::btreemap;
+    use maplit::btreeset;
     use maplit::hashmap;
     use maplit::hashset;
 
@@ -993,6 +1023,7 @@ mod tests {
                         "v1.0".into() => tracked_remote_ref(&tag_v1_origin_target),
                         "deleted".into() => new_remote_ref(&tag_deleted_origin_target),
                     },
+                    bookmark_tombstones: btreeset! {"removed".into()},
                 },
             },
             git_refs: btreemap! {
@@ -1000,6 +1031,9 @@ mod tests {
                 "refs/heads/feature".into() => git_refs_feature_target,
             },
             git_head: RefTarget::normal(CommitId::from_hex("fff111")),
+            git_worktree_heads: btreemap! {
+                "wt".into() => RefTarget::normal(CommitId::from_hex("fff222")),
+            },
             wc_commit_ids: btreemap! {
                 WorkspaceName::DEFAULT.to_owned() => default_wc_commit_id,
                 "test".into() => test_wc_commit_id,
@@ -1055,7 +1089,7 @@ mod tests {
         // Test exact output so we detect regressions in compatibility
         assert_snapshot!(
             ViewId::new(blake2b_hash(&create_view()).to_vec()).hex(),
-            @"2c0b174d117ca85e7faa96f6d997362403105e8eb31e7f82ac9abd3dc48ae62683e9a76ef5d117ebc8a743d17e1945236df9ccefd7574f7e4b5336a63796b967"
+            @"a958df88fadcc1eaf4097927dc95d7fd67a59a20c695db205be556129f8b2ec150baea182a0c2036b77f7ec2d5f6daebdf13020ca9c728e08e4beb3259a38b63"
         );
     }
 
@@ -1101,8 +1135,9 @@ mod tests {
         let mut view = create_view();
         assert!(!view.remote_views.is_empty());
         for remote_view in view.remote_views.values_mut() {
-            // remote tags cannot be preserved in "legacy" format
+            // remote tags and tombstones cannot be preserved in "legacy" format
             remote_view.tags.clear();
+            remote_view.bookmark_tombstones.clear();
         }
         let mut proto = view_to_proto(&view);
         proto.remote_views.clear(); // drop "new" format
@@ -1123,6 +1158,21 @@ mod tests {
         assert_eq!(view.remote_views, view_reconstructed.remote_views);
     }
 
+    #[test]
+    fn test_remote_bookmark_tombstones_proto() {
+        // Tombstones are stored in a separate field, so the remote refs can
+        // still be decoded by older versions.
+        let proto = view_to_proto(&create_view());
+        let remote_view = &proto.remote_views[0];
+        assert_eq!(remote_view.bookmark_tombstones, ["removed"]);
+        assert!(
+            remote_view
+                .bookmarks
+                .iter()
+                .all(|remote_ref| remote_ref.name != "removed")
+        );
+    }
+
     #[test]
     fn test_migrate_git_refs_to_remote_tags() {
         let tracked_remote_ref = |target: &RefTarget| RemoteRef {
@@ -1143,6 +1193,7 @@ mod tests {
                     "main".into() => tracked_remote_ref(&main_target),
                 },
                 tags: btreemap! {},
+                bookmark_tombstones: btreeset! {},
             },
         };
         let proto = crate::protos::simple_op_store::View {
@@ -1167,6 +1218,7 @@ mod tests {
                         tags: btreemap! {
                             "v1.0".into() => tracked_remote_ref(&v1_target),
                         },
+                        bookmark_tombstones: btreeset! {},
                     },
                 }
             );
@@ -1210,12 +1262,14 @@ mod tests {
                     "bookmark1".into() => tracked_remote_ref(&git_bookmark1_target),
                 },
                 tags: btreemap! {},
+                bookmark_tombstones: btreeset! {},
             },
             "remote1".into() => RemoteView {
                 bookmarks: btreemap! {
                     "bookmark1".into() => tracked_remote_ref(&remote1_bookmark1_target),
                 },
                 tags: btreemap! {},
+                bookmark_tombstones: btreeset! {},
             },
             "remote2".into() => RemoteView {
                 bookmarks: btreemap! {
@@ -1224,6 +1278,7 @@ mod tests {
                     "bookmark4".into() => tracked_remote_ref(&remote2_bookmark4_target),
                 },
                 tags: btreemap! {},
+                bookmark_tombstones: btreeset! {},
             },
         };
 
@@ -0,0 +1,372 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Patterns selecting the paths present in a sparse working copy.
+
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::path::Path;
+
+use itertools::Itertools as _;
+use serde::Deserialize;
+use thiserror::Error;
+use tokio::io::AsyncReadExt as _;
+
+use crate::backend::BackendError;
+use crate::backend::TreeValue;
+use crate::dsl_util;
+use crate::fileset;
+use crate::fileset::FilesetAliasesMap;
+use crate::fileset::FilesetDiagnostics;
+use crate::fileset::FilesetExpression;
+use crate::fileset::FilesetParseContext;
+use crate::fileset::FilesetParseError;
+use crate::fileset::FilesetParseResult;
+use crate::matchers::Matcher;
+use crate::matchers::PrefixMatcher;
+use crate::merged_tree::MergedTree;
+use crate::repo_path::RelativePathParseError;
+use crate::repo_path::RepoPathBuf;
+use crate::repo_path::RepoPathUiConverter;
+
+/// Directory in the repository where sparse profiles are stored.
+pub const SPARSE_PROFILE_DIR: &str = ".jjsparse";
+
+/// Patterns that decide which paths from the current tree should be checked
+/// out in the working copy.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SparsePatterns {
+    /// Paths under any of the directory prefixes. An empty list means that no
+    /// paths should be checked out. A single `RepoPath::root()` entry means
+    /// that all files should be checked out.
+    Prefixes(Vec<RepoPathBuf>),
+    /// Paths matching a fileset expression.
+    Fileset(SparseFileset),
+}
+
+impl SparsePatterns {
+    /// Patterns that check out all files.
+    pub fn all() -> Self {
+        Self::Prefixes(vec![RepoPathBuf::root()])
+    }
+
+    /// Builds a matcher for the paths to check out.
+    pub fn to_matcher(&self) -> Box<dyn Matcher> {
+        match self {
+            Self::Prefixes(prefixes) => Box::new(PrefixMatcher::new(prefixes)),
+            Self::Fileset(fileset) => fileset.expression.to_matcher(),
+        }
+    }
+
+    /// Returns the patterns as fileset expression text. Directory prefixes are
+    /// converted to `root:` patterns.
+    pub fn to_fileset_text(&self) -> String {
+        match self {
+            Self::Prefixes(prefixes) => match prefixes.as_slice() {
+                [] => "none()".to_owned(),
+                [prefix] if prefix.is_root() => "all()".to_owned(),
+                _ => prefixes
+                    .iter()
+                    .map(|prefix| {
+                        format!(
+                            r#"root:"{}""#,
+                            dsl_util::escape_string(prefix.as_internal_file_string())
+                        )
+                    })
+                    .join(" | "),
+            },
+            Self::Fileset(fileset) => fileset.text.clone(),
+        }
+    }
+}
+
+/// Fileset expression selecting the paths to check out.
+///
+/// Since the expression is stored in the working copy state, paths are
+/// relative to the workspace root, and aliases aren't expanded.
+#[derive(Clone, Debug)]
+pub struct SparseFileset {
+    text: String,
+    expression: FilesetExpression,
+}
+
+impl SparseFileset {
+    /// Parses `text` as a fileset expression.
+    pub fn parse(text: impl Into<String>) -> FilesetParseResult<Self> {
+        let text = text.into();
+        let path_converter = RepoPathUiConverter::Fs {
+            cwd: "".into(),
+            base: "".into(),
+        };
+        let context = FilesetParseContext {
+            aliases_map: &FilesetAliasesMap::new(),
+            path_converter: &path_converter,
+        };
+        let expression = fileset::parse(&mut FilesetDiagnostics::new(), &text, &context)?;
+        Ok(Self { text, expression })
+    }
+
+    /// The source text of the expression.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The parsed expression.
+    pub fn expression(&self) -> &FilesetExpression {
+        &self.expression
+    }
+}
+
+impl PartialEq for SparseFileset {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text
+    }
+}
+
+impl Eq for SparseFileset {}
+
+/// Sparse profile and the patterns it was resolved to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SparseProfile {
+    name: String,
+    patterns: SparsePatterns,
+}
+
+impl SparseProfile {
+    /// Creates a profile named `name` which was resolved to `patterns`.
+    pub fn new(name: impl Into<String>, patterns: SparsePatterns) -> Self {
+        Self {
+            name: name.into(),
+            patterns,
+        }
+    }
+
+    /// The name of the profile.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The patterns the profile was resolved to.
+    pub fn patterns(&self) -> &SparsePatterns {
+        &self.patterns
+    }
+
+    /// Returns the name and the patterns of the profile.
+    pub fn into_parts(self) -> (String, SparsePatterns) {
+        (self.name, self.patterns)
+    }
+}
+
+/// Error that may occur while resolving a sparse profile.
+#[derive(Debug, Error)]
+pub enum SparseProfileError {
+    /// The profile name can't be mapped to a file path.
+    #[error("Invalid sparse profile name: {name:?}")]
+    InvalidName {
+        /// The profile name.
+        name: String,
+    },
+    /// The profile file doesn't exist.
+    #[error("Sparse profile {name} not found")]
+    NotFound {
+        /// The profile name.
+        name: String,
+    },
+    /// The profile file has unresolved conflicts.
+    #[error("Sparse profile {name} has conflicts")]
+    Conflicted {
+        /// The profile name.
+        name: String,
+    },
+    /// The profile file isn't valid TOML or has unknown fields.
+    #[error("Failed to parse sparse profile {name}")]
+    Parse {
+        /// The profile name.
+        name: String,
+        /// The source error.
+        source: toml_edit::de::Error,
+    },
+    /// The profile contains an invalid path.
+    #[error("Invalid path in sparse profile {name}")]
+    InvalidPath {
+        /// The profile name.
+        name: String,
+        /// The source error.
+        source: RelativePathParseError,
+    },
+    /// The profile contains an invalid fileset expression.
+    #[error("Invalid fileset in sparse profile {name}")]
+    InvalidFileset {
+        /// The profile name.
+        name: String,
+        /// The source error.
+        source: FilesetParseError,
+    },
+    /// Failed to read the profile from the backend.
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SparseProfileFile {
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default)]
+    paths: Vec<String>,
+    fileset: Option<String>,
+}
+
+/// Returns the path of the file that defines the profile `name`.
+pub fn sparse_profile_path(name: &str) -> Result<RepoPathBuf, SparseProfileError> {
+    let invalid_name = || SparseProfileError::InvalidName {
+        name: name.to_owned(),
+    };
+    if name.is_empty() {
+        return Err(invalid_name());
+    }
+    let path = Path::new(SPARSE_PROFILE_DIR).join(format!("{name}.toml"));
+    RepoPathBuf::from_relative_path(path).map_err(|_| invalid_name())
+}
+
+/// Resolves the sparse profile `name` and the profiles it includes from the
+/// files in `tree`.
+///
+/// Profiles are TOML files in the `.jjsparse` directory. `paths` lists
+/// directories to check out, `fileset` is an expression matching additional
+/// files, and `include` lists other profiles to merge in. The `.jjsparse`
+/// directory itself is always checked out.
+pub async fn resolve_sparse_profile(
+    tree: &MergedTree,
+    name: &str,
+) -> Result<SparseProfile, SparseProfileError> {
+    // Profiles are always checked out so they can be edited
+    let mut prefixes = vec![RepoPathBuf::from_internal_string(SPARSE_PROFILE_DIR).unwrap()];
+    let mut fileset_texts = vec![];
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::from([name.to_owned()]);
+    while let Some(current) = queue.pop_front() {
+        if !visited.insert(current.clone()) {
+            continue;
+        }
+        let file = read_sparse_profile_file(tree, &current).await?;
+        for path in &file.paths {
+            let prefix = RepoPathBuf::from_relative_path(path).map_err(|source| {
+                SparseProfileError::InvalidPath {
+                    name: current.clone(),
+                    source,
+                }
+            })?;
+            prefixes.push(prefix);
+        }
+        if let Some(text) = file.fileset {
+            // Parse individually to report the profile that has the error
+            SparseFileset::parse(&text).map_err(|source| SparseProfileError::InvalidFileset {
+                name: current.clone(),
+                source,
+            })?;
+            fileset_texts.push(text);
+        }
+        queue.extend(file.include);
+    }
+    prefixes.sort_unstable();
+    prefixes.dedup();
+    let patterns = if fileset_texts.is_empty() {
+        SparsePatterns::Prefixes(prefixes)
+    } else {
+        let text = [SparsePatterns::Prefixes(prefixes).to_fileset_text()]
+            .into_iter()
+            .chain(fileset_texts.iter().map(|text| format!("({text})")))
+            .join(" | ");
+        let fileset =
+            SparseFileset::parse(text).map_err(|source| SparseProfileError::InvalidFileset {
+                name: name.to_owned(),
+                source,
+            })?;
+        SparsePatterns::Fileset(fileset)
+    };
+    Ok(SparseProfile::new(name, patterns))
+}
+
+async fn read_sparse_profile_file(
+    tree: &MergedTree,
+    name: &str,
+) -> Result<SparseProfileFile, SparseProfileError> {
+    let path = sparse_profile_path(name)?;
+    let value = tree.path_value(&path).await?;
+    let Some(value) = value.as_resolved() else {
+        return Err(SparseProfileError::Conflicted {
+            name: name.to_owned(),
+        });
+    };
+    let Some(TreeValue::File { id, .. }) = value else {
+        return Err(SparseProfileError::NotFound {
+            name: name.to_owned(),
+        });
+    };
+    let mut reader = tree.store().read_file(&path, id).await?;
+    let mut content = vec![];
+    reader
+        .read_to_end(&mut content)
+        .await
+        .map_err(|err| BackendError::ReadFile {
+            path: path.clone(),
+            id: id.clone(),
+            source: err.into(),
+        })?;
+    toml_edit::de::from_slice(&content).map_err(|source| SparseProfileError::Parse {
+        name: name.to_owned(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matchers::Visit;
+    use crate::repo_path::RepoPath;
+
+    fn repo_path(value: &str) -> &RepoPath {
+        RepoPath::from_internal_string(value).unwrap()
+    }
+
+    #[test]
+    fn test_fileset_matcher() {
+        let fileset =
+            SparseFileset::parse("services/api ~ glob:'services/api/**/fixtures/**'").unwrap();
+        let matcher = SparsePatterns::Fileset(fileset).to_matcher();
+        assert!(matcher.matches(repo_path("services/api/main.rs")));
+        assert!(matcher.matches(repo_path("services/api/src/lib.rs")));
+        assert!(!matcher.matches(repo_path("services/api/tests/fixtures/data.json")));
+        assert!(!matcher.matches(repo_path("services/web/main.rs")));
+        assert_eq!(matcher.visit(repo_path("docs")), Visit::Nothing);
+    }
+
+    #[test]
+    fn test_prefixes_to_fileset_text() {
+        assert_eq!(SparsePatterns::all().to_fileset_text(), "all()");
+        assert_eq!(SparsePatterns::Prefixes(vec![]).to_fileset_text(), "none()");
+        let patterns = SparsePatterns::Prefixes(vec![
+            RepoPathBuf::from_internal_string("lib").unwrap(),
+            RepoPathBuf::from_internal_string(r#"weird "dir""#).unwrap(),
+        ]);
+        let text = patterns.to_fileset_text();
+        assert_eq!(text, r#"root:"lib" | root:"weird \"dir\"""#);
+        // The text parses back to an equivalent expression
+        let matcher = SparsePatterns::Fileset(SparseFileset::parse(text).unwrap()).to_matcher();
+        assert!(matcher.matches(repo_path("lib/foo")));
+        assert!(matcher.matches(repo_path(r#"weird "dir"/foo"#)));
+        assert!(!matcher.matches(repo_path("other")));
+    }
+}
@@ -0,0 +1,711 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Native SSH signing backend which talks to an ssh-agent.
+//!
+//! Signatures are created by the agent over the ssh-agent protocol and
+//! serialized in the SSHSIG format, so they are interchangeable with the ones
+//! created by `ssh-keygen -Y sign`. Signatures are verified in-process against
+//! an allowed-signers file.
+
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::io::Read as _;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use chrono::DateTime;
+use chrono::Local;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::TimeZone as _;
+use chrono::Utc;
+use ssh_key::HashAlg;
+use ssh_key::LineEnding;
+use ssh_key::PublicKey;
+use ssh_key::Signature;
+use ssh_key::SshSig;
+use thiserror::Error;
+
+use crate::config::ConfigGetError;
+use crate::config::ConfigGetResultExt as _;
+use crate::settings::UserSettings;
+use crate::signing::SigStatus;
+use crate::signing::SignError;
+use crate::signing::SigningBackend;
+use crate::signing::Verification;
+use crate::signing::hash_trust_files;
+
+/// Namespace used for signatures of Git objects, see `ssh-keygen -Y sign -n`.
+const NAMESPACE: &str = "git";
+
+const SSH_AGENT_FAILURE: u8 = 5;
+const SSH_AGENTC_SIGN_REQUEST: u8 = 13;
+const SSH_AGENT_SIGN_RESPONSE: u8 = 14;
+const SSH_AGENT_RSA_SHA2_512: u32 = 4;
+
+/// Maximum size of an agent response. The same limit is used by OpenSSH.
+const MAX_AGENT_MESSAGE_LEN: usize = 256 * 1024;
+
+/// Signing backend that signs with keys held by an ssh-agent, and verifies
+/// SSH signatures without external programs.
+#[derive(Debug)]
+pub struct SshAgentBackend {
+    auth_sock: Option<PathBuf>,
+    allowed_signers: Option<PathBuf>,
+    revocation_list: Option<PathBuf>,
+    /// Connection to the agent, which is kept open across sign requests.
+    connection: Mutex<Option<AgentStream>>,
+}
+
+/// Error that may occur while signing or verifying with the ssh-agent backend.
+#[derive(Debug, Error)]
+pub enum SshAgentError {
+    /// The agent socket isn't configured.
+    #[error("No ssh-agent socket configured; set SSH_AUTH_SOCK")]
+    NoAgent,
+    /// Failed to connect to the agent.
+    #[error("Failed to connect to ssh-agent at {}", path.display())]
+    Connect {
+        /// The agent socket path.
+        path: PathBuf,
+        /// The source error.
+        source: io::Error,
+    },
+    /// Failed to communicate with the agent.
+    #[error("Failed to communicate with ssh-agent")]
+    Io(#[from] io::Error),
+    /// The agent refused to sign.
+    #[error("ssh-agent failed to sign with key {fingerprint}; is the key loaded?")]
+    AgentFailure {
+        /// The fingerprint of the signing key.
+        fingerprint: String,
+    },
+    /// The agent sent a malformed response.
+    #[error("Unexpected response from ssh-agent")]
+    BadResponse,
+    /// The signing key wasn't specified.
+    #[error("Signing key required")]
+    MissingKey,
+    /// The signing key couldn't be parsed as an SSH public key.
+    #[error("Failed to parse SSH public key {key:?}")]
+    InvalidKey {
+        /// The configured key.
+        key: String,
+        /// The source error.
+        source: ssh_key::Error,
+    },
+    /// Failed to read a key file.
+    #[error("Failed to read {}", path.display())]
+    ReadFile {
+        /// The file path.
+        path: PathBuf,
+        /// The source error.
+        source: io::Error,
+    },
+    /// The revocation list is in the binary KRL format.
+    #[error("Key revocation lists (KRL) are not supported; list revoked public keys instead")]
+    UnsupportedRevocationList,
+    /// Failed to encode the signature.
+    #[error("Failed to encode SSH signature")]
+    Encode(#[source] ssh_key::Error),
+}
+
+impl From<SshAgentError> for SignError {
+    fn from(e: SshAgentError) -> Self {
+        Self::Backend(Box::new(e))
+    }
+}
+
+type SshAgentResult<T> = Result<T, SshAgentError>;
+
+impl SshAgentBackend {
+    /// Creates a backend which connects to the agent at `auth_sock`.
+    pub fn new(
+        auth_sock: Option<PathBuf>,
+        allowed_signers: Option<PathBuf>,
+        revocation_list: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            auth_sock,
+            allowed_signers,
+            revocation_list,
+            connection: Mutex::new(None),
+        }
+    }
+
+    /// Creates a backend from the `signing.backends.ssh-agent` settings. The
+    /// agent socket defaults to `$SSH_AUTH_SOCK`.
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        let get_expanded_path = |name| {
+            Ok(settings
+                .get_string(name)
+                .optional()?
+                .map(|v| crate::file_util::expand_home_path(v.as_str())))
+        };
+
+        let auth_sock = get_expanded_path("signing.backends.ssh-agent.auth-sock")?
+            .or_else(|| std::env::var_os("SSH_AUTH_SOCK").map(PathBuf::from))
+            .or_else(default_auth_sock);
+        let allowed_signers = get_expanded_path("signing.backends.ssh-agent.allowed-signers")?;
+        let revocation_list = get_expanded_path("signing.backends.ssh-agent.revocation-list")?;
+
+        Ok(Self::new(auth_sock, allowed_signers, revocation_list))
+    }
+
+    fn load_revoked_keys(&self) -> SshAgentResult<Vec<PublicKey>> {
+        load_revoked_keys(self.revocation_list.as_deref())
+    }
+
+    fn load_allowed_signers(&self) -> SshAgentResult<Vec<AllowedSigner>> {
+        load_allowed_signers(self.allowed_signers.as_deref())
+    }
+
+    fn verify_with(
+        &self,
+        data: &[u8],
+        signature: &[u8],
+        allowed_signers: &[AllowedSigner],
+        revoked_keys: &[PublicKey],
+    ) -> Result<Verification, SignError> {
+        let check = check_signature(data, signature, allowed_signers, revoked_keys)?;
+        let verification = match (check.is_valid && !check.is_revoked, check.principal) {
+            (false, principal) => {
+                Verification::new(SigStatus::Bad, Some(check.fingerprint), principal)
+            }
+            (true, Some(principal)) => {
+                Verification::new(SigStatus::Good, Some(check.fingerprint), Some(principal))
+            }
+            (true, None) => Verification::new(
+                SigStatus::Unknown,
+                Some(check.fingerprint),
+                Some("Signature OK. Unknown principal".into()),
+            ),
+        };
+        Ok(verification)
+    }
+
+    /// Sends a request to the agent and returns its response. The connection
+    /// is reused by subsequent requests.
+    fn agent_request(&self, auth_sock: &Path, request: &[u8]) -> SshAgentResult<Vec<u8>> {
+        let mut connection = self.connection.lock().unwrap();
+        if let Some(stream) = connection.as_mut() {
+            // The agent may have closed the connection since the last request.
+            match send_agent_request(stream, request) {
+                Ok(response) => return Ok(response),
+                Err(err) => tracing::debug!(?err, "reconnecting to ssh-agent"),
+            }
+        }
+        *connection = None;
+        tracing::info!(?auth_sock, "connecting to ssh-agent");
+        let stream = connect_agent(auth_sock).map_err(|source| SshAgentError::Connect {
+            path: auth_sock.to_owned(),
+            source,
+        })?;
+        let stream = connection.insert(stream);
+        let result = send_agent_request(stream, request);
+        if result.is_err() {
+            *connection = None;
+        }
+        result
+    }
+
+    /// Asks the agent to sign `data` with the private key of `public_key`.
+    fn agent_sign(
+        &self,
+        auth_sock: &Path,
+        public_key: &PublicKey,
+        data: &[u8],
+    ) -> SshAgentResult<Signature> {
+        let key_blob = public_key.to_bytes().map_err(SshAgentError::Encode)?;
+        let flags = match public_key.algorithm() {
+            ssh_key::Algorithm::Rsa { .. } => SSH_AGENT_RSA_SHA2_512,
+            _ => 0,
+        };
+        let mut request = vec![SSH_AGENTC_SIGN_REQUEST];
+        put_string(&mut request, &key_blob);
+        put_string(&mut request, data);
+        request.extend_from_slice(&flags.to_be_bytes());
+
+        let response = self.agent_request(auth_sock, &request)?;
+        match response.split_first() {
+            Some((&SSH_AGENT_SIGN_RESPONSE, mut rest)) => {
+                let signature_blob = get_string(&mut rest).ok_or(SshAgentError::BadResponse)?;
+                Signature::try_from(signature_blob).map_err(|_| SshAgentError::BadResponse)
+            }
+            Some((&SSH_AGENT_FAILURE, _)) => Err(SshAgentError::AgentFailure {
+                fingerprint: public_key.fingerprint(HashAlg::Sha256).to_string(),
+            }),
+            _ => Err(SshAgentError::BadResponse),
+        }
+    }
+}
+
+impl SigningBackend for SshAgentBackend {
+    fn name(&self) -> &'static str {
+        "ssh-agent"
+    }
+
+    fn can_read(&self, signature: &[u8]) -> bool {
+        signature.starts_with(b"-----BEGIN SSH SIGNATURE-----")
+    }
+
+    fn sign(&self, data: &[u8], key: Option<&str>) -> Result<Vec<u8>, SignError> {
+        let Some(key) = key else {
+            return Err(SshAgentError::MissingKey.into());
+        };
+        let public_key = load_public_key(key)?;
+        let auth_sock = self.auth_sock.as_ref().ok_or(SshAgentError::NoAgent)?;
+        let signed_data =
+            SshSig::signed_data(NAMESPACE, HashAlg::Sha512, data).map_err(SshAgentError::Encode)?;
+        let signature = self.agent_sign(auth_sock, &public_key, &signed_data)?;
+        let sshsig = SshSig::new(
+            public_key.key_data().clone(),
+            NAMESPACE,
+            HashAlg::Sha512,
+            signature,
+        )
+        .map_err(SshAgentError::Encode)?;
+        let pem = sshsig
+            .to_pem(LineEnding::LF)
+            .map_err(SshAgentError::Encode)?;
+        Ok(pem.into_bytes())
+    }
+
+    fn verify(&self, data: &[u8], signature: &[u8]) -> Result<Verification, SignError> {
+        SshSig::from_pem(signature).map_err(|_| SignError::InvalidSignatureFormat)?;
+        let allowed_signers = self.load_allowed_signers()?;
+        let revoked_keys = self.load_revoked_keys()?;
+        self.verify_with(data, signature, &allowed_signers, &revoked_keys)
+    }
+
+    fn verify_batch(&self, signatures: &[(&[u8], &[u8])]) -> Vec<Result<Verification, SignError>> {
+        // Read the allowed-signers and revocation files once for the whole
+        // batch. If either can't be read, verify the signatures one by one to
+        // report the error for each of them.
+        let (Ok(allowed_signers), Ok(revoked_keys)) =
+            (self.load_allowed_signers(), self.load_revoked_keys())
+        else {
+            return signatures
+                .iter()
+                .map(|(data, signature)| self.verify(data, signature))
+                .collect();
+        };
+        signatures
+            .iter()
+            .map(|(data, signature)| {
+                self.verify_with(data, signature, &allowed_signers, &revoked_keys)
+            })
+            .collect()
+    }
+
+    fn trust_state(&self) -> Option<Vec<u8>> {
+        ssh_trust_state(
+            self.allowed_signers.as_deref(),
+            self.revocation_list.as_deref(),
+        )
+    }
+}
+
+/// Reads the public keys listed in the revocation list at `path`.
+pub(crate) fn load_revoked_keys(path: Option<&Path>) -> SshAgentResult<Vec<PublicKey>> {
+    let Some(path) = path else {
+        return Ok(vec![]);
+    };
+    let content = fs::read(path).map_err(|source| SshAgentError::ReadFile {
+        path: path.to_owned(),
+        source,
+    })?;
+    if content.starts_with(b"SSHKRL\n\0") {
+        return Err(SshAgentError::UnsupportedRevocationList);
+    }
+    let revoked_keys = String::from_utf8_lossy(&content)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| PublicKey::from_openssh(line).ok())
+        .collect();
+    Ok(revoked_keys)
+}
+
+/// Reads the allowed-signers file at `path`.
+pub(crate) fn load_allowed_signers(path: Option<&Path>) -> SshAgentResult<Vec<AllowedSigner>> {
+    let Some(path) = path else {
+        return Ok(vec![]);
+    };
+    let content = fs::read_to_string(path).map_err(|source| SshAgentError::ReadFile {
+        path: path.to_owned(),
+        source,
+    })?;
+    Ok(content.lines().filter_map(AllowedSigner::parse).collect())
+}
+
+/// Outcome of checking an SSH signature against the allowed signers.
+pub(crate) struct SignatureCheck {
+    /// Whether the signature was made by its key over the data.
+    pub is_valid: bool,
+    /// Whether the key is in the revocation list.
+    pub is_revoked: bool,
+    /// The SHA256 fingerprint of the key.
+    pub fingerprint: String,
+    /// The principals of the allowed signer valid for the key now, if any.
+    pub principal: Option<String>,
+}
+
+/// Checks a `git` namespace signature over `data`.
+pub(crate) fn check_signature(
+    data: &[u8],
+    signature: &[u8],
+    allowed_signers: &[AllowedSigner],
+    revoked_keys: &[PublicKey],
+) -> Result<SignatureCheck, SignError> {
+    let sshsig = SshSig::from_pem(signature).map_err(|_| SignError::InvalidSignatureFormat)?;
+    let public_key = PublicKey::from(sshsig.public_key().clone());
+    let now = Utc::now();
+    Ok(SignatureCheck {
+        is_valid: public_key.verify(NAMESPACE, data, &sshsig).is_ok(),
+        is_revoked: revoked_keys
+            .iter()
+            .any(|revoked| revoked.key_data() == public_key.key_data()),
+        fingerprint: public_key.fingerprint(HashAlg::Sha256).to_string(),
+        principal: allowed_signers
+            .iter()
+            .find(|signer| {
+                signer.key.key_data() == public_key.key_data() && signer.is_valid(NAMESPACE, now)
+            })
+            .map(|signer| signer.principals.clone()),
+    })
+}
+
+/// Digest of the allowed-signers and revocation files, to be returned by
+/// [`SigningBackend::trust_state()`]. Since entries of the allowed-signers
+/// file may only be valid for some period, which entries are valid now is
+/// part of the digest.
+pub(crate) fn ssh_trust_state(
+    allowed_signers: Option<&Path>,
+    revocation_list: Option<&Path>,
+) -> Option<Vec<u8>> {
+    let now = Utc::now();
+    let mut state = hash_trust_files(allowed_signers.into_iter().chain(revocation_list));
+    state.extend(
+        load_allowed_signers(allowed_signers)
+            .ok()?
+            .iter()
+            .map(|signer| u8::from(signer.is_valid(NAMESPACE, now))),
+    );
+    Some(state)
+}
+
+/// Parses the configured signing key, which is either an inline public key or
+/// a path to a public or private key file. For a private key file, the public
+/// key is read from the `.pub` file next to it.
+fn load_public_key(key: &str) -> SshAgentResult<PublicKey> {
+    let invalid_key = |source| SshAgentError::InvalidKey {
+        key: key.to_owned(),
+        source,
+    };
+    let key_path = crate::file_util::expand_home_path(key);
+    if !key_path.is_absolute() {
+        return PublicKey::from_openssh(key).map_err(invalid_key);
+    }
+    let read_key = |path: &Path| {
+        fs::read_to_string(path).map_err(|source| SshAgentError::ReadFile {
+            path: path.to_owned(),
+            source,
+        })
+    };
+    let content = read_key(&key_path)?;
+    if let Ok(public_key) = PublicKey::from_openssh(content.trim()) {
+        return Ok(public_key);
+    }
+    let mut pub_key_path = key_path.into_os_string();
+    pub_key_path.push(".pub");
+    let content = read_key(Path::new(&pub_key_path))?;
+    PublicKey::from_openssh(content.trim()).map_err(invalid_key)
+}
+
+#[cfg(unix)]
+type AgentStream = std::os::unix::net::UnixStream;
+
+// The Windows OpenSSH agent listens on a named pipe, which can be opened like
+// a file.
+#[cfg(windows)]
+type AgentStream = fs::File;
+
+#[cfg(unix)]
+fn connect_agent(path: &Path) -> io::Result<AgentStream> {
+    AgentStream::connect(path)
+}
+
+#[cfg(windows)]
+fn connect_agent(path: &Path) -> io::Result<AgentStream> {
+    fs::OpenOptions::new().read(true).write(true).open(path)
+}
+
+#[cfg(unix)]
+fn default_auth_sock() -> Option<PathBuf> {
+    None
+}
+
+#[cfg(windows)]
+fn default_auth_sock() -> Option<PathBuf> {
+    Some(PathBuf::from(r"\\.\pipe\openssh-ssh-agent"))
+}
+
+/// Writes a length-prefixed request and reads the length-prefixed response.
+fn send_agent_request(stream: &mut AgentStream, request: &[u8]) -> SshAgentResult<Vec<u8>> {
+    let request_len = u32::try_from(request.len()).unwrap();
+    stream.write_all(&request_len.to_be_bytes())?;
+    stream.write_all(request)?;
+    stream.flush()?;
+
+    let mut len_buf = [0; 4];
+    stream.read_exact(&mut len_buf)?;
+    let response_len = u32::from_be_bytes(len_buf) as usize;
+    if response_len == 0 || response_len > MAX_AGENT_MESSAGE_LEN {
+        return Err(SshAgentError::BadResponse);
+    }
+    let mut response = vec![0; response_len];
+    stream.read_exact(&mut response)?;
+    Ok(response)
+}
+
+fn put_string(buf: &mut Vec<u8>, data: &[u8]) {
+    let len = u32::try_from(data.len()).unwrap();
+    buf.extend_from_slice(&len.to_be_bytes());
+    buf.extend_from_slice(data);
+}
+
+fn get_string<'a>(buf: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let (len, rest) = buf.split_first_chunk::<4>()?;
+    let len = u32::from_be_bytes(*len) as usize;
+    let (data, rest) = rest.split_at_checked(len)?;
+    *buf = rest;
+    Some(data)
+}
+
+/// Entry of an allowed-signers file, see the `ALLOWED SIGNERS` section of
+/// `ssh-keygen(1)`.
+#[derive(Debug)]
+pub(crate) struct AllowedSigner {
+    principals: String,
+    pub(crate) cert_authority: bool,
+    pub(crate) namespaces: Option<String>,
+    valid_after: Option<DateTime<Utc>>,
+    valid_before: Option<DateTime<Utc>>,
+    key: PublicKey,
+}
+
+impl AllowedSigner {
+    /// Parses a line of the file. Returns `None` for comments and lines that
+    /// can't be parsed, which `ssh-keygen` ignores as well.
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (principals, rest) = split_token(line)?;
+        // Options come before the key, and there can be a comment after it
+        let (options, key) = match PublicKey::from_openssh(rest) {
+            Ok(key) => (None, key),
+            Err(_) => {
+                let (options, rest) = split_token(rest)?;
+                (Some(options), PublicKey::from_openssh(rest).ok()?)
+            }
+        };
+        let mut signer = Self {
+            principals: principals.to_owned(),
+            cert_authority: false,
+            namespaces: None,
+            valid_after: None,
+            valid_before: None,
+            key,
+        };
+        for option in options.into_iter().flat_map(split_options) {
+            let (name, value) = match option.split_once('=') {
+                Some((name, value)) => (name, Some(value.trim_matches('"'))),
+                None => (option, None),
+            };
+            match (name.to_ascii_lowercase().as_str(), value) {
+                ("cert-authority", None) => signer.cert_authority = true,
+                ("namespaces", Some(value)) => signer.namespaces = Some(value.to_owned()),
+                ("valid-after", Some(value)) => signer.valid_after = Some(parse_time(value)?),
+                ("valid-before", Some(value)) => signer.valid_before = Some(parse_time(value)?),
+                _ => return None,
+            }
+        }
+        Some(signer)
+    }
+
+    /// Whether the entry allows signatures in `namespace` at `time`.
+    /// Certificate authorities are never valid since only plain keys are
+    /// supported.
+    fn is_valid(&self, namespace: &str, time: DateTime<Utc>) -> bool {
+        !self.cert_authority
+            && self
+                .namespaces
+                .as_ref()
+                .is_none_or(|namespaces| match_pattern_list(namespace, namespaces))
+            && self.valid_after.is_none_or(|after| time >= after)
+            && self.valid_before.is_none_or(|before| time <= before)
+    }
+}
+
+/// Splits the leading whitespace-separated token, keeping quoted sections
+/// intact.
+fn split_token(s: &str) -> Option<(&str, &str)> {
+    let mut in_quotes = false;
+    let end = s
+        .char_indices()
+        .find(|&(_, c)| {
+            if c == '"' {
+                in_quotes = !in_quotes;
+            }
+            !in_quotes && c.is_ascii_whitespace()
+        })
+        .map_or(s.len(), |(i, _)| i);
+    let (token, rest) = s.split_at(end);
+    (!token.is_empty()).then(|| (token, rest.trim_start()))
+}
+
+/// Splits comma-separated options, keeping quoted values intact.
+fn split_options(s: &str) -> impl Iterator<Item = &str> {
+    let mut in_quotes = false;
+    s.split(move |c| {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        !in_quotes && c == ','
+    })
+}
+
+/// Matches `value` against a comma-separated list of `*`/`?` wildcard
+/// patterns, where `!`-prefixed patterns exclude matches.
+fn match_pattern_list(value: &str, patterns: &str) -> bool {
+    let mut matched = false;
+    for pattern in patterns.split(',') {
+        let (negated, pattern) = match pattern.strip_prefix('!') {
+            Some(pattern) => (true, pattern),
+            None => (false, pattern),
+        };
+        if match_wildcard(value.as_bytes(), pattern.as_bytes()) {
+            if negated {
+                return false;
+            }
+            matched = true;
+        }
+    }
+    matched
+}
+
+fn match_wildcard(value: &[u8], pattern: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => value.is_empty(),
+        Some((b'*', rest)) => (0..=value.len()).any(|i| match_wildcard(&value[i..], rest)),
+        Some((b'?', rest)) => !value.is_empty() && match_wildcard(&value[1..], rest),
+        Some((c, rest)) => value.first() == Some(c) && match_wildcard(&value[1..], rest),
+    }
+}
+
+/// Parses `YYYYMMDD[HHMM[SS]][Z]`. Times without the `Z` suffix are in local
+/// time.
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    let (value, is_utc) = match value.strip_suffix(['Z', 'z']) {
+        Some(value) => (value, true),
+        None => (value, false),
+    };
+    let time = match value.len() {
+        8 => NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()?
+            .and_hms_opt(0, 0, 0)?,
+        12 => NaiveDateTime::parse_from_str(value, "%Y%m%d%H%M").ok()?,
+        14 => NaiveDateTime::parse_from_str(value, "%Y%m%d%H%M%S").ok()?,
+        _ => return None,
+    };
+    if is_utc {
+        Some(time.and_utc())
+    } else {
+        Some(Local.from_local_datetime(&time).earliest()?.to_utc())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUBLIC_KEY: &str =
+        "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIGj+J6N6SO+4P8dOZqfR1oiay2yxhhHnagH52avUqw5h";
+
+    fn time(value: &str) -> DateTime<Utc> {
+        parse_time(value).unwrap()
+    }
+
+    #[test]
+    fn test_parse_allowed_signer() {
+        let signer =
+            AllowedSigner::parse(&format!("user@example.com {PUBLIC_KEY} comment")).unwrap();
+        assert_eq!(signer.principals, "user@example.com");
+        assert_eq!(
+            signer.key.key_data(),
+            PublicKey::from_openssh(PUBLIC_KEY).unwrap().key_data()
+        );
+        assert!(signer.is_valid("git", time("20260101Z")));
+
+        let signer = AllowedSigner::parse(&format!(
+            r#"a@example.com,*@example.org namespaces="file,git",valid-after=20260101Z {PUBLIC_KEY}"#
+        ))
+        .unwrap();
+        assert_eq!(signer.principals, "a@example.com,*@example.org");
+        assert!(signer.is_valid("git", time("20260102Z")));
+        assert!(!signer.is_valid("git", time("20251231Z")));
+        assert!(!signer.is_valid("email", time("20260102Z")));
+
+        let signer =
+            AllowedSigner::parse(&format!("*@example.com cert-authority {PUBLIC_KEY}")).unwrap();
+        assert!(!signer.is_valid("git", time("20260101Z")));
+
+        assert!(AllowedSigner::parse("# comment").is_none());
+        assert!(AllowedSigner::parse("").is_none());
+        assert!(AllowedSigner::parse("user@example.com INVALID KEY").is_none());
+        assert!(AllowedSigner::parse(&format!("user@example.com unknown {PUBLIC_KEY}")).is_none());
+    }
+
+    #[test]
+    fn test_match_pattern_list() {
+        assert!(match_pattern_list("git", "git"));
+        assert!(match_pattern_list("git", "file,git"));
+        assert!(match_pattern_list("git", "g*"));
+        assert!(match_pattern_list("git", "g?t"));
+        assert!(!match_pattern_list("git", "file"));
+        assert!(!match_pattern_list("git", "*,!git"));
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(time("20260102Z").to_rfc3339(), "2026-01-02T00:00:00+00:00");
+        assert_eq!(
+            time("202601021530Z").to_rfc3339(),
+            "2026-01-02T15:30:00+00:00"
+        );
+        assert_eq!(
+            time("20260102153045Z").to_rfc3339(),
+            "2026-01-02T15:30:45+00:00"
+        );
+        assert!(parse_time("2026").is_none());
+        assert!(parse_time("20261340Z").is_none());
+    }
+}
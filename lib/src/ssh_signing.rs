@@ -24,6 +24,9 @@ use std::process::ExitStatus;
 use std::process::Stdio;
 
 use either::Either;
+use rayon::iter::IntoParallelRefIterator as _;
+use rayon::iter::ParallelIterator as _;
+use ssh_key::PublicKey;
 use thiserror::Error;
 
 use crate::config::ConfigGetError;
@@ -33,6 +36,12 @@ use crate::signing::SigStatus;
 use crate::signing::SignError;
 use crate::signing::SigningBackend;
 use crate::signing::Verification;
+use crate::ssh_agent_signing::AllowedSigner;
+use crate::ssh_agent_signing::SignatureCheck;
+use crate::ssh_agent_signing::check_signature;
+use crate::ssh_agent_signing::load_allowed_signers;
+use crate::ssh_agent_signing::load_revoked_keys;
+use crate::ssh_agent_signing::ssh_trust_state;
 
 #[derive(Debug)]
 pub struct SshBackend {
@@ -207,6 +216,53 @@ impl SshBackend {
         }
         Ok(Some(principal))
     }
+
+    /// Loads the allowed signers and revoked keys to verify signatures
+    /// in-process. Returns `None` if the results could differ from the ones
+    /// of `ssh-keygen`, e.g. because another program is configured or
+    /// certificates are involved.
+    fn load_verification_inputs(&self) -> Option<(Vec<AllowedSigner>, Vec<PublicKey>)> {
+        if Path::new(&self.program).file_stem()? != "ssh-keygen" {
+            return None;
+        }
+        let allowed_signers =
+            load_allowed_signers(self.allowed_signers.as_deref().map(Path::new)).ok()?;
+        let revoked_keys =
+            load_revoked_keys(self.revocation_list.as_deref().map(Path::new)).ok()?;
+        if allowed_signers
+            .iter()
+            .any(|signer| signer.cert_authority || signer.namespaces.is_some())
+        {
+            return None;
+        }
+        Some((allowed_signers, revoked_keys))
+    }
+
+    /// Builds the verification `ssh-keygen` would report for the checked
+    /// signature.
+    fn to_verification(&self, check: SignatureCheck) -> Verification {
+        match (check.principal, self.allowed_signers.is_some()) {
+            (Some(principal), true) => {
+                if check.is_valid && !check.is_revoked {
+                    Verification::new(SigStatus::Good, Some(check.fingerprint), Some(principal))
+                } else {
+                    Verification::new(SigStatus::Bad, None, Some(principal))
+                }
+            }
+            // The revocation list isn't checked without a known principal
+            _ => {
+                if check.is_valid {
+                    Verification::new(
+                        SigStatus::Unknown,
+                        Some(check.fingerprint),
+                        Some("Signature OK. Unknown principal".into()),
+                    )
+                } else {
+                    Verification::new(SigStatus::Bad, None, None)
+                }
+            }
+        }
+    }
 }
 
 impl SigningBackend for SshBackend {
@@ -309,6 +365,36 @@ impl SigningBackend for SshBackend {
             }
         }
     }
+
+    fn verify_batch(&self, signatures: &[(&[u8], &[u8])]) -> Vec<Result<Verification, SignError>> {
+        // Verify the signatures in-process rather than running ssh-keygen
+        // twice per signature. Signatures that can't be parsed are left to
+        // ssh-keygen, which reports them its own way.
+        if let Some((allowed_signers, revoked_keys)) = self.load_verification_inputs() {
+            return signatures
+                .iter()
+                .map(|(data, signature)| {
+                    match check_signature(data, signature, &allowed_signers, &revoked_keys) {
+                        Ok(check) => Ok(self.to_verification(check)),
+                        Err(_) => self.verify(data, signature),
+                    }
+                })
+                .collect();
+        }
+        // Otherwise each verification spawns ssh-keygen processes, so run
+        // them in parallel.
+        signatures
+            .par_iter()
+            .map(|(data, signature)| self.verify(data, signature))
+            .collect()
+    }
+
+    fn trust_state(&self) -> Option<Vec<u8>> {
+        ssh_trust_state(
+            self.allowed_signers.as_deref().map(Path::new),
+            self.revocation_list.as_deref().map(Path::new),
+        )
+    }
 }
 
 #[cfg(test)]
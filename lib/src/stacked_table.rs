@@ -444,6 +444,11 @@ impl TableStore {
         self.key_size
     }
 
+    /// Starts a new table that doesn't inherit entries from any other table.
+    pub fn start_empty_table(&self) -> MutableTable {
+        MutableTable::full(self.key_size)
+    }
+
     pub fn load(dir: PathBuf, key_size: usize) -> Self {
         Self {
             dir,
@@ -468,6 +473,23 @@ impl TableStore {
         Ok(table)
     }
 
+    /// Saves `mut_table` as the head in place of `old_head`, even if
+    /// `mut_table` wasn't derived from it.
+    ///
+    /// Entries of the `old_head` not copied to `mut_table` are dropped. The
+    /// unreachable segments can then be removed by `gc()`.
+    pub fn replace_head(
+        &self,
+        old_head: &Arc<ReadonlyTable>,
+        mut_table: MutableTable,
+    ) -> TableStoreResult<Arc<ReadonlyTable>> {
+        let table = self.save_table(mut_table)?;
+        if old_head.name != table.name {
+            self.remove_head(old_head);
+        }
+        Ok(table)
+    }
+
     fn add_head(&self, table: &Arc<ReadonlyTable>) -> TableStoreResult<()> {
         std::fs::write(self.dir.join("heads").join(&table.name), "")
             .map_err(TableStoreError::SaveHeads)
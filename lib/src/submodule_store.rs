@@ -12,10 +12,408 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-#![expect(missing_docs)]
+//! Storage of the repositories backing Git submodules.
 
+use std::error;
 use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::iter;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::ExitStatus;
 
+use futures::StreamExt as _;
+use itertools::Itertools as _;
+use thiserror::Error;
+use tokio::io::AsyncReadExt as _;
+
+use crate::backend::CommitId;
+use crate::backend::TreeValue;
+use crate::file_util::IoResultExt as _;
+use crate::file_util::PathError;
+use crate::matchers::Matcher;
+use crate::merged_tree::MergedTree;
+use crate::merged_tree::TreeDiffEntry;
+use crate::repo_path::RepoPath;
+use crate::repo_path::RepoPathBuf;
+use crate::working_copy::CheckoutError;
+use crate::working_copy::SubmoduleCheckoutFailure;
+
+/// Error that may occur while fetching or checking out a submodule.
+#[derive(Debug, Error)]
+pub enum SubmoduleStoreError {
+    /// The submodule name can't be used as a path within the store.
+    #[error("Invalid submodule name {0:?}")]
+    InvalidName(String),
+    /// Failed to access the file system.
+    #[error(transparent)]
+    Path(#[from] PathError),
+    /// Failed to spawn the Git executable.
+    #[error("Failed to run {}", program.display())]
+    SpawnGit {
+        /// The Git executable.
+        program: PathBuf,
+        /// The underlying error.
+        source: io::Error,
+    },
+    /// The Git command exited with an error.
+    #[error("Git command failed with {status}: {stderr}")]
+    GitCommand {
+        /// The exit status of the command.
+        status: ExitStatus,
+        /// The error output of the command.
+        stderr: String,
+    },
+}
+
+/// Keeps track of the repositories of Git submodules.
 pub trait SubmoduleStore: Send + Sync + Debug {
+    /// Name of the store, which is recorded in the `type` file.
     fn name(&self) -> &str;
+
+    /// Returns the path to the Git directory of submodule `name` if it has
+    /// already been cloned into the store.
+    fn get_submodule_repo(&self, name: &str) -> Result<Option<PathBuf>, SubmoduleStoreError>;
+
+    /// Makes sure the repository of submodule `name` contains `commit_id`,
+    /// cloning it from `url` or fetching from `url` as needed. Returns the
+    /// path to its Git directory.
+    fn fetch_submodule(
+        &self,
+        name: &str,
+        url: &str,
+        commit_id: &CommitId,
+    ) -> Result<PathBuf, SubmoduleStoreError>;
+
+    /// Checks out `commit_id` in the submodule directory at `disk_path`. If
+    /// the directory isn't a Git repository yet, it is created as a worktree
+    /// of the repository of submodule `name` in the store, so it doesn't
+    /// share `HEAD` and index with the other workspaces.
+    fn check_out_submodule(
+        &self,
+        name: &str,
+        disk_path: &Path,
+        commit_id: &CommitId,
+    ) -> Result<(), SubmoduleStoreError>;
+}
+
+/// A submodule entry of a `.gitmodules` file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubmoduleConfig {
+    /// The name of the submodule, which identifies its repository.
+    pub name: String,
+    /// Where the submodule is checked out.
+    pub path: RepoPathBuf,
+    /// Where the submodule repository is cloned from.
+    pub url: String,
+}
+
+/// Parses the content of a `.gitmodules` file. Entries without a valid path
+/// or URL are skipped.
+pub fn parse_gitmodules(content: &[u8]) -> Vec<SubmoduleConfig> {
+    let mut configs = vec![];
+    let mut current: Option<(String, Option<String>, Option<String>)> = None;
+    let mut flush = |current: Option<(String, Option<String>, Option<String>)>| {
+        if let Some((name, Some(path), Some(url))) = current
+            && let Ok(path) = RepoPathBuf::from_internal_string(path.trim_end_matches('/'))
+        {
+            configs.push(SubmoduleConfig { name, path, url });
+        }
+    };
+    for line in String::from_utf8_lossy(content).lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(['#', ';']) {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('[') {
+            flush(current.take());
+            let header = header.trim_end_matches(']').trim();
+            current = header
+                .strip_prefix("submodule")
+                .map(|name| name.trim().trim_matches('"'))
+                .filter(|name| !name.is_empty())
+                .map(|name| (name.to_owned(), None, None));
+        } else if let Some((_, path, url)) = &mut current
+            && let Some((key, value)) = line.split_once('=')
+        {
+            let value = value.trim().trim_matches('"').to_owned();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "path" => *path = Some(value),
+                "url" => *url = Some(value),
+                _ => {}
+            }
+        }
+    }
+    flush(current);
+    configs
+}
+
+/// Resolves submodule `url` relative to `base_url` if it starts with `./` or
+/// `../`, the same way as Git does. Other URLs are returned as is.
+pub fn resolve_submodule_url(base_url: &str, url: &str) -> String {
+    let mut base = base_url.trim_end_matches('/');
+    let mut separator = '/';
+    let mut rest = url;
+    if !rest.starts_with("./") && !rest.starts_with("../") {
+        return url.to_owned();
+    }
+    loop {
+        if let Some(stripped) = rest.strip_prefix("./") {
+            rest = stripped;
+        } else if let Some(stripped) = rest.strip_prefix("../") {
+            rest = stripped;
+            // The path of an scp-like URL (host:path) starts after ':'.
+            if let Some(pos) = base.rfind(['/', ':']) {
+                separator = base[pos..].chars().next().unwrap();
+                base = &base[..pos];
+            }
+        } else {
+            break;
+        }
+    }
+    format!("{base}{separator}{rest}")
+}
+
+/// Reads the commit checked out in the Git repository at `disk_path`. Returns
+/// `None` if the directory isn't a Git repository or if its `HEAD` can't be
+/// resolved.
+pub fn read_submodule_head(disk_path: &Path) -> io::Result<Option<CommitId>> {
+    let dot_git = disk_path.join(".git");
+    let git_dir = match fs::symlink_metadata(&dot_git) {
+        Ok(metadata) if metadata.is_dir() => dot_git,
+        Ok(_) => {
+            let content = fs::read_to_string(&dot_git)?;
+            let Some(target) = content.trim().strip_prefix("gitdir:") else {
+                return Ok(None);
+            };
+            disk_path.join(target.trim())
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    // Linked worktrees keep their refs in the common directory.
+    let common_dir = match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(content) => git_dir.join(content.trim()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => git_dir.clone(),
+        Err(err) => return Err(err),
+    };
+    let mut head = match fs::read_to_string(git_dir.join("HEAD")) {
+        Ok(content) => content.trim().to_owned(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    // Follow a bounded chain of symbolic refs.
+    for _ in 0..5 {
+        let Some(ref_name) = head.strip_prefix("ref:") else {
+            return Ok(CommitId::try_from_hex(&head));
+        };
+        let ref_name = ref_name.trim().to_owned();
+        head = match fs::read_to_string(common_dir.join(&ref_name)) {
+            Ok(content) => content.trim().to_owned(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return find_packed_ref(&common_dir, &ref_name);
+            }
+            Err(err) => return Err(err),
+        };
+    }
+    Ok(None)
+}
+
+fn find_packed_ref(git_dir: &Path, ref_name: &str) -> io::Result<Option<CommitId>> {
+    let content = match fs::read_to_string(git_dir.join("packed-refs")) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    Ok(content
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(_, name)| *name == ref_name)
+        .and_then(|(hex, _)| CommitId::try_from_hex(hex)))
+}
+
+/// Checks out the submodules that changed between `old_tree` and `new_tree`
+/// in the workspace at `workspace_root`.
+///
+/// The submodule repositories are looked up in the `.gitmodules` file of
+/// `new_tree`. Relative URLs are resolved against `base_url`, which should be
+/// the URL of the superproject's default remote. Submodules without an entry there are left alone, as are
+/// submodules which already have the right commit checked out. Submodules that
+/// can't be fetched or checked out are returned instead of failing the whole
+/// checkout.
+pub async fn check_out_submodules(
+    submodule_store: &dyn SubmoduleStore,
+    workspace_root: &Path,
+    base_url: &str,
+    old_tree: &MergedTree,
+    new_tree: &MergedTree,
+    matcher: &dyn Matcher,
+) -> Result<Vec<SubmoduleCheckoutFailure>, CheckoutError> {
+    let mut changed_submodules = vec![];
+    let mut diff_stream = old_tree.diff_stream(new_tree, matcher);
+    while let Some(TreeDiffEntry { path, values }) = diff_stream.next().await {
+        if let Some(Some(TreeValue::GitSubmodule(id))) = values?.after.as_resolved() {
+            changed_submodules.push((path, id.clone()));
+        }
+    }
+    if changed_submodules.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let gitmodules_path = RepoPath::from_internal_string(".gitmodules").unwrap();
+    let configs = match new_tree.path_value(gitmodules_path).await?.into_resolved() {
+        Ok(Some(TreeValue::File { id, .. })) => {
+            let mut content = vec![];
+            new_tree
+                .store()
+                .read_file(gitmodules_path, &id)
+                .await?
+                .read_to_end(&mut content)
+                .await
+                .map_err(|err| CheckoutError::Other {
+                    message: "Failed to read .gitmodules".to_owned(),
+                    err: err.into(),
+                })?;
+            parse_gitmodules(&content)
+        }
+        _ => vec![],
+    };
+
+    let mut failures = vec![];
+    for (path, commit_id) in changed_submodules {
+        let Some(config) = configs.iter().find(|config| config.path == path) else {
+            continue;
+        };
+        let disk_path = path.to_fs_path(workspace_root)?;
+        let url = resolve_submodule_url(base_url, &config.url);
+        if let Err(err) = check_out_submodule(submodule_store, config, &url, &disk_path, &commit_id)
+        {
+            let message =
+                iter::successors(Some(&err as &dyn error::Error), |err| err.source()).join(": ");
+            failures.push(SubmoduleCheckoutFailure { path, message });
+        }
+    }
+    Ok(failures)
+}
+
+fn check_out_submodule(
+    submodule_store: &dyn SubmoduleStore,
+    config: &SubmoduleConfig,
+    url: &str,
+    disk_path: &Path,
+    commit_id: &CommitId,
+) -> Result<(), SubmoduleStoreError> {
+    let head = read_submodule_head(disk_path).context(disk_path)?;
+    if head.as_ref() == Some(commit_id) {
+        return Ok(());
+    }
+    submodule_store.fetch_submodule(&config.name, url, commit_id)?;
+    submodule_store.check_out_submodule(&config.name, disk_path, commit_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::tests::TestResult;
+    use crate::tests::new_temp_dir;
+
+    #[test]
+    fn test_parse_gitmodules() {
+        let content = indoc! {r#"
+            # comment
+            [submodule "lib/foo"]
+            	path = lib/foo
+            	url = https://example.com/foo.git
+            [submodule "no-url"]
+            	path = bar
+            [core]
+            	path = ignored
+            [submodule "quoted"]
+            	Path = "baz/"
+            	URL = ../baz.git
+        "#};
+        assert_eq!(
+            parse_gitmodules(content.as_bytes()),
+            vec![
+                SubmoduleConfig {
+                    name: "lib/foo".to_owned(),
+                    path: RepoPathBuf::from_internal_string("lib/foo").unwrap(),
+                    url: "https://example.com/foo.git".to_owned(),
+                },
+                SubmoduleConfig {
+                    name: "quoted".to_owned(),
+                    path: RepoPathBuf::from_internal_string("baz").unwrap(),
+                    url: "../baz.git".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_submodule_url() {
+        let base = "https://example.com/org/super.git";
+        assert_eq!(
+            resolve_submodule_url(base, "../foo.git"),
+            "https://example.com/org/foo.git"
+        );
+        assert_eq!(
+            resolve_submodule_url(base, "./foo.git"),
+            "https://example.com/org/super.git/foo.git"
+        );
+        assert_eq!(
+            resolve_submodule_url(&format!("{base}/"), "../../other/foo.git"),
+            "https://example.com/other/foo.git"
+        );
+        assert_eq!(
+            resolve_submodule_url("git@example.com:org/super.git", "../foo.git"),
+            "git@example.com:org/foo.git"
+        );
+        assert_eq!(
+            resolve_submodule_url("git@example.com:super.git", "../foo.git"),
+            "git@example.com:foo.git"
+        );
+        assert_eq!(
+            resolve_submodule_url("/path/to/super", "../foo"),
+            "/path/to/foo"
+        );
+        assert_eq!(
+            resolve_submodule_url(base, "https://example.com/foo.git"),
+            "https://example.com/foo.git"
+        );
+    }
+
+    #[test]
+    fn test_read_submodule_head() -> TestResult {
+        let temp_dir = new_temp_dir();
+        let work_dir = temp_dir.path().join("work");
+        let git_dir = temp_dir.path().join("modules").join("sub");
+        let commit_hex = "0123456789abcdef0123456789abcdef01234567";
+        let commit_id = CommitId::from_hex(commit_hex);
+        assert_eq!(read_submodule_head(&work_dir)?, None);
+
+        // Detached HEAD in a Git directory linked from a .git file
+        fs::create_dir_all(&work_dir)?;
+        fs::create_dir_all(git_dir.join("refs/heads"))?;
+        fs::write(
+            work_dir.join(".git"),
+            format!("gitdir: {}\n", git_dir.display()),
+        )?;
+        fs::write(git_dir.join("HEAD"), format!("{commit_hex}\n"))?;
+        assert_eq!(read_submodule_head(&work_dir)?, Some(commit_id.clone()));
+
+        // Symbolic ref resolved from packed refs, then from a loose ref
+        fs::write(git_dir.join("HEAD"), "ref: refs/heads/main\n")?;
+        assert_eq!(read_submodule_head(&work_dir)?, None);
+        fs::write(
+            git_dir.join("packed-refs"),
+            format!("# pack-refs with: peeled\n{commit_hex} refs/heads/main\n"),
+        )?;
+        assert_eq!(read_submodule_head(&work_dir)?, Some(commit_id.clone()));
+        fs::write(git_dir.join("refs/heads/main"), format!("{commit_hex}\n"))?;
+        assert_eq!(read_submodule_head(&work_dir)?, Some(commit_id));
+        Ok(())
+    }
 }
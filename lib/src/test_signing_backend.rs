@@ -73,4 +73,9 @@ impl SigningBackend for TestSigningBackend {
             })
         }
     }
+
+    fn trust_state(&self) -> Option<Vec<u8>> {
+        // Verification only depends on the signed data
+        Some(vec![])
+    }
 }
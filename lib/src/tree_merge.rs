@@ -21,6 +21,7 @@ use std::iter::zip;
 use std::sync::Arc;
 use std::vec;
 
+use bstr::BString;
 use futures::FutureExt as _;
 use futures::StreamExt as _;
 use futures::future::BoxFuture;
@@ -37,10 +38,12 @@ use crate::backend::TreeValue;
 use crate::config::ConfigGetError;
 use crate::files;
 use crate::files::FileMergeHunkLevel;
+use crate::files::MergeResult;
 use crate::merge::Merge;
 use crate::merge::MergedTreeVal;
 use crate::merge::MergedTreeValue;
 use crate::merge::SameChange;
+use crate::merge_driver::MergeDrivers;
 use crate::merged_tree::all_merged_tree_entries;
 use crate::object_id::ObjectId as _;
 use crate::repo_path::RepoPath;
@@ -57,6 +60,10 @@ pub struct MergeOptions {
     pub hunk_level: FileMergeHunkLevel,
     /// Whether to resolve conflict that makes the same change at all sides.
     pub same_change: SameChange,
+    /// External commands to merge files which can't be merged by hunks.
+    pub merge_drivers: MergeDrivers,
+    /// How to resolve conflicting hunks.
+    pub strategy: MergeStrategy,
 }
 
 impl MergeOptions {
@@ -67,13 +74,40 @@ impl MergeOptions {
             // needed. It wouldn't be translated to FileMergeHunkLevel.
             hunk_level: settings.get("merge.hunk-level")?,
             same_change: settings.get("merge.same-change")?,
+            // Merge drivers are run only when rewriting commits.
+            merge_drivers: MergeDrivers::default(),
+            strategy: MergeStrategy::default(),
         })
     }
 }
 
+/// How to resolve hunks which can't be merged automatically.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MergeStrategy {
+    /// Leave conflicting hunks as conflicts.
+    #[default]
+    Conflict,
+    /// Concatenate all sides of conflicting hunks.
+    Union,
+    /// Take the first side of conflicting hunks.
+    Ours,
+    /// Take the last side of conflicting hunks.
+    Theirs,
+}
+
 /// The returned conflict will either be resolved or have the same number of
 /// sides as the input.
 pub async fn merge_trees(store: &Arc<Store>, merge: Merge<TreeId>) -> BackendResult<Merge<TreeId>> {
+    merge_trees_with_options(store, merge, store.merge_options()).await
+}
+
+/// Like [`merge_trees()`], but resolves file conflicts with the given
+/// `options` instead of the store's default options.
+pub async fn merge_trees_with_options(
+    store: &Arc<Store>,
+    merge: Merge<TreeId>,
+    options: &MergeOptions,
+) -> BackendResult<Merge<TreeId>> {
     let merge = match merge.into_resolved() {
         Ok(tree) => return Ok(Merge::resolved(tree)),
         Err(merge) => merge,
@@ -81,6 +115,7 @@ pub async fn merge_trees(store: &Arc<Store>, merge: Merge<TreeId>) -> BackendRes
 
     let mut merger = TreeMerger {
         store: store.clone(),
+        options: Arc::new(options.clone()),
         trees_to_resolve: BTreeMap::new(),
         work: FuturesUnordered::new(),
         unstarted_work: BTreeMap::new(),
@@ -195,6 +230,7 @@ enum TreeMergeWorkItemKey {
 
 struct TreeMerger {
     store: Arc<Store>,
+    options: Arc<MergeOptions>,
     // Trees we're currently working on.
     trees_to_resolve: BTreeMap<RepoPathBuf, MergedTreeInput>,
     // Futures we're currently processing. In order to respect the backend's concurrency limit.
@@ -247,7 +283,7 @@ impl TreeMerger {
     fn process_tree(&mut self, dir: RepoPathBuf, tree: Merge<Tree>) {
         // First resolve trivial merges (those that we don't need to load any more data
         // for)
-        let same_change = self.store.merge_options().same_change;
+        let same_change = self.options.same_change;
         let mut resolved = vec![];
         let mut non_trivial = vec![];
         for (basename, path_merge) in all_merged_tree_entries(&tree) {
@@ -305,8 +341,13 @@ impl TreeMerger {
 
     fn enqueue_file_merge(&mut self, path: RepoPathBuf, value: MergedTreeValue) {
         let key = TreeMergeWorkItemKey::MergeFiles { path: path.clone() };
-        let work_fut = resolve_file_values_owned(self.store.clone(), path.clone(), value)
-            .map(|result| TreeMergerWorkOutput::MergedFiles { path, result });
+        let work_fut = resolve_file_values_owned(
+            self.store.clone(),
+            self.options.clone(),
+            path.clone(),
+            value,
+        )
+        .map(|result| TreeMergerWorkOutput::MergedFiles { path, result });
         if self.work.len() < self.store.concurrency() {
             self.work.push(Box::pin(work_fut));
         } else {
@@ -317,7 +358,7 @@ impl TreeMerger {
     fn mark_completed(&mut self, path: &RepoPath, value: MergedTreeValue) {
         let (dir, basename) = path.split().unwrap();
         let tree = self.trees_to_resolve.get_mut(dir).unwrap();
-        let same_change = self.store.merge_options().same_change;
+        let same_change = self.options.same_change;
         tree.mark_completed(basename.to_owned(), value, same_change);
         // If all entries in this tree have been processed (either resolved or still a
         // conflict), schedule the writing of the tree(s) to the backend.
@@ -358,10 +399,11 @@ async fn write_trees(
 
 async fn resolve_file_values_owned(
     store: Arc<Store>,
+    options: Arc<MergeOptions>,
     path: RepoPathBuf,
     values: MergedTreeValue,
 ) -> BackendResult<MergedTreeValue> {
-    let maybe_resolved = try_resolve_file_values(&store, &path, &values).await?;
+    let maybe_resolved = try_resolve_file_values(&store, &options, &path, &values).await?;
     Ok(maybe_resolved.unwrap_or(values))
 }
 
@@ -378,12 +420,14 @@ pub async fn resolve_file_values(
         return Ok(Merge::resolved(resolved.clone()));
     }
 
-    let maybe_resolved = try_resolve_file_values(store, path, &values).await?;
+    let maybe_resolved =
+        try_resolve_file_values(store, store.merge_options(), path, &values).await?;
     Ok(maybe_resolved.unwrap_or(values))
 }
 
 async fn try_resolve_file_values<T: Borrow<TreeValue>>(
     store: &Arc<Store>,
+    options: &MergeOptions,
     path: &RepoPath,
     values: &Merge<Option<T>>,
 ) -> BackendResult<Option<MergedTreeValue>> {
@@ -394,7 +438,7 @@ async fn try_resolve_file_values<T: Borrow<TreeValue>>(
         .simplify();
     // No fast path for simplified.is_resolved(). If it could be resolved, it would
     // have been caught by values.resolve_trivial() above.
-    if let Some(resolved) = try_resolve_file_conflict(store, path, &simplified).await? {
+    if let Some(resolved) = try_resolve_file_conflict(store, options, path, &simplified).await? {
         Ok(Some(Merge::normal(resolved)))
     } else {
         // Failed to merge the files, or the paths are not files
@@ -408,10 +452,10 @@ async fn try_resolve_file_values<T: Borrow<TreeValue>>(
 /// non-file values that cancel each other.
 async fn try_resolve_file_conflict(
     store: &Store,
+    options: &MergeOptions,
     filename: &RepoPath,
     conflict: &MergedTreeVal<'_>,
 ) -> BackendResult<Option<TreeValue>> {
-    let options = store.merge_options();
     // If there are any non-file or any missing parts in the conflict, we can't
     // merge it. We check early so we don't waste time reading file contents if
     // we can't merge them anyway. At the same time we determine whether the
@@ -489,7 +533,16 @@ async fn try_resolve_file_conflict(
             BackendResult::Ok(content)
         })
         .await?;
-    if let Some(merged_content) = files::try_merge(&contents, options) {
+    let merged_content = match files::merge_hunks(&contents, options) {
+        MergeResult::Resolved(content) => Some(content.into()),
+        MergeResult::Conflict(hunks) => {
+            match options.merge_drivers.try_merge(filename, &contents).await {
+                Some(content) => Some(content),
+                None => resolve_hunks_by_strategy(hunks, options.strategy),
+            }
+        }
+    };
+    if let Some(merged_content) = merged_content {
         let id = store
             .write_file(filename, &mut merged_content.as_slice())
             .await?;
@@ -502,3 +555,34 @@ async fn try_resolve_file_conflict(
         Ok(None)
     }
 }
+
+/// Resolves conflicting `hunks` by picking or concatenating their sides as
+/// specified by `strategy`. Returns `None` if the strategy leaves conflicts.
+fn resolve_hunks_by_strategy(
+    hunks: Vec<Merge<BString>>,
+    strategy: MergeStrategy,
+) -> Option<Vec<u8>> {
+    let mut content = vec![];
+    for hunk in hunks {
+        if let Some(resolved) = hunk.as_resolved() {
+            content.extend_from_slice(resolved);
+            continue;
+        }
+        match strategy {
+            MergeStrategy::Conflict => return None,
+            MergeStrategy::Union => {
+                for (i, side) in hunk.adds().enumerate() {
+                    // Keep lines from different sides apart if a side ends
+                    // without newline at end of file.
+                    if i > 0 && !content.is_empty() && !content.ends_with(b"\n") {
+                        content.push(b'\n');
+                    }
+                    content.extend_from_slice(side);
+                }
+            }
+            MergeStrategy::Ours => content.extend_from_slice(hunk.first()),
+            MergeStrategy::Theirs => content.extend_from_slice(hunk.adds().last().unwrap()),
+        }
+    }
+    Some(content)
+}
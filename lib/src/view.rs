@@ -101,6 +101,17 @@ impl View {
         &self.data.git_head
     }
 
+    /// Returns the HEADs of the linked Git worktrees, keyed by worktree name.
+    pub fn git_worktree_heads(&self) -> &BTreeMap<String, RefTarget> {
+        &self.data.git_worktree_heads
+    }
+
+    /// Returns the HEAD of the linked Git worktree of the given name. Returns
+    /// absent target if the worktree isn't known.
+    pub fn get_git_worktree_head(&self, name: &str) -> &RefTarget {
+        self.data.git_worktree_heads.get(name).flatten()
+    }
+
     pub fn set_wc_commit(&mut self, name: WorkspaceNameBuf, commit_id: CommitId) {
         self.data.wc_commit_ids.insert(name, commit_id);
     }
@@ -193,6 +204,21 @@ impl View {
         op_store::flatten_remote_refs(&self.data.remote_views, |view| &view.bookmarks)
     }
 
+    /// Iterates over symbols of the remote bookmarks known to have been
+    /// deleted from the remote, in lexicographical order.
+    pub fn all_remote_bookmark_tombstones(&self) -> impl Iterator<Item = RemoteRefSymbol<'_>> {
+        self.data
+            .remote_views
+            .iter()
+            .map(|(remote, remote_view)| {
+                remote_view
+                    .bookmark_tombstones
+                    .iter()
+                    .map(move |name| name.to_remote_symbol(remote))
+            })
+            .kmerge()
+    }
+
     /// Iterates over `(name, remote_ref)`s for all remote bookmarks of the
     /// specified remote in lexicographical order.
     pub fn remote_bookmarks(
@@ -241,7 +267,8 @@ impl View {
 
     /// Sets remote-tracking bookmark to the given target and state. If the
     /// target is absent and if no tracking local bookmark exists, the bookmark
-    /// will be removed.
+    /// will be removed. The tombstone of the bookmark, if any, will also be
+    /// removed.
     pub fn set_remote_bookmark(&mut self, symbol: RemoteRefSymbol<'_>, remote_ref: RemoteRef) {
         if remote_ref.is_present()
             || (remote_ref.is_tracked() && self.get_local_bookmark(symbol.name).is_present())
@@ -251,14 +278,39 @@ impl View {
                 .remote_views
                 .entry(symbol.remote.to_owned())
                 .or_default();
+            remote_view.bookmark_tombstones.remove(symbol.name);
             remote_view
                 .bookmarks
                 .insert(symbol.name.to_owned(), remote_ref);
         } else if let Some(remote_view) = self.data.remote_views.get_mut(symbol.remote) {
+            remote_view.bookmark_tombstones.remove(symbol.name);
             remote_view.bookmarks.remove(symbol.name);
         }
     }
 
+    /// Returns true if the remote bookmark is known to have been deleted from
+    /// the remote.
+    pub fn has_remote_bookmark_tombstone(&self, symbol: RemoteRefSymbol<'_>) -> bool {
+        self.data
+            .remote_views
+            .get(symbol.remote)
+            .is_some_and(|remote_view| remote_view.bookmark_tombstones.contains(symbol.name))
+    }
+
+    /// Removes the remote bookmark and records that it has been deleted from
+    /// the remote.
+    pub fn add_remote_bookmark_tombstone(&mut self, symbol: RemoteRefSymbol<'_>) {
+        let remote_view = self
+            .data
+            .remote_views
+            .entry(symbol.remote.to_owned())
+            .or_default();
+        remote_view.bookmarks.remove(symbol.name);
+        remote_view
+            .bookmark_tombstones
+            .insert(symbol.name.to_owned());
+    }
+
     /// Iterates over `(name, {local_ref, remote_ref})`s for every bookmark
     /// present locally and/or on the specified remote, in lexicographical
     /// order.
@@ -512,6 +564,16 @@ impl View {
         self.data.git_head = target;
     }
 
+    /// Sets the HEAD of the linked Git worktree to point to the given target.
+    /// If the target is absent, the worktree will be forgotten.
+    pub fn set_git_worktree_head_target(&mut self, name: &str, target: RefTarget) {
+        if target.is_present() {
+            self.data.git_worktree_heads.insert(name.to_owned(), target);
+        } else {
+            self.data.git_worktree_heads.remove(name);
+        }
+    }
+
     /// Iterates all commit ids referenced by this view.
     ///
     /// This can include hidden commits referenced by remote bookmarks, previous
@@ -536,6 +598,7 @@ impl View {
             remote_views,
             git_refs,
             git_head,
+            git_worktree_heads,
             wc_commit_ids,
         } = &self.data;
         itertools::chain!(
@@ -543,12 +606,17 @@ impl View {
             local_bookmarks.values().flat_map(ref_target_ids),
             local_tags.values().flat_map(ref_target_ids),
             remote_views.values().flat_map(|remote_view| {
-                let op_store::RemoteView { bookmarks, tags } = remote_view;
+                let op_store::RemoteView {
+                    bookmarks,
+                    tags,
+                    bookmark_tombstones: _,
+                } = remote_view;
                 itertools::chain(bookmarks.values(), tags.values())
                     .flat_map(|remote_ref| ref_target_ids(&remote_ref.target))
             }),
             git_refs.values().flat_map(ref_target_ids),
             ref_target_ids(git_head),
+            git_worktree_heads.values().flat_map(ref_target_ids),
             wc_commit_ids.values()
         )
     }
@@ -643,6 +711,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_remote_bookmark_tombstones() {
+        let mut view = View {
+            data: op_store::View::make_root(CommitId::from_hex("000000")),
+        };
+
+        // Tombstone replaces the remote bookmark
+        view.set_remote_bookmark(
+            remote_symbol("foo", "origin"),
+            RemoteRef {
+                target: RefTarget::normal(CommitId::from_hex("111111")),
+                state: RemoteRefState::Tracked,
+            },
+        );
+        view.add_remote_bookmark_tombstone(remote_symbol("foo", "origin"));
+        assert!(view.has_remote_bookmark_tombstone(remote_symbol("foo", "origin")));
+        assert_eq!(
+            view.get_remote_bookmark(remote_symbol("foo", "origin")),
+            RemoteRef::absent_ref()
+        );
+        assert_eq!(view.all_remote_bookmarks().count(), 0);
+        assert_eq!(view.remote_bookmarks("origin".as_ref()).count(), 0);
+        assert_eq!(
+            view.all_remote_bookmark_tombstones().collect_vec(),
+            [remote_symbol("foo", "origin")]
+        );
+
+        // Tombstone shouldn't be removed if local ref becomes absent
+        view.set_local_bookmark_target(
+            "foo".as_ref(),
+            RefTarget::normal(CommitId::from_hex("111111")),
+        );
+        view.set_local_bookmark_target("foo".as_ref(), RefTarget::absent());
+        assert_eq!(view.all_remote_bookmark_tombstones().count(), 1);
+
+        // Absent remote ref removes tombstone
+        view.set_remote_bookmark(remote_symbol("foo", "origin"), RemoteRef::absent());
+        assert_eq!(view.all_remote_bookmark_tombstones().count(), 0);
+    }
+
     #[test]
     fn test_absent_tracked_tags() {
         let mut view = View {
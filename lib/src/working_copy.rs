@@ -45,6 +45,8 @@ use crate::repo_path::InvalidRepoPathError;
 use crate::repo_path::RepoPath;
 use crate::repo_path::RepoPathBuf;
 use crate::settings::UserSettings;
+use crate::sparse::SparsePatterns;
+use crate::sparse::SparseProfile;
 use crate::store::Store;
 use crate::transaction::TransactionCommitError;
 
@@ -64,10 +66,12 @@ pub trait WorkingCopy: Any + Send {
     fn tree(&self) -> Result<&MergedTree, WorkingCopyStateError>;
 
     /// Patterns that decide which paths from the current tree should be checked
-    /// out in the working copy. An empty list means that no paths should be
-    /// checked out in the working copy. A single `RepoPath::root()` entry means
-    /// that all files should be checked out.
-    fn sparse_patterns(&self) -> Result<&[RepoPathBuf], WorkingCopyStateError>;
+    /// out in the working copy.
+    fn sparse_patterns(&self) -> Result<&SparsePatterns, WorkingCopyStateError>;
+
+    /// Name of the sparse profile the sparse patterns were resolved from, if
+    /// any.
+    fn sparse_profile(&self) -> Result<Option<&str>, WorkingCopyStateError>;
 
     /// Locks the working copy and returns an instance with methods for updating
     /// the working copy files and state.
@@ -120,6 +124,9 @@ pub trait LockedWorkingCopy: Any + Send {
     ) -> Result<(MergedTree, SnapshotStats), SnapshotError>;
 
     /// Check out the specified commit in the working copy.
+    ///
+    /// If a sparse profile is in use, it's resolved again from the commit, and
+    /// the sparse patterns are updated along with the files.
     async fn check_out(&mut self, commit: &Commit) -> Result<CheckoutStats, CheckoutError>;
 
     /// Update the workspace name.
@@ -133,17 +140,28 @@ pub trait LockedWorkingCopy: Any + Send {
     async fn recover(&mut self, commit: &Commit) -> Result<(), ResetError>;
 
     /// See `WorkingCopy::sparse_patterns()`
-    fn sparse_patterns(&self) -> Result<&[RepoPathBuf], WorkingCopyStateError>;
+    fn sparse_patterns(&self) -> Result<&SparsePatterns, WorkingCopyStateError>;
+
+    /// See `WorkingCopy::sparse_profile()`
+    fn sparse_profile(&self) -> Result<Option<&str>, WorkingCopyStateError>;
 
     /// Updates the patterns that decide which paths from the current tree
-    /// should be checked out in the working copy.
+    /// should be checked out in the working copy. Stops using the sparse
+    /// profile, if any.
     // TODO: Use a different error type here so we can include a
     // `SparseNotSupported` variants for working copies that don't support sparse
     // checkouts (e.g. because they use a virtual file system so there's no reason
     // to use sparse).
     async fn set_sparse_patterns(
         &mut self,
-        new_sparse_patterns: Vec<RepoPathBuf>,
+        new_sparse_patterns: SparsePatterns,
+    ) -> Result<CheckoutStats, CheckoutError>;
+
+    /// Updates the sparse patterns to the ones resolved from the profile, and
+    /// keeps using the profile on later checkouts.
+    async fn set_sparse_profile(
+        &mut self,
+        profile: SparseProfile,
     ) -> Result<CheckoutStats, CheckoutError>;
 
     /// Finish the modifications to the working copy by writing the updated
@@ -270,6 +288,33 @@ pub struct CheckoutStats {
     /// working copy but were skipped because there was an untracked (probably
     /// ignored) file in its place.
     pub skipped_files: u32,
+    /// Submodules that couldn't be checked out. The rest of the working copy
+    /// was updated regardless.
+    pub failed_submodules: Vec<SubmoduleCheckoutFailure>,
+    /// The sparse profile in use, if the checkout updated the sparse patterns
+    /// from a new version of the profile.
+    pub updated_sparse_profile: Option<String>,
+    /// The sparse profile in use couldn't be resolved from the new commit, so
+    /// the previous sparse patterns were kept.
+    pub failed_sparse_profile: Option<SparseProfileFailure>,
+}
+
+/// A submodule which couldn't be checked out.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SubmoduleCheckoutFailure {
+    /// The path of the submodule in the working copy.
+    pub path: RepoPathBuf,
+    /// The error message, including its sources.
+    pub message: String,
+}
+
+/// A sparse profile which couldn't be resolved.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SparseProfileFailure {
+    /// The name of the profile.
+    pub name: String,
+    /// The error message, including its sources.
+    pub message: String,
 }
 
 /// The working-copy checkout failed.
@@ -36,6 +36,7 @@ use crate::op_heads_store::OpHeadsStoreError;
 use crate::op_store::OperationId;
 use crate::ref_name::WorkspaceName;
 use crate::ref_name::WorkspaceNameBuf;
+use crate::remote_backend::RemoteBackend;
 use crate::repo::BackendInitializer;
 use crate::repo::CheckOutCommitError;
 use crate::repo::IndexStoreInitializer;
@@ -53,6 +54,7 @@ use crate::settings::UserSettings;
 use crate::signing::SignInitError;
 use crate::signing::Signer;
 use crate::simple_backend::SimpleBackend;
+use crate::submodule_store::check_out_submodules;
 use crate::transaction::TransactionCommitError;
 use crate::working_copy::CheckoutError;
 use crate::working_copy::CheckoutStats;
@@ -201,6 +203,19 @@ impl Workspace {
         Self::init_with_backend(user_settings, workspace_root, backend_initializer, signer).await
     }
 
+    /// Initializes a workspace with a backend that stores objects in the
+    /// remote content-addressed store at `url`.
+    pub async fn init_remote(
+        user_settings: &UserSettings,
+        workspace_root: &Path,
+        url: &str,
+    ) -> Result<(Self, Arc<ReadonlyRepo>), WorkspaceInitError> {
+        let backend_initializer: &BackendInitializer =
+            &|_settings, store_path| Ok(Box::new(RemoteBackend::init(store_path, url)?));
+        let signer = Signer::from_settings(user_settings)?;
+        Self::init_with_backend(user_settings, workspace_root, backend_initializer, signer).await
+    }
+
     /// Initializes a workspace with a new Git backend and bare Git repo in
     /// `.jj/repo/store/git`.
     #[cfg(feature = "git")]
@@ -455,6 +470,15 @@ impl Workspace {
         old_tree: Option<&MergedTree>,
         commit: &Commit,
     ) -> Result<CheckoutStats, CheckoutError> {
+        // Checking out submodules may fetch them over the network, so it has
+        // to be enabled explicitly.
+        let submodules_enabled = self
+            .settings()
+            .get_bool("git.check-out-submodules")
+            .map_err(|err| CheckoutError::Other {
+                message: "Failed to read the submodule settings".to_string(),
+                err: err.into(),
+            })?;
         let mut locked_ws = self.start_working_copy_mutation()?;
         // Check if the current working-copy commit has changed on disk compared to what
         // the caller expected. It's safe to check out another commit
@@ -466,7 +490,10 @@ impl Workspace {
         {
             return Err(CheckoutError::ConcurrentCheckout);
         }
-        let stats = locked_ws.locked_wc().check_out(commit).await?;
+        let old_tree = locked_ws.locked_wc().old_tree().clone();
+        let mut stats = locked_ws.locked_wc().check_out(commit).await?;
+        // The check out may have updated the patterns from the sparse profile
+        let sparse_patterns = locked_ws.locked_wc().sparse_patterns()?.clone();
         locked_ws
             .finish(operation_id)
             .await
@@ -474,8 +501,36 @@ impl Workspace {
                 message: "Failed to save the working copy state".to_string(),
                 err: err.into(),
             })?;
+        // Submodules are checked out once the working copy is up to date so a
+        // submodule that can't be fetched doesn't leave it half updated. Such
+        // failures are reported in the stats instead.
+        if submodules_enabled {
+            stats.failed_submodules = check_out_submodules(
+                self.repo_loader.submodule_store().as_ref(),
+                &self.workspace_root,
+                &self.submodule_base_url(),
+                &old_tree,
+                &commit.tree(),
+                sparse_patterns.to_matcher().as_ref(),
+            )
+            .await?;
+        }
         Ok(stats)
     }
+
+    /// Returns the URL relative submodule URLs are resolved against. Like Git,
+    /// this is the URL of the "origin" remote, or the workspace root if
+    /// there's no such remote.
+    fn submodule_base_url(&self) -> String {
+        #[cfg(feature = "git")]
+        if let Ok(git_backend) = crate::git::get_git_backend(self.repo_loader.store())
+            && let Some(Ok(remote)) = git_backend.git_repo().try_find_remote("origin")
+            && let Some(url) = remote.url(gix::remote::Direction::Fetch)
+        {
+            return url.to_bstring().to_string();
+        }
+        self.workspace_root.to_string_lossy().into_owned()
+    }
 }
 
 pub struct LockedWorkspace<'a> {
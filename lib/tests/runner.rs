@@ -38,6 +38,8 @@ mod test_rewrite;
 mod test_rewrite_duplicate;
 mod test_rewrite_transform;
 mod test_signing;
+#[cfg(unix)]
+mod test_ssh_agent_signing;
 mod test_ssh_signing;
 mod test_view;
 mod test_workspace;
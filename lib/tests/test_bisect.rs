@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::slice;
 use std::sync::Arc;
 
 use assert_matches::assert_matches;
@@ -267,6 +268,45 @@ fn test_bisect_disjoint_sets() {
     );
 }
 
+#[test]
+fn test_bisect_parallel_candidates() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let commit1 = write_random_commit(tx.repo_mut());
+    let commit2 = write_random_commit_with_parents(tx.repo_mut(), &[&commit1]);
+    let commit3 = write_random_commit_with_parents(tx.repo_mut(), &[&commit2]);
+    let commit4 = write_random_commit_with_parents(tx.repo_mut(), &[&commit3]);
+    let commit5 = write_random_commit_with_parents(tx.repo_mut(), &[&commit4]);
+    let commit6 = write_random_commit_with_parents(tx.repo_mut(), &[&commit5]);
+    let commit7 = write_random_commit_with_parents(tx.repo_mut(), &[&commit6]);
+
+    let input_range = ResolvedRevsetExpression::commit(commit7.id().clone()).ancestors();
+    let mut bisector = Bisector::new(tx.repo(), input_range).block_on().unwrap();
+
+    // The candidates split the range evenly
+    let candidates = bisector.next_candidates(3).block_on().unwrap();
+    assert_eq!(
+        candidates,
+        [commit5.clone(), commit3.clone(), commit1.clone()]
+    );
+    bisector.mark(commit5.id().clone(), Evaluation::Bad);
+    bisector.mark(commit3.id().clone(), Evaluation::Good);
+    bisector.mark(commit1.id().clone(), Evaluation::Good);
+
+    // No more candidates than remaining commits are returned
+    let candidates = bisector.next_candidates(3).block_on().unwrap();
+    assert_eq!(candidates, slice::from_ref(&commit4));
+    bisector.mark(commit4.id().clone(), Evaluation::Good);
+
+    assert!(bisector.next_candidates(3).block_on().unwrap().is_empty());
+    assert_eq!(
+        bisector.next_step().block_on().unwrap(),
+        NextStep::Done(BisectionResult::Found(vec![commit5.clone()]))
+    );
+}
+
 #[test]
 fn test_bisect_abort() {
     let test_repo = TestRepo::init();
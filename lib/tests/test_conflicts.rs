@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use bstr::BString;
 use indoc::indoc;
 use itertools::Itertools as _;
 use jj_lib::backend::FileId;
@@ -24,13 +25,16 @@ use jj_lib::conflicts::extract_as_single_hunk;
 use jj_lib::conflicts::materialize_merge_result_to_bytes;
 use jj_lib::conflicts::parse_conflict;
 use jj_lib::conflicts::update_from_content;
+use jj_lib::conflicts::update_from_hunks;
 use jj_lib::files::FileMergeHunkLevel;
 use jj_lib::merge::Merge;
 use jj_lib::merge::SameChange;
+use jj_lib::merge_driver::MergeDrivers;
 use jj_lib::repo::Repo as _;
 use jj_lib::repo_path::RepoPath;
 use jj_lib::store::Store;
 use jj_lib::tree_merge::MergeOptions;
+use jj_lib::tree_merge::MergeStrategy;
 use pollster::FutureExt as _;
 use test_case::test_case;
 use testutils::TestRepo;
@@ -1805,6 +1809,58 @@ fn test_update_conflict_from_content() -> TestResult {
     Ok(())
 }
 
+#[test]
+fn test_update_conflict_from_hunks() -> TestResult {
+    let test_repo = TestRepo::init();
+    let store = test_repo.repo.store();
+
+    let path = repo_path("dir/file");
+    let base_file_id = testutils::write_file(store, path, "line 1\nline 2\nline 3\n");
+    let left_file_id = testutils::write_file(store, path, "left 1\nline 2\nleft 3\n");
+    let right_file_id = testutils::write_file(store, path, "right 1\nline 2\nright 3\n");
+    let conflict = Merge::from_removes_adds(
+        vec![Some(base_file_id.clone())],
+        vec![Some(left_file_id.clone()), Some(right_file_id.clone())],
+    );
+    let update = |hunks: &[Merge<BString>]| {
+        update_from_hunks(&conflict, store, path, hunks)
+            .block_on()
+            .unwrap()
+    };
+    let conflict_hunk = |base: &str, left: &str, right: &str| {
+        Merge::from_vec(vec![left.into(), base.into(), right.into()])
+    };
+
+    // If all hunks are resolved, we get a resolved file back.
+    let expected_file_id = testutils::write_file(store, path, "resolved 1\nline 2\nresolved 3\n");
+    assert_eq!(
+        update(&[
+            Merge::resolved("resolved 1\n".into()),
+            Merge::resolved("line 2\n".into()),
+            Merge::resolved("resolved 3\n".into()),
+        ]),
+        Merge::normal(expected_file_id)
+    );
+
+    // If the conflict is partially resolved, we get a new conflict back.
+    let new_conflict = update(&[
+        Merge::resolved("resolved 1\n".into()),
+        Merge::resolved("line 2\n".into()),
+        conflict_hunk("line 3\n", "left 3\n", "right 3\n"),
+    ]);
+    let new_base_file_id = testutils::write_file(store, path, "resolved 1\nline 2\nline 3\n");
+    let new_left_file_id = testutils::write_file(store, path, "resolved 1\nline 2\nleft 3\n");
+    let new_right_file_id = testutils::write_file(store, path, "resolved 1\nline 2\nright 3\n");
+    assert_eq!(
+        new_conflict,
+        Merge::from_removes_adds(
+            vec![Some(new_base_file_id)],
+            vec![Some(new_left_file_id), Some(new_right_file_id)]
+        )
+    );
+    Ok(())
+}
+
 #[test]
 fn test_update_conflict_from_content_modify_delete() -> TestResult {
     let test_repo = TestRepo::init();
@@ -2547,6 +2603,8 @@ fn materialize_conflict_string_with_labels(
         merge: MergeOptions {
             hunk_level: FileMergeHunkLevel::Line,
             same_change: SameChange::Accept,
+            merge_drivers: MergeDrivers::default(),
+            strategy: MergeStrategy::Conflict,
         },
     };
     String::from_utf8(
@@ -14,11 +14,14 @@
 
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::time::SystemTime;
 
 use jj_lib::backend::CommitId;
 use jj_lib::backend::FileId;
 use jj_lib::fix::FileFixer;
 use jj_lib::fix::FileToFix;
+use jj_lib::fix::FixCache;
+use jj_lib::fix::FixCacheKey;
 use jj_lib::fix::FixError;
 use jj_lib::fix::ParallelFileFixer;
 use jj_lib::fix::fix_files;
@@ -533,3 +536,68 @@ fn test_fix_multiple_revisions() -> TestResult {
     assert_tree_eq!(new_commit_a.tree(), expected_tree_a);
     Ok(())
 }
+
+#[test]
+fn test_fix_cache_persists_entries() -> TestResult {
+    let temp_dir = testutils::new_temp_dir();
+    let cache_dir = temp_dir.path().join("fix_cache");
+    let input = FileId::from_hex("0123");
+    let output = FileId::from_hex("4567");
+    let tool_args = vec![b"tool".to_vec(), b"--arg".to_vec()];
+    let key = FixCacheKey::new(&tool_args, &input);
+
+    let cache = FixCache::load(cache_dir.clone())?;
+    assert_eq!(cache.get(&key), None);
+    cache.insert(key.clone(), output.clone());
+    assert_eq!(cache.get(&key), Some(output.clone()));
+    cache.save()?;
+
+    // The entry is visible after reloading, but only for the same tool and input
+    let cache = FixCache::load(cache_dir)?;
+    assert_eq!(cache.get(&key), Some(output));
+    let other_tool_key = FixCacheKey::new(&[b"tool".to_vec()], &input);
+    assert_eq!(cache.get(&other_tool_key), None);
+    let other_input_key = FixCacheKey::new(&tool_args, &FileId::from_hex("89ab"));
+    assert_eq!(cache.get(&other_input_key), None);
+    Ok(())
+}
+
+#[test]
+fn test_fix_cache_load_concurrently() -> TestResult {
+    let temp_dir = testutils::new_temp_dir();
+    let cache_dir = temp_dir.path().join("fix_cache");
+
+    // Concurrent commands may create the cache directory at the same time
+    std::thread::scope(|s| {
+        let handles = (0..8)
+            .map(|_| s.spawn(|| FixCache::load(cache_dir.clone()).map(|_| ())))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .try_for_each(|handle| handle.join().unwrap())
+    })?;
+    Ok(())
+}
+
+#[test]
+fn test_fix_cache_gc() -> TestResult {
+    let temp_dir = testutils::new_temp_dir();
+    let cache_dir = temp_dir.path().join("fix_cache");
+    let tool_args = vec![b"tool".to_vec()];
+    let key1 = FixCacheKey::new(&tool_args, &FileId::from_hex("01"));
+    let key2 = FixCacheKey::new(&tool_args, &FileId::from_hex("02"));
+
+    let cache = FixCache::load(cache_dir.clone())?;
+    cache.insert(key1.clone(), FileId::from_hex("11"));
+    cache.save()?;
+    let cache = FixCache::load(cache_dir.clone())?;
+    cache.insert(key2.clone(), FileId::from_hex("22"));
+    cache.save()?;
+
+    // Reachable segments are preserved
+    FixCache::load(cache_dir.clone())?.gc(SystemTime::now())?;
+    let cache = FixCache::load(cache_dir)?;
+    assert_eq!(cache.get(&key1), Some(FileId::from_hex("11")));
+    assert_eq!(cache.get(&key2), Some(FileId::from_hex("22")));
+    Ok(())
+}
@@ -206,7 +206,7 @@ fn fetch_with(
     let refspecs = expand_fetch_refspecs(remote, ref_expr).expect("ref patterns should be valid");
     let depth = None;
     let fetch_tags = None;
-    fetcher.fetch(remote, refspecs, &mut NullCallback, depth, fetch_tags)
+    fetcher.fetch(remote, refspecs, &mut NullCallback, depth, None, fetch_tags)
 }
 
 fn push_status_rejected_references(push_stats: GitPushStats) -> Vec<GitRefNameBuf> {
@@ -1156,6 +1156,122 @@ fn test_import_refs_reimport_absent_tracked_remote_bookmarks() -> TestResult {
     Ok(())
 }
 
+#[test]
+fn test_import_refs_reimport_remote_bookmark_tombstones() -> TestResult {
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_repo.repo;
+    let git_repo = get_git_repo(repo);
+    let import_options = auto_track_import_options();
+
+    let commit1 = empty_git_commit(&git_repo, "refs/remotes/origin/feature", &[]);
+    git_ref(&git_repo, "refs/remotes/upstream/main", commit1);
+    let mut tx = repo.start_transaction();
+    git::import_refs(tx.repo_mut(), &import_options).block_on()?;
+    tx.repo_mut().rebase_descendants().block_on()?;
+    let repo = tx.commit("test").block_on()?;
+    assert_eq!(
+        repo.view().get_local_bookmark("feature".as_ref()),
+        &RefTarget::normal(jj_id(commit1))
+    );
+
+    // Deleted tracked remote bookmark should be recorded as tombstone since
+    // upstream may have the bookmark
+    delete_git_ref(&git_repo, "refs/remotes/origin/feature");
+    let mut tx = repo.start_transaction();
+    git::import_refs(tx.repo_mut(), &import_options).block_on()?;
+    tx.repo_mut().rebase_descendants().block_on()?;
+    let repo = tx.commit("test").block_on()?;
+    let view = repo.view();
+    assert!(view.get_local_bookmark("feature".as_ref()).is_absent());
+    assert_eq!(
+        view.get_remote_bookmark(remote_symbol("feature", "origin")),
+        RemoteRef::absent_ref()
+    );
+    assert_eq!(
+        view.all_remote_bookmarks()
+            .map(|(symbol, _)| symbol)
+            .collect_vec(),
+        [remote_symbol("main", "upstream")]
+    );
+    assert_eq!(
+        view.all_remote_bookmark_tombstones().collect_vec(),
+        [remote_symbol("feature", "origin")]
+    );
+
+    // Bookmark deleted from origin shouldn't be resurrected by upstream
+    git_ref(&git_repo, "refs/remotes/upstream/feature", commit1);
+    let mut tx = repo.start_transaction();
+    git::import_refs(tx.repo_mut(), &import_options).block_on()?;
+    tx.repo_mut().rebase_descendants().block_on()?;
+    let repo = tx.commit("test").block_on()?;
+    let view = repo.view();
+    assert!(view.get_local_bookmark("feature".as_ref()).is_absent());
+    assert_eq!(
+        view.get_remote_bookmark(remote_symbol("feature", "upstream")),
+        &RemoteRef {
+            target: RefTarget::normal(jj_id(commit1)),
+            state: RemoteRefState::New,
+        }
+    );
+
+    // Bookmark recreated on origin replaces the tombstone
+    let commit2 = empty_git_commit(&git_repo, "refs/remotes/origin/feature", &[commit1]);
+    let mut tx = repo.start_transaction();
+    git::import_refs(tx.repo_mut(), &import_options).block_on()?;
+    tx.repo_mut().rebase_descendants().block_on()?;
+    let repo = tx.commit("test").block_on()?;
+    let view = repo.view();
+    assert_eq!(
+        view.get_local_bookmark("feature".as_ref()),
+        &RefTarget::normal(jj_id(commit2))
+    );
+    assert_eq!(
+        view.get_remote_bookmark(remote_symbol("feature", "origin")),
+        &RemoteRef {
+            target: RefTarget::normal(jj_id(commit2)),
+            state: RemoteRefState::Tracked,
+        }
+    );
+    assert_eq!(view.all_remote_bookmark_tombstones().count(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_import_refs_prune_remote_bookmark_tombstones() -> TestResult {
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_repo.repo;
+    let git_repo = get_git_repo(repo);
+    let import_options = auto_track_import_options();
+    let import_refs = |repo: &Arc<ReadonlyRepo>| -> TestResult<Arc<ReadonlyRepo>> {
+        let mut tx = repo.start_transaction();
+        git::import_refs(tx.repo_mut(), &import_options).block_on()?;
+        tx.repo_mut().rebase_descendants().block_on()?;
+        Ok(tx.commit("test").block_on()?)
+    };
+
+    // No tombstone is needed if there's no other remote
+    let commit1 = empty_git_commit(&git_repo, "refs/remotes/origin/single", &[]);
+    let repo = import_refs(repo)?;
+    delete_git_ref(&git_repo, "refs/remotes/origin/single");
+    let repo = import_refs(&repo)?;
+    assert_eq!(repo.view().all_remote_bookmark_tombstones().count(), 0);
+
+    // Tombstones are removed once all remotes have seen the deletion
+    git_ref(&git_repo, "refs/remotes/origin/feature", commit1);
+    git_ref(&git_repo, "refs/remotes/upstream/feature", commit1);
+    let repo = import_refs(&repo)?;
+    delete_git_ref(&git_repo, "refs/remotes/origin/feature");
+    let repo = import_refs(&repo)?;
+    assert_eq!(
+        repo.view().all_remote_bookmark_tombstones().collect_vec(),
+        [remote_symbol("feature", "origin")]
+    );
+    delete_git_ref(&git_repo, "refs/remotes/upstream/feature");
+    let repo = import_refs(&repo)?;
+    assert_eq!(repo.view().all_remote_bookmark_tombstones().count(), 0);
+    Ok(())
+}
+
 #[test]
 fn test_import_refs_reimport_absent_tracked_remote_tags() -> TestResult {
     let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
@@ -2037,11 +2153,81 @@ fn test_export_refs_worktree_head_changed() -> TestResult {
         RefTarget::normal(new_commit.id().clone()),
     );
     let stats = git::export_refs(mut_repo)?;
-    assert!(stats.failed_bookmarks.is_empty());
+    // The branch checked out in the worktree isn't moved
+    assert_eq!(stats.failed_bookmarks.len(), 1);
+    assert_eq!(
+        stats.failed_bookmarks[0].0,
+        remote_symbol("wt-branch", "git")
+    );
+    assert_matches!(
+        &stats.failed_bookmarks[0].1,
+        FailedRefExportReason::CheckedOutInWorktree(name) if name == "git-wt"
+    );
     assert!(stats.failed_tags.is_empty());
 
     let git_repo_wt = gix::open(&worktree_dir)?;
-    assert!(git_repo_wt.head()?.is_detached());
+    assert!(!git_repo_wt.head()?.is_detached());
+    assert_eq!(
+        git_repo_wt.head_id()?.detach(),
+        git_repo.find_reference("refs/heads/main")?.id().detach()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_import_head_worktrees() -> TestResult {
+    let test_data = GitRepoData::create();
+    let git_repo = test_data.git_repo;
+    let commit1 = empty_git_commit(&git_repo, "refs/heads/main", &[]);
+    let commit2 = empty_git_commit(&git_repo, "refs/heads/feature", &[commit1]);
+    testutils::git::set_symbolic_reference(&git_repo, "HEAD", "refs/heads/main");
+
+    let git_workdir = git_repo.workdir().expect("git repo must have workdir");
+    let add_worktree = |name: &str, commitish: &str| -> TestResult {
+        let output = std::process::Command::new("git")
+            .args(["worktree", "add", "--detach"])
+            .arg(test_data._temp_dir.path().join(name))
+            .arg(commitish)
+            .current_dir(git_workdir)
+            .output()?;
+        assert!(
+            output.status.success(),
+            "Failed to create worktree: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        Ok(())
+    };
+    add_worktree("wt1", "main")?;
+    add_worktree("wt2", "feature")?;
+
+    let mut tx = test_data.repo.start_transaction();
+    git::import_head(tx.repo_mut()).block_on()?;
+    let repo = tx.commit("test").block_on()?;
+    let view = repo.view();
+    assert_eq!(view.git_head(), &RefTarget::normal(jj_id(commit1)));
+    assert_eq!(
+        *view.git_worktree_heads(),
+        BTreeMap::from([
+            ("wt1".to_owned(), RefTarget::normal(jj_id(commit1))),
+            ("wt2".to_owned(), RefTarget::normal(jj_id(commit2))),
+        ])
+    );
+    // The worktree HEAD should be visible
+    assert!(view.heads().contains(&jj_id(commit2)));
+
+    // Removed worktree should be forgotten
+    let output = std::process::Command::new("git")
+        .args(["worktree", "remove"])
+        .arg(test_data._temp_dir.path().join("wt2"))
+        .current_dir(git_workdir)
+        .output()?;
+    assert!(output.status.success());
+    let mut tx = repo.start_transaction();
+    git::import_head(tx.repo_mut()).block_on()?;
+    assert_eq!(
+        *tx.repo().view().git_worktree_heads(),
+        BTreeMap::from([("wt1".to_owned(), RefTarget::normal(jj_id(commit1)))])
+    );
     Ok(())
 }
 
@@ -3830,7 +4016,7 @@ fn test_fetch_local_remote_conflicts() -> TestResult {
         let depth = None;
         let fetch_tags = Some(FetchTagsOverride::NoTags);
         fetcher
-            .fetch(remote, refspecs, &mut NullCallback, depth, fetch_tags)
+            .fetch(remote, refspecs, &mut NullCallback, depth, None, fetch_tags)
             .unwrap();
         fetcher.import_refs().block_on().unwrap()
     };
@@ -3956,7 +4142,7 @@ fn test_fetch_with_explicit_tag_patterns() -> TestResult {
         let depth = None;
         let fetch_tags = Some(FetchTagsOverride::NoTags);
         fetcher
-            .fetch(remote, refspecs, &mut NullCallback, depth, fetch_tags)
+            .fetch(remote, refspecs, &mut NullCallback, depth, None, fetch_tags)
             .unwrap();
         fetcher.import_refs().block_on().unwrap()
     };
@@ -4044,7 +4230,7 @@ fn test_fetch_export_annotated_tags() -> TestResult {
         let depth = None;
         let fetch_tags = Some(FetchTagsOverride::NoTags);
         fetcher
-            .fetch(remote, refspecs, &mut NullCallback, depth, fetch_tags)
+            .fetch(remote, refspecs, &mut NullCallback, depth, None, fetch_tags)
             .unwrap();
         fetcher.import_refs().block_on().unwrap()
     };
@@ -4169,7 +4355,7 @@ fn test_fetch_with_fetch_tags_override() -> TestResult {
             let refspecs = expand_fetch_refspecs(remote, ref_expr).unwrap();
             let depth = None;
             fetcher
-                .fetch(remote, refspecs, &mut NullCallback, depth, fetch_tags)
+                .fetch(remote, refspecs, &mut NullCallback, depth, None, fetch_tags)
                 .unwrap();
             fetcher.import_refs().block_on().unwrap()
         };
@@ -4287,6 +4473,7 @@ fn test_fetch_rejected_tag_updates() -> TestResult {
             expand_fetch_refspecs("origin".as_ref(), ref_expr)?,
             &mut NullCallback,
             None,
+            None,
             Some(FetchTagsOverride::AllTags),
         ),
         Err(GitFetchError::RejectedUpdates(refs)) if refs == ["refs/tags/tag"]
@@ -14,15 +14,23 @@
 
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::error::Error as _;
+use std::fs;
 use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::SystemTime;
 
+use assert_matches::assert_matches;
 use futures::executor::block_on_stream;
 use itertools::Itertools as _;
+use jj_lib::backend::Backend as _;
+use jj_lib::backend::BackendError;
 use jj_lib::backend::CommitId;
 use jj_lib::backend::CopyRecord;
+use jj_lib::backend::FileId;
+use jj_lib::backend::TreeId;
+use jj_lib::backend::TreeValue;
 use jj_lib::commit::Commit;
 use jj_lib::conflict_labels::ConflictLabels;
 use jj_lib::git_backend::GitBackend;
@@ -53,8 +61,10 @@ use testutils::create_tree;
 use testutils::is_external_tool_installed;
 use testutils::repo_path;
 use testutils::repo_path_buf;
+use testutils::repo_path_component;
 use testutils::write_random_commit;
 use testutils::write_random_commit_with_parents;
+use tokio::io::AsyncReadExt as _;
 
 fn get_git_backend(repo: &Arc<ReadonlyRepo>) -> &GitBackend {
     repo.store().backend_impl().unwrap()
@@ -501,3 +511,101 @@ fn test_conflict_headers_roundtrip() -> TestResult {
     assert_tree_eq!(repo.store().get_commit(commit.id())?.tree(), merged_tree);
     Ok(())
 }
+
+#[test]
+fn test_read_missing_objects_from_partial_clone() -> TestResult {
+    if !is_external_tool_installed("git") {
+        eprintln!("Skipping because git command might fail to run");
+        return Ok(());
+    }
+    let run_git = |dir: &Path, args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .current_dir(dir)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed: {output:?}");
+    };
+
+    let settings = testutils::user_settings();
+    let temp_dir = testutils::new_temp_dir();
+    let source_dir = temp_dir.path().join("source");
+    let source_repo = testutils::git::init(&source_dir);
+    let initial = testutils::git::add_commit(
+        &source_repo,
+        "refs/heads/main",
+        "dir/file1",
+        b"contents 1\n",
+        "initial",
+        &[],
+    );
+    let commit = testutils::git::add_commit(
+        &source_repo,
+        "refs/heads/main",
+        "dir/file2",
+        b"contents 2\n",
+        "second",
+        &[initial.commit_id],
+    );
+    run_git(&source_dir, &["config", "uploadpack.allowFilter", "true"]);
+
+    // Omit all trees and blobs
+    let source_url = format!("file://{}", source_dir.to_str().unwrap());
+    run_git(
+        temp_dir.path(),
+        &["clone", "--bare", "--filter=tree:0", &source_url, "clone"],
+    );
+    let store_dir = temp_dir.path().join("store");
+    fs::create_dir(&store_dir)?;
+    let backend = GitBackend::init_external(&settings, &store_dir, &temp_dir.path().join("clone"))
+        .map_err(|err| *err)?;
+
+    let read_file_ids = |tree_id: &TreeId| -> Result<Vec<FileId>, BackendError> {
+        let root_tree = backend.read_tree(RepoPath::root(), tree_id).block_on()?;
+        let Some(TreeValue::Tree(dir_id)) = root_tree.value(repo_path_component("dir")) else {
+            panic!("dir should be a tree");
+        };
+        let dir_tree = backend.read_tree(repo_path("dir"), dir_id).block_on()?;
+        Ok(dir_tree
+            .entries()
+            .map(|entry| match entry.value() {
+                TreeValue::File { id, .. } => id.clone(),
+                value => panic!("unexpected tree value: {value:?}"),
+            })
+            .collect())
+    };
+    let read_file = |path: &RepoPath, id: &FileId| -> Result<Vec<u8>, BackendError> {
+        let mut reader = backend.read_file(path, id).block_on()?;
+        let mut content = vec![];
+        reader.read_to_end(&mut content).block_on().unwrap();
+        Ok(content)
+    };
+
+    // Missing trees are fetched on demand, and the blobs of a tree are
+    // prefetched when the tree is read
+    let tree_id = TreeId::from_bytes(commit.tree_id.as_bytes());
+    let file_ids = read_file_ids(&tree_id)?;
+    assert_eq!(file_ids.len(), 2);
+
+    // Fetched objects remain available when the remote is unreachable
+    fs::remove_dir_all(&source_dir)?;
+    assert_eq!(read_file_ids(&tree_id)?, file_ids);
+    assert_eq!(
+        read_file(repo_path("dir/file1"), &file_ids[0])?,
+        b"contents 1\n"
+    );
+    assert_eq!(
+        read_file(repo_path("dir/file2"), &file_ids[1])?,
+        b"contents 2\n"
+    );
+
+    // Other objects can't be fetched
+    let tree_id = TreeId::from_bytes(initial.tree_id.as_bytes());
+    let err = read_file_ids(&tree_id).unwrap_err();
+    assert_matches!(err, BackendError::ReadObject { .. });
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "Object is missing from the partial clone and remote 'origin' can't be reached to fetch it"
+    );
+    Ok(())
+}
@@ -352,6 +352,50 @@ fn gpg_invalid_signature() -> TestResult {
     Ok(())
 }
 
+#[test]
+#[cfg_attr(windows, ignore = "stuck randomly on Windows CI #3140")] // FIXME
+fn gpg_verify_batch() -> TestResult {
+    gpg_guard!();
+
+    let env = GpgEnvironment::new().unwrap();
+    let backend = gpg_backend(&env);
+    let data = b"hello world";
+    let signature = backend.sign(data, None)?;
+    let other_signature = backend.sign(b"other", Some("Someone Else"))?;
+    let unknown_signature = br"-----BEGIN PGP SIGNATURE-----
+
+    iHUEABYKAB0WIQQs238pU7eC/ROoPJ0HH+PjJN1zMwUCZWPa5AAKCRAHH+PjJN1z
+    MyylAP9WQ3sZdbC4b1C+/nxs+Wl+rfwzeQWGbdcsBMyDABcpmgD/U+4KdO7eZj/I
+    e+U6bvqw3pOBoI53Th35drQ0qPI+jAE=
+    =kwsk
+    -----END PGP SIGNATURE-----";
+    let invalid_signature = br"-----BEGIN PGP SIGNATURE-----
+
+    super duper invalid
+    -----END PGP SIGNATURE-----";
+    // gpg exits after the bad signature, so the verification process has to
+    // be restarted for the following ones.
+    let signatures: [(&[u8], &[u8]); _] = [
+        (data, &signature),
+        (b"so so bad", &signature),
+        (b"other", &other_signature),
+        (data, unknown_signature),
+        (data, invalid_signature),
+        (data, &signature),
+    ];
+    let results = backend.verify_batch(&signatures);
+    assert_eq!(results.len(), signatures.len());
+    for ((data, signature), result) in signatures.iter().zip(results) {
+        assert_eq!(
+            result.map_err(|err| err.to_string()),
+            backend
+                .verify(data, signature)
+                .map_err(|err| err.to_string())
+        );
+    }
+    Ok(())
+}
+
 #[test]
 #[cfg_attr(windows, ignore = "stuck randomly on Windows CI #3140")] // FIXME
 fn gpgsm_signing_roundtrip() -> TestResult {
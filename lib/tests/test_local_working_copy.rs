@@ -48,6 +48,7 @@ use jj_lib::local_working_copy::TreeStateSettings;
 use jj_lib::matchers::FilesMatcher;
 use jj_lib::merge::Merge;
 use jj_lib::merge::SameChange;
+use jj_lib::merge_driver::MergeDrivers;
 use jj_lib::merged_tree::MergedTree;
 use jj_lib::merged_tree_builder::MergedTreeBuilder;
 use jj_lib::op_store::OperationId;
@@ -58,8 +59,10 @@ use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::rewrite::merge_commit_trees;
 use jj_lib::secret_backend::SecretBackend;
+use jj_lib::sparse::SparsePatterns;
 use jj_lib::tree_builder::TreeBuilder;
 use jj_lib::tree_merge::MergeOptions;
+use jj_lib::tree_merge::MergeStrategy;
 use jj_lib::working_copy::CheckoutError;
 use jj_lib::working_copy::CheckoutStats;
 use jj_lib::working_copy::SnapshotOptions;
@@ -129,7 +132,7 @@ fn test_root() -> TestResult {
     let mut test_workspace = TestWorkspace::init();
 
     let wc = test_workspace.workspace.working_copy();
-    assert_eq!(wc.sparse_patterns()?, vec![RepoPathBuf::root()]);
+    assert_eq!(wc.sparse_patterns()?, &SparsePatterns::all());
     let new_tree = test_workspace.snapshot()?;
     let repo = &test_workspace.repo;
     let wc_commit_id = repo
@@ -681,7 +684,10 @@ fn test_conflicting_changes_on_disk() -> TestResult {
             updated_files: 0,
             added_files: 3,
             removed_files: 0,
-            skipped_files: 3
+            skipped_files: 3,
+            failed_submodules: vec![],
+            updated_sparse_profile: None,
+            failed_sparse_profile: None,
         }
     );
 
@@ -905,7 +911,10 @@ fn test_materialize_snapshot_conflicted_files() -> TestResult {
             updated_files: 0,
             added_files: 2,
             removed_files: 0,
-            skipped_files: 0
+            skipped_files: 0,
+            failed_submodules: vec![],
+            updated_sparse_profile: None,
+            failed_sparse_profile: None,
         }
     );
 
@@ -1362,6 +1371,8 @@ fn test_snapshot_modified_materialized_conflict(
                 merge: MergeOptions {
                     hunk_level: FileMergeHunkLevel::Line,
                     same_change: SameChange::Accept,
+                    merge_drivers: MergeDrivers::default(),
+                    strategy: MergeStrategy::Conflict,
                 },
             },
         )?;
@@ -2582,6 +2593,104 @@ fn test_fsmonitor() -> TestResult {
     Ok(())
 }
 
+#[cfg(target_os = "linux")]
+#[test]
+fn test_fsmonitor_inotify() -> TestResult {
+    use jj_lib::fsmonitor::inotify;
+
+    let temp_dir = testutils::new_temp_dir();
+    let workspace_root = temp_dir.path().join("workspace");
+    let daemon_dir = temp_dir.path().join("daemon");
+    std::fs::create_dir(&workspace_root)?;
+    std::fs::create_dir_all(workspace_root.join("dir"))?;
+    std::fs::create_dir_all(workspace_root.join("dir").join("ignored"))?;
+    std::fs::write(workspace_root.join("dir").join(".gitignore"), "ignored/\n")?;
+
+    let daemon = std::thread::spawn({
+        let workspace_root = workspace_root.clone();
+        let daemon_dir = daemon_dir.clone();
+        move || inotify::run_daemon(&workspace_root, &daemon_dir, GitIgnoreFile::empty())
+    });
+    let query = |clock| {
+        let start = std::time::Instant::now();
+        loop {
+            match inotify::query_changed_files(&daemon_dir, clock) {
+                Err(inotify::InotifyError::NotRunning)
+                    if start.elapsed() < Duration::from_secs(10) =>
+                {
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+                result => return result.unwrap(),
+            }
+        }
+    };
+
+    // The first query doesn't know what changed before
+    let (clock, changed_files) = query(None);
+    assert_eq!(changed_files, None);
+
+    let (clock, changed_files) = query(Some(&clock));
+    assert_eq!(changed_files, Some(vec![]));
+
+    // Only one daemon can watch the working copy
+    assert_matches!(
+        inotify::run_daemon(&workspace_root, &daemon_dir, GitIgnoreFile::empty()),
+        Err(inotify::InotifyError::AlreadyRunning)
+    );
+    assert!(!inotify::is_watch_limit_reached(&daemon_dir));
+
+    std::fs::write(workspace_root.join("foo"), "foo\n")?;
+    std::fs::write(workspace_root.join("dir").join("bar"), "bar\n")?;
+    std::fs::create_dir(workspace_root.join("new"))?;
+    std::fs::write(workspace_root.join("new").join("baz"), "baz\n")?;
+    let (clock, changed_files) = query(Some(&clock));
+    let changed_files = changed_files.unwrap();
+    assert!(changed_files.contains(&PathBuf::from("foo")));
+    assert!(changed_files.contains(&PathBuf::from("dir/bar")));
+    assert!(changed_files.contains(&PathBuf::from("new/baz")));
+
+    // Changes in the .jj directory aren't reported
+    std::fs::create_dir(workspace_root.join(".jj"))?;
+    std::fs::write(workspace_root.join(".jj").join("ignored"), "")?;
+    let (clock, changed_files) = query(Some(&clock));
+    assert_eq!(changed_files, Some(vec![]));
+
+    // Ignored directories aren't watched but listed instead
+    std::fs::write(workspace_root.join("new").join(".gitignore"), "ignored\n")?;
+    let (clock, changed_files) = query(Some(&clock));
+    assert_eq!(changed_files, Some(vec![PathBuf::from("new/.gitignore")]));
+    std::fs::write(workspace_root.join("dir").join("ignored").join("file"), "")?;
+    std::fs::create_dir(workspace_root.join("new").join("ignored"))?;
+    std::fs::write(workspace_root.join("new").join("ignored").join("file"), "")?;
+    let (clock, changed_files) = query(Some(&clock));
+    assert_eq!(changed_files, Some(vec![]));
+    assert_eq!(
+        inotify::ignored_dirs(&daemon_dir)?,
+        [PathBuf::from("dir/ignored"), PathBuf::from("new/ignored")]
+    );
+
+    // Unignored directories are watched
+    std::fs::write(workspace_root.join("new").join(".gitignore"), "")?;
+    let (clock, _changed_files) = query(Some(&clock));
+    std::fs::write(workspace_root.join("new").join("ignored").join("file"), "a")?;
+    let (clock, changed_files) = query(Some(&clock));
+    assert_eq!(changed_files, Some(vec![PathBuf::from("new/ignored/file")]));
+    assert_eq!(
+        inotify::ignored_dirs(&daemon_dir)?,
+        [PathBuf::from("dir/ignored")]
+    );
+
+    // Moving a directory away invalidates everything below it
+    std::fs::rename(workspace_root.join("dir"), temp_dir.path().join("dir"))?;
+    let (_clock, changed_files) = query(Some(&clock));
+    assert_eq!(changed_files, None);
+
+    assert!(inotify::stop_daemon(&daemon_dir)?);
+    daemon.join().unwrap()?;
+    assert!(!inotify::is_daemon_running(&daemon_dir)?);
+    Ok(())
+}
+
 #[test]
 fn track_ignored_with_flag_and_fsmonitor() -> TestResult {
     let test_repo = TestRepo::init();
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use assert_matches::assert_matches;
 use futures::StreamExt as _;
 use itertools::Itertools as _;
 use jj_lib::local_working_copy::LocalWorkingCopy;
@@ -19,6 +20,10 @@ use jj_lib::matchers::EverythingMatcher;
 use jj_lib::repo::Repo as _;
 use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::sparse::SparseFileset;
+use jj_lib::sparse::SparsePatterns;
+use jj_lib::sparse::SparseProfileError;
+use jj_lib::sparse::resolve_sparse_profile;
 use jj_lib::working_copy::CheckoutStats;
 use jj_lib::working_copy::WorkingCopy as _;
 use pollster::FutureExt as _;
@@ -69,7 +74,7 @@ fn test_sparse_checkout() -> TestResult {
 
     // Set sparse patterns to only dir1/
     let mut locked_ws = ws.start_working_copy_mutation()?;
-    let sparse_patterns = to_owned_path_vec(&[dir1_path]);
+    let sparse_patterns = SparsePatterns::Prefixes(to_owned_path_vec(&[dir1_path]));
     let stats = locked_ws
         .locked_wc()
         .set_sparse_patterns(sparse_patterns.clone())
@@ -81,9 +86,12 @@ fn test_sparse_checkout() -> TestResult {
             added_files: 0,
             removed_files: 3,
             skipped_files: 0,
+            failed_submodules: vec![],
+            updated_sparse_profile: None,
+            failed_sparse_profile: None,
         }
     );
-    assert_eq!(locked_ws.locked_wc().sparse_patterns()?, sparse_patterns);
+    assert_eq!(locked_ws.locked_wc().sparse_patterns()?, &sparse_patterns);
     assert!(
         !root_file1_path
             .to_fs_path_unchecked(&working_copy_path)
@@ -122,7 +130,7 @@ fn test_sparse_checkout() -> TestResult {
         wc.file_states()?.paths().collect_vec(),
         vec![dir1_file1_path, dir1_file2_path, dir1_subdir1_file1_path]
     );
-    assert_eq!(wc.sparse_patterns()?, sparse_patterns);
+    assert_eq!(wc.sparse_patterns()?, &sparse_patterns);
 
     // Reload the state to check that it was persisted
     let wc = LocalWorkingCopy::load(
@@ -135,11 +143,15 @@ fn test_sparse_checkout() -> TestResult {
         wc.file_states()?.paths().collect_vec(),
         vec![dir1_file1_path, dir1_file2_path, dir1_subdir1_file1_path]
     );
-    assert_eq!(wc.sparse_patterns()?, sparse_patterns);
+    assert_eq!(wc.sparse_patterns()?, &sparse_patterns);
 
     // Set sparse patterns to file2, dir1/subdir1/ and dir2/
     let mut locked_wc = wc.start_mutation()?;
-    let sparse_patterns = to_owned_path_vec(&[root_file1_path, dir1_subdir1_path, dir2_path]);
+    let sparse_patterns = SparsePatterns::Prefixes(to_owned_path_vec(&[
+        root_file1_path,
+        dir1_subdir1_path,
+        dir2_path,
+    ]));
     let stats = locked_wc
         .set_sparse_patterns(sparse_patterns.clone())
         .block_on()?;
@@ -150,9 +162,12 @@ fn test_sparse_checkout() -> TestResult {
             added_files: 2,
             removed_files: 2,
             skipped_files: 0,
+            failed_submodules: vec![],
+            updated_sparse_profile: None,
+            failed_sparse_profile: None,
         }
     );
-    assert_eq!(locked_wc.sparse_patterns()?, sparse_patterns);
+    assert_eq!(locked_wc.sparse_patterns()?, &sparse_patterns);
     assert!(
         root_file1_path
             .to_fs_path_unchecked(&working_copy_path)
@@ -192,6 +207,251 @@ fn test_sparse_checkout() -> TestResult {
     Ok(())
 }
 
+#[test]
+fn test_sparse_checkout_fileset() -> TestResult {
+    let mut test_workspace = TestWorkspace::init();
+    let repo = &test_workspace.repo;
+
+    let root_file1_path = repo_path("file1");
+    let dir1_file1_path = repo_path("dir1/file1");
+    let dir1_fixtures_file1_path = repo_path("dir1/fixtures/file1");
+    let dir1_subdir1_file1_path = repo_path("dir1/subdir1/file1");
+    let dir1_subdir1_fixtures_file1_path = repo_path("dir1/subdir1/fixtures/file1");
+    let dir2_file1_path = repo_path("dir2/file1");
+
+    let tree = create_tree(
+        repo,
+        &[
+            (root_file1_path, "contents"),
+            (dir1_file1_path, "contents"),
+            (dir1_fixtures_file1_path, "contents"),
+            (dir1_subdir1_file1_path, "contents"),
+            (dir1_subdir1_fixtures_file1_path, "contents"),
+            (dir2_file1_path, "contents"),
+        ],
+    );
+    let commit = commit_with_tree(repo.store(), tree);
+
+    test_workspace
+        .workspace
+        .check_out(repo.op_id().clone(), None, &commit)
+        .block_on()?;
+    let ws = &mut test_workspace.workspace;
+
+    // Check out dir1/ except for fixtures, and the root file
+    let mut locked_ws = ws.start_working_copy_mutation()?;
+    let sparse_patterns = SparsePatterns::Fileset(SparseFileset::parse(
+        "dir1 ~ glob:'dir1/**/fixtures/**' | root-file:file1",
+    )?);
+    let stats = locked_ws
+        .locked_wc()
+        .set_sparse_patterns(sparse_patterns.clone())
+        .block_on()?;
+    assert_eq!(
+        stats,
+        CheckoutStats {
+            updated_files: 0,
+            added_files: 0,
+            removed_files: 3,
+            skipped_files: 0,
+            failed_submodules: vec![],
+            updated_sparse_profile: None,
+            failed_sparse_profile: None,
+        }
+    );
+    locked_ws.finish(repo.op_id().clone()).block_on()?;
+
+    // Reload the state to check that it was persisted
+    let wc: &LocalWorkingCopy = ws.working_copy().downcast_ref().unwrap();
+    let wc = LocalWorkingCopy::load(
+        repo.store().clone(),
+        ws.workspace_root().to_path_buf(),
+        wc.state_path().to_path_buf(),
+        repo.settings(),
+    )?;
+    assert_eq!(wc.sparse_patterns()?, &sparse_patterns);
+    assert_eq!(
+        wc.file_states()?.paths().collect_vec(),
+        vec![dir1_file1_path, dir1_subdir1_file1_path, root_file1_path]
+    );
+
+    // Going back to prefixes restores the excluded files
+    let mut locked_wc = wc.start_mutation()?;
+    let stats = locked_wc
+        .set_sparse_patterns(SparsePatterns::all())
+        .block_on()?;
+    assert_eq!(
+        stats,
+        CheckoutStats {
+            updated_files: 0,
+            added_files: 3,
+            removed_files: 0,
+            skipped_files: 0,
+            failed_submodules: vec![],
+            updated_sparse_profile: None,
+            failed_sparse_profile: None,
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_sparse_profile() -> TestResult {
+    let mut test_workspace = TestWorkspace::init();
+    let repo = &test_workspace.repo;
+
+    let backend_profile_path = repo_path(".jjsparse/backend.toml");
+    let common_profile_path = repo_path(".jjsparse/common.toml");
+    let dir1_file1_path = repo_path("dir1/file1");
+    let dir2_file1_path = repo_path("dir2/file1");
+    let dir3_file1_path = repo_path("dir3/file1");
+    let dir3_script_path = repo_path("dir3/script.sh");
+
+    let tree = create_tree(
+        repo,
+        &[
+            (
+                backend_profile_path,
+                "include = ['common']\npaths = ['dir1']\n",
+            ),
+            (
+                common_profile_path,
+                "include = ['backend']\nfileset = 'glob:\"dir3/*.sh\"'\n",
+            ),
+            (dir1_file1_path, "contents"),
+            (dir2_file1_path, "contents"),
+            (dir3_file1_path, "contents"),
+            (dir3_script_path, "contents"),
+        ],
+    );
+    let commit = commit_with_tree(repo.store(), tree.clone());
+
+    // Included profiles are merged, and include cycles are harmless
+    let profile = resolve_sparse_profile(&tree, "backend").block_on()?;
+    assert_eq!(profile.name(), "backend");
+    assert_eq!(
+        profile.patterns(),
+        &SparsePatterns::Fileset(SparseFileset::parse(
+            r#"root:".jjsparse" | root:"dir1" | (glob:"dir3/*.sh")"#
+        )?)
+    );
+    // Profiles without filesets resolve to prefixes
+    let tree_without_fileset = create_tree(
+        repo,
+        &[(backend_profile_path, "paths = ['dir2', 'dir1', 'dir2']")],
+    );
+    let profile = resolve_sparse_profile(&tree_without_fileset, "backend").block_on()?;
+    assert_eq!(
+        profile.patterns(),
+        &SparsePatterns::Prefixes(to_owned_path_vec(&[
+            repo_path(".jjsparse"),
+            repo_path("dir1"),
+            repo_path("dir2")
+        ]))
+    );
+    assert_matches!(
+        resolve_sparse_profile(&tree, "frontend").block_on(),
+        Err(SparseProfileError::NotFound { name }) if name == "frontend"
+    );
+    assert_matches!(
+        resolve_sparse_profile(&tree, "../backend").block_on(),
+        Err(SparseProfileError::InvalidName { .. })
+    );
+    let invalid_tree = create_tree(repo, &[(backend_profile_path, "paths = 'dir1'")]);
+    assert_matches!(
+        resolve_sparse_profile(&invalid_tree, "backend").block_on(),
+        Err(SparseProfileError::Parse { .. })
+    );
+
+    test_workspace
+        .workspace
+        .check_out(repo.op_id().clone(), None, &commit)
+        .block_on()?;
+    let ws = &mut test_workspace.workspace;
+
+    let mut locked_ws = ws.start_working_copy_mutation()?;
+    let profile = resolve_sparse_profile(&tree, "backend").block_on()?;
+    locked_ws
+        .locked_wc()
+        .set_sparse_profile(profile.clone())
+        .block_on()?;
+    locked_ws.finish(repo.op_id().clone()).block_on()?;
+
+    // Reload the state to check that the profile name was persisted
+    let wc: &LocalWorkingCopy = ws.working_copy().downcast_ref().unwrap();
+    let wc = LocalWorkingCopy::load(
+        repo.store().clone(),
+        ws.workspace_root().to_path_buf(),
+        wc.state_path().to_path_buf(),
+        repo.settings(),
+    )?;
+    assert_eq!(wc.sparse_patterns()?, profile.patterns());
+    assert_eq!(wc.sparse_profile()?, Some("backend"));
+    assert_eq!(
+        wc.file_states()?.paths().collect_vec(),
+        vec![
+            backend_profile_path,
+            common_profile_path,
+            dir1_file1_path,
+            dir3_script_path
+        ]
+    );
+
+    // Checking out a commit with a changed profile updates the patterns along
+    // with the files
+    let tree2 = create_tree(
+        repo,
+        &[
+            (backend_profile_path, "paths = ['dir2']\n"),
+            (dir1_file1_path, "contents"),
+            (dir2_file1_path, "modified"),
+            (dir3_file1_path, "contents"),
+            (dir3_script_path, "contents"),
+        ],
+    );
+    let commit2 = commit_with_tree(repo.store(), tree2.clone());
+    let stats = ws
+        .check_out(repo.op_id().clone(), None, &commit2)
+        .block_on()?;
+    assert_eq!(
+        stats,
+        CheckoutStats {
+            updated_files: 1,
+            added_files: 1,
+            removed_files: 3,
+            skipped_files: 0,
+            failed_submodules: vec![],
+            updated_sparse_profile: Some("backend".to_owned()),
+            failed_sparse_profile: None,
+        }
+    );
+    let wc: &LocalWorkingCopy = ws.working_copy().downcast_ref().unwrap();
+    assert_eq!(wc.sparse_profile()?, Some("backend"));
+    assert_eq!(
+        wc.file_states()?.paths().collect_vec(),
+        vec![backend_profile_path, dir2_file1_path]
+    );
+
+    // The patterns are kept if the profile can't be resolved
+    let tree3 = create_tree(repo, &[(dir2_file1_path, "contents")]);
+    let commit3 = commit_with_tree(repo.store(), tree3);
+    let stats = ws
+        .check_out(repo.op_id().clone(), None, &commit3)
+        .block_on()?;
+    assert_matches!(
+        stats.failed_sparse_profile,
+        Some(failure) if failure.name == "backend"
+    );
+    assert_eq!(stats.updated_files, 1);
+    assert_eq!(
+        ws.working_copy().sparse_patterns()?,
+        resolve_sparse_profile(&tree2, "backend")
+            .block_on()?
+            .patterns()
+    );
+    Ok(())
+}
+
 /// Test that sparse patterns are respected on commit
 #[test]
 fn test_sparse_commit() -> TestResult {
@@ -223,7 +483,7 @@ fn test_sparse_commit() -> TestResult {
 
     // Set sparse patterns to only dir1/
     let mut locked_ws = test_workspace.workspace.start_working_copy_mutation()?;
-    let sparse_patterns = to_owned_path_vec(&[dir1_path]);
+    let sparse_patterns = SparsePatterns::Prefixes(to_owned_path_vec(&[dir1_path]));
     locked_ws
         .locked_wc()
         .set_sparse_patterns(sparse_patterns)
@@ -258,7 +518,7 @@ fn test_sparse_commit() -> TestResult {
 
     // Set sparse patterns to also include dir2/
     let mut locked_ws = test_workspace.workspace.start_working_copy_mutation()?;
-    let sparse_patterns = to_owned_path_vec(&[dir1_path, dir2_path]);
+    let sparse_patterns = SparsePatterns::Prefixes(to_owned_path_vec(&[dir1_path, dir2_path]));
     locked_ws
         .locked_wc()
         .set_sparse_patterns(sparse_patterns)
@@ -291,7 +551,7 @@ fn test_sparse_commit_gitignore() -> TestResult {
 
     // Set sparse patterns to only dir1/
     let mut locked_ws = test_workspace.workspace.start_working_copy_mutation()?;
-    let sparse_patterns = to_owned_path_vec(&[dir1_path]);
+    let sparse_patterns = SparsePatterns::Prefixes(to_owned_path_vec(&[dir1_path]));
     locked_ws
         .locked_wc()
         .set_sparse_patterns(sparse_patterns)
@@ -45,6 +45,8 @@ use jj_lib::merged_tree_builder::MergedTreeBuilder;
 use jj_lib::repo::Repo as _;
 use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::tree_merge::MergeOptions;
+use jj_lib::tree_merge::MergeStrategy;
 use pollster::FutureExt as _;
 use pretty_assertions::assert_eq;
 use testutils::TestRepo;
@@ -1378,6 +1380,56 @@ fn test_merge_partial_resolution() -> TestResult {
     Ok(())
 }
 
+/// Merge 3 resolved trees with conflicting hunks resolved by strategy
+#[test]
+fn test_merge_with_strategy() -> TestResult {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let path = repo_path("file");
+    let base1 = create_single_tree(repo, &[(path, "a\nb\nbase\nd\ne\n")]);
+    let side1 = create_single_tree(repo, &[(path, "a1\nb\nside1\nd\ne\n")]);
+    let side2 = create_single_tree(repo, &[(path, "a\nb\nside2\nd\ne2\n")]);
+    let merge = Merge::from_vec(vec![
+        (
+            MergedTree::resolved(repo.store().clone(), side1.id().clone()),
+            "side 1".to_owned(),
+        ),
+        (
+            MergedTree::resolved(repo.store().clone(), base1.id().clone()),
+            "base 1".to_owned(),
+        ),
+        (
+            MergedTree::resolved(repo.store().clone(), side2.id().clone()),
+            "side 2".to_owned(),
+        ),
+    ]);
+    let merge_with_strategy = |strategy| {
+        let options = MergeOptions {
+            strategy,
+            ..repo.store().merge_options().clone()
+        };
+        MergedTree::merge_with_options(merge.clone(), &options).block_on()
+    };
+
+    // The default strategy leaves the conflict
+    let merged = merge_with_strategy(MergeStrategy::Conflict)?;
+    assert!(merged.has_conflict());
+
+    let merged = merge_with_strategy(MergeStrategy::Union)?;
+    let expected = create_tree(repo, &[(path, "a1\nb\nside1\nside2\nd\ne2\n")]);
+    assert_tree_eq!(merged, expected);
+
+    let merged = merge_with_strategy(MergeStrategy::Ours)?;
+    let expected = create_tree(repo, &[(path, "a1\nb\nside1\nd\ne2\n")]);
+    assert_tree_eq!(merged, expected);
+
+    let merged = merge_with_strategy(MergeStrategy::Theirs)?;
+    let expected = create_tree(repo, &[(path, "a1\nb\nside2\nd\ne2\n")]);
+    assert_tree_eq!(merged, expected);
+    Ok(())
+}
+
 /// Merge 3 trees where each one is a 3-way conflict and the result is arrived
 /// at by only simplifying the conflict (no need to recurse)
 #[test]
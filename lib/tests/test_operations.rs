@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
 use std::path::Path;
 use std::slice;
 use std::sync::Arc;
@@ -31,6 +32,8 @@ use jj_lib::op_walk;
 use jj_lib::op_walk::OpsetEvaluationError;
 use jj_lib::op_walk::OpsetResolutionError;
 use jj_lib::operation::Operation;
+use jj_lib::opset;
+use jj_lib::opset::OpsetParseContext;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo;
 use jj_lib::settings::UserSettings;
@@ -648,6 +651,91 @@ fn test_reparent_discarding_predecessors(op_stores_commit_predecessors: bool) ->
     Ok(())
 }
 
+#[test]
+fn test_abandon_operations() -> TestResult {
+    let test_repo = TestRepo::init();
+    let repo_0 = test_repo.repo;
+    let loader = repo_0.loader();
+    let op_store = repo_0.op_store();
+
+    let read_op = |id| loader.load_operation(id).block_on().unwrap();
+
+    fn op_parents<const N: usize>(op: &Operation) -> [Operation; N] {
+        let parents = op.parents().block_on().unwrap();
+        parents.try_into().unwrap()
+    }
+
+    // Set up operation graph:
+    // E
+    // |\
+    // D |
+    // | C
+    // B |
+    // |/
+    // A
+    // 0 (initial)
+    let random_tx = |repo: &Arc<ReadonlyRepo>| {
+        let mut tx = repo.start_transaction();
+        write_random_commit(tx.repo_mut());
+        tx
+    };
+    let repo_a = random_tx(&repo_0).commit("op A").block_on()?;
+    let repo_b = random_tx(&repo_a).commit("op B").block_on()?;
+    let repo_c = random_tx(&repo_a).commit("op C").block_on()?;
+    let repo_d = random_tx(&repo_b).commit("op D").block_on()?;
+    let mut tx = repo_d.start_transaction();
+    tx.merge_operation(repo_c.operation().clone()).block_on()?;
+    let repo_e = tx.commit("op E").block_on()?;
+    let head_op = repo_e.operation();
+
+    // Abandon non-contiguous B and C:
+    // E'
+    // D'
+    // A
+    let abandoned_ids = [repo_b.op_id().clone(), repo_c.op_id().clone()];
+    let stats = op_walk::abandon_operations(
+        op_store.as_ref(),
+        slice::from_ref(head_op),
+        &abandoned_ids.into_iter().collect(),
+    )
+    .block_on()?;
+    assert_eq!(stats.new_head_ids.len(), 1);
+    assert_eq!(stats.rewritten_count, 2);
+    assert_eq!(stats.unreachable_count, 2);
+    let new_op_e = read_op(&stats.new_head_ids[0]);
+    assert_eq!(new_op_e.metadata(), head_op.metadata());
+    assert_eq!(new_op_e.view_id(), head_op.view_id());
+    let [new_op_d, op_a] = op_parents(&new_op_e);
+    assert_eq!(new_op_d.metadata(), repo_d.operation().metadata());
+    assert_eq!(new_op_d.parent_ids(), slice::from_ref(repo_a.op_id()));
+    assert_eq!(op_a.id(), repo_a.op_id());
+
+    // Abandon the merge parent D, which makes E a linear operation
+    let abandoned_ids = [repo_d.op_id().clone()];
+    let stats = op_walk::abandon_operations(
+        op_store.as_ref(),
+        slice::from_ref(head_op),
+        &abandoned_ids.into_iter().collect(),
+    )
+    .block_on()?;
+    assert_eq!(stats.rewritten_count, 1);
+    assert_eq!(stats.unreachable_count, 1);
+    let new_op_e = read_op(&stats.new_head_ids[0]);
+    assert_eq!(
+        new_op_e.parent_ids(),
+        [repo_b.op_id().clone(), repo_c.op_id().clone()]
+    );
+
+    // Abandon nothing
+    let stats =
+        op_walk::abandon_operations(op_store.as_ref(), slice::from_ref(head_op), &HashSet::new())
+            .block_on()?;
+    assert_eq!(stats.new_head_ids, vec![head_op.id().clone()]);
+    assert_eq!(stats.rewritten_count, 0);
+    assert_eq!(stats.unreachable_count, 0);
+    Ok(())
+}
+
 fn stable_op_id_settings() -> UserSettings {
     let mut config = testutils::base_user_config();
     config.add_layer(
@@ -670,20 +758,20 @@ fn test_resolve_op_id() -> TestResult {
     let mut operations = Vec::new();
     // The actual value of `i` doesn't matter, we just need to make sure we end
     // up with hashes with ambiguous prefixes.
-    for i in (1..5).chain([10, 24]) {
+    for i in (1..5).chain([16, 45]) {
         let tx = repo.start_transaction();
         let repo = tx.commit(format!("transaction {i}")).block_on()?;
         operations.push(repo.operation().clone());
     }
-    // "3" and "0" are ambiguous
+    // "6" and "0" are ambiguous
     insta::assert_debug_snapshot!(operations.iter().map(|op| op.id().hex()).collect_vec(), @r#"
     [
-        "3fb99188ad57448697795ade6d59a7fc36c4ba9daa5ce5501ec2e2bb23a027e7358ededd902994b7e9fc319d262c7679af7f079cdf5403ec2784a33f79f17c21",
-        "75f94ddb7d65b220acca16ff4d5a1851945051803d809b66aeb1cd12b77ad8a1cf8973cb531a4524c1948812bf3ccd650bf8988e692d7bd7fa47f08f4c506abd",
-        "de94b57efe85cd747450956e5f8221a277db649e91e643d9ccd524ab0574630abc04dd4fe81108090bfc4e183c62b3ba1b1a3ee077020ea6587a59703990ddc2",
-        "9874219f414e4bba6564c54782ed1016a5ae63d695d6b0e1165983365d527bf10af3016a0e20020c24d2208bf20ef284e2a628c8c99d9742475d51eb6417c867",
-        "380b1e3403ee19d0696441eb38eb9aeac36ac82769012a1bc370825705b745a8dab42f3bbdb1223e0adf860d15290134340842ba2d3e3ec7462a0c8cce54d54c",
-        "08b1bc4a1537ea549fa001dbc29b474f8fd3469facbab331ee2b5e3807eaaf95d04d967e270499ffb7a38098eec0dff97cdb5c7b44ba755f9b71c9924f80c16e",
+        "68ed1e50d1169d6ffdcc66a975a5d4fd44a05dce62a9fbbee0c995878b8680544ee19831fda7a17d414a257410ce6f70375e1746e8d76216866d4df6509166da",
+        "efe8073bf24180446a5f0ddbd2195129c01c112d06fd846c2256d207c9f1264e078107ae8df716a91ed56709aafc198fa0cc5ee8fc508204a16e1808ae2fd60a",
+        "41cd4f03c95558284f5ee478e4289cde576e26ab0304672a0f379ebaff99ac754c5ed228289743f676c61443f99eb590ec835c8b5f9342f4bfbf6816121c8096",
+        "9742cdef1bed927d85f55d602b9ae79ff78082441f2ab7a5fa022c7f09a443b9ff7fb0ac18e8bde6ac6ca447854c489941dfd49609ba250f8b132e2b44dfc6c5",
+        "68fd662adbc728add37817fa780a482cb8415d4ad8b3aa7a1d5a431a4de62cea4a2912f465a0b2e1fd940244f06d2d4cd25b0352c77037f00882f9191c618b6d",
+        "0a3ad40e1f4cf8afe776e08f514197bb87497524146ff7e75d6d3c0eb67a7158d27f654f3e28f28a852f95d4c04618e1db5b6bdd4f74638df41f3528abc32c23",
     ]
     "#);
 
@@ -698,7 +786,7 @@ fn test_resolve_op_id() -> TestResult {
     assert_eq!(resolve(&operations[1].id().hex()[..2])?, operations[1]);
     // Ambiguous id
     assert_matches!(
-        resolve("3"),
+        resolve("6"),
         Err(OpsetEvaluationError::OpsetResolution(
             OpsetResolutionError::AmbiguousIdPrefix(_)
         ))
@@ -721,7 +809,7 @@ fn test_resolve_op_id() -> TestResult {
     let root_operation = loader.root_operation().block_on();
     assert_eq!(resolve(&root_operation.id().hex())?, root_operation);
     assert_eq!(resolve("00")?, root_operation);
-    assert_eq!(resolve("08")?, operations[5]);
+    assert_eq!(resolve("0a")?, operations[5]);
     assert_matches!(
         resolve("0"),
         Err(OpsetEvaluationError::OpsetResolution(
@@ -993,6 +1081,122 @@ fn test_walk_ancestors() -> TestResult {
     Ok(())
 }
 
+#[test]
+fn test_resolve_ops() -> TestResult {
+    let test_repo = TestRepo::init();
+    let repo_0 = test_repo.repo;
+    let op_store = repo_0.op_store();
+
+    // Set up operation graph:
+    // D
+    // C
+    // | B
+    // A |
+    // |/
+    // 0 (initial)
+    let repo_a = repo_0.start_transaction().commit("op A").block_on()?;
+    let repo_b = repo_0.start_transaction().commit("op B").block_on()?;
+    let repo_c = repo_a.start_transaction().commit("op C").block_on()?;
+    let repo_d = repo_c.start_transaction().commit("fetch D").block_on()?;
+    let head_ops = [repo_d.operation().clone(), repo_b.operation().clone()];
+
+    let resolve = |text: &str| -> Result<Vec<OperationId>, OpsetEvaluationError> {
+        let context = OpsetParseContext {
+            date_pattern_context: chrono::Local::now().into(),
+        };
+        let expression = opset::parse(text, &context)?;
+        let ops = op_walk::resolve_ops_at(op_store, &head_ops, &expression).block_on()?;
+        Ok(ops.iter().map(|op| op.id().clone()).collect())
+    };
+    let id = |repo: &ReadonlyRepo| repo.op_id().clone();
+
+    assert_eq!(
+        resolve("all()")?,
+        [
+            id(&repo_d),
+            id(&repo_c),
+            id(&repo_a),
+            id(&repo_b),
+            id(&repo_0)
+        ]
+    );
+    assert_eq!(resolve("root()")?, [op_store.root_operation_id().clone()]);
+    assert_eq!(resolve("none()")?, []);
+    assert_eq!(
+        resolve(&format!("{}::", repo_a.op_id().hex()))?,
+        [id(&repo_d), id(&repo_c), id(&repo_a)]
+    );
+    assert_eq!(
+        resolve(&format!("{}..", repo_a.op_id().hex()))?,
+        [id(&repo_d), id(&repo_c), id(&repo_b)]
+    );
+    assert_eq!(
+        resolve(&format!("::{} & ~root()", repo_c.op_id().hex()))?,
+        [id(&repo_c), id(&repo_a)]
+    );
+    assert_eq!(
+        resolve(r#"description("op") ~ description(exact:"op B")"#)?,
+        [id(&repo_c), id(&repo_a)]
+    );
+    assert_eq!(resolve("latest(all(), 2)")?, [id(&repo_d), id(&repo_c)]);
+    // `@` is ambiguous if there are multiple heads
+    assert_matches!(
+        resolve("@"),
+        Err(OpsetEvaluationError::OpsetResolution(
+            OpsetResolutionError::MultipleOperations { .. }
+        ))
+    );
+    assert_matches!(
+        resolve("unknown()"),
+        Err(OpsetEvaluationError::OpsetParse(_))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_resolve_op_date_pattern_uses_commit_timestamp() -> TestResult {
+    let test_repo = TestRepo::init();
+    let repo = test_repo
+        .repo
+        .start_transaction()
+        .commit("op A")
+        .block_on()?;
+    let op_store = repo.op_store();
+    let head_ops = slice::from_ref(repo.operation());
+
+    // Operations created now are after 1 day before the current time
+    let settings = testutils::user_settings();
+    let op = op_walk::resolve_op_at(
+        &settings,
+        op_store,
+        head_ops,
+        r#"latest(after("1 day ago"), 1)"#,
+    )
+    .block_on()?;
+    assert_eq!(op.id(), repo.op_id());
+
+    // ...but not after 1 day before the configured commit timestamp
+    let mut config = testutils::base_user_config();
+    config.add_layer(ConfigLayer::parse(
+        ConfigSource::User,
+        "debug.commit-timestamp = 2100-01-01T00:00:00+00:00",
+    )?);
+    let settings = UserSettings::from_config(config)?;
+    assert_matches!(
+        op_walk::resolve_op_at(
+            &settings,
+            op_store,
+            head_ops,
+            r#"latest(after("1 day ago"), 1)"#
+        )
+        .block_on(),
+        Err(OpsetEvaluationError::OpsetResolution(
+            OpsetResolutionError::EmptyOperations(_)
+        ))
+    );
+    Ok(())
+}
+
 #[test]
 fn test_gc() -> TestResult {
     let settings = stable_op_id_settings();
@@ -2591,6 +2591,38 @@ fn test_evaluate_expression_git_head() {
     );
 }
 
+#[test]
+fn test_evaluate_expression_git_worktree_heads() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+
+    let commit1 = write_random_commit(mut_repo);
+    let commit2 = write_random_commit(mut_repo);
+    let commit3 = write_random_commit(mut_repo);
+
+    // Can get worktree head when it's not set
+    assert_eq!(resolve_commit_ids(mut_repo, "git_head(wt1)"), vec![]);
+    mut_repo.set_git_head_target(RefTarget::normal(commit1.id().clone()));
+    mut_repo.set_git_worktree_head_target("wt1", RefTarget::normal(commit2.id().clone()));
+    mut_repo.set_git_worktree_head_target("wt2", RefTarget::normal(commit3.id().clone()));
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "git_head(wt1)"),
+        vec![commit2.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "git_head('wt*')"),
+        vec![commit3.id().clone(), commit2.id().clone()]
+    );
+    assert_eq!(resolve_commit_ids(mut_repo, "git_head(exact:wt)"), vec![]);
+
+    // Forgotten worktree
+    mut_repo.set_git_worktree_head_target("wt1", RefTarget::absent());
+    assert_eq!(resolve_commit_ids(mut_repo, "git_head(wt1)"), vec![]);
+}
+
 #[test]
 fn test_evaluate_expression_bookmarks() {
     let test_repo = TestRepo::init();
@@ -1,13 +1,25 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use assert_matches::assert_matches;
+use itertools::Itertools as _;
 use jj_lib::backend::MillisSinceEpoch;
 use jj_lib::backend::Signature;
 use jj_lib::backend::Timestamp;
+use jj_lib::commit::verify_commit_signatures;
+use jj_lib::commit::verify_commit_signatures_uncached;
 use jj_lib::config::ConfigLayer;
 use jj_lib::config::ConfigSource;
 use jj_lib::repo::Repo as _;
 use jj_lib::settings::UserSettings;
 use jj_lib::signing::SigStatus;
 use jj_lib::signing::SignBehavior;
+use jj_lib::signing::SignPolicy;
+use jj_lib::signing::SignPolicyViolation;
+use jj_lib::signing::SignRequirement;
+use jj_lib::signing::SignResult;
 use jj_lib::signing::Signer;
+use jj_lib::signing::SigningBackend;
 use jj_lib::signing::Verification;
 use jj_lib::test_signing_backend::TestSigningBackend;
 use pollster::FutureExt as _;
@@ -61,6 +73,49 @@ fn good_verification() -> Option<Verification> {
     })
 }
 
+/// Test backend which records the sizes of the verified batches.
+#[derive(Debug)]
+struct RecordingBackend {
+    batches: Arc<Mutex<Vec<usize>>>,
+    trust_state: Option<Vec<u8>>,
+}
+
+impl Default for RecordingBackend {
+    fn default() -> Self {
+        Self {
+            batches: Default::default(),
+            trust_state: Some(vec![]),
+        }
+    }
+}
+
+impl SigningBackend for RecordingBackend {
+    fn name(&self) -> &'static str {
+        "test"
+    }
+
+    fn can_read(&self, signature: &[u8]) -> bool {
+        TestSigningBackend.can_read(signature)
+    }
+
+    fn sign(&self, data: &[u8], key: Option<&str>) -> SignResult<Vec<u8>> {
+        TestSigningBackend.sign(data, key)
+    }
+
+    fn verify(&self, data: &[u8], signature: &[u8]) -> SignResult<Verification> {
+        self.verify_batch(&[(data, signature)]).pop().unwrap()
+    }
+
+    fn verify_batch(&self, signatures: &[(&[u8], &[u8])]) -> Vec<SignResult<Verification>> {
+        self.batches.lock().unwrap().push(signatures.len());
+        TestSigningBackend.verify_batch(signatures)
+    }
+
+    fn trust_state(&self) -> Option<Vec<u8>> {
+        self.trust_state.clone()
+    }
+}
+
 #[test_case(TestRepoBackend::Simple ; "simple backend")]
 #[test_case(TestRepoBackend::Git ; "git backend")]
 fn manual(backend: TestRepoBackend) -> TestResult {
@@ -210,3 +265,139 @@ fn drop_behavior(backend: TestRepoBackend) -> TestResult {
     assert_eq!(rewritten_commit.verification()?, None);
     Ok(())
 }
+
+#[test_case(TestRepoBackend::Simple ; "simple backend")]
+#[test_case(TestRepoBackend::Git ; "git backend")]
+fn batch_verification(backend: TestRepoBackend) -> TestResult {
+    let settings = user_settings(SignBehavior::Own);
+    let temp_dir = testutils::new_temp_dir();
+
+    let backend_impl = RecordingBackend::default();
+    let batches = backend_impl.batches.clone();
+    let signer = Signer::new(Some(Box::new(backend_impl)), vec![]);
+    let test_workspace = TestWorkspace::init_with_backend_and_signer(backend, signer, &settings);
+
+    let repo = &test_workspace.repo;
+
+    let mut tx = repo.start_transaction();
+    let commit1 = create_random_commit(tx.repo_mut())
+        .set_sign_behavior(SignBehavior::Own)
+        .set_author(someone_else())
+        .write_unwrap();
+    let commit2 = create_random_commit(tx.repo_mut())
+        .set_sign_behavior(SignBehavior::Own)
+        .write_unwrap();
+    let commit3 = create_random_commit(tx.repo_mut())
+        .set_sign_behavior(SignBehavior::Own)
+        .write_unwrap();
+    tx.commit("test").block_on()?;
+    let commits = [commit1, commit2, commit3];
+
+    let results: Vec<_> = verify_commit_signatures(&commits)
+        .into_iter()
+        .try_collect()?;
+    assert_eq!(results, [None, good_verification(), good_verification()]);
+    assert_eq!(*batches.lock().unwrap(), [2]);
+
+    // Verifications are cached in memory
+    assert_eq!(commits[1].verification()?, good_verification());
+    assert_eq!(*batches.lock().unwrap(), [2]);
+
+    // unless verifying without caches
+    let results: Vec<_> = verify_commit_signatures_uncached(&commits)
+        .into_iter()
+        .try_collect()?;
+    assert_eq!(results, [None, good_verification(), good_verification()]);
+    assert_eq!(*batches.lock().unwrap(), [2, 2]);
+
+    // and on disk
+    let backend_impl = RecordingBackend::default();
+    let batches = backend_impl.batches.clone();
+    let signer = Signer::new(Some(Box::new(backend_impl)), vec![])
+        .with_persistent_cache(test_workspace.repo_path().join("signature_cache"));
+    let signatures = commits[1..]
+        .iter()
+        .map(|commit| {
+            let sig = commit.store_commit().secure_sig.as_ref().unwrap();
+            (commit.id(), &*sig.data, &*sig.sig)
+        })
+        .collect_vec();
+    let results: Vec<_> = signer.verify_batch(&signatures).into_iter().try_collect()?;
+    assert_eq!(results, vec![good_verification().unwrap(); 2]);
+    assert!(batches.lock().unwrap().is_empty());
+
+    // but not if the trust state of the backend changes
+    let backend_impl = RecordingBackend {
+        trust_state: Some(b"other".to_vec()),
+        ..Default::default()
+    };
+    let batches = backend_impl.batches.clone();
+    let signer = Signer::new(Some(Box::new(backend_impl)), vec![])
+        .with_persistent_cache(test_workspace.repo_path().join("signature_cache"));
+    let results: Vec<_> = signer.verify_batch(&signatures).into_iter().try_collect()?;
+    assert_eq!(results, vec![good_verification().unwrap(); 2]);
+    assert_eq!(*batches.lock().unwrap(), [2]);
+
+    // and nothing is persisted if the results depend on the time
+    let backend_impl = RecordingBackend {
+        trust_state: None,
+        ..Default::default()
+    };
+    let signer = Signer::new(Some(Box::new(backend_impl)), vec![])
+        .with_persistent_cache(temp_dir.path().join("signature_cache"));
+    signer.verify_batch(&signatures);
+    assert!(!temp_dir.path().join("signature_cache").exists());
+    Ok(())
+}
+
+#[test]
+fn sign_policy() {
+    let verification =
+        |status, key: Option<&str>| Verification::new(status, key.map(str::to_owned), None);
+    let mut policy = SignPolicy {
+        require: SignRequirement::Push,
+        trusted_keys: vec![],
+    };
+    assert_matches!(policy.check(None), Err(SignPolicyViolation::Unsigned));
+    assert_matches!(
+        policy.check(Some(&verification(SigStatus::Bad, Some("impeccable")))),
+        Err(SignPolicyViolation::InvalidSignature(SigStatus::Bad))
+    );
+    assert_matches!(
+        policy.check(Some(&verification(SigStatus::Unknown, None))),
+        Err(SignPolicyViolation::InvalidSignature(SigStatus::Unknown))
+    );
+    // Any good signature is accepted if no signers are configured
+    assert_matches!(
+        policy.check(Some(&verification(SigStatus::Good, None))),
+        Ok(())
+    );
+
+    policy.trusted_keys = vec!["impeccable".to_owned()];
+    assert_matches!(
+        policy.check(Some(&verification(SigStatus::Good, Some("impeccable")))),
+        Ok(())
+    );
+    assert_matches!(
+        policy.check(Some(&verification(SigStatus::Good, Some("rogue")))),
+        Err(SignPolicyViolation::UntrustedKey(key)) if key == "rogue"
+    );
+    assert_matches!(
+        policy.check(Some(&verification(SigStatus::Good, None))),
+        Err(SignPolicyViolation::UnidentifiedKey)
+    );
+
+    // GnuPG long key ids match the fingerprint they're the suffix of
+    policy.trusted_keys = vec!["4ED5 56E9 7294 9F45 D31D 6E94 5C9E 3A7B 8F2D 1C60".to_owned()];
+    assert_matches!(
+        policy.check(Some(&verification(
+            SigStatus::Good,
+            Some("5c9e3a7b8f2d1c60")
+        ))),
+        Ok(())
+    );
+    assert_matches!(
+        policy.check(Some(&verification(SigStatus::Good, Some("8F2D1C60")))),
+        Err(SignPolicyViolation::UntrustedKey(_))
+    );
+}
@@ -221,3 +221,40 @@ fn ssh_signing_revocation_unrevoked() -> TestResult {
     assert_eq!(check.key.unwrap(), FINGERPRINT);
     Ok(())
 }
+
+#[test]
+fn ssh_signing_verify_batch() -> TestResult {
+    let mut env = SshEnvironment::new()?;
+    let data = b"hello world";
+    let signature = backend(&env).sign(data, Some(env.private_key_path.to_str().unwrap()))?;
+    let invalid_signature =
+        b"-----BEGIN SSH SIGNATURE-----\ninvalid\n-----END SSH SIGNATURE-----\n";
+    let signatures: [(&[u8], &[u8]); _] = [
+        (data, &signature),
+        (b"invalid-commit-data", &signature),
+        (data, invalid_signature),
+    ];
+
+    // The signatures verified together get the same results as the ones
+    // verified by ssh-keygen one by one.
+    let assert_same_as_verify = |backend: &SshBackend| {
+        let results = backend.verify_batch(&signatures);
+        assert_eq!(results.len(), signatures.len());
+        for ((data, signature), result) in signatures.iter().zip(results) {
+            assert_eq!(
+                result.map_err(|err| err.to_string()),
+                backend
+                    .verify(data, signature)
+                    .map_err(|err| err.to_string())
+            );
+        }
+    };
+    assert_same_as_verify(&backend(&env));
+    env.with_revocation_list(PUBLIC_KEY.as_bytes());
+    assert_same_as_verify(&backend(&env));
+    env.with_bad_public_key();
+    assert_same_as_verify(&backend(&env));
+    env.allowed_signers = None;
+    assert_same_as_verify(&backend(&env));
+    Ok(())
+}
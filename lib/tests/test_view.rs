@@ -540,6 +540,92 @@ fn test_merge_views_git_heads() -> TestResult {
     Ok(())
 }
 
+#[test]
+fn test_merge_views_git_worktree_heads() -> TestResult {
+    // Tests merging of git worktree heads (by performing divergent operations).
+    // See test_refs.rs for tests of merging of individual ref targets.
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx0 = repo.start_transaction();
+    let tx0_head = write_random_commit(tx0.repo_mut());
+    tx0.repo_mut()
+        .set_git_worktree_head_target("wt1", RefTarget::normal(tx0_head.id().clone()));
+    tx0.repo_mut()
+        .set_git_worktree_head_target("wt2", RefTarget::normal(tx0_head.id().clone()));
+    let repo = tx0.commit("test").block_on()?;
+
+    let mut tx1 = repo.start_transaction();
+    let tx1_head = write_random_commit(tx1.repo_mut());
+    tx1.repo_mut()
+        .set_git_worktree_head_target("wt1", RefTarget::normal(tx1_head.id().clone()));
+    tx1.repo_mut()
+        .set_git_worktree_head_target("wt3", RefTarget::normal(tx1_head.id().clone()));
+
+    let mut tx2 = repo.start_transaction();
+    let tx2_head = write_random_commit(tx2.repo_mut());
+    tx2.repo_mut()
+        .set_git_worktree_head_target("wt1", RefTarget::normal(tx2_head.id().clone()));
+    tx2.repo_mut()
+        .set_git_worktree_head_target("wt2", RefTarget::absent());
+
+    let repo = commit_transactions(vec![tx1, tx2]);
+    let expected_wt1_head = RefTarget::from_legacy_form(
+        [tx0_head.id().clone()],
+        [tx1_head.id().clone(), tx2_head.id().clone()],
+    );
+    assert_eq!(
+        *repo.view().git_worktree_heads(),
+        btreemap! {
+            "wt1".to_owned() => expected_wt1_head,
+            "wt3".to_owned() => RefTarget::normal(tx1_head.id().clone()),
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_merge_views_remote_bookmark_tombstones() -> TestResult {
+    // Tests merging of tombstones of remote bookmarks, which aren't included in
+    // the regular remote bookmarks.
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx0 = repo.start_transaction();
+    let commit = write_random_commit(tx0.repo_mut());
+    let tracked_ref = RemoteRef {
+        target: RefTarget::normal(commit.id().clone()),
+        state: RemoteRefState::Tracked,
+    };
+    for name in ["foo", "bar", "baz"] {
+        tx0.repo_mut()
+            .set_remote_bookmark(remote_symbol(name, "origin"), tracked_ref.clone());
+    }
+    let repo = tx0.commit("test").block_on()?;
+
+    let mut tx1 = repo.start_transaction();
+    tx1.repo_mut()
+        .add_remote_bookmark_tombstone(remote_symbol("foo", "origin"));
+
+    let mut tx2 = repo.start_transaction();
+    tx2.repo_mut()
+        .add_remote_bookmark_tombstone(remote_symbol("bar", "origin"));
+
+    let repo = commit_transactions(vec![tx1, tx2]);
+    assert_eq!(
+        repo.view().all_remote_bookmarks().collect_vec(),
+        vec![(remote_symbol("baz", "origin"), &tracked_ref)]
+    );
+    assert_eq!(
+        repo.view().all_remote_bookmark_tombstones().collect_vec(),
+        vec![
+            remote_symbol("bar", "origin"),
+            remote_symbol("foo", "origin")
+        ]
+    );
+    Ok(())
+}
+
 #[test]
 fn test_merge_views_divergent() -> TestResult {
     // We start with just commit A. Operation 1 rewrites it as A2. Operation 2